@@ -1,19 +1,138 @@
 use crate::combat::simulation::CumulativeResults;
+use crate::combat::trip::{TripConfig, TripEnd, TripResult};
 use crate::constants::SECONDS_PER_TICK;
 use core::f64;
 
+// Two-sided 95% quantile of the standard normal distribution
+const Z_95: f64 = 1.959_963_984_540_054;
+
+/// Sample mean with its standard error and 95% confidence interval.
+#[derive(Debug, Default, PartialEq, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub struct Estimate {
+    pub mean: f64,
+    pub std_err: f64,
+    pub ci_low: f64,
+    pub ci_high: f64,
+}
+
+impl Estimate {
+    /// Normal-approximation interval for the mean of the samples.
+    ///
+    /// The mean is NaN without samples, and the standard error is NaN with fewer than two.
+    pub fn from_samples<I>(samples: I) -> Self
+    where
+        I: IntoIterator<Item = f64>,
+    {
+        // Welford's algorithm to avoid cancellation with large sample counts
+        let (mut n, mut mean, mut m2) = (0u64, 0.0, 0.0);
+        for x in samples {
+            n += 1;
+            let delta = x - mean;
+            mean += delta / n as f64;
+            m2 += delta * (x - mean);
+        }
+        if n == 0 {
+            return Self::nan();
+        }
+
+        let std_err = if n < 2 {
+            f64::NAN
+        } else {
+            (m2 / (n - 1) as f64 / n as f64).sqrt()
+        };
+
+        Self {
+            mean,
+            std_err,
+            ci_low: mean - Z_95 * std_err,
+            ci_high: mean + Z_95 * std_err,
+        }
+    }
+
+    /// Wilson score interval for a proportion, which stays within [0, 1] near the edges.
+    pub fn from_proportion(successes: usize, trials: usize) -> Self {
+        if trials == 0 {
+            return Self::nan();
+        }
+
+        let n = trials as f64;
+        let p = successes as f64 / n;
+        let z2 = Z_95 * Z_95;
+        let center = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+        let half_width = Z_95 / (1.0 + z2 / n) * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt();
+
+        Self {
+            mean: p,
+            std_err: (p * (1.0 - p) / n).sqrt(),
+            ci_low: (center - half_width).max(0.0),
+            ci_high: (center + half_width).min(1.0),
+        }
+    }
+
+    pub fn half_width(&self) -> f64 {
+        (self.ci_high - self.ci_low) / 2.0
+    }
+
+    fn nan() -> Self {
+        Self {
+            mean: f64::NAN,
+            std_err: f64::NAN,
+            ci_low: f64::NAN,
+            ci_high: f64::NAN,
+        }
+    }
+}
+
+/// TTK percentiles of successful kills, in seconds.
+#[derive(Debug, Default, PartialEq, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub struct TtkPercentiles {
+    pub p10: f64,
+    pub p50: f64,
+    pub p90: f64,
+    pub p99: f64,
+}
+
+impl TtkPercentiles {
+    pub fn new(ttks_ticks: &[i32]) -> Self {
+        let mut sorted = ttks_ticks.to_vec();
+        sorted.sort_unstable();
+
+        Self {
+            p10: percentile(&sorted, 0.10),
+            p50: percentile(&sorted, 0.50),
+            p90: percentile(&sorted, 0.90),
+            p99: percentile(&sorted, 0.99),
+        }
+    }
+}
+
+// Nearest-rank percentile of sorted TTKs, converted to seconds
+fn percentile(sorted_ticks: &[i32], p: f64) -> f64 {
+    if sorted_ticks.is_empty() {
+        return f64::NAN;
+    }
+    let rank = (p * sorted_ticks.len() as f64).ceil() as usize;
+    f64::from(sorted_ticks[rank.clamp(1, sorted_ticks.len()) - 1]) * SECONDS_PER_TICK
+}
+
 #[derive(Debug, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
 pub struct SimulationStats {
     pub ttk: f64,
+    pub ttk_ci: Estimate, // Seconds
+    pub ttk_percentiles: TtkPercentiles,
     pub ttk_dist: Vec<f64>,
     pub accuracy: f64,
     pub hit_dist: Vec<f64>,
     pub success_rate: f64,
+    pub success_rate_ci: Estimate,
     pub avg_food_eaten: f64,
+    pub food_eaten_ci: Estimate,
     pub food_eaten_dist: Vec<f64>,
     pub avg_damage_taken: f64,
     pub avg_leftover_burn: f64,
+    pub avg_prayer_used: f64,
     pub total_deaths: u32,
+    pub total_supply_outs: u32,
 }
 
 impl SimulationStats {
@@ -21,6 +140,13 @@ impl SimulationStats {
         let total_ticks: u64 = results.ttks_ticks.iter().map(|&t| t as u64).sum();
         let total_successful_fights = results.ttks_ticks.len();
         let ttk = (total_ticks as f64 / total_successful_fights as f64) * SECONDS_PER_TICK;
+        let ttk_ci = Estimate::from_samples(
+            results
+                .ttks_ticks
+                .iter()
+                .map(|&t| f64::from(t) * SECONDS_PER_TICK),
+        );
+        let ttk_percentiles = TtkPercentiles::new(&results.ttks_ticks);
 
         let ttks_as_u32: Vec<u32> = results.ttks_ticks.iter().map(|&t| t as u32).collect();
         let ttk_dist = calculate_dist(&ttks_as_u32);
@@ -31,27 +157,80 @@ impl SimulationStats {
         let total_attempts: u64 = results.hit_attempt_counts.iter().map(|&a| a as u64).sum();
         let accuracy = (total_hits as f64 / total_attempts as f64) * 100.0;
 
-        let total_fights = total_successful_fights + results.player_deaths;
-        let success_rate = 1.0 - (results.player_deaths as f64 / total_fights as f64);
+        // Running out of supplies counts as a failed kill alongside deaths
+        let total_failures = results.player_deaths + results.supply_outs;
+        let total_fights = total_successful_fights + total_failures;
+        let success_rate = 1.0 - (total_failures as f64 / total_fights as f64);
+        let success_rate_ci = Estimate::from_proportion(total_successful_fights, total_fights);
 
+        // Failed fights are included in the food samples unless only successful stats are kept,
+        // so the average and its interval both use every recorded sample
         let avg_food_eaten =
-            results.food_eaten.iter().sum::<u32>() as f64 / total_successful_fights as f64;
+            results.food_eaten.iter().sum::<u32>() as f64 / results.food_eaten.len() as f64;
+        let food_eaten_ci =
+            Estimate::from_samples(results.food_eaten.iter().map(|&f| f64::from(f)));
         let avg_damage_taken =
             results.damage_taken.iter().sum::<u32>() as f64 / total_successful_fights as f64;
         let avg_leftover_burn =
             results.leftover_burn.iter().sum::<u32>() as f64 / total_successful_fights as f64;
+        let avg_prayer_used =
+            results.prayer_used.iter().sum::<u32>() as f64 / total_successful_fights as f64;
 
         Self {
             ttk,
+            ttk_ci,
+            ttk_percentiles,
             ttk_dist,
             accuracy,
             hit_dist,
             success_rate,
+            success_rate_ci,
             avg_food_eaten,
+            food_eaten_ci,
             food_eaten_dist,
             avg_damage_taken,
             avg_leftover_burn,
+            avg_prayer_used,
             total_deaths: results.player_deaths as u32,
+            total_supply_outs: results.supply_outs as u32,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TripStats {
+    pub kills_per_hour: f64,
+    pub kills_per_trip: f64,
+    pub avg_trip_length: f64, // Seconds spent at the monster per trip, excluding banking
+    pub trip_length_dist: Vec<f64>, // Indexed by trip length in ticks
+    pub death_rate: f64,
+}
+
+impl TripStats {
+    pub fn new(results: &[TripResult], config: &TripConfig) -> Self {
+        let total_trips = results.len() as f64;
+        let total_kills: u64 = results.iter().map(|trip| u64::from(trip.kills)).sum();
+        let trip_ticks: Vec<u32> = results.iter().map(|trip| trip.trip_ticks).collect();
+        let total_trip_ticks: u64 = trip_ticks.iter().map(|&t| u64::from(t)).sum();
+
+        // Every trip, including ones that end in a death, is followed by a bank
+        let total_ticks = total_trip_ticks + u64::from(config.bank_ticks) * results.len() as u64;
+        let total_hours = total_ticks as f64 * SECONDS_PER_TICK / 3600.0;
+        let kills_per_hour = total_kills as f64 / total_hours;
+
+        let kills_per_trip = total_kills as f64 / total_trips;
+        let avg_trip_length = total_trip_ticks as f64 / total_trips * SECONDS_PER_TICK;
+        let deaths = results
+            .iter()
+            .filter(|trip| trip.end == TripEnd::PlayerDeath)
+            .count();
+
+        Self {
+            kills_per_hour,
+            kills_per_trip,
+            avg_trip_length,
+            trip_length_dist: calculate_dist(&trip_ticks),
+            death_rate: deaths as f64 / total_trips,
         }
     }
 }
@@ -75,3 +254,77 @@ fn calculate_dist(data: &[u32]) -> Vec<f64> {
 
     dist
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_from_samples() {
+        let estimate = Estimate::from_samples([2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+        assert_eq!(estimate.mean, 5.0);
+        // Sample standard deviation is sqrt(32 / 7)
+        let std_err = (32.0_f64 / 7.0).sqrt() / 8.0_f64.sqrt();
+        assert!((estimate.std_err - std_err).abs() < 1e-12);
+        assert!((estimate.half_width() - Z_95 * std_err).abs() < 1e-12);
+
+        assert!(Estimate::from_samples([3.0]).std_err.is_nan());
+        assert!(Estimate::from_samples([]).mean.is_nan());
+    }
+
+    #[test]
+    fn test_estimate_from_proportion() {
+        let estimate = Estimate::from_proportion(50, 100);
+        assert_eq!(estimate.mean, 0.5);
+        assert!((estimate.std_err - 0.05).abs() < 1e-12);
+        assert!((estimate.ci_low - 0.4038).abs() < 1e-4);
+        assert!((estimate.ci_high - 0.5962).abs() < 1e-4);
+
+        // All successes still gives an interval below 1
+        let estimate = Estimate::from_proportion(100, 100);
+        assert_eq!(estimate.ci_high, 1.0);
+        assert!(estimate.ci_low < 1.0 && estimate.ci_low > 0.95);
+    }
+
+    #[test]
+    fn test_ttk_percentiles() {
+        let ttks: Vec<i32> = (1..=100).rev().collect();
+        let percentiles = TtkPercentiles::new(&ttks);
+        assert_eq!(percentiles.p10, 10.0 * SECONDS_PER_TICK);
+        assert_eq!(percentiles.p50, 50.0 * SECONDS_PER_TICK);
+        assert_eq!(percentiles.p90, 90.0 * SECONDS_PER_TICK);
+        assert_eq!(percentiles.p99, 99.0 * SECONDS_PER_TICK);
+
+        assert_eq!(TtkPercentiles::new(&[7]).p10, 7.0 * SECONDS_PER_TICK);
+        assert!(TtkPercentiles::new(&[]).p50.is_nan());
+    }
+
+    #[test]
+    fn test_simulation_stats_intervals() {
+        let mut results = CumulativeResults::new(4);
+        results.ttks_ticks = vec![10, 20, 30, 40];
+        results.food_eaten = vec![0, 1, 2, 3];
+        results.player_deaths = 1;
+
+        let stats = SimulationStats::new(&results);
+        assert!((stats.ttk_ci.mean - stats.ttk).abs() < 1e-9);
+        assert!(stats.ttk_ci.ci_low < stats.ttk && stats.ttk < stats.ttk_ci.ci_high);
+        assert_eq!(stats.success_rate_ci.mean, 0.8);
+        assert_eq!(stats.food_eaten_ci.mean, stats.avg_food_eaten);
+        assert_eq!(stats.ttk_percentiles.p50, 20.0 * SECONDS_PER_TICK);
+    }
+
+    #[test]
+    fn test_food_eaten_includes_failures() {
+        let mut results = CumulativeResults::new(3);
+        results.ttks_ticks = vec![10, 20];
+        // The last sample comes from a death that ate through the whole inventory
+        results.food_eaten = vec![2, 4, 12];
+        results.player_deaths = 1;
+
+        let stats = SimulationStats::new(&results);
+        assert_eq!(stats.avg_food_eaten, 6.0);
+        assert!((stats.food_eaten_ci.mean - stats.avg_food_eaten).abs() < 1e-9);
+        assert!(stats.food_eaten_ci.ci_low < 6.0 && 6.0 < stats.food_eaten_ci.ci_high);
+    }
+}
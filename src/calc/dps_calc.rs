@@ -8,6 +8,8 @@ use crate::calc::hit_dist::{
 };
 use crate::calc::monster_scaling;
 use crate::calc::rolls::{calc_active_player_rolls, get_demonbane_factor, monster_def_rolls};
+use crate::combat::attacks::drains;
+use crate::combat::thralls::Thrall;
 use crate::constants;
 use crate::dists;
 use crate::dists::bolts::{self, BoltContext};
@@ -280,7 +282,17 @@ pub fn get_distribution(
     monster: &Monster,
     using_spec: bool,
 ) -> Result<AttackDistribution, DpsCalcError> {
-    // Get the attack distribution for the given player and monster
+    build_distribution(player, monster, using_spec, true)
+}
+
+// Get the attack distribution for the given player and monster, optionally stopping short of the
+// monster's post-roll limiters (damage caps, flat armour, etc.)
+fn build_distribution(
+    player: &Player,
+    monster: &Monster,
+    using_spec: bool,
+    with_limiters: bool,
+) -> Result<AttackDistribution, DpsCalcError> {
     let acc = get_hit_chance(player, monster, using_spec)?;
     let combat_type = player.combat_type();
     let (mut min_hit, max_hit) = if using_spec {
@@ -807,6 +819,10 @@ pub fn get_distribution(
         );
     }
 
+    if !with_limiters {
+        return Ok(dist);
+    }
+
     Ok(apply_limiters(dist, player, monster))
 }
 
@@ -863,6 +879,7 @@ fn get_spec_min_max_hit(player: &Player, monster: &Monster) -> Result<(u32, u32)
         "Magic shortbow" | "Magic shortbow (i)" | "Magic longbow" | "Magic comp bow"
         | "Seercull" => (0, player.seercull_spec_max()),
         "Eye of ayak" => (0, base_max_hit * 13 / 10),
+        "Elder maul" => (0, base_max_hit),
         _ => {
             return Err(DpsCalcError::SpecNotImplemented(
                 player.gear.weapon.name.clone(),
@@ -988,8 +1005,6 @@ fn apply_limiters(
             dist = dist.transform(&flat_limit_transformer(0, 10), &TransformOpts::default());
         }
     }
-    if player.is_wearing("Efaritay's aid", None) && monster.vampyre_tier() == Some(2) {}
-
     if monster.info.id == Some(constants::HUEYCOATL_TAIL_ID) {
         let using_crush = player.combat_type() == CombatType::Crush
             && player.bonuses.attack.crush > player.bonuses.attack.stab
@@ -1048,14 +1063,18 @@ pub fn get_expected_damage(
     Ok(dist.get_expected_damage() + get_dot_expected(player, monster, using_spec)?)
 }
 
-// Get the average damage per tick
-fn get_dpt(dist: &AttackDistribution, player: &Player, using_spec: bool) -> f64 {
-    let speed = if using_spec && player.is_wearing("Eye of ayak", None) {
+// Get the number of ticks between attacks
+fn get_attack_speed(player: &Player, using_spec: bool) -> i32 {
+    if using_spec && player.is_wearing("Eye of ayak", None) {
         5
     } else {
         player.gear.weapon.speed
-    };
-    dist.get_expected_damage() / speed as f64
+    }
+}
+
+// Get the average damage per tick
+fn get_dpt(dist: &AttackDistribution, player: &Player, using_spec: bool) -> f64 {
+    dist.get_expected_damage() / get_attack_speed(player, using_spec) as f64
 }
 
 // Get the average damage per second
@@ -1096,9 +1115,31 @@ pub fn get_ttk(
     using_spec: bool,
     remove_final_hit_delay: bool,
 ) -> Result<f64, DpsCalcError> {
-    let ttk = if dist_is_current_hp_dependent(player, monster) {
+    get_ttk_with_thrall(
+        dist,
+        player,
+        monster,
+        using_spec,
+        remove_final_hit_delay,
+        None,
+    )
+}
+
+// Get the expected time to kill with a thrall attacking alongside the player
+pub fn get_ttk_with_thrall(
+    dist: &AttackDistribution,
+    player: &Player,
+    monster: &Monster,
+    using_spec: bool,
+    remove_final_hit_delay: bool,
+    thrall: Option<Thrall>,
+) -> Result<f64, DpsCalcError> {
+    let thrall = active_thrall(thrall, monster);
+    let ttk = if dist_is_current_hp_dependent(player, monster) || thrall.is_some() {
         // More expensive than get_htk, so only use this if the hit dist changes during the fight
-        let ttk_dist = get_ttk_distribution(&mut dist.clone(), player, monster, using_spec)?;
+        // or thrall hits are interleaved with the player's
+        let ttk_dist =
+            get_ttk_distribution(&mut dist.clone(), player, monster, using_spec, thrall)?;
 
         // Find the expected value of the ttk distribution
         ttk_dist
@@ -1117,20 +1158,51 @@ pub fn get_ttk(
     }
 }
 
+// The thrall attacking the monster, if any and the monster isn't immune to it
+fn active_thrall(thrall: Option<Thrall>, monster: &Monster) -> Option<Thrall> {
+    thrall.filter(|thrall| !monster.is_immune_to_thrall(*thrall))
+}
+
+// Get the average damage per second of a thrall
+pub fn get_thrall_dps(thrall: Option<Thrall>, monster: &Monster) -> f64 {
+    active_thrall(thrall, monster).map_or(0.0, |thrall| {
+        thrall.max_hit() as f64
+            / 2.0
+            / constants::THRALL_ATTACK_SPEED as f64
+            / constants::SECONDS_PER_TICK
+    })
+}
+
+// Get the average damage per second of the player and a thrall combined
+pub fn get_dps_with_thrall(
+    dist: &AttackDistribution,
+    player: &Player,
+    monster: &Monster,
+    using_spec: bool,
+    thrall: Option<Thrall>,
+) -> f64 {
+    get_dps(dist, player, using_spec) + get_thrall_dps(thrall, monster)
+}
+
 // Get the full ttk distribution
 pub fn get_ttk_distribution(
     dist: &mut AttackDistribution,
     player: &Player,
     monster: &Monster,
     using_spec: bool,
+    thrall: Option<Thrall>,
 ) -> Result<HashMap<usize, f64>, DpsCalcError> {
     let speed = player.gear.weapon.speed as usize;
     let max_hp = monster.stats.hitpoints.current as usize;
     let mut dist_copy = dist.clone();
     let dist_single = dist_copy.get_single_hitsplat();
 
+    // Thrall hits roll uniformly from 0 to the thrall's max hit
+    let thrall_dist = active_thrall(thrall, monster)
+        .map(|thrall| HitDistribution::linear(1.0, 0, thrall.max_hit()));
+
     // Return empty distribution if the expected damage is 0
-    if dist_single.expected_hit() == 0.0 {
+    if dist_single.expected_hit() == 0.0 && thrall_dist.is_none() {
         return Ok(HashMap::new());
     }
 
@@ -1164,51 +1236,525 @@ pub fn get_ttk_distribution(
         // Initialize the updated hp probability distribution
         let mut next_hps = vec![0.0; max_hp + 1];
 
-        // For each possible hp value
-        for (hp, hp_prob) in hps.iter().enumerate() {
-            // Get the current hit distribution (the original or cached one based on current hp)
-            let current_dist = if recalc_dist_on_hp {
+        // Get the current hit distribution (the original or cached one based on current hp)
+        let dist_for_hp = |hp: usize| {
+            if recalc_dist_on_hp {
                 hp_hit_dists
                     .get(&hp)
                     .ok_or_else(|| DpsCalcError::MissingHpHitDist {
                         monster_name: monster.info.name.clone(),
                         hp,
-                    })?
+                    })
             } else {
-                dist_single
+                Ok(dist_single)
+            }
+        };
+
+        let tick = (hit + 1) * speed;
+        let killed =
+            step_hp_distribution(&hps, dist_for_hp, tick, &mut ttks, |_, new_hp, chance| {
+                next_hps[new_hp] += chance
+            })?;
+        epsilon -= killed;
+
+        // Thrall attacks before the player's next attack. A kill by the thrall ends the fight on
+        // the same tick as a kill by the player's last attack, since the sims count the player's
+        // attack delay either way
+        if let Some(ref thrall_dist) = thrall_dist {
+            for _ in thrall_attacks_between(hit * speed, tick) {
+                let mut after_thrall = vec![0.0; max_hp + 1];
+                epsilon -= step_hp_distribution(
+                    &next_hps,
+                    |_| Ok(thrall_dist),
+                    tick,
+                    &mut ttks,
+                    |_, new_hp, chance| after_thrall[new_hp] += chance,
+                )?;
+                next_hps = after_thrall;
+            }
+        }
+
+        // Update counters and repeat
+        hps = next_hps;
+    }
+
+    Ok(ttks)
+}
+
+// Ticks in [start, end) that the thrall attacks on. The sims summon the thrall so that it first
+// attacks one tick after the player, then every THRALL_ATTACK_SPEED ticks
+fn thrall_attacks_between(start: usize, end: usize) -> impl Iterator<Item = usize> {
+    let speed = constants::THRALL_ATTACK_SPEED as usize;
+    (start..end).filter(move |tick| tick % speed == 1)
+}
+
+// Apply one attack to a distribution of monster hp values. Kills are added to `ttks` at `tick`,
+// and every surviving outcome is passed to `survive` with its hit index, new hp and probability
+fn step_hp_distribution<'a, D, S>(
+    hps: &[f64],
+    dist_for_hp: D,
+    tick: usize,
+    ttks: &mut HashMap<usize, f64>,
+    mut survive: S,
+) -> Result<f64, DpsCalcError>
+where
+    D: Fn(usize) -> Result<&'a HitDistribution, DpsCalcError>,
+    S: FnMut(usize, usize, f64),
+{
+    let mut killed = 0.0;
+
+    for (hp, hp_prob) in hps.iter().enumerate() {
+        if *hp_prob == 0.0 {
+            continue;
+        }
+
+        // For each possible damage amount
+        for (index, h) in dist_for_hp(hp)?.hits.iter().enumerate() {
+            // Chance of this path being reached is the previous chance of landing here * the chance of hitting this amount
+            let chance_of_action = h.probability * hp_prob;
+            if chance_of_action == 0.0 {
+                continue;
+            }
+
+            // If the hp we are about to arrive at is <= 0, the NPC is killed on this tick
+            let new_hp = hp as i32 - h.get_sum() as i32;
+            if new_hp <= 0 {
+                *ttks.entry(tick).or_insert(0.0) += chance_of_action;
+                killed += chance_of_action;
+            } else {
+                survive(index, new_hp as usize, chance_of_action);
+            }
+        }
+    }
+
+    Ok(killed)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SequenceAttack {
+    Spec,
+    Main,
+}
+
+// Order of attacks until the last spec. Specs are used whenever there's enough energy, so specs
+// that have to wait for energy regen are interleaved with main weapon attacks, as in the sims
+fn spec_sequence_schedule(
+    spec_player: &Player,
+    main_player: &Player,
+    max_specs: u32,
+) -> Result<Vec<SequenceAttack>, DpsCalcError> {
+    let weapon = &spec_player.gear.weapon.name;
+    let cost = constants::SPEC_COSTS
+        .iter()
+        .find(|w| w.0 == weapon)
+        .ok_or_else(|| DpsCalcError::SpecNotImplemented(weapon.clone()))?
+        .1;
+    let lightbearer = main_player.is_wearing("Lightbearer", None);
+
+    let mut schedule = Vec::new();
+    let mut energy = spec_player.stats.spec.value();
+    let mut regen_counter: Option<u32> = None;
+    let mut next_attack = 0;
+    let mut specs = 0;
+
+    for tick in 0.. {
+        if specs >= max_specs {
+            break;
+        }
+
+        if tick == next_attack {
+            if energy >= cost {
+                energy -= cost;
+                specs += 1;
+                regen_counter.get_or_insert(0);
+                schedule.push(SequenceAttack::Spec);
+                next_attack += get_attack_speed(spec_player, true);
+            } else if regen_counter.is_none() {
+                // No energy is regenerating, so no more specs can happen
+                break;
+            } else {
+                schedule.push(SequenceAttack::Main);
+                next_attack += get_attack_speed(main_player, false);
+            }
+        }
+
+        // Energy regenerates every 50 ticks (25 with Lightbearer) after a spec until it's full
+        if let Some(counter) = regen_counter.as_mut() {
+            *counter += 1;
+            if (lightbearer && counter.is_multiple_of(25)) || counter.is_multiple_of(50) {
+                energy = min(energy + constants::SPEC_REGEN, constants::FULL_SPEC);
+            }
+            if energy == constants::FULL_SPEC {
+                regen_counter = None;
+            }
+        }
+    }
+
+    Ok(schedule)
+}
+
+// Apply the stat drains of a special attack to the monster, using the same drain rules as the
+// spec functions. `rolled` is the spec's hit before the monster's post-roll limiters
+fn apply_spec_drains(player: &Player, monster: &mut Monster, rolled: &WeightedHit) {
+    let accurate = rolled.any_accurate();
+    let damage = max(1, rolled.get_sum());
+
+    match player.gear.weapon.name.as_str() {
+        "Dragon warhammer" => drains::drain_defence_percent(monster, 30, accurate),
+        "Elder maul" => drains::drain_defence_percent(monster, 35, accurate),
+        "Bandos godsword" if accurate => drains::bgs_drain(monster, damage),
+        "Barrelchest anchor" if accurate => drains::barrelchest_anchor_drain(monster, damage),
+        "Bone crossbow" | "Bone dagger" if accurate => drains::dorgeshuun_drain(monster, damage),
+        "Arclight" | "Darklight" if accurate => drains::demonbane_drain(monster, false),
+        "Emberlight" if accurate => drains::demonbane_drain(monster, true),
+        "Accursed sceptre" | "Accursed sceptre (a)" if accurate => {
+            drains::accursed_sceptre_drain(monster)
+        }
+        "Seercull" => drains::seercull_drain(monster, damage),
+        "Tonalztics of ralos" => {
+            // Each accurate hit drains defence separately
+            for _ in rolled.hitsplats.iter().filter(|h| h.accurate) {
+                drains::tonalztics_drain(monster);
+            }
+        }
+        "Eye of ayak" if accurate => drains::eye_of_ayak_drain(monster, damage),
+        _ => {}
+    }
+}
+
+// Monster hp distribution for one outcome of the stat drains from specs
+struct DrainBranch {
+    monster: Monster,
+    hps: Vec<f64>,
+    main_dist: Option<AttackDistribution>,
+    hp_hit_dists: HashMap<usize, HitDistribution>,
+}
+
+impl DrainBranch {
+    fn new(monster: Monster, max_hp: usize) -> Self {
+        Self {
+            monster,
+            hps: vec![0.0; max_hp + 1],
+            main_dist: None,
+            hp_hit_dists: HashMap::new(),
+        }
+    }
+}
+
+// Everything a spec drain can change (combat levels and the magic defence bonus), so outcomes are
+// only merged when the drained monsters are identical
+fn drain_key(monster: &Monster) -> [i64; 6] {
+    let stats = &monster.stats;
+    [
+        i64::from(stats.attack.current),
+        i64::from(stats.strength.current),
+        i64::from(stats.defence.current),
+        i64::from(stats.ranged.current),
+        i64::from(stats.magic.current),
+        i64::from(monster.bonuses.defence.magic),
+    ]
+}
+
+// One damage roll of a spec with the hitsplats it deals once the monster's limiters apply.
+// Drains follow the rolled damage, and the monster's hp follows the dealt damage
+struct SpecHit {
+    rolled: WeightedHit,
+    dealt: HitDistribution,
+}
+
+// Possible outcomes of a spec, with the individual hitsplats kept so drains can depend on each
+// hit's accuracy. Specs that always hit on the first attack only keep their accurate rolls then
+fn get_spec_hits(
+    spec_player: &Player,
+    monster: &Monster,
+    first_attack: bool,
+) -> Result<Vec<SpecHit>, DpsCalcError> {
+    let dist = build_distribution(spec_player, monster, true, false)?;
+    let mut rolls = dist.dists[0].clone();
+    for d in &dist.dists[1..] {
+        rolls = rolls.zip(d);
+    }
+
+    if first_attack && drains::spec_always_hits_first_attack(spec_player, monster) {
+        let accurate: Vec<WeightedHit> = rolls
+            .hits
+            .into_iter()
+            .filter(WeightedHit::any_accurate)
+            .collect();
+        let total: f64 = accurate.iter().map(|h| h.probability).sum();
+        rolls = HitDistribution::new(accurate).scale_probability(1.0 / total);
+    }
+
+    Ok(rolls
+        .hits
+        .into_iter()
+        .map(|rolled| {
+            let single =
+                AttackDistribution::new(vec![HitDistribution::new(vec![WeightedHit::new(
+                    1.0,
+                    rolled.hitsplats.clone(),
+                )])]);
+            let dealt = apply_limiters(single, spec_player, monster).dists[0]
+                .flatten()
+                .scale_probability(rolled.probability);
+            SpecHit { rolled, dealt }
+        })
+        .collect())
+}
+
+// Apply a spec to every branch, splitting them by the drains each spec outcome leaves behind
+fn spec_step(
+    branches: Vec<DrainBranch>,
+    spec_player: &Player,
+    max_hp: usize,
+    first_attack: bool,
+    tick: usize,
+    ttks: &mut HashMap<usize, f64>,
+) -> Result<(Vec<DrainBranch>, f64), DpsCalcError> {
+    let mut next_branches: Vec<DrainBranch> = Vec::new();
+    let mut killed = 0.0;
+
+    for branch in branches {
+        // Find the branch each outcome of the spec leads to
+        let mut hits = HitDistribution::default();
+        let mut targets = Vec::new();
+        for spec_hit in get_spec_hits(spec_player, &branch.monster, first_attack)? {
+            let mut monster = branch.monster.clone();
+            apply_spec_drains(spec_player, &mut monster, &spec_hit.rolled);
+            let key = drain_key(&monster);
+            let target = match next_branches
+                .iter()
+                .position(|b| drain_key(&b.monster) == key)
+            {
+                Some(index) => index,
+                None => {
+                    next_branches.push(DrainBranch::new(monster, max_hp));
+                    next_branches.len() - 1
+                }
             };
+            for hit in spec_hit.dealt.hits {
+                hits.add_hit(hit);
+                targets.push(target);
+            }
+        }
 
-            // For each possible damage amount
-            for h in &current_dist.hits {
-                let dmg_prob = h.probability;
-                let dmg = h.hitsplats[0].damage as usize; // Single hitsplat, so guaranteed to be length 1
+        killed += step_hp_distribution(
+            &branch.hps,
+            |_| Ok(&hits),
+            tick,
+            ttks,
+            |index, new_hp, chance| next_branches[targets[index]].hps[new_hp] += chance,
+        )?;
+    }
 
-                // Chance of this path being reached is the previous chance of landing here * the chance of hitting this amount
-                let chance_of_action = dmg_prob * hp_prob;
-                if chance_of_action == 0.0 {
-                    continue;
+    Ok((next_branches, killed))
+}
+
+// Apply a main weapon attack to a branch, caching its hit distributions
+fn main_step(
+    branch: &mut DrainBranch,
+    main_player: &Player,
+    tick: usize,
+    ttks: &mut HashMap<usize, f64>,
+) -> Result<f64, DpsCalcError> {
+    let DrainBranch {
+        monster,
+        hps,
+        main_dist,
+        hp_hit_dists,
+    } = branch;
+
+    if main_dist.is_none() {
+        *main_dist = Some(get_distribution(main_player, monster, false)?);
+    }
+    let dist = main_dist.as_mut().unwrap();
+
+    let recalc_dist_on_hp = dist_is_current_hp_dependent(main_player, monster);
+    if recalc_dist_on_hp {
+        for (hp, prob) in hps.iter().enumerate() {
+            if *prob > 0.0 && !hp_hit_dists.contains_key(&hp) {
+                dist_at_hp(dist, hp, main_player, monster, hp_hit_dists, false)?;
+            }
+        }
+    }
+    let dist_single = dist.get_single_hitsplat();
+
+    let mut next_hps = vec![0.0; hps.len()];
+    let killed = step_hp_distribution(
+        hps,
+        |hp| {
+            if recalc_dist_on_hp {
+                hp_hit_dists
+                    .get(&hp)
+                    .ok_or_else(|| DpsCalcError::MissingHpHitDist {
+                        monster_name: monster.info.name.clone(),
+                        hp,
+                    })
+            } else {
+                Ok(dist_single)
+            }
+        },
+        tick,
+        ttks,
+        |_, new_hp, chance| next_hps[new_hp] += chance,
+    )?;
+    *hps = next_hps;
+
+    Ok(killed)
+}
+
+/// Get the ttk distribution for up to `max_specs` special attacks with the spec player's weapon,
+/// followed by the main player's weapon until the monster dies.
+///
+/// Specs are gated by the spec player's energy (with regen) like in the sims, and each spec's
+/// stat drains apply to the rest of the fight. Spec hits don't rescale with the monster's hp.
+pub fn get_spec_ttk_distribution(
+    spec_player: &Player,
+    main_player: &Player,
+    monster: &Monster,
+    max_specs: u32,
+) -> Result<HashMap<usize, f64>, DpsCalcError> {
+    let schedule = spec_sequence_schedule(spec_player, main_player, max_specs)?;
+    let spec_speed = get_attack_speed(spec_player, true) as usize;
+    let main_speed = get_attack_speed(main_player, false) as usize;
+    let max_hp = monster.stats.hitpoints.current as usize;
+
+    let mut start = DrainBranch::new(monster.clone(), max_hp);
+    start.hps[max_hp] = 1.0;
+    let mut branches = vec![start];
+
+    let mut ttks: HashMap<usize, f64> = HashMap::new();
+    let mut epsilon = 1.0;
+    let mut tick = 0;
+
+    // Scripted attacks first, then the main weapon until the remaining probability is negligible
+    let attacks = schedule.into_iter().chain(std::iter::repeat_n(
+        SequenceAttack::Main,
+        constants::TTK_DIST_MAX_ITER_ROUNDS + 1,
+    ));
+    for attack in attacks {
+        if epsilon < constants::TTK_DIST_EPSILON {
+            break;
+        }
+
+        match attack {
+            SequenceAttack::Spec => {
+                tick += spec_speed;
+                let first_attack = tick == spec_speed;
+                let (next_branches, killed) =
+                    spec_step(branches, spec_player, max_hp, first_attack, tick, &mut ttks)?;
+                branches = next_branches;
+                epsilon -= killed;
+            }
+            SequenceAttack::Main => {
+                tick += main_speed;
+                for branch in &mut branches {
+                    epsilon -= main_step(branch, main_player, tick, &mut ttks)?;
                 }
+            }
+        }
+    }
 
-                let new_hp = hp as i32 - dmg as i32;
+    Ok(ttks)
+}
 
-                // If the hp we are about to arrive at is <= 0, the NPC is killed, the iteration count is the number of hits done,
-                // and we add this probability path into the delta
-                if new_hp <= 0 {
-                    let tick = (hit + 1) * speed;
-                    ttks.insert(tick, ttks.get(&tick).unwrap_or(&0.0) + chance_of_action);
-                    epsilon -= chance_of_action;
-                } else {
-                    // Otherwise, we add the chance of this path to the next iteration's hp value
-                    next_hps[new_hp as usize] += chance_of_action;
+/// Get the expected time to kill (in seconds) for a spec-then-main-weapon sequence.
+pub fn get_spec_ttk(
+    spec_player: &Player,
+    main_player: &Player,
+    monster: &Monster,
+    max_specs: u32,
+) -> Result<f64, DpsCalcError> {
+    let ttk_dist = get_spec_ttk_distribution(spec_player, main_player, monster, max_specs)?;
+
+    Ok(ttk_dist
+        .iter()
+        .map(|(ticks, prob)| *prob * *ticks as f64)
+        .sum::<f64>()
+        * constants::SECONDS_PER_TICK)
+}
+
+// Possible states of the monster after the given specs, with their probabilities
+fn spec_drain_outcomes(
+    specs: &[&Player],
+    monster: &Monster,
+) -> Result<Vec<(Monster, f64)>, DpsCalcError> {
+    let mut outcomes = vec![(monster.clone(), 1.0)];
+
+    for (index, spec_player) in specs.iter().enumerate() {
+        let mut next_outcomes: Vec<(Monster, f64)> = Vec::new();
+        for (state, state_prob) in &outcomes {
+            for spec_hit in get_spec_hits(spec_player, state, index == 0)? {
+                let prob = state_prob * spec_hit.rolled.probability;
+                if prob == 0.0 {
+                    continue;
+                }
+
+                let mut drained = state.clone();
+                apply_spec_drains(spec_player, &mut drained, &spec_hit.rolled);
+                let key = drain_key(&drained);
+                match next_outcomes.iter_mut().find(|(m, _)| drain_key(m) == key) {
+                    Some((_, total)) => *total += prob,
+                    None => next_outcomes.push((drained, prob)),
                 }
             }
         }
+        outcomes = next_outcomes;
+    }
 
-        // Update counters and repeat
-        hps = next_hps;
+    Ok(outcomes)
+}
+
+/// Get the distribution of the monster's defence level after the given specs, in order.
+///
+/// Each spec player should have the spec weapon equipped and rolls calculated against the
+/// monster. The first spec is the first attack of the fight, and specs are assumed not to kill
+/// the monster.
+pub fn get_def_reduction_distribution(
+    specs: &[&Player],
+    monster: &Monster,
+) -> Result<HashMap<u32, f64>, DpsCalcError> {
+    let mut def_levels = HashMap::new();
+    for (drained, prob) in spec_drain_outcomes(specs, monster)? {
+        *def_levels
+            .entry(drained.stats.defence.current)
+            .or_insert(0.0) += prob;
     }
 
-    Ok(ttks)
+    Ok(def_levels)
+}
+
+/// Get the expected main weapon dps after the given specs, averaged over their drain outcomes.
+pub fn get_def_reduced_dps(
+    specs: &[&Player],
+    player: &Player,
+    monster: &Monster,
+) -> Result<f64, DpsCalcError> {
+    spec_drain_outcomes(specs, monster)?
+        .iter()
+        .map(|(drained, prob)| {
+            let dist = get_distribution(player, drained, false)?;
+            Ok(prob * get_dps(&dist, player, false))
+        })
+        .sum()
+}
+
+/// Get the expected main weapon time to kill after the given specs, averaged over their drain
+/// outcomes. The monster starts at full hp, so the specs' own damage and time aren't included
+/// (see `get_spec_ttk` for the full sequence).
+pub fn get_def_reduced_ttk(
+    specs: &[&Player],
+    player: &Player,
+    monster: &Monster,
+    remove_final_hit_delay: bool,
+) -> Result<f64, DpsCalcError> {
+    spec_drain_outcomes(specs, monster)?
+        .iter()
+        .map(|(drained, prob)| {
+            let dist = get_distribution(player, drained, false)?;
+            Ok(prob * get_ttk(&dist, player, drained, false, remove_final_hit_delay)?)
+        })
+        .sum()
 }
 
 fn dist_from_multiple_hits(hits_vec: Vec<Vec<WeightedHit>>) -> AttackDistribution {
@@ -1279,7 +1825,12 @@ fn dist_at_hp<'a>(
     Ok(())
 }
 
-fn get_wardens_p2_min_max(player: &Player, monster: &Monster) -> Result<(u32, u32), DpsCalcError> {
+/// Min and max hit against the Wardens in P2, where every hit lands and accuracy scales the
+/// damage range instead.
+pub fn get_wardens_p2_min_max(
+    player: &Player,
+    monster: &Monster,
+) -> Result<(u32, u32), DpsCalcError> {
     let att_roll = max(
         0,
         player.att_rolls.get(player.combat_type())?
@@ -1433,4 +1984,243 @@ mod tests {
 
         assert!(num::abs(ttk - 236.2) < 0.1);
     }
+
+    fn spec_players(monster: &Monster, weapon: &str, style: CombatStyle) -> (Player, Player) {
+        let mut main_player = crate::utils::loadouts::max_melee_player();
+        calc_active_player_rolls(&mut main_player, monster);
+
+        let mut spec_player = main_player.clone();
+        spec_player.equip(weapon, None).unwrap();
+        spec_player.update_bonuses();
+        spec_player.set_active_style(style);
+        calc_active_player_rolls(&mut spec_player, monster);
+
+        (spec_player, main_player)
+    }
+
+    fn dwh_players(monster: &Monster) -> (Player, Player) {
+        spec_players(monster, "Dragon warhammer", CombatStyle::Pound)
+    }
+
+    #[test]
+    fn test_spec_schedule_waits_for_regen() {
+        let monster = Monster::new("General Graardor", None).expect("Error creating monster.");
+        let (spec_player, main_player) = dwh_players(&monster);
+
+        let schedule = spec_sequence_schedule(&spec_player, &main_player, 3).unwrap();
+
+        // Two specs from full energy, then rapier attacks until 50% has regenerated at tick 250
+        assert_eq!(schedule[..2], [SequenceAttack::Spec, SequenceAttack::Spec]);
+        assert_eq!(schedule.last(), Some(&SequenceAttack::Spec));
+        assert_eq!(
+            schedule
+                .iter()
+                .filter(|a| **a == SequenceAttack::Main)
+                .count(),
+            60
+        );
+    }
+
+    #[test]
+    fn test_spec_ttk_matches_simulation() {
+        use crate::combat::simulation::simulate_n_fights;
+        use crate::combat::spec::{SpecConfig, SpecRestorePolicy, SpecStrategy};
+        use crate::sims::single_way::{SingleWayConfig, SingleWayFight};
+        use crate::types::player::{GearSwitch, SwitchType};
+
+        let monster = Monster::new("General Graardor", None).expect("Error creating monster.");
+        let no_spec_ticks = {
+            let (_, main_player) = dwh_players(&monster);
+            get_ttk(
+                &get_distribution(&main_player, &monster, false).unwrap(),
+                &main_player,
+                &monster,
+                false,
+                false,
+            )
+            .unwrap()
+                / constants::SECONDS_PER_TICK
+        };
+
+        // Each spec's drains come from the same rules in the calc and the sim
+        for (weapon, style) in [
+            ("Dragon warhammer", CombatStyle::Pound),
+            ("Bandos godsword", CombatStyle::Slash),
+            ("Elder maul", CombatStyle::Pound),
+        ] {
+            let (spec_player, main_player) = spec_players(&monster, weapon, style);
+            let analytic_ticks = get_spec_ttk(&spec_player, &main_player, &monster, 2).unwrap()
+                / constants::SECONDS_PER_TICK;
+
+            let mut player = main_player.clone();
+            let switch = GearSwitch::new(SwitchType::Spec(weapon.into()), &spec_player, &monster);
+            player.switches.push(switch.clone());
+            let spec_config = SpecConfig::new(
+                vec![SpecStrategy::builder(&switch).with_max_attempts(2).build()],
+                SpecRestorePolicy::RestoreEveryKill,
+                None,
+                false,
+            );
+            let fight = SingleWayFight::new(
+                player,
+                monster.clone(),
+                SingleWayConfig::default(),
+                Some(spec_config),
+                false,
+            )
+            .unwrap();
+            let results = simulate_n_fights(Box::new(fight), 20_000, false, Some(3)).unwrap();
+            let simulated_ticks =
+                results.ttks_ticks.iter().sum::<i32>() as f64 / results.ttks_ticks.len() as f64;
+
+            assert!(analytic_ticks < no_spec_ticks, "{weapon}");
+            assert!(
+                (analytic_ticks - simulated_ticks).abs() / simulated_ticks < 0.01,
+                "{weapon}: {analytic_ticks} analytic vs {simulated_ticks} simulated"
+            );
+        }
+    }
+
+    #[test]
+    fn test_tekton_first_spec_always_hits() {
+        let monster = Monster::new("Tekton", Some("Normal")).expect("Error creating monster.");
+        let (spec_player, _) = dwh_players(&monster);
+        let base = monster.stats.defence.current;
+        let hit_level = base - base * 3 / 10;
+
+        // The first spec always lands, and a later miss still drains 5%
+        let first = get_def_reduction_distribution(&[&spec_player], &monster).unwrap();
+        assert_eq!(first.keys().copied().collect::<Vec<_>>(), vec![hit_level]);
+
+        let second =
+            get_def_reduction_distribution(&[&spec_player, &spec_player], &monster).unwrap();
+        let mut levels: Vec<u32> = second.keys().copied().collect();
+        levels.sort_unstable();
+        assert_eq!(
+            levels,
+            vec![hit_level - hit_level * 3 / 10, hit_level - hit_level / 20]
+        );
+    }
+
+    #[test]
+    fn test_dwh_def_reduction_distribution() {
+        let monster = Monster::new("General Graardor", None).expect("Error creating monster.");
+        let (spec_player, main_player) = dwh_players(&monster);
+
+        let def_levels =
+            get_def_reduction_distribution(&[&spec_player, &spec_player], &monster).unwrap();
+
+        // Each landed DWH spec drains 30% of Graardor's current defence: 250 -> 175 -> 123
+        let mut levels: Vec<u32> = def_levels.keys().copied().collect();
+        levels.sort_unstable();
+        assert_eq!(levels, vec![123, 175, 250]);
+        assert!((def_levels.values().sum::<f64>() - 1.0).abs() < 1e-9);
+
+        let base_dist = get_distribution(&main_player, &monster, false).unwrap();
+        let base_dps = get_dps(&base_dist, &main_player, false);
+        let base_ttk = get_ttk(&base_dist, &main_player, &monster, false, false).unwrap();
+        let specs = [&spec_player, &spec_player];
+        assert!(get_def_reduced_dps(&specs, &main_player, &monster).unwrap() > base_dps);
+        assert!(get_def_reduced_ttk(&specs, &main_player, &monster, false).unwrap() < base_ttk);
+
+        // No specs leaves the monster untouched
+        assert!(
+            (get_def_reduced_dps(&[], &main_player, &monster).unwrap() - base_dps).abs() < 1e-9
+        );
+    }
+
+    #[test]
+    fn test_bgs_def_reduction_distribution() {
+        let monster = Monster::new("General Graardor", None).expect("Error creating monster.");
+        let (_, main_player) = dwh_players(&monster);
+        let mut bgs_player = main_player.clone();
+        bgs_player.equip("Bandos godsword", None).unwrap();
+        bgs_player.update_bonuses();
+        bgs_player.set_active_style(CombatStyle::Slash);
+        calc_active_player_rolls(&mut bgs_player, &monster);
+
+        let def_levels = get_def_reduction_distribution(&[&bgs_player], &monster).unwrap();
+        let miss_chance = 1.0 - get_hit_chance(&bgs_player, &monster, true).unwrap();
+        let expected_drain = def_levels
+            .iter()
+            .map(|(level, prob)| (250 - level) as f64 * prob)
+            .sum::<f64>();
+
+        // BGS drains defence by the damage dealt, and only misses leave it at 250
+        assert!((def_levels[&250] - miss_chance).abs() < 1e-9);
+        assert!(def_levels.len() > 40);
+        assert!(expected_drain > 20.0);
+    }
+
+    #[test]
+    fn test_drain_outcomes_track_every_drained_stat() {
+        // Eye of ayak only drains the magic defence bonus, which still changes the state
+        let monster = Monster::new("Vorkath", Some("Post-quest")).expect("Error creating monster.");
+        let mut drained = monster.clone();
+        drains::eye_of_ayak_drain(&mut drained, 30);
+        assert_eq!(drained.stats.defence.current, monster.stats.defence.current);
+        assert_eq!(
+            drained.bonuses.defence.magic,
+            monster.bonuses.defence.magic - 30
+        );
+        assert!(
+            drained.def_rolls.get(CombatType::Magic) < monster.def_rolls.get(CombatType::Magic)
+        );
+        assert_ne!(drain_key(&drained), drain_key(&monster));
+
+        // BGS drains past defence into strength and attack on a low-defence monster
+        let monster = Monster::new("Goblin", Some("Level 13")).unwrap();
+        let (bgs_player, _) = spec_players(&monster, "Bandos godsword", CombatStyle::Slash);
+        let outcomes = spec_drain_outcomes(&[&bgs_player], &monster).unwrap();
+        let drained_def = outcomes
+            .iter()
+            .filter(|(m, _)| m.stats.defence.current == 1)
+            .count();
+        assert!(drained_def > 1);
+    }
+
+    #[test]
+    fn test_thrall_ttk_matches_simulation() {
+        use crate::combat::simulation::simulate_n_fights;
+        use crate::sims::single_way::{SingleWayConfig, SingleWayFight};
+
+        let monster = Monster::new("General Graardor", None).expect("Error creating monster.");
+        let (_, player) = dwh_players(&monster);
+        let thrall = Some(Thrall::GreaterMagic);
+
+        let dist = get_distribution(&player, &monster, false).unwrap();
+        let ttk = get_ttk(&dist, &player, &monster, false, false).unwrap();
+        let thrall_ttk = get_ttk_with_thrall(&dist, &player, &monster, false, false, thrall)
+            .unwrap()
+            / constants::SECONDS_PER_TICK;
+
+        let config = SingleWayConfig {
+            thralls: thrall,
+            ..SingleWayConfig::default()
+        };
+        let fight = SingleWayFight::new(player.clone(), monster.clone(), config, None, false)
+            .expect("Error setting up fight.");
+        let results = simulate_n_fights(Box::new(fight), 20_000, false, Some(5)).unwrap();
+        let simulated_ticks =
+            results.ttks_ticks.iter().sum::<i32>() as f64 / results.ttks_ticks.len() as f64;
+
+        assert!(thrall_ttk * constants::SECONDS_PER_TICK < ttk);
+        assert!((thrall_ttk - simulated_ticks).abs() / simulated_ticks < 0.01);
+        assert!(
+            (get_dps_with_thrall(&dist, &player, &monster, false, thrall)
+                - get_dps(&dist, &player, false)
+                - 1.5 / 4.0 / constants::SECONDS_PER_TICK)
+                .abs()
+                < 1e-9
+        );
+    }
+
+    #[test]
+    fn test_thrall_immunity() {
+        let monster = Monster::new("Kraken", Some("Kraken")).expect("Error creating monster.");
+
+        assert_eq!(get_thrall_dps(Some(Thrall::GreaterMelee), &monster), 0.0);
+        assert!(get_thrall_dps(Some(Thrall::GreaterMagic), &monster) > 0.0);
+        assert_eq!(get_thrall_dps(None, &monster), 0.0);
+    }
 }
@@ -2,4 +2,5 @@ pub mod analysis;
 pub mod dps_calc;
 pub mod hit_dist;
 pub mod monster_scaling;
+pub mod optimizer;
 pub mod rolls;
@@ -126,3 +126,46 @@ pub fn build_vard_scaling_table(monster: &Monster) -> HpScalingTable {
 
     HpScalingTable::new(table)
 }
+
+/// How a monster's HP scales with the number of players fighting it.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum PartyScaling {
+    #[default]
+    None,
+    Tob,         // 75% HP for 1-3 players, 87.5% for 4 and full HP for 5
+    Toa, // +90% HP for each of the second and third players, then +60% for each player after
+    Linear(f64), // Extra fraction of base HP for each player beyond the first
+}
+
+impl PartyScaling {
+    pub fn hp_multiplier(&self, party_size: u32) -> f64 {
+        let extra_players = party_size.max(1) - 1;
+        match self {
+            Self::None => 1.0,
+            Self::Tob => match party_size {
+                0..=3 => 0.75,
+                4 => 0.875,
+                _ => 1.0,
+            },
+            Self::Toa => {
+                let first_two = extra_players.min(2) as f64;
+                let rest = extra_players.saturating_sub(2) as f64;
+                1.0 + 0.9 * first_two + 0.6 * rest
+            }
+            Self::Linear(per_player) => 1.0 + per_player * extra_players as f64,
+        }
+    }
+}
+
+/// Scale a monster's base HP for the party size and reset it to full HP.
+///
+/// This multiplies the current base HP, so it should only be applied once per monster.
+pub fn scale_monster_party_hp(monster: &mut Monster, party_size: u32, scaling: PartyScaling) {
+    let multiplier = scaling.hp_multiplier(party_size);
+    if multiplier == 1.0 {
+        return;
+    }
+    let scaled = (monster.stats.hitpoints.base as f64 * multiplier).floor() as u32;
+    monster.stats.hitpoints.base = scaled.max(1);
+    monster.reset();
+}
@@ -0,0 +1,1189 @@
+use crate::calc::dps_calc::{get_distribution, get_dps, get_ttk};
+use crate::calc::rolls::{calc_active_player_rolls, has_magic_max_hit};
+use crate::constants;
+use crate::error::{GearError, OptimizerError};
+use crate::types::bank::Bank;
+use crate::types::equipment::{
+    Armor, CombatStance, CombatStyle, CombatType, EquipmentBonuses, Gear, GearSlot, Weapon,
+    all_equipment,
+};
+use crate::types::monster::Monster;
+use crate::types::player::Player;
+use crate::types::potions::Potion;
+use crate::types::prayers::{Prayer, PrayerBoost};
+use crate::types::spells::Spell;
+use crate::types::stats::PlayerStats;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use strum_macros::Display;
+
+// Armor slots searched by the optimizer, in the order they are improved
+const ARMOR_SLOTS: [GearSlot; 10] = [
+    GearSlot::Head,
+    GearSlot::Body,
+    GearSlot::Legs,
+    GearSlot::Neck,
+    GearSlot::Cape,
+    GearSlot::Hands,
+    GearSlot::Feet,
+    GearSlot::Ring,
+    GearSlot::Shield,
+    GearSlot::Ammo,
+];
+
+// Items with effects that aren't captured by their stats, on top of the sets and bolts in constants
+const EFFECT_ITEMS: [(&str, Option<&str>); 6] = [
+    ("Crystal helm", Some("Active")),
+    ("Crystal body", Some("Active")),
+    ("Crystal legs", Some("Active")),
+    ("Dizana's quiver", Some("Charged")),
+    ("Amulet of avarice", None),
+    ("Berserker necklace", None),
+];
+
+// Upper bound on improvement passes over all slots for a single starting point
+const MAX_PASSES: usize = 5;
+
+const DEFAULT_CANDIDATE_WEAPONS: usize = 10;
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Display, serde::Serialize, serde::Deserialize,
+)]
+pub enum GearStyle {
+    #[default]
+    Melee,
+    Ranged,
+    Magic,
+}
+
+impl GearStyle {
+    pub fn includes(&self, combat_type: CombatType) -> bool {
+        match self {
+            GearStyle::Melee => matches!(
+                combat_type,
+                CombatType::Stab | CombatType::Slash | CombatType::Crush
+            ),
+            GearStyle::Ranged => matches!(
+                combat_type,
+                CombatType::Light | CombatType::Standard | CombatType::Heavy
+            ),
+            GearStyle::Magic => combat_type == CombatType::Magic,
+        }
+    }
+
+    fn offensive_bonuses(&self, bonuses: &EquipmentBonuses) -> Vec<f32> {
+        // Bonuses that can affect the damage output of this style
+        match self {
+            GearStyle::Melee => vec![
+                bonuses.attack.stab as f32,
+                bonuses.attack.slash as f32,
+                bonuses.attack.crush as f32,
+                bonuses.strength.melee as f32,
+            ],
+            GearStyle::Ranged => vec![bonuses.attack.ranged as f32, bonuses.strength.ranged as f32],
+            GearStyle::Magic => vec![bonuses.attack.magic as f32, bonuses.strength.magic],
+        }
+    }
+
+    fn uses_prayer(&self, prayer: Prayer) -> bool {
+        let boost = PrayerBoost::new(prayer);
+        match self {
+            GearStyle::Melee => boost.attack > 0 || boost.strength > 0,
+            GearStyle::Ranged => boost.ranged_att > 0 || boost.ranged_str > 0,
+            GearStyle::Magic => boost.magic_att > 0 || boost.magic_str > 0,
+        }
+    }
+
+    fn set_seeds(&self) -> Vec<Vec<(&'static str, Option<&'static str>)>> {
+        // Full sets whose bonus only applies once every piece is worn
+        let helm = match self {
+            GearStyle::Melee => "Void melee helm",
+            GearStyle::Ranged => "Void ranger helm",
+            GearStyle::Magic => "Void mage helm",
+        };
+        let mut seeds = vec![
+            vec![
+                (helm, None),
+                ("Void knight top", None),
+                ("Void knight robe", None),
+                ("Void knight gloves", None),
+            ],
+            vec![
+                (helm, None),
+                ("Elite void top", None),
+                ("Elite void robe", None),
+                ("Void knight gloves", None),
+            ],
+        ];
+
+        match self {
+            GearStyle::Melee => {
+                seeds.push(constants::FULL_INQUISITOR.to_vec());
+                seeds.push(constants::FULL_OBSIDIAN.to_vec());
+            }
+            GearStyle::Ranged => seeds.push(vec![
+                ("Crystal helm", Some("Active")),
+                ("Crystal body", Some("Active")),
+                ("Crystal legs", Some("Active")),
+            ]),
+            GearStyle::Magic => {}
+        }
+
+        seeds
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum OptimizeTarget {
+    #[default]
+    Dps,
+    Ttk,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankedGear {
+    pub gear: Gear,
+    pub style: CombatStyle,
+    pub dps: f64,
+    pub ttk: f64, // Expected time to kill in seconds
+}
+
+// Candidate items after filtering and pruning
+#[derive(Debug, Clone, Default)]
+struct ItemPool {
+    weapons: Vec<Weapon>,
+    armor: HashMap<GearSlot, Vec<Armor>>,
+}
+
+impl ItemPool {
+    fn armor(&self, slot: GearSlot) -> &[Armor] {
+        self.armor.get(&slot).map_or(&[], Vec::as_slice)
+    }
+
+    fn find(&self, slot: GearSlot, name: &str, version: Option<&str>) -> Option<&Armor> {
+        self.armor(slot)
+            .iter()
+            .find(|a| a.name == name && a.version.as_deref() == version)
+    }
+}
+
+/// Searches the equipment database (or a list of allowed items) for the best gear setups
+/// against a monster.
+///
+/// Weapons are ranked with a quick estimate first, then the best few are fully searched by
+/// improving one slot at a time from several starting points, including full sets like void
+/// and inquisitor's that only pay off once every piece is worn. Setups are built to maximise
+/// DPS, then the finished setups are ranked by DPS or expected time to kill.
+///
+/// # Example
+/// ```
+/// use osrs::calc::optimizer::{GearOptimizer, GearStyle};
+/// use osrs::types::monster::Monster;
+/// use osrs::types::potions::Potion;
+/// use osrs::types::prayers::Prayer;
+///
+/// let monster = Monster::new("Ammonite Crab", None)?;
+/// let optimizer = GearOptimizer::builder(monster)
+///     .style(GearStyle::Melee)
+///     .potion(Potion::SuperCombat)
+///     .prayer(Prayer::Piety)
+///     .item("Abyssal whip", None)
+///     .item("Ghrazi rapier", None)
+///     .item("Dragon defender", None)
+///     .item("Bandos chestplate", None)
+///     .item("Fighter torso", None)
+///     .top_k(2)
+///     .build()?;
+///
+/// let best = optimizer.optimize()?;
+/// assert_eq!(best[0].gear.weapon.name, "Ghrazi rapier");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct GearOptimizer {
+    monster: Monster,
+    player: Player,
+    spell: Option<Spell>,
+    style: GearStyle,
+    target: OptimizeTarget,
+    top_k: usize,
+    candidate_weapons: usize,
+    pool: ItemPool,
+}
+
+impl GearOptimizer {
+    pub fn builder(monster: Monster) -> GearOptimizerBuilder {
+        GearOptimizerBuilder::new(monster)
+    }
+
+    /// Find the top-k gear setups, best first. Each weapon appears at most once.
+    pub fn optimize(&self) -> Result<Vec<RankedGear>, OptimizerError> {
+        let weapons = self.rank_weapons();
+        if weapons.is_empty() {
+            return Err(OptimizerError::NoUsableWeapons(self.style));
+        }
+
+        let mut results = Vec::new();
+        for (weapon, style) in weapons {
+            let mut best: Option<(f64, Player)> = None;
+            for start in self.starting_points(weapon) {
+                let mut player = self.player.clone();
+                player.gear = Arc::new(start);
+                let Some(dps) = self.climb(&mut player, style) else {
+                    continue;
+                };
+                if best.as_ref().is_none_or(|(b, _)| dps > *b) {
+                    best = Some((dps, player));
+                }
+            }
+            if let Some((_, mut player)) = best {
+                results.push(self.ranked(&mut player, style));
+            }
+        }
+
+        if results.is_empty() {
+            return Err(OptimizerError::NoUsableWeapons(self.style));
+        }
+
+        match self.target {
+            OptimizeTarget::Dps => {
+                results.sort_by(|a, b| b.dps.partial_cmp(&a.dps).unwrap_or(Ordering::Equal));
+            }
+            OptimizeTarget::Ttk => {
+                results.sort_by(|a, b| a.ttk.partial_cmp(&b.ttk).unwrap_or(Ordering::Equal));
+            }
+        }
+        results.truncate(self.top_k);
+
+        Ok(results)
+    }
+
+    fn rank_weapons(&self) -> Vec<(&Weapon, CombatStyle)> {
+        // Estimate each weapon with the highest-stat armor and keep the best few, along with
+        // their best combat style
+        let mut scored: Vec<(f64, &Weapon, CombatStyle)> = self
+            .pool
+            .weapons
+            .iter()
+            .filter_map(|weapon| {
+                let gear = self.heuristic_gear(weapon);
+                self.weapon_styles(weapon)
+                    .into_iter()
+                    .filter_map(|style| {
+                        let mut player = self.player.clone();
+                        player.gear = Arc::new(gear.clone());
+                        self.evaluate(&mut player, style)
+                            .map(|score| (score, style))
+                    })
+                    .max_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal))
+                    .map(|(score, style)| (score, weapon, style))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
+
+        // Only search the best version of each weapon
+        let mut seen = HashSet::new();
+        scored
+            .into_iter()
+            .filter(|(_, weapon, _)| seen.insert(weapon.name.as_str()))
+            .map(|(_, weapon, style)| (weapon, style))
+            .take(self.candidate_weapons.max(self.top_k))
+            .collect()
+    }
+
+    fn weapon_styles(&self, weapon: &Weapon) -> Vec<CombatStyle> {
+        let mut styles: Vec<CombatStyle> = weapon
+            .combat_styles
+            .iter()
+            .filter(|(_, option)| self.style.includes(option.combat_type))
+            .filter(|(_, option)| {
+                // Autocasting needs a spell to cast
+                self.spell.is_some()
+                    || !matches!(
+                        option.stance,
+                        CombatStance::Autocast | CombatStance::DefensiveAutocast
+                    )
+            })
+            .map(|(style, _)| *style)
+            .collect();
+        styles.sort_by_key(|style| *style as u8);
+        styles
+    }
+
+    fn heuristic_gear(&self, weapon: &Weapon) -> Gear {
+        // Highest-stat item in each slot for the weapon's main combat type
+        let combat_type = weapon
+            .combat_styles
+            .values()
+            .map(|option| option.combat_type)
+            .find(|combat_type| self.style.includes(*combat_type))
+            .unwrap_or_default();
+
+        let mut gear = Gear {
+            weapon: weapon.clone(),
+            ..Gear::default()
+        };
+        for slot in ARMOR_SLOTS {
+            if !self.slot_available(slot, weapon) {
+                continue;
+            }
+            *armor_slot(&mut gear, slot) = self
+                .candidates(slot, weapon)
+                .map(|armor| (heuristic_score(&armor.bonuses, combat_type), armor))
+                .filter(|(score, _)| *score > 0.0)
+                .max_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal))
+                .map(|(_, armor)| armor.clone());
+        }
+
+        gear
+    }
+
+    fn starting_points(&self, weapon: &Weapon) -> Vec<Gear> {
+        let base = self.heuristic_gear(weapon);
+        let mut starts = vec![base.clone()];
+
+        // Set bonuses are all-or-nothing, so each available set gets its own starting point
+        for set in self.style.set_seeds() {
+            let pieces: Option<Vec<&Armor>> = set
+                .iter()
+                .map(|(name, version)| {
+                    ARMOR_SLOTS
+                        .iter()
+                        .find_map(|slot| self.pool.find(*slot, name, *version))
+                })
+                .collect();
+            if let Some(pieces) = pieces {
+                let mut gear = base.clone();
+                for piece in pieces {
+                    *armor_slot(&mut gear, piece.slot) = Some(piece.clone());
+                }
+                starts.push(gear);
+            }
+        }
+
+        starts
+    }
+
+    fn slot_available(&self, slot: GearSlot, weapon: &Weapon) -> bool {
+        !(slot == GearSlot::Shield && weapon.is_two_handed)
+    }
+
+    fn candidates<'a>(
+        &'a self,
+        slot: GearSlot,
+        weapon: &'a Weapon,
+    ) -> impl Iterator<Item = &'a Armor> + 'a {
+        self.pool
+            .armor(slot)
+            .iter()
+            .filter(move |armor| slot != GearSlot::Ammo || ammo_fits(weapon, armor))
+    }
+
+    fn climb(&self, player: &mut Player, style: CombatStyle) -> Option<f64> {
+        // Improve one slot at a time until no single swap helps
+        let weapon = player.gear.weapon.clone();
+        let mut best = self.evaluate(player, style);
+
+        for _ in 0..MAX_PASSES {
+            let mut improved = false;
+            for slot in ARMOR_SLOTS {
+                if !self.slot_available(slot, &weapon) {
+                    continue;
+                }
+
+                let current = armor_slot(Arc::make_mut(&mut player.gear), slot).clone();
+                let mut best_item = current.clone();
+                for candidate in
+                    std::iter::once(None).chain(self.candidates(slot, &weapon).map(Some))
+                {
+                    if candidate == current.as_ref() {
+                        continue;
+                    }
+                    *armor_slot(Arc::make_mut(&mut player.gear), slot) = candidate.cloned();
+                    if let Some(score) = self.evaluate(player, style)
+                        && best.is_none_or(|b| score > b + f64::EPSILON)
+                    {
+                        best = Some(score);
+                        best_item = candidate.cloned();
+                        improved = true;
+                    }
+                }
+                *armor_slot(Arc::make_mut(&mut player.gear), slot) = best_item;
+            }
+
+            if !improved {
+                break;
+            }
+        }
+
+        // Leave the player's rolls matching the final gear
+        self.evaluate(player, style)
+    }
+
+    fn evaluate(&self, player: &mut Player, style: CombatStyle) -> Option<f64> {
+        // TTK is too slow to compute for every swap when the hit distribution depends on the
+        // monster's HP, so setups are built by DPS and only ranked by TTK at the end
+        self.calc(player, style, false).map(|(dps, _)| dps)
+    }
+
+    fn calc(&self, player: &mut Player, style: CombatStyle, with_ttk: bool) -> Option<(f64, f64)> {
+        // Only autocasting uses the spell, powered staves have their own max hit
+        player.attrs.spell = match player.gear.weapon.combat_styles[&style].stance {
+            CombatStance::Autocast | CombatStance::DefensiveAutocast => self.spell,
+            _ => None,
+        };
+        player.set_quiver_bonuses();
+        player.set_active_style(style);
+        player.update_bonuses();
+        player.update_set_effects();
+        if self.monster.is_immune(player) || (player.is_using_magic() && !has_magic_max_hit(player))
+        {
+            return None;
+        }
+
+        calc_active_player_rolls(player, &self.monster);
+        let dist = get_distribution(player, &self.monster, false).ok()?;
+        let dps = get_dps(&dist, player, false);
+        if dps <= 0.0 || !dps.is_finite() {
+            return None;
+        }
+
+        let ttk = if with_ttk {
+            get_ttk(&dist, player, &self.monster, false, false)
+                .ok()
+                .filter(|ttk| ttk.is_finite())?
+        } else {
+            0.0
+        };
+
+        Some((dps, ttk))
+    }
+
+    fn ranked(&self, player: &mut Player, style: CombatStyle) -> RankedGear {
+        let (dps, ttk) = self
+            .calc(player, style, true)
+            .unwrap_or((0.0, f64::INFINITY));
+        RankedGear {
+            gear: (*player.gear).clone(),
+            style,
+            dps,
+            ttk,
+        }
+    }
+
+    fn best_upgrade(
+        &self,
+        all_items: &GearOptimizer,
+        best: Option<&RankedGear>,
+        bank: &Bank,
+    ) -> Option<Upgrade> {
+        // Best setup after adding one unowned item: either a piece of armor swapped into the
+        // current best setup, or a new weapon searched with the owned armor
+        let mut upgrades = Vec::new();
+
+        if let Some(best) = best {
+            let mut player = self.player.clone();
+            player.gear = Arc::new(best.gear.clone());
+            let weapon = best.gear.weapon.clone();
+
+            let mut best_swap: Option<(f64, GearSlot, &Armor)> = None;
+            for slot in ARMOR_SLOTS {
+                if !self.slot_available(slot, &weapon) {
+                    continue;
+                }
+                let current = armor_slot(Arc::make_mut(&mut player.gear), slot).clone();
+                for candidate in all_items.candidates(slot, &weapon) {
+                    if bank.owns(&candidate.name, candidate.version.as_deref()) {
+                        continue;
+                    }
+                    *armor_slot(Arc::make_mut(&mut player.gear), slot) = Some(candidate.clone());
+                    if let Some(dps) = self.evaluate(&mut player, best.style)
+                        && best_swap.is_none_or(|(b, _, _)| dps > b)
+                    {
+                        best_swap = Some((dps, slot, candidate));
+                    }
+                }
+                *armor_slot(Arc::make_mut(&mut player.gear), slot) = current;
+            }
+
+            if let Some((_, slot, armor)) = best_swap {
+                *armor_slot(Arc::make_mut(&mut player.gear), slot) = Some(armor.clone());
+                upgrades.push(Upgrade {
+                    name: armor.name.clone(),
+                    version: armor.version.clone(),
+                    setup: self.ranked(&mut player, best.style),
+                    dps_gain: 0.0,
+                });
+            }
+        }
+
+        let mut with_new_weapon = self.clone();
+        with_new_weapon.top_k = 1;
+        with_new_weapon.target = OptimizeTarget::Dps;
+        with_new_weapon.pool.weapons = all_items
+            .pool
+            .weapons
+            .iter()
+            .filter(|weapon| !bank.owns(&weapon.name, weapon.version.as_deref()))
+            .cloned()
+            .collect();
+        if let Ok(setup) = with_new_weapon.optimize()
+            && let Some(setup) = setup.into_iter().next()
+        {
+            upgrades.push(Upgrade {
+                name: setup.gear.weapon.name.clone(),
+                version: setup.gear.weapon.version.clone(),
+                setup,
+                dps_gain: 0.0,
+            });
+        }
+
+        upgrades.into_iter().max_by(|a, b| {
+            a.setup
+                .dps
+                .partial_cmp(&b.setup.dps)
+                .unwrap_or(Ordering::Equal)
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Upgrade {
+    pub name: String,
+    pub version: Option<String>,
+    pub setup: RankedGear, // Best setup found once the item is added
+    pub dps_gain: f64,     // Gain over the best owned setup of any style
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BankReport {
+    pub melee: Option<RankedGear>,
+    pub ranged: Option<RankedGear>,
+    pub magic: Option<RankedGear>,
+    pub best_upgrade: Option<Upgrade>,
+}
+
+impl BankReport {
+    pub fn best(&self) -> Option<&RankedGear> {
+        // Highest DPS setup of any style
+        [&self.melee, &self.ranged, &self.magic]
+            .into_iter()
+            .flatten()
+            .max_by(|a, b| a.dps.partial_cmp(&b.dps).unwrap_or(Ordering::Equal))
+    }
+}
+
+/// Find the best melee, ranged and magic setups from the items in a player's bank, plus the
+/// single unowned item that would raise their best DPS against the monster the most.
+///
+/// The builder's stats, potions, prayers and spell are shared by all three styles, while its
+/// style and item list are replaced. Styles without an owned weapon have no setup.
+///
+/// # Example
+/// ```
+/// use osrs::calc::optimizer::{GearOptimizer, best_in_bank};
+/// use osrs::types::bank::Bank;
+/// use osrs::types::monster::Monster;
+/// use osrs::types::prayers::Prayer;
+///
+/// let bank = Bank::parse("Abyssal whip\nDragon defender\nFighter torso\nCoins")?;
+/// let monster = Monster::new("Ammonite Crab", None)?;
+/// let builder = GearOptimizer::builder(monster)
+///     .prayer(Prayer::Piety)
+///     .prayer(Prayer::Rigour)
+///     .prayer(Prayer::Augury);
+///
+/// let report = best_in_bank(builder, &bank)?;
+/// assert_eq!(report.melee.unwrap().gear.weapon.name, "Abyssal whip");
+/// assert!(report.ranged.is_none());
+/// assert!(report.best_upgrade.is_some_and(|upgrade| upgrade.dps_gain > 0.0));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn best_in_bank(
+    builder: GearOptimizerBuilder,
+    bank: &Bank,
+) -> Result<BankReport, OptimizerError> {
+    let mut report = BankReport::default();
+    let mut upgrades = Vec::new();
+
+    for style in [GearStyle::Melee, GearStyle::Ranged, GearStyle::Magic] {
+        let all_items = GearOptimizerBuilder {
+            style,
+            items: None,
+            bank: None,
+            top_k: 1,
+            ..builder.clone()
+        };
+        let owned = all_items.clone().bank(bank).build()?;
+        let all_items = all_items.build()?;
+
+        let best = match owned.optimize() {
+            Ok(setups) => setups.into_iter().next(),
+            Err(OptimizerError::NoUsableWeapons(_)) => None,
+            Err(e) => return Err(e),
+        };
+        upgrades.extend(owned.best_upgrade(&all_items, best.as_ref(), bank));
+
+        match style {
+            GearStyle::Melee => report.melee = best,
+            GearStyle::Ranged => report.ranged = best,
+            GearStyle::Magic => report.magic = best,
+        }
+    }
+
+    // Upgrades only count if they beat the best owned setup, whatever its style
+    let best_dps = report.best().map_or(0.0, |best| best.dps);
+    report.best_upgrade = upgrades
+        .into_iter()
+        .map(|upgrade| Upgrade {
+            dps_gain: upgrade.setup.dps - best_dps,
+            ..upgrade
+        })
+        .filter(|upgrade| upgrade.dps_gain > 0.0)
+        .max_by(|a, b| {
+            a.dps_gain
+                .partial_cmp(&b.dps_gain)
+                .unwrap_or(Ordering::Equal)
+        });
+
+    Ok(report)
+}
+
+fn armor_slot(gear: &mut Gear, slot: GearSlot) -> &mut Option<Armor> {
+    match slot {
+        GearSlot::Head => &mut gear.head,
+        GearSlot::Neck => &mut gear.neck,
+        GearSlot::Cape => &mut gear.cape,
+        GearSlot::Ammo => &mut gear.ammo,
+        GearSlot::Shield => &mut gear.shield,
+        GearSlot::Body => &mut gear.body,
+        GearSlot::Legs => &mut gear.legs,
+        GearSlot::Hands => &mut gear.hands,
+        GearSlot::Feet => &mut gear.feet,
+        GearSlot::Ring => &mut gear.ring,
+        GearSlot::Weapon | GearSlot::None => unreachable!("{slot} is not an armor slot"),
+    }
+}
+
+fn heuristic_score(bonuses: &EquipmentBonuses, combat_type: CombatType) -> f64 {
+    // Rough value of an item's stats, weighting strength above accuracy
+    let (attack, strength) = match combat_type {
+        CombatType::Stab => (bonuses.attack.stab, bonuses.strength.melee as f32),
+        CombatType::Slash => (bonuses.attack.slash, bonuses.strength.melee as f32),
+        CombatType::Crush => (bonuses.attack.crush, bonuses.strength.melee as f32),
+        CombatType::Magic => (bonuses.attack.magic, bonuses.strength.magic),
+        _ => (bonuses.attack.ranged, bonuses.strength.ranged as f32),
+    };
+    f64::from(attack) + 3.0 * f64::from(strength)
+}
+
+fn ammo_fits(weapon: &Weapon, ammo: &Armor) -> bool {
+    // Non-ranged ammo (blessings, etc.) can always be worn
+    if !ammo.is_valid_ranged_ammo() {
+        return true;
+    }
+
+    if constants::USES_OWN_AMMO.contains(&(weapon.name.as_str(), weapon.version.as_deref())) {
+        return false;
+    }
+
+    if weapon.name.contains("ballista") {
+        ammo.name.contains("javelin")
+    } else if weapon.name.contains("rossbow") {
+        ammo.is_bolt()
+    } else if weapon
+        .combat_styles
+        .values()
+        .any(|option| option.combat_type == CombatType::Standard)
+    {
+        ammo.is_arrow()
+    } else {
+        false
+    }
+}
+
+fn is_effect_item(armor: &Armor) -> bool {
+    // Never prune items whose value depends on the monster, the rest of the set or the weapon
+    let item = (armor.name.as_str(), armor.version.as_deref());
+    EFFECT_ITEMS
+        .iter()
+        .chain(constants::FULL_VOID.iter())
+        .chain(constants::FULL_INQUISITOR.iter())
+        .chain(constants::FULL_OBSIDIAN.iter())
+        .chain(constants::FULL_BLOOD_MOON.iter())
+        .chain(constants::FULL_BLUE_MOON.iter())
+        .chain(constants::FULL_ECLIPSE_MOON.iter())
+        .chain(constants::BLOODBARK_ARMOR.iter())
+        .chain(constants::BLACK_MASKS.iter())
+        .chain(constants::SALVE_UNENCHANTED.iter())
+        .chain(constants::SALVE_ENCHANTED.iter())
+        .chain(constants::SALVE_IMBUED.iter())
+        .chain(constants::BROAD_BOLTS.iter())
+        .chain(constants::OPAL_BOLTS.iter())
+        .chain(constants::PEARL_BOLTS.iter())
+        .chain(constants::DIAMOND_BOLTS.iter())
+        .chain(constants::DRAGONSTONE_BOLTS.iter())
+        .chain(constants::ONYX_BOLTS.iter())
+        .chain(constants::RUBY_BOLTS.iter())
+        .any(|effect_item| *effect_item == item)
+}
+
+fn ammo_group(armor: &Armor) -> u8 {
+    // Ammo only competes with ammo fired by the same weapons
+    if armor.slot != GearSlot::Ammo || !armor.is_valid_ranged_ammo() {
+        0
+    } else if armor.is_bolt() {
+        1
+    } else if armor.is_arrow() {
+        2
+    } else if armor.name.contains("javelin") {
+        3
+    } else {
+        4
+    }
+}
+
+fn dominates(a: &[f32], b: &[f32]) -> bool {
+    a.iter().zip(b).all(|(x, y)| x >= y) && a.iter().zip(b).any(|(x, y)| x > y)
+}
+
+fn prune_armor(style: GearStyle, items: Vec<Armor>) -> Vec<Armor> {
+    // Drop items that add nothing to this style, and items beaten on every offensive stat by
+    // another item that fits the same weapons
+    let mut kept: Vec<(Vec<f32>, Armor)> = Vec::new();
+    for armor in items {
+        if is_effect_item(&armor) {
+            kept.push((Vec::new(), armor));
+            continue;
+        }
+
+        let stats = style.offensive_bonuses(&armor.bonuses);
+        if stats.iter().all(|stat| *stat <= 0.0) {
+            continue;
+        }
+
+        let group = ammo_group(&armor);
+        let competes = |other: &Armor| !is_effect_item(other) && ammo_group(other) == group;
+        if kept.iter().any(|(other_stats, other)| {
+            competes(other) && (dominates(other_stats, &stats) || *other_stats == stats)
+        }) {
+            continue;
+        }
+        kept.retain(|(other_stats, other)| !competes(other) || !dominates(&stats, other_stats));
+        kept.push((stats, armor));
+    }
+
+    kept.into_iter().map(|(_, armor)| armor).collect()
+}
+
+/// Builder for configuring a `GearOptimizer`.
+#[derive(Debug, Clone)]
+pub struct GearOptimizerBuilder {
+    monster: Monster,
+    stats: Option<PlayerStats>,
+    style: GearStyle,
+    target: OptimizeTarget,
+    items: Option<Vec<(String, Option<String>)>>,
+    bank: Option<Bank>,
+    potions: Vec<Potion>,
+    prayers: Vec<Prayer>,
+    spell: Option<Spell>,
+    on_task: bool,
+    top_k: usize,
+    candidate_weapons: usize,
+}
+
+impl GearOptimizerBuilder {
+    pub fn new(monster: Monster) -> Self {
+        Self {
+            monster,
+            stats: None,
+            style: GearStyle::default(),
+            target: OptimizeTarget::default(),
+            items: None,
+            bank: None,
+            potions: Vec::new(),
+            prayers: Vec::new(),
+            spell: None,
+            on_task: false,
+            top_k: 1,
+            candidate_weapons: DEFAULT_CANDIDATE_WEAPONS,
+        }
+    }
+
+    /// Set the player's stats (defaults to maxed stats).
+    pub fn stats(mut self, stats: PlayerStats) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
+    /// Set the combat style to optimize for.
+    pub fn style(mut self, style: GearStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Rank the finished setups by DPS or by expected time to kill.
+    pub fn rank_by(mut self, target: OptimizeTarget) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Allow an item in the search. If no items are given, the whole database is searched.
+    pub fn item(mut self, name: &str, version: Option<&str>) -> Self {
+        self.items
+            .get_or_insert_with(Vec::new)
+            .push((name.to_string(), version.map(ToString::to_string)));
+        self
+    }
+
+    /// Allow several items in the search.
+    pub fn items<I>(mut self, items: I) -> Self
+    where
+        I: IntoIterator<Item = (String, Option<String>)>,
+    {
+        self.items.get_or_insert_with(Vec::new).extend(items);
+        self
+    }
+
+    /// Allow every item in a player's bank. Bank items without a version allow all versions,
+    /// and anything that isn't equipment is ignored.
+    pub fn bank(mut self, bank: &Bank) -> Self {
+        self.bank = Some(bank.clone());
+        self
+    }
+
+    /// Add a potion boost.
+    pub fn potion(mut self, potion: Potion) -> Self {
+        self.potions.push(potion);
+        self
+    }
+
+    /// Add an active prayer. Prayers that don't boost the optimized style are ignored, so
+    /// one builder can hold the prayers for every style.
+    pub fn prayer(mut self, prayer: Prayer) -> Self {
+        self.prayers.push(prayer);
+        self
+    }
+
+    /// Set the spell to autocast with staves.
+    pub fn spell(mut self, spell: Spell) -> Self {
+        self.spell = Some(spell);
+        self
+    }
+
+    /// Set whether the player is on a slayer task.
+    pub fn on_task(mut self, on_task: bool) -> Self {
+        self.on_task = on_task;
+        self
+    }
+
+    /// Set the number of setups to return.
+    pub fn top_k(mut self, top_k: usize) -> Self {
+        self.top_k = top_k;
+        self
+    }
+
+    /// Set how many of the best-estimated weapons get a full search.
+    pub fn candidate_weapons(mut self, candidate_weapons: usize) -> Self {
+        self.candidate_weapons = candidate_weapons;
+        self
+    }
+
+    /// Build the `GearOptimizer`, resolving the allowed items against the equipment database.
+    pub fn build(self) -> Result<GearOptimizer, OptimizerError> {
+        if self.top_k == 0 {
+            return Err(OptimizerError::ConfigError(
+                "top_k must be at least 1".to_string(),
+            ));
+        }
+
+        let mut player = Player::builder()
+            .player_stats(self.stats.unwrap_or_default())
+            .on_task(self.on_task)
+            .build()?;
+        for potion in self.potions {
+            player.add_potion(potion);
+        }
+        for prayer in self.prayers {
+            if self.style.uses_prayer(prayer) {
+                player.add_prayer(prayer);
+            }
+        }
+        if let Some(spell) = self.spell {
+            player.set_spell(spell)?;
+        }
+
+        let allowed = match &self.items {
+            Some(items) => {
+                let allowed: HashSet<(&str, Option<&str>)> = items
+                    .iter()
+                    .map(|(name, version)| (name.as_str(), version.as_deref()))
+                    .collect();
+                for (name, version) in &allowed {
+                    if !all_equipment()
+                        .iter()
+                        .any(|item| item.name == *name && item.version.as_deref() == *version)
+                    {
+                        return Err(GearError::EquipmentNotFound {
+                            name: name.to_string(),
+                            version: version.map(ToString::to_string),
+                        }
+                        .into());
+                    }
+                }
+                Some(allowed)
+            }
+            None => None,
+        };
+
+        let mut pool = ItemPool::default();
+        let mut armor: HashMap<GearSlot, Vec<Armor>> = HashMap::new();
+        for item in all_equipment() {
+            let is_allowed = if allowed.is_none() && self.bank.is_none() {
+                // Deadman Mode variants are only searched when asked for by name
+                !format!("{} {:?}", item.name, item.version)
+                    .to_lowercase()
+                    .contains("deadman")
+            } else {
+                let key = (item.name.as_str(), item.version.as_deref());
+                allowed
+                    .as_ref()
+                    .is_some_and(|allowed| allowed.contains(&key))
+                    || self
+                        .bank
+                        .as_ref()
+                        .is_some_and(|bank| bank.owns(key.0, key.1))
+            };
+            if !is_allowed {
+                continue;
+            }
+
+            if item.slot == "weapon" {
+                if let Ok(weapon) = item.clone().into_weapon()
+                    && weapon
+                        .combat_styles
+                        .values()
+                        .any(|option| self.style.includes(option.combat_type))
+                {
+                    pool.weapons.push(weapon);
+                }
+            } else if let Ok(item) = item.clone().into_armor() {
+                armor.entry(item.slot).or_default().push(item);
+            }
+        }
+
+        for slot in ARMOR_SLOTS {
+            let items = armor.remove(&slot).unwrap_or_default();
+            pool.armor.insert(slot, prune_armor(self.style, items));
+        }
+
+        Ok(GearOptimizer {
+            monster: self.monster,
+            player,
+            spell: self.spell,
+            style: self.style,
+            target: self.target,
+            top_k: self.top_k,
+            candidate_weapons: self.candidate_weapons,
+            pool,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::spells::StandardSpell;
+
+    fn ammonite_crab() -> Monster {
+        Monster::new("Ammonite Crab", None).expect("Error creating monster.")
+    }
+
+    #[test]
+    fn test_picks_best_melee_items() {
+        let optimizer = GearOptimizer::builder(ammonite_crab())
+            .potion(Potion::SuperCombat)
+            .prayer(Prayer::Piety)
+            .item("Abyssal whip", None)
+            .item("Ghrazi rapier", None)
+            .item("Dragon defender", None)
+            .item("Avernic defender", None)
+            .item("Torva full helm", None)
+            .item("Neitiznot faceguard", None)
+            .item("Bandos chestplate", None)
+            .item("Torva platebody", None)
+            .item("Amulet of fury", None)
+            .item("Amulet of torture", None)
+            .item("Infernal cape", None)
+            .item("Ferocious gloves", None)
+            .item("Primordial boots", None)
+            .item("Ultor ring", None)
+            .top_k(2)
+            .build()
+            .unwrap();
+
+        let results = optimizer.optimize().unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results[0].dps >= results[1].dps);
+
+        let best = &results[0].gear;
+        assert_eq!(best.weapon.name, "Ghrazi rapier");
+        assert!(best.is_wearing("Avernic defender", None));
+        assert!(best.is_wearing("Torva full helm", None));
+        assert!(best.is_wearing("Torva platebody", None));
+        assert!(best.is_wearing("Amulet of torture", None));
+        assert!(best.is_wearing("Ultor ring", None));
+        assert_eq!(results[1].gear.weapon.name, "Abyssal whip");
+    }
+
+    #[test]
+    fn test_two_handed_weapons_drop_shield() {
+        let optimizer = GearOptimizer::builder(ammonite_crab())
+            .item("Dragon warhammer", None)
+            .item("Elder maul", None)
+            .item("Avernic defender", None)
+            .top_k(2)
+            .build()
+            .unwrap();
+
+        let results = optimizer.optimize().unwrap();
+        let maul = results
+            .iter()
+            .find(|ranked| ranked.gear.weapon.name == "Elder maul")
+            .expect("Elder maul setup missing.");
+        assert!(maul.gear.shield.is_none());
+
+        let warhammer = results
+            .iter()
+            .find(|ranked| ranked.gear.weapon.name == "Dragon warhammer")
+            .expect("Dragon warhammer setup missing.");
+        assert!(warhammer.gear.is_wearing("Avernic defender", None));
+    }
+
+    #[test]
+    fn test_finds_full_void_set() {
+        // None of the void pieces are worth wearing alone
+        let optimizer = GearOptimizer::builder(ammonite_crab())
+            .style(GearStyle::Ranged)
+            .item("Rune crossbow", None)
+            .item("Runite bolts", Some("Unpoisoned"))
+            .item("Void ranger helm", None)
+            .item("Elite void top", None)
+            .item("Elite void robe", None)
+            .item("Void knight gloves", None)
+            .item("Black d'hide body", None)
+            .build()
+            .unwrap();
+
+        let best = &optimizer.optimize().unwrap()[0].gear;
+        assert!(best.is_wearing("Elite void top", None));
+        assert!(best.is_wearing("Void ranger helm", None));
+        assert!(best.is_wearing("Runite bolts", Some("Unpoisoned")));
+    }
+
+    #[test]
+    fn test_ranks_by_ttk() {
+        let optimizer = GearOptimizer::builder(ammonite_crab())
+            .item("Abyssal whip", None)
+            .item("Ghrazi rapier", None)
+            .rank_by(OptimizeTarget::Ttk)
+            .top_k(2)
+            .build()
+            .unwrap();
+
+        let results = optimizer.optimize().unwrap();
+        assert!(results[0].ttk <= results[1].ttk);
+        assert!(results.iter().all(|ranked| ranked.ttk.is_finite()));
+    }
+
+    #[test]
+    fn test_optimizes_magic_setup() {
+        // Staves without a known max hit are skipped rather than ranked
+        for spell in [None, Some(Spell::Standard(StandardSpell::FireSurge))] {
+            let mut builder = GearOptimizer::builder(ammonite_crab()).style(GearStyle::Magic);
+            if let Some(spell) = spell {
+                builder = builder.spell(spell);
+            }
+
+            let results = builder.build().unwrap().optimize().unwrap();
+            assert!(!results.is_empty());
+            assert!(results.iter().all(|ranked| ranked.dps > 0.0));
+        }
+    }
+
+    #[test]
+    fn test_ammo_compatibility() {
+        let crossbow = Weapon::new("Rune crossbow", None).unwrap();
+        let bow = Weapon::new("Magic shortbow", None).unwrap();
+        let blowpipe = Weapon::new("Toxic blowpipe", Some("Dragon")).unwrap();
+        let bolts = Armor::new("Runite bolts", Some("Unpoisoned")).unwrap();
+        let arrows = Armor::new("Amethyst arrow", Some("Unpoisoned")).unwrap();
+        let blessing = Armor::new("Rada's blessing 4", None).unwrap();
+
+        assert!(ammo_fits(&crossbow, &bolts));
+        assert!(!ammo_fits(&crossbow, &arrows));
+        assert!(ammo_fits(&bow, &arrows));
+        assert!(!ammo_fits(&bow, &bolts));
+        assert!(!ammo_fits(&blowpipe, &arrows));
+        assert!(ammo_fits(&blowpipe, &blessing));
+    }
+
+    #[test]
+    fn test_best_in_bank() {
+        let bank =
+            Bank::parse("Abyssal whip\nDragon defender\nRune crossbow\nRunite bolts\nShark\nCoins")
+                .unwrap();
+        let builder = GearOptimizer::builder(ammonite_crab())
+            .potion(Potion::SuperCombat)
+            .potion(Potion::Ranging)
+            .prayer(Prayer::Piety)
+            .prayer(Prayer::Rigour);
+
+        let report = best_in_bank(builder, &bank).unwrap();
+        let melee = report.melee.as_ref().unwrap();
+        assert_eq!(melee.gear.weapon.name, "Abyssal whip");
+        assert!(melee.gear.is_wearing("Dragon defender", None));
+
+        let ranged = report.ranged.as_ref().unwrap();
+        assert_eq!(ranged.gear.weapon.name, "Rune crossbow");
+        assert!(ranged.gear.is_wearing_any_version("Runite bolts"));
+        assert!(report.magic.is_none());
+
+        let upgrade = report.best_upgrade.as_ref().unwrap();
+        assert!(!bank.owns(&upgrade.name, upgrade.version.as_deref()));
+        assert!(upgrade.dps_gain > 0.0);
+        assert!((upgrade.setup.dps - report.best().unwrap().dps - upgrade.dps_gain).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_prayers_filtered_by_style() {
+        let optimizer = GearOptimizer::builder(ammonite_crab())
+            .style(GearStyle::Ranged)
+            .prayer(Prayer::Piety)
+            .prayer(Prayer::Rigour)
+            .item("Rune crossbow", None)
+            .build()
+            .unwrap();
+
+        assert!(
+            optimizer
+                .player
+                .prayers
+                .active_prayers
+                .as_ref()
+                .is_some_and(
+                    |prayers| prayers.len() == 1 && prayers[0].prayer_type == Prayer::Rigour
+                )
+        );
+    }
+
+    #[test]
+    fn test_unknown_item() {
+        let result = GearOptimizer::builder(ammonite_crab())
+            .item("Not a real item", None)
+            .build();
+        assert!(matches!(result, Err(OptimizerError::GearError(_))));
+    }
+}
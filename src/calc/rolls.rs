@@ -621,10 +621,8 @@ fn apply_melee_weapon_boosts(
         "Colossal blade" => {
             max_hit += 2 * min(monster.info.size, 5);
         }
-        "Bone mace" => {
-            if monster.is_rat() {
-                max_hit += 10;
-            }
+        "Bone mace" if monster.is_rat() => {
+            max_hit += 10;
         }
         _ => {}
     }
@@ -811,10 +809,22 @@ fn get_base_magic_hit(player: &Player) -> u32 {
     } else if player.is_wearing_salamander() {
         salamander_max_hit(player)
     } else {
-        charged_staff_max_hit(player)
+        charged_staff_max_hit(player).unwrap_or_else(|| {
+            panic!(
+                "Magic max hit could not be determined for {}",
+                player.gear.weapon.name
+            )
+        })
     }
 }
 
+pub fn has_magic_max_hit(player: &Player) -> bool {
+    // Check if the magic max hit is known for the player's spell or weapon
+    player.attrs.spell.is_some()
+        || player.is_wearing_salamander()
+        || charged_staff_max_hit(player).is_some()
+}
+
 fn salamander_max_hit(player: &Player) -> u32 {
     let factor = match player.gear.weapon.name.as_str() {
         "Swamp lizard" => 120,
@@ -828,9 +838,9 @@ fn salamander_max_hit(player: &Player) -> u32 {
     (1 + 2 * player.stats.magic.current * factor) / 1280
 }
 
-fn charged_staff_max_hit(player: &Player) -> u32 {
+fn charged_staff_max_hit(player: &Player) -> Option<u32> {
     let visible_magic = player.stats.magic.current;
-    match player.gear.weapon.name.as_str() {
+    let max_hit = match player.gear.weapon.name.as_str() {
         "Starter staff" => 8,
         "Warped sceptre" => (8 * visible_magic + 96) / 37,
         "Trident of the seas" | "Trident of the seas (e)" => {
@@ -848,11 +858,10 @@ fn charged_staff_max_hit(player: &Player) -> u32 {
         "Crystal staff (basic)" | "Corrupted staff (basic)" => 23,
         "Crystal staff (attuned)" | "Corrupted staff (attuned)" => 31,
         "Crystal staff (perfected)" | "Corrupted staff (perfected)" => 39,
-        _ => panic!(
-            "Magic max hit could not be determined for {}",
-            player.gear.weapon.name
-        ),
-    }
+        _ => return None,
+    };
+
+    Some(max_hit)
 }
 
 fn apply_shadow_boost(magic_attack: i32, magic_damage: u32, monster: &Monster) -> (i32, u32) {
@@ -0,0 +1,560 @@
+use crate::cli::CliError;
+use clap::Args;
+use osrs::calc::dps_calc::{get_distribution, get_dps};
+use osrs::calc::rolls::calc_active_player_rolls;
+use osrs::combat::thralls::Thrall;
+use osrs::error::LoadoutError;
+use osrs::types::equipment::CombatStyle;
+use osrs::types::food::Foods;
+use osrs::types::loadout::{Loadout, LoadoutGear, LoadoutItem};
+use osrs::types::monster::Monster;
+use osrs::types::player::{Player, SwitchType};
+use osrs::types::potions::Potion;
+use osrs::types::prayers::Prayer;
+use osrs::types::spells::Spell;
+use osrs::utils::names::{parse_display_name, parse_variant};
+use serde::Deserialize;
+use std::fmt::{Debug, Display};
+use std::path::{Path, PathBuf};
+use strum::IntoEnumIterator;
+
+pub fn parse_name<T>(name: &str) -> Result<T, String>
+where
+    T: IntoEnumIterator + Debug,
+{
+    parse_variant(name).ok_or_else(|| format!("unknown name '{name}'"))
+}
+
+// For enums with display names, e.g., potions where "Overload (-)" and "Overload" differ
+pub fn parse_display<T>(name: &str) -> Result<T, String>
+where
+    T: IntoEnumIterator + Debug + Display,
+{
+    parse_display_name(name).ok_or_else(|| format!("unknown name '{name}'"))
+}
+
+pub fn parse_spell(name: &str) -> Result<Spell, String> {
+    Spell::from_name(name).ok_or_else(|| format!("unknown spell '{name}'"))
+}
+
+pub fn parse_switch_type(name: &str) -> Result<SwitchType, String> {
+    match name.to_lowercase().as_str() {
+        "melee" => Ok(SwitchType::Melee),
+        "ranged" => Ok(SwitchType::Ranged),
+        "magic" | "mage" => Ok(SwitchType::Magic),
+        _ => Ok(SwitchType::Custom(name.into())),
+    }
+}
+
+// Parse a name from a loadout file with the same rules as the command-line flags
+fn parse_field<T>(kind: &'static str, name: &str) -> Result<T, CliError>
+where
+    T: IntoEnumIterator + Debug,
+{
+    parse_variant(name).ok_or_else(|| CliError::InvalidName {
+        kind,
+        name: name.to_string(),
+    })
+}
+
+// Items on the command line are written as NAME or NAME:VERSION
+fn parse_item_arg(arg: &str) -> LoadoutItem {
+    match arg.split_once(':') {
+        Some((name, version)) => LoadoutItem::new(name.trim(), Some(version.trim())),
+        None => LoadoutItem::new(arg.trim(), None),
+    }
+}
+
+// Boss config options that can be set in a loadout file instead of on the command line
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FightEntry {
+    pub food: Option<String>,
+    pub food_count: Option<u32>,
+    pub eat_hp: Option<u32>,
+    pub thrall: Option<String>,
+    pub styles: Vec<String>,
+    pub armor_tier: Option<u32>,
+    pub smite_skip: bool,
+    pub script: Option<String>,
+    pub flick_success: Option<f64>,
+}
+
+/// Loadout file accepted by `--loadout`.
+///
+/// A TOML or JSON `Loadout`, plus an optional `fight` table with boss config options.
+#[derive(Debug, Clone, Default)]
+pub struct LoadoutFile {
+    pub loadout: Loadout,
+    pub fight: FightEntry,
+}
+
+impl LoadoutFile {
+    pub fn read(path: &Path) -> Result<Self, CliError> {
+        let contents = std::fs::read_to_string(path).map_err(|source| CliError::ReadError {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::parse(path, &contents).map_err(|source| CliError::LoadoutParseError {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    fn parse(path: &Path, contents: &str) -> Result<Self, LoadoutError> {
+        // Split off the fight table before handing the rest to the library loadout parser
+        let extension = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        match extension.as_str() {
+            "toml" => {
+                let mut table: toml::Table = toml::from_str(contents)?;
+                let fight = match table.remove("fight") {
+                    Some(fight) => fight.try_into()?,
+                    None => FightEntry::default(),
+                };
+                Ok(Self {
+                    loadout: table.try_into()?,
+                    fight,
+                })
+            }
+            "json" => {
+                let mut value: serde_json::Value = serde_json::from_str(contents)?;
+                let fight = match value.as_object_mut().and_then(|obj| obj.remove("fight")) {
+                    Some(fight) => serde_json::from_value(fight)?,
+                    None => FightEntry::default(),
+                };
+                Ok(Self {
+                    loadout: serde_json::from_value(value)?,
+                    fight,
+                })
+            }
+            other => Err(LoadoutError::UnsupportedFormat(other.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Args)]
+pub struct MonsterArgs {
+    /// Monster name, e.g., "Vorkath"
+    #[arg(short, long)]
+    pub monster: String,
+
+    /// Monster version, e.g., "Post-quest"
+    #[arg(long)]
+    pub monster_version: Option<String>,
+
+    /// Raid level for Tombs of Amascut monsters
+    #[arg(long)]
+    pub toa_level: Option<u32>,
+
+    /// Path level for Tombs of Amascut monsters
+    #[arg(long)]
+    pub toa_path_level: Option<u32>,
+}
+
+impl MonsterArgs {
+    pub fn build(&self) -> Result<Monster, CliError> {
+        let mut monster = Monster::new(&self.monster, self.monster_version.as_deref())?;
+        if self.toa_level.is_some() || self.toa_path_level.is_some() {
+            monster.info.toa_level = self.toa_level.unwrap_or(0);
+            monster.info.toa_path_level = self.toa_path_level.unwrap_or(0);
+            monster.scale_toa();
+        }
+
+        Ok(monster)
+    }
+}
+
+#[derive(Debug, Clone, Default, Args)]
+pub struct PlayerArgs {
+    /// TOML or JSON loadout file to start from; the other player flags are applied on top of it
+    #[arg(short, long)]
+    pub loadout: Option<PathBuf>,
+
+    #[arg(long)]
+    pub attack: Option<u32>,
+
+    #[arg(long)]
+    pub strength: Option<u32>,
+
+    #[arg(long)]
+    pub defence: Option<u32>,
+
+    #[arg(long)]
+    pub ranged: Option<u32>,
+
+    #[arg(long)]
+    pub magic: Option<u32>,
+
+    #[arg(long)]
+    pub hitpoints: Option<u32>,
+
+    #[arg(long)]
+    pub prayer_level: Option<u32>,
+
+    /// Item to equip as NAME or NAME:VERSION, e.g., "Scythe of vitur:Charged" (repeatable)
+    #[arg(short, long = "gear", value_name = "ITEM")]
+    pub gear: Vec<String>,
+
+    /// Combat style, e.g., "Chop" (defaults to the weapon's highest-DPS style)
+    #[arg(short, long, value_parser = parse_display::<CombatStyle>)]
+    pub style: Option<CombatStyle>,
+
+    /// Prayer to activate, e.g., "Piety" (repeatable)
+    #[arg(short, long = "prayer", value_name = "PRAYER", value_parser = parse_display::<Prayer>)]
+    pub prayers: Vec<Prayer>,
+
+    /// Potion boost to apply, e.g., "Super combat" (repeatable)
+    #[arg(long = "potion", value_name = "POTION", value_parser = parse_display::<Potion>)]
+    pub potions: Vec<Potion>,
+
+    /// Spell to autocast, e.g., "Ice Barrage"
+    #[arg(long, value_parser = parse_spell)]
+    pub spell: Option<Spell>,
+
+    /// Not on a slayer task
+    #[arg(long)]
+    pub off_task: bool,
+
+    /// In the wilderness
+    #[arg(long)]
+    pub wilderness: bool,
+
+    /// Without the Kandarin hard diary
+    #[arg(long)]
+    pub no_kandarin_diary: bool,
+}
+
+impl PlayerArgs {
+    pub fn from_loadout(path: &Path) -> Self {
+        Self {
+            loadout: Some(path.to_path_buf()),
+            ..Self::default()
+        }
+    }
+
+    pub fn read_loadout(&self) -> Result<LoadoutFile, CliError> {
+        self.loadout
+            .as_deref()
+            .map_or_else(|| Ok(LoadoutFile::default()), LoadoutFile::read)
+    }
+
+    /// Build a player from the loadout file and flags, with rolls calculated against `monster`.
+    pub fn build(&self, monster: &Monster) -> Result<Player, CliError> {
+        let mut loadout = self.read_loadout()?.loadout;
+        self.apply_flags(&mut loadout)?;
+
+        // Fill in missing styles with each setup's highest-DPS style
+        if loadout.style.is_none() {
+            let style = best_style(
+                &loadout,
+                &loadout.gear,
+                loadout.spell.clone(),
+                loadout.prayers.clone(),
+                monster,
+            )?;
+            loadout.style = Some(style.to_string());
+        }
+        for i in 0..loadout.switches.len() {
+            let switch = &loadout.switches[i];
+            if switch.style.is_none() {
+                let style = best_style(
+                    &loadout,
+                    &switch.gear,
+                    switch.spell.clone(),
+                    switch.prayers.clone(),
+                    monster,
+                )?;
+                loadout.switches[i].style = Some(style.to_string());
+            }
+        }
+
+        // Start in the first switch unless the loadout says otherwise
+        if loadout.active_switch.is_none() {
+            loadout.active_switch = loadout.switches.first().map(|s| s.name.clone());
+        }
+
+        Ok(loadout.to_player(Some(monster))?)
+    }
+
+    fn apply_flags(&self, loadout: &mut Loadout) -> Result<(), CliError> {
+        let stats = &mut loadout.stats;
+        let levels = [
+            (&mut stats.attack, self.attack),
+            (&mut stats.strength, self.strength),
+            (&mut stats.defence, self.defence),
+            (&mut stats.ranged, self.ranged),
+            (&mut stats.magic, self.magic),
+            (&mut stats.hitpoints, self.hitpoints),
+            (&mut stats.prayer, self.prayer_level),
+        ];
+        for (stat, level) in levels {
+            if let Some(level) = level {
+                *stat = level;
+            }
+        }
+
+        for arg in &self.gear {
+            let item = parse_item_arg(arg);
+            loadout.gear.equip(&item.name, item.version.as_deref())?;
+        }
+        if let Some(style) = self.style {
+            loadout.style = Some(style.to_string());
+        }
+        if let Some(spell) = self.spell {
+            loadout.spell = Some(spell.to_string());
+        }
+        loadout
+            .prayers
+            .extend(self.prayers.iter().map(ToString::to_string));
+        loadout
+            .potions
+            .extend(self.potions.iter().map(ToString::to_string));
+
+        let boosts = &mut loadout.boosts;
+        boosts.on_task &= !self.off_task;
+        boosts.in_wilderness |= self.wilderness;
+        boosts.kandarin_diary &= !self.no_kandarin_diary;
+
+        Ok(())
+    }
+}
+
+fn best_style(
+    loadout: &Loadout,
+    gear: &LoadoutGear,
+    spell: Option<String>,
+    prayers: Vec<String>,
+    monster: &Monster,
+) -> Result<CombatStyle, CliError> {
+    // Pick the weapon style with the highest DPS, falling back to the first listed style
+    let player = Loadout {
+        gear: gear.clone(),
+        spell,
+        prayers,
+        style: None,
+        active_switch: None,
+        switches: Vec::new(),
+        ..loadout.clone()
+    }
+    .to_player(Some(monster))?;
+    let dps = |style: CombatStyle| {
+        let mut player = player.clone();
+        player.set_active_style(style);
+        player.update_bonuses();
+        calc_active_player_rolls(&mut player, monster);
+        get_distribution(&player, monster, false)
+            .map(|dist| get_dps(&dist, &player, false))
+            .unwrap_or(0.0)
+    };
+
+    Ok(CombatStyle::iter()
+        .filter(|style| player.gear.weapon.combat_styles.contains_key(style))
+        .map(|style| (style, dps(style)))
+        .fold(
+            None,
+            |best: Option<(CombatStyle, f64)>, (style, dps)| match best {
+                Some((_, best_dps)) if best_dps >= dps => best,
+                _ => Some((style, dps)),
+            },
+        )
+        .map_or(player.attrs.active_style, |(style, _)| style))
+}
+
+#[derive(Debug, Clone, Default, Args)]
+pub struct FightArgs {
+    /// Food to bring, e.g., "Shark" (replaces the boss's default inventory)
+    #[arg(long, value_parser = parse_name::<Foods>)]
+    pub food: Option<Foods>,
+
+    /// Number of food to bring
+    #[arg(long)]
+    pub food_count: Option<u32>,
+
+    /// Eat when HP drops below this value
+    #[arg(long)]
+    pub eat_hp: Option<u32>,
+
+    /// Thrall to summon, e.g., "GreaterMagic"
+    #[arg(long, value_parser = parse_name::<Thrall>)]
+    pub thrall: Option<Thrall>,
+
+    /// Hunllef attack styles in order of use, e.g., "magic,ranged" or "magic,ranged,melee".
+    /// For Zulrah, the switches used on the serpentine, magma, tanzanite and Jad phases.
+    /// For Muspah, the switches used on the ranged, melee, shielded and post-shield phases.
+    /// For Olm, the switches used on the mage hand, melee hand and head
+    #[arg(long, value_delimiter = ',', value_parser = parse_switch_type)]
+    pub styles: Vec<SwitchType>,
+
+    /// Hunllef armor tier (0-3), read from the armour worn if not given
+    #[arg(long)]
+    pub armor_tier: Option<u32>,
+
+    /// Smite the Phantom Muspah before its shield phase to skip it
+    #[arg(long)]
+    pub smite_skip: bool,
+
+    /// Boss script for scripted fights: a TOML or JSON file, or a bundled script, e.g., "sarachnis"
+    #[arg(long)]
+    pub script: Option<String>,
+
+    /// Chance (0-1) the right protection prayer is up in time for each boss attack
+    #[arg(long)]
+    pub flick_success: Option<f64>,
+}
+
+impl FightArgs {
+    // Fill in options that weren't given on the command line from the loadout file
+    pub fn merge(mut self, entry: &FightEntry) -> Result<Self, CliError> {
+        if self.food.is_none() {
+            self.food = entry
+                .food
+                .as_deref()
+                .map(|food| parse_field("food", food))
+                .transpose()?;
+        }
+        if self.thrall.is_none() {
+            self.thrall = entry
+                .thrall
+                .as_deref()
+                .map(|thrall| parse_field("thrall", thrall))
+                .transpose()?;
+        }
+        if self.styles.is_empty() {
+            self.styles = entry
+                .styles
+                .iter()
+                .map(|style| parse_switch_type(style).map_err(CliError::InvalidArgs))
+                .collect::<Result<_, _>>()?;
+        }
+        self.food_count = self.food_count.or(entry.food_count);
+        self.eat_hp = self.eat_hp.or(entry.eat_hp);
+        self.armor_tier = self.armor_tier.or(entry.armor_tier);
+        self.flick_success = self.flick_success.or(entry.flick_success);
+        self.smite_skip |= entry.smite_skip;
+        if self.script.is_none() {
+            self.script = entry.script.clone();
+        }
+
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use osrs::error::PlayerError;
+
+    fn write_loadout(name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("osrs_cli_{name}"));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_item_args() {
+        let item = parse_item_arg("Scythe of vitur:Charged");
+        assert_eq!(item.name, "Scythe of vitur");
+        assert_eq!(item.version.as_deref(), Some("Charged"));
+        assert_eq!(parse_item_arg("Abyssal whip").version, None);
+    }
+
+    #[test]
+    fn test_flags_override_loadout() {
+        let path = write_loadout(
+            "override.json",
+            r#"{
+                "stats": {"strength": 90},
+                "gear": {"weapon": {"name": "Abyssal whip"}},
+                "style": "Lash",
+                "prayers": ["Piety"],
+                "potions": ["Super combat"],
+                "fight": {"food": "Shark"}
+            }"#,
+        );
+        let monster = Monster::new("General Graardor", None).unwrap();
+        let args = PlayerArgs {
+            loadout: Some(path),
+            attack: Some(80),
+            gear: vec!["Amulet of torture".to_string()],
+            style: Some(CombatStyle::Flick),
+            ..PlayerArgs::default()
+        };
+        assert_eq!(
+            args.read_loadout().unwrap().fight.food.as_deref(),
+            Some("Shark")
+        );
+        let player = args.build(&monster).unwrap();
+
+        assert_eq!(player.stats.attack.base, 80);
+        assert_eq!(player.stats.strength.base, 90);
+        assert!(player.is_wearing("Abyssal whip", None));
+        assert!(player.is_wearing("Amulet of torture", None));
+        assert_eq!(player.attrs.active_style, CombatStyle::Flick);
+        assert!(player.prayers.contains_prayer(Prayer::Piety));
+    }
+
+    #[test]
+    fn test_loadout_switches() {
+        let path = write_loadout(
+            "switches.toml",
+            r#"
+            [gear]
+            head = { name = "Corrupted helm (basic)" }
+
+            [[switches]]
+            name = "Ranged"
+            style = "Rapid"
+            prayers = ["Rigour"]
+            gear = { head = { name = "Corrupted helm (basic)" }, weapon = { name = "Corrupted bow (perfected)" } }
+
+            [[switches]]
+            name = "Magic"
+            prayers = ["Augury"]
+            gear = { head = { name = "Corrupted helm (basic)" }, weapon = { name = "Corrupted staff (perfected)" } }
+
+            [fight]
+            armor_tier = 1
+            "#,
+        );
+        let monster = Monster::new("Corrupted Hunllef", None).unwrap();
+        let args = PlayerArgs::from_loadout(&path);
+        assert_eq!(args.read_loadout().unwrap().fight.armor_tier, Some(1));
+        let player = args.build(&monster).unwrap();
+
+        assert_eq!(player.switches.len(), 2);
+        assert_eq!(player.current_switch, Some(SwitchType::Ranged));
+        assert!(player.is_wearing("Corrupted bow (perfected)", None));
+        assert!(player.is_wearing("Corrupted helm (basic)", None));
+        assert_eq!(player.switches[1].active_style, CombatStyle::Accurate);
+    }
+
+    #[test]
+    fn test_invalid_loadout() {
+        let path = write_loadout("invalid.json", r#"{"prayers": ["Pietyy"]}"#);
+        let monster = Monster::new("General Graardor", None).unwrap();
+        let result = PlayerArgs::from_loadout(&path).build(&monster);
+        assert!(matches!(
+            result,
+            Err(CliError::LoadoutError(LoadoutError::PlayerError(
+                PlayerError::UnknownName { kind: "prayer", .. }
+            )))
+        ));
+
+        let path = write_loadout("unknown_field.toml", r#"helmet = "Torva full helm""#);
+        let result = PlayerArgs::from_loadout(&path).build(&monster);
+        assert!(matches!(result, Err(CliError::LoadoutParseError { .. })));
+
+        let path = write_loadout("loadout.txt", "");
+        let result = PlayerArgs::from_loadout(&path).build(&monster);
+        assert!(matches!(
+            result,
+            Err(CliError::LoadoutParseError {
+                source: LoadoutError::UnsupportedFormat(_),
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,851 @@
+use crate::cli::args::{FightArgs, MonsterArgs, PlayerArgs};
+use crate::cli::table::Table;
+use crate::cli::{Boss, CliError};
+use osrs::calc::analysis::SimulationStats;
+use osrs::calc::dps_calc::{get_distribution, get_dps, get_max, get_ttk, get_ttk_distribution};
+use osrs::combat::simulation::{
+    ConvergenceConfig, Simulation, simulate_n_fights_parallel, simulate_until_converged,
+};
+use osrs::constants::SECONDS_PER_TICK;
+use osrs::error::SimulationError;
+use osrs::sims::graardor::{GraardorConfig, GraardorFight};
+use osrs::sims::hunleff::{AttackStrategy, HunllefConfig, HunllefEatStrategy, HunllefFight};
+use osrs::sims::muspah::{MuspahConfig, MuspahFight, MuspahSwitches};
+use osrs::sims::olm::{OlmConfig, OlmFight, OlmSwitches};
+use osrs::sims::scripted::{BossScript, ScriptedConfig, ScriptedFight};
+use osrs::sims::single_way::{SingleWayConfig, SingleWayFight};
+use osrs::sims::vardorvis::{VardorvisConfig, VardorvisEatStrategy, VardorvisFight};
+use osrs::sims::vorkath::{VorkathConfig, VorkathFight};
+use osrs::sims::zulrah::{AttackChart, ZulrahConfig, ZulrahFight};
+use osrs::types::equipment::CombatStyle;
+use osrs::types::food::Foods;
+use osrs::types::inventory::Inventory;
+use osrs::types::monster::Monster;
+use osrs::types::player::Player;
+use osrs::utils::logging::FightLogger;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+// Food brought when only a food type or count is given
+const DEFAULT_FOOD: Foods = Foods::Shark;
+const DEFAULT_FOOD_COUNT: u32 = 20;
+
+fn print_json<T: Serialize>(value: &T) -> Result<(), CliError> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DpsReport {
+    pub monster: String,
+    pub monster_version: Option<String>,
+    pub weapon: String,
+    pub style: CombatStyle,
+    pub max_hit: u32,
+    pub expected_hit: f64,
+    pub dps: f64,
+    pub ttk: f64,
+}
+
+pub fn calc_dps(player: &Player, monster: &Monster, spec: bool) -> Result<DpsReport, CliError> {
+    if monster.is_immune(player) {
+        return Err(CliError::MonsterImmune(monster.info.name.clone()));
+    }
+    let dist = get_distribution(player, monster, spec)?;
+
+    Ok(DpsReport {
+        monster: monster.info.name.clone(),
+        monster_version: monster.info.version.clone(),
+        weapon: player.gear.weapon.name.clone(),
+        style: player.attrs.active_style,
+        max_hit: get_max(&dist, player, monster, spec),
+        expected_hit: dist.get_expected_damage(),
+        dps: get_dps(&dist, player, spec),
+        ttk: get_ttk(&dist, player, monster, spec, false)?,
+    })
+}
+
+fn monster_label(name: &str, version: Option<&str>) -> String {
+    match version {
+        Some(version) => format!("{name} ({version})"),
+        None => name.to_string(),
+    }
+}
+
+pub fn dps(
+    monster_args: &MonsterArgs,
+    player_args: &PlayerArgs,
+    spec: bool,
+    json: bool,
+) -> Result<(), CliError> {
+    let monster = monster_args.build()?;
+    let player = player_args.build(&monster)?;
+    let report = calc_dps(&player, &monster, spec)?;
+    if json {
+        return print_json(&report);
+    }
+
+    let mut table = Table::new(&[]);
+    table.row([
+        "Monster".to_string(),
+        monster_label(&report.monster, report.monster_version.as_deref()),
+    ]);
+    table.row(["Weapon".to_string(), report.weapon.clone()]);
+    table.row(["Style".to_string(), report.style.to_string()]);
+    table.row(["Max hit".to_string(), report.max_hit.to_string()]);
+    table.row([
+        "Expected hit".to_string(),
+        format!("{:.2}", report.expected_hit),
+    ]);
+    table.row(["DPS".to_string(), format!("{:.4}", report.dps)]);
+    table.row(["TTK".to_string(), format!("{:.2} s", report.ttk)]);
+    print!("{table}");
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TtkEntry {
+    pub ticks: usize,
+    pub seconds: f64,
+    pub probability: f64,
+    pub cumulative: f64,
+}
+
+pub fn calc_ttk_dist(
+    player: &Player,
+    monster: &Monster,
+    spec: bool,
+) -> Result<Vec<TtkEntry>, CliError> {
+    if monster.is_immune(player) {
+        return Err(CliError::MonsterImmune(monster.info.name.clone()));
+    }
+    let mut dist = get_distribution(player, monster, spec)?;
+    let mut ttks: Vec<(usize, f64)> = get_ttk_distribution(&mut dist, player, monster, spec, None)?
+        .into_iter()
+        .collect();
+    ttks.sort_by_key(|&(ticks, _)| ticks);
+
+    let mut cumulative = 0.0;
+    Ok(ttks
+        .into_iter()
+        .map(|(ticks, probability)| {
+            cumulative += probability;
+            TtkEntry {
+                ticks,
+                seconds: ticks as f64 * SECONDS_PER_TICK,
+                probability,
+                cumulative,
+            }
+        })
+        .collect())
+}
+
+pub fn ttk_dist(
+    monster_args: &MonsterArgs,
+    player_args: &PlayerArgs,
+    spec: bool,
+    json: bool,
+) -> Result<(), CliError> {
+    let monster = monster_args.build()?;
+    let player = player_args.build(&monster)?;
+    let entries = calc_ttk_dist(&player, &monster, spec)?;
+    if json {
+        return print_json(&entries);
+    }
+
+    let mut table = Table::new(&["Ticks", "Seconds", "Probability", "Cumulative"]);
+    for entry in &entries {
+        table.row([
+            entry.ticks.to_string(),
+            format!("{:.1}", entry.seconds),
+            format!("{:.4}%", entry.probability * 100.0),
+            format!("{:.4}%", entry.cumulative * 100.0),
+        ]);
+    }
+    print!("{table}");
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SimulateOptions {
+    pub fights: u32,
+    pub seed: Option<u64>,
+    pub threads: Option<usize>,
+    pub trace: Option<PathBuf>,
+    pub target_ci: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulationReport {
+    pub boss: String,
+    pub fights: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub converged: Option<bool>, // Only set when running until a target CI
+    pub stats: SimulationStats,
+}
+
+fn build_inventory(fight: &FightArgs, default: Inventory) -> Result<Inventory, CliError> {
+    if fight.food.is_none() && fight.food_count.is_none() {
+        return Ok(default);
+    }
+
+    Ok(Inventory::builder()
+        .food(
+            fight.food.unwrap_or(DEFAULT_FOOD),
+            fight.food_count.unwrap_or(DEFAULT_FOOD_COUNT),
+        )
+        .build()?)
+}
+
+fn build_hunllef(
+    player_args: &PlayerArgs,
+    fight: &FightArgs,
+    trace: bool,
+) -> Result<HunllefFight, CliError> {
+    let monster = Monster::new("Corrupted Hunllef", None)?;
+    let player = player_args.build(&monster)?;
+    let default = HunllefConfig::default();
+
+    // Without explicit styles, rotate through the loadout's switches in order
+    let styles = if fight.styles.is_empty() {
+        player
+            .switches
+            .iter()
+            .map(|switch| switch.switch_type.clone())
+            .collect()
+    } else {
+        fight.styles.clone()
+    };
+    let attack_strategy = match styles.as_slice() {
+        [style1, style2] => AttackStrategy::TwoT3Weapons {
+            style1: style1.clone(),
+            style2: style2.clone(),
+        },
+        [main_style, other_style1, other_style2] => AttackStrategy::FiveToOne {
+            main_style: main_style.clone(),
+            other_style1: other_style1.clone(),
+            other_style2: other_style2.clone(),
+        },
+        _ => {
+            return Err(CliError::InvalidArgs(
+                "Hunllef needs two or three attack styles (--styles or loadout switches)."
+                    .to_string(),
+            ));
+        }
+    };
+
+    let config = HunllefConfig {
+        inventory: build_inventory(fight, default.inventory.clone())?,
+        eat_strategy: fight
+            .eat_hp
+            .map_or(default.eat_strategy.clone(), HunllefEatStrategy::EatAtHp),
+        attack_strategy,
+        armor_tier: fight.armor_tier.or(default.armor_tier),
+        flick_success: fight.flick_success.unwrap_or(default.flick_success),
+        logger: FightLogger::new(trace),
+        ..default
+    };
+    Ok(HunllefFight::new(player, config, None)?)
+}
+
+fn build_simulation(
+    boss: Boss,
+    monster: Option<&MonsterArgs>,
+    player_args: &PlayerArgs,
+    fight: &FightArgs,
+    trace: bool,
+) -> Result<Box<dyn Simulation>, CliError> {
+    let simulation: Box<dyn Simulation> = match boss {
+        Boss::SingleWay => {
+            let monster = monster
+                .ok_or_else(|| CliError::InvalidArgs("Single-way fights need a monster.".into()))?
+                .build()?;
+            let player = player_args.build(&monster)?;
+            let config = SingleWayConfig {
+                thralls: fight.thrall,
+                ..SingleWayConfig::default()
+            };
+            Box::new(SingleWayFight::new(player, monster, config, None, trace)?)
+        }
+        Boss::Graardor => {
+            let monster = Monster::new("General Graardor", None)?;
+            let player = player_args.build(&monster)?;
+            let default = GraardorConfig::default();
+            let config = GraardorConfig {
+                eat_hp: fight.eat_hp.unwrap_or(default.eat_hp),
+                inventory: build_inventory(fight, default.inventory.clone())?,
+                logger: FightLogger::new(trace),
+                ..default
+            };
+            Box::new(GraardorFight::new(player, config, None)?)
+        }
+        Boss::Hunllef => Box::new(build_hunllef(player_args, fight, trace)?),
+        Boss::Vardorvis => {
+            let monster = Monster::new("Vardorvis", Some("Post-quest"))?;
+            let player = player_args.build(&monster)?;
+            let default = VardorvisConfig::default();
+            let config = VardorvisConfig {
+                inventory: build_inventory(fight, default.inventory.clone())?,
+                eat_strategy: fight
+                    .eat_hp
+                    .map_or(default.eat_strategy.clone(), VardorvisEatStrategy::EatAtHp),
+                thralls: fight.thrall.or(default.thralls),
+                flick_success: fight.flick_success.unwrap_or(default.flick_success),
+                logger: FightLogger::new(trace),
+                ..default
+            };
+            Box::new(VardorvisFight::new(player, config, None)?)
+        }
+        Boss::Muspah => {
+            let monster = Monster::new("Phantom Muspah", Some("Ranged"))?;
+            let player = player_args.build(&monster)?;
+            let default = MuspahConfig::default();
+            let switches = match fight.styles.as_slice() {
+                [] => default.switches.clone(),
+                [ranged_form, melee_form, shielded, post_shield] => MuspahSwitches {
+                    ranged_form: ranged_form.clone(),
+                    melee_form: melee_form.clone(),
+                    shielded: shielded.clone(),
+                    post_shield: post_shield.clone(),
+                },
+                _ => {
+                    return Err(CliError::InvalidArgs(
+                        "Muspah needs four attack styles (ranged, melee, shielded, post-shield)."
+                            .to_string(),
+                    ));
+                }
+            };
+            let config = MuspahConfig {
+                inventory: build_inventory(fight, default.inventory.clone())?,
+                eat_hp: fight.eat_hp.unwrap_or(default.eat_hp),
+                switches,
+                smite_skip: fight.smite_skip,
+                flick_success: fight.flick_success.unwrap_or(default.flick_success),
+                logger: FightLogger::new(trace),
+                ..default
+            };
+            Box::new(MuspahFight::new(player, config, None)?)
+        }
+        Boss::Olm => {
+            let monster = Monster::new("Great Olm", Some("Head (Normal)"))?;
+            let player = player_args.build(&monster)?;
+            let default = OlmConfig::default();
+            let switches = match fight.styles.as_slice() {
+                [] => default.switches.clone(),
+                [mage_hand, melee_hand, head] => OlmSwitches {
+                    mage_hand: mage_hand.clone(),
+                    melee_hand: melee_hand.clone(),
+                    head: head.clone(),
+                },
+                _ => {
+                    return Err(CliError::InvalidArgs(
+                        "Olm needs three attack styles (mage hand, melee hand, head).".to_string(),
+                    ));
+                }
+            };
+            let config = OlmConfig {
+                inventory: build_inventory(fight, default.inventory.clone())?,
+                eat_hp: fight.eat_hp.unwrap_or(default.eat_hp),
+                switches,
+                flick_success: fight.flick_success.unwrap_or(default.flick_success),
+                thralls: fight.thrall,
+                logger: FightLogger::new(trace),
+                ..default
+            };
+            Box::new(OlmFight::new(player, config, None)?)
+        }
+        Boss::Scripted => {
+            let script = fight.script.as_deref().ok_or_else(|| {
+                CliError::InvalidArgs("Scripted fights need a boss script (--script).".into())
+            })?;
+
+            // Anything that isn't an existing file is looked up among the bundled scripts
+            let path = Path::new(script);
+            let script = if path.exists() {
+                BossScript::load(path)?
+            } else {
+                BossScript::bundled(script)?
+            };
+            let monster = Monster::new(&script.monster, script.version.as_deref())?;
+            let player = player_args.build(&monster)?;
+            let default = ScriptedConfig::default();
+            let config = ScriptedConfig {
+                inventory: build_inventory(fight, default.inventory.clone())?,
+                eat_hp: fight.eat_hp.unwrap_or(default.eat_hp),
+                thralls: fight.thrall.or(default.thralls),
+                flick_success: fight.flick_success.unwrap_or(default.flick_success),
+                logger: FightLogger::new(trace),
+                ..default
+            };
+            Box::new(ScriptedFight::new(player, script, config, None)?)
+        }
+        Boss::Vorkath => {
+            let monster = Monster::new("Vorkath", Some("Post-quest"))?;
+            let player = player_args.build(&monster)?;
+            let default = VorkathConfig::default();
+            let config = VorkathConfig {
+                inventory: build_inventory(fight, default.inventory.clone())?,
+                eat_hp: fight.eat_hp.unwrap_or(default.eat_hp),
+                thralls: fight.thrall.or(default.thralls),
+                flick_success: fight.flick_success.unwrap_or(default.flick_success),
+                logger: FightLogger::new(trace),
+                ..default
+            };
+            Box::new(VorkathFight::new(player, config, None)?)
+        }
+        Boss::Zulrah => {
+            let monster = Monster::new("Zulrah", Some("Serpentine"))?;
+            let player = player_args.build(&monster)?;
+            let default = ZulrahConfig::default();
+            let attack_chart = match fight.styles.as_slice() {
+                [] => default.attack_chart.clone(),
+                [serpentine, magma, tanzanite, jad] => AttackChart {
+                    serpentine: serpentine.clone(),
+                    magma: magma.clone(),
+                    tanzanite: tanzanite.clone(),
+                    jad: jad.clone(),
+                    ..AttackChart::default()
+                },
+                _ => {
+                    return Err(CliError::InvalidArgs(
+                        "Zulrah needs four attack styles (serpentine, magma, tanzanite, Jad)."
+                            .to_string(),
+                    ));
+                }
+            };
+            let config = ZulrahConfig {
+                inventory: build_inventory(fight, default.inventory.clone())?,
+                eat_hp: fight.eat_hp.unwrap_or(default.eat_hp),
+                attack_chart,
+                flick_success: fight.flick_success.unwrap_or(default.flick_success),
+                logger: FightLogger::new(trace),
+                ..default
+            };
+            Box::new(ZulrahFight::new(player, config, None)?)
+        }
+    };
+
+    Ok(simulation)
+}
+
+pub fn simulate(
+    boss: Boss,
+    monster: Option<&MonsterArgs>,
+    player_args: &PlayerArgs,
+    fight: FightArgs,
+    options: &SimulateOptions,
+    json: bool,
+) -> Result<(), CliError> {
+    let fight = fight.merge(&player_args.read_loadout()?.fight)?;
+    if let Some(path) = &options.trace {
+        let simulation = build_simulation(boss, monster, player_args, &fight, true)?;
+        return write_trace(simulation, options.seed, path);
+    }
+    let simulation = build_simulation(boss, monster, player_args, &fight, false)?;
+    let boss_name = simulation.monster().info.name.clone();
+    let report = match options.target_ci {
+        Some(target_half_width) => {
+            // -n caps the number of fights instead of fixing it
+            let config = ConvergenceConfig {
+                target_half_width,
+                max_fights: options.fights,
+                threads: options.threads,
+                ..ConvergenceConfig::default()
+            };
+            let converged = simulate_until_converged(simulation, &config, true, options.seed)?;
+            SimulationReport {
+                boss: boss_name,
+                fights: converged.fights,
+                converged: Some(converged.converged),
+                stats: SimulationStats::new(&converged.results),
+            }
+        }
+        None => {
+            let results = simulate_n_fights_parallel(
+                simulation,
+                options.fights,
+                true,
+                options.seed,
+                options.threads,
+            )?;
+            SimulationReport {
+                boss: boss_name,
+                fights: options.fights,
+                converged: None,
+                stats: SimulationStats::new(&results),
+            }
+        }
+    };
+    if json {
+        return print_json(&report);
+    }
+
+    let stats = &report.stats;
+    let mut table = Table::new(&[]);
+    table.row(["Boss".to_string(), report.boss.clone()]);
+    let fights = match report.converged {
+        Some(true) => format!("{} (converged)", report.fights),
+        Some(false) => format!("{} (did not converge)", report.fights),
+        None => report.fights.to_string(),
+    };
+    table.row(["Fights".to_string(), fights]);
+    table.row([
+        "Average TTK".to_string(),
+        format!(
+            "{:.2} s (95% CI {:.2} - {:.2})",
+            stats.ttk, stats.ttk_ci.ci_low, stats.ttk_ci.ci_high
+        ),
+    ]);
+    let p = &stats.ttk_percentiles;
+    table.row([
+        "TTK p10/p50/p90/p99".to_string(),
+        format!(
+            "{:.1} / {:.1} / {:.1} / {:.1} s",
+            p.p10, p.p50, p.p90, p.p99
+        ),
+    ]);
+    table.row(["Accuracy".to_string(), format!("{:.2}%", stats.accuracy)]);
+    table.row([
+        "Success rate".to_string(),
+        format!(
+            "{:.2}% (95% CI {:.2}% - {:.2}%)",
+            stats.success_rate * 100.0,
+            stats.success_rate_ci.ci_low * 100.0,
+            stats.success_rate_ci.ci_high * 100.0
+        ),
+    ]);
+    table.row([
+        "Food eaten per kill".to_string(),
+        format!(
+            "{:.2} (95% CI {:.2} - {:.2})",
+            stats.avg_food_eaten, stats.food_eaten_ci.ci_low, stats.food_eaten_ci.ci_high
+        ),
+    ]);
+    table.row([
+        "Damage taken per kill".to_string(),
+        format!("{:.2}", stats.avg_damage_taken),
+    ]);
+    table.row([
+        "Prayer used per kill".to_string(),
+        format!("{:.2}", stats.avg_prayer_used),
+    ]);
+    table.row(["Deaths".to_string(), stats.total_deaths.to_string()]);
+    table.row([
+        "Supply outs".to_string(),
+        stats.total_supply_outs.to_string(),
+    ]);
+    print!("{table}");
+
+    Ok(())
+}
+
+// Run a single fight with the event trace enabled and write it out
+fn write_trace(
+    mut simulation: Box<dyn Simulation>,
+    seed: Option<u64>,
+    path: &Path,
+) -> Result<(), CliError> {
+    if simulation.is_immune() {
+        return Err(CliError::MonsterImmune(
+            simulation.monster().info.name.clone(),
+        ));
+    }
+    if let Some(seed) = seed {
+        simulation.set_seed(seed);
+    }
+    simulation.set_attack_function();
+
+    // Fights that end in a death or supply out still carry their trace
+    let (result, outcome) = match simulation.simulate() {
+        Ok(result) => (result, "kill"),
+        Err(SimulationError::PlayerDeathError(result)) => (result, "player death"),
+        Err(SimulationError::SupplyOutError(result)) => (result, "supply out"),
+        Err(e) => return Err(e.into()),
+    };
+    let trace = result.trace.unwrap_or_default();
+
+    // JSON Lines for .jsonl files, readable text otherwise
+    let contents = if path.extension().is_some_and(|ext| ext == "jsonl") {
+        trace.to_jsonl()?
+    } else {
+        trace.to_string()
+    };
+    std::fs::write(path, contents).map_err(|source| CliError::WriteError {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    println!(
+        "Wrote {} events ({outcome} after {} ticks) to {}",
+        trace.events.len(),
+        result.ttk_ticks,
+        path.display()
+    );
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ComparisonRow {
+    pub loadout: PathBuf,
+    #[serde(flatten)]
+    pub report: DpsReport,
+}
+
+pub fn compare(
+    monster_args: &MonsterArgs,
+    loadouts: &[PathBuf],
+    json: bool,
+) -> Result<(), CliError> {
+    let monster = monster_args.build()?;
+    let rows = loadouts
+        .iter()
+        .map(|path| {
+            let player = PlayerArgs::from_loadout(path).build(&monster)?;
+            Ok(ComparisonRow {
+                loadout: path.clone(),
+                report: calc_dps(&player, &monster, false)?,
+            })
+        })
+        .collect::<Result<Vec<_>, CliError>>()?;
+    if json {
+        return print_json(&rows);
+    }
+
+    let best_dps = rows.iter().map(|row| row.report.dps).fold(0.0, f64::max);
+    let mut table = Table::new(&[
+        "Loadout", "Weapon", "Style", "Max hit", "DPS", "TTK (s)", "vs. best",
+    ]);
+    for row in &rows {
+        table.row([
+            loadout_name(&row.loadout),
+            row.report.weapon.clone(),
+            row.report.style.to_string(),
+            row.report.max_hit.to_string(),
+            format!("{:.4}", row.report.dps),
+            format!("{:.2}", row.report.ttk),
+            relative_dps(row.report.dps, best_dps),
+        ]);
+    }
+    print!("{table}");
+
+    Ok(())
+}
+
+// DPS relative to the best setup, or "-" if no setup can deal damage
+fn relative_dps(dps: f64, best_dps: f64) -> String {
+    if best_dps == 0.0 {
+        "-".to_string()
+    } else {
+        format!("{:+.2}%", (dps / best_dps - 1.0) * 100.0)
+    }
+}
+
+fn loadout_name(path: &Path) -> String {
+    path.file_stem().map_or_else(
+        || path.display().to_string(),
+        |stem| stem.to_string_lossy().into_owned(),
+    )
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MonsterSummary {
+    pub name: String,
+    pub version: Option<String>,
+    pub combat_level: u32,
+    pub hitpoints: u32,
+    pub attack: u32,
+    pub strength: u32,
+    pub defence: u32,
+    pub ranged: u32,
+    pub magic: u32,
+    pub defence_bonuses: [i32; 7], // Stab, slash, crush, magic, light, standard, heavy
+    pub attack_speed: Option<u32>,
+    pub max_hits: Vec<String>,
+    pub attributes: Vec<String>,
+}
+
+impl From<&Monster> for MonsterSummary {
+    fn from(monster: &Monster) -> Self {
+        let def = &monster.bonuses.defence;
+        Self {
+            name: monster.info.name.clone(),
+            version: monster.info.version.clone(),
+            combat_level: monster.info.combat_level,
+            hitpoints: monster.stats.hitpoints.base,
+            attack: monster.stats.attack.base,
+            strength: monster.stats.strength.base,
+            defence: monster.stats.defence.base,
+            ranged: monster.stats.ranged.base,
+            magic: monster.stats.magic.base,
+            defence_bonuses: [
+                def.stab,
+                def.slash,
+                def.crush,
+                def.magic,
+                def.light,
+                def.standard,
+                def.heavy,
+            ],
+            attack_speed: monster.info.attack_speed,
+            max_hits: monster
+                .max_hits
+                .iter()
+                .flatten()
+                .map(|hit| format!("{} ({})", hit.value, hit.style))
+                .collect(),
+            attributes: monster
+                .info
+                .attributes
+                .iter()
+                .flatten()
+                .map(|attr| format!("{attr:?}"))
+                .collect(),
+        }
+    }
+}
+
+pub fn lookup_monster(name: &str, version: Option<&str>, json: bool) -> Result<(), CliError> {
+    let mut monsters = Monster::search(name)?;
+    if let Some(version) = version {
+        monsters.retain(|m| m.info.version.as_deref() == Some(version));
+    }
+
+    // Show full details for exact name matches, otherwise list the candidates
+    let exact: Vec<&Monster> = monsters
+        .iter()
+        .filter(|m| m.info.name.eq_ignore_ascii_case(name))
+        .collect();
+    let summaries: Vec<MonsterSummary> = if exact.is_empty() {
+        monsters.iter().map(MonsterSummary::from).collect()
+    } else {
+        exact.into_iter().map(MonsterSummary::from).collect()
+    };
+    if summaries.is_empty() {
+        return Err(CliError::MonsterError(
+            osrs::error::MonsterError::MonsterNotFound(name.to_string()),
+        ));
+    }
+    if json {
+        return print_json(&summaries);
+    }
+
+    if summaries.iter().any(|m| !m.name.eq_ignore_ascii_case(name)) {
+        let mut table = Table::new(&["Name", "Version", "Combat", "HP"]);
+        for m in &summaries {
+            table.row([
+                m.name.clone(),
+                m.version.clone().unwrap_or_default(),
+                m.combat_level.to_string(),
+                m.hitpoints.to_string(),
+            ]);
+        }
+        print!("{table}");
+        return Ok(());
+    }
+
+    let mut table = Table::new(&[
+        "Version", "Combat", "HP", "Att", "Str", "Def", "Rng", "Mage", "Stab", "Slash", "Crush",
+        "Magic", "Light", "Std", "Heavy", "Speed", "Max hits",
+    ]);
+    for m in &summaries {
+        let mut row = vec![
+            m.version.clone().unwrap_or_default(),
+            m.combat_level.to_string(),
+            m.hitpoints.to_string(),
+            m.attack.to_string(),
+            m.strength.to_string(),
+            m.defence.to_string(),
+            m.ranged.to_string(),
+            m.magic.to_string(),
+        ];
+        row.extend(m.defence_bonuses.iter().map(ToString::to_string));
+        row.push(m.attack_speed.map(|s| s.to_string()).unwrap_or_default());
+        row.push(m.max_hits.join(", "));
+        table.row(row);
+    }
+    println!("{}", summaries[0].name);
+    if let Some(attributes) = summaries.iter().find(|m| !m.attributes.is_empty()) {
+        println!("Attributes: {}", attributes.attributes.join(", "));
+    }
+    print!("{table}");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use osrs::types::player::SwitchType;
+
+    #[test]
+    fn test_calc_dps_matches_library() {
+        let monster = Monster::new("General Graardor", None).unwrap();
+        let args = PlayerArgs {
+            gear: vec!["Abyssal whip".to_string()],
+            style: Some(CombatStyle::Lash),
+            ..PlayerArgs::default()
+        };
+        let player = args.build(&monster).unwrap();
+        let report = calc_dps(&player, &monster, false).unwrap();
+
+        let dist = get_distribution(&player, &monster, false).unwrap();
+        assert!((report.dps - get_dps(&dist, &player, false)).abs() < 1e-9);
+        assert_eq!(report.weapon, "Abyssal whip");
+        assert!(report.ttk > 0.0);
+    }
+
+    #[test]
+    fn test_ttk_dist_sums_to_one() {
+        let monster = Monster::new("General Graardor", None).unwrap();
+        let args = PlayerArgs {
+            gear: vec!["Abyssal whip".to_string()],
+            ..PlayerArgs::default()
+        };
+        let player = args.build(&monster).unwrap();
+        let entries = calc_ttk_dist(&player, &monster, false).unwrap();
+
+        assert!(entries.windows(2).all(|w| w[0].ticks < w[1].ticks));
+        let total = entries.last().unwrap().cumulative;
+        assert!((total - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_relative_dps() {
+        assert_eq!(relative_dps(4.0, 5.0), "-20.00%");
+        assert_eq!(relative_dps(5.0, 5.0), "+0.00%");
+        assert_eq!(relative_dps(0.0, 0.0), "-");
+    }
+
+    #[test]
+    fn test_hunllef_armor_tier() {
+        let max_hit = |armor_tier: Option<u32>| {
+            let fight = FightArgs {
+                styles: vec![SwitchType::Ranged, SwitchType::Magic],
+                armor_tier,
+                ..FightArgs::default()
+            };
+            build_hunllef(&PlayerArgs::default(), &fight, false)
+                .unwrap()
+                .max_hit()
+        };
+
+        // No armour is worn, so only an explicit tier lowers the max hit
+        assert_eq!(max_hit(None), 16);
+        assert_eq!(max_hit(Some(0)), 16);
+        assert_eq!(max_hit(Some(3)), 8);
+    }
+
+    #[test]
+    fn test_single_way_needs_monster() {
+        let result = build_simulation(
+            Boss::SingleWay,
+            None,
+            &PlayerArgs::default(),
+            &FightArgs::default(),
+            false,
+        );
+        assert!(matches!(result, Err(CliError::InvalidArgs(_))));
+    }
+}
@@ -0,0 +1,202 @@
+pub mod args;
+pub mod commands;
+pub mod table;
+
+use args::{FightArgs, MonsterArgs, PlayerArgs};
+use clap::{Parser, Subcommand, ValueEnum};
+use osrs::error::{
+    DpsCalcError, GearError, InventoryError, LoadoutError, MonsterError, PlayerError, ScriptError,
+    SimulationError,
+};
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum CliError {
+    #[error("Error reading {path}: {source}")]
+    ReadError {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Error writing {path}: {source}")]
+    WriteError {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Error parsing loadout {path}: {source}")]
+    LoadoutParseError { path: PathBuf, source: LoadoutError },
+    #[error("Unknown {kind}: {name}")]
+    InvalidName { kind: &'static str, name: String },
+    #[error("{0}")]
+    InvalidArgs(String),
+    #[error("Monster {0} is immune to the player's setup.")]
+    MonsterImmune(String),
+    #[error("Loadout error: {0}")]
+    LoadoutError(#[from] LoadoutError),
+    #[error("Gear error: {0}")]
+    GearError(#[from] GearError),
+    #[error("Player error: {0}")]
+    PlayerError(#[from] PlayerError),
+    #[error("Monster error: {0}")]
+    MonsterError(#[from] MonsterError),
+    #[error("Simulation error: {0}")]
+    SimulationError(#[from] SimulationError),
+    #[error("DPS calc error: {0}")]
+    DpsCalcError(#[from] DpsCalcError),
+    #[error("Inventory error: {0}")]
+    InventoryError(#[from] InventoryError),
+    #[error("Boss script error: {0}")]
+    ScriptError(#[from] ScriptError),
+    #[error("Error writing JSON: {0}")]
+    JsonError(#[from] serde_json::Error),
+}
+
+/// Combat calculator and boss fight simulator for Old School RuneScape.
+#[derive(Debug, Parser)]
+#[command(name = "osrs")]
+pub struct Cli {
+    /// Print results as JSON instead of tables
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Calculate max hit, DPS and expected time to kill against a monster
+    Dps {
+        #[command(flatten)]
+        monster: MonsterArgs,
+        #[command(flatten)]
+        player: PlayerArgs,
+        /// Use the weapon's special attack
+        #[arg(long)]
+        spec: bool,
+    },
+    /// Calculate the distribution of times to kill a monster
+    TtkDist {
+        #[command(flatten)]
+        monster: MonsterArgs,
+        #[command(flatten)]
+        player: PlayerArgs,
+        /// Use the weapon's special attack
+        #[arg(long)]
+        spec: bool,
+    },
+    /// Simulate many fights against a boss
+    Simulate {
+        #[arg(value_enum)]
+        boss: Boss,
+        /// Monster to fight (single-way only)
+        #[arg(short, long)]
+        monster: Option<String>,
+        /// Monster version (single-way only)
+        #[arg(long)]
+        monster_version: Option<String>,
+        #[command(flatten)]
+        player: PlayerArgs,
+        #[command(flatten)]
+        fight: FightArgs,
+        /// Number of fights to simulate (the cap when using --target-ci)
+        #[arg(short = 'n', long, default_value_t = 100_000)]
+        fights: u32,
+        /// Keep simulating until the 95% CI of the mean TTK is within this many seconds
+        #[arg(long, value_name = "SECONDS")]
+        target_ci: Option<f64>,
+        /// Seed for reproducible runs
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Worker threads (defaults to the available parallelism)
+        #[arg(long)]
+        threads: Option<usize>,
+        /// Run a single fight and write its event trace here (.jsonl for JSON Lines, else text)
+        #[arg(long, value_name = "FILE")]
+        trace: Option<PathBuf>,
+    },
+    /// Compare DPS and time to kill of several loadout files against a monster
+    Compare {
+        #[command(flatten)]
+        monster: MonsterArgs,
+        /// Loadout files to compare
+        #[arg(required = true, num_args = 1..)]
+        loadouts: Vec<PathBuf>,
+    },
+    /// Search the monster database by name
+    LookupMonster {
+        /// Full or partial monster name
+        name: String,
+        /// Only show this version of the monster
+        #[arg(long)]
+        monster_version: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Boss {
+    SingleWay,
+    Graardor,
+    Hunllef,
+    Muspah,
+    Olm,
+    Scripted,
+    Vardorvis,
+    Vorkath,
+    Zulrah,
+}
+
+pub fn run(cli: Cli) -> Result<(), CliError> {
+    let json = cli.json;
+    match cli.command {
+        Command::Dps {
+            monster,
+            player,
+            spec,
+        } => commands::dps(&monster, &player, spec, json),
+        Command::TtkDist {
+            monster,
+            player,
+            spec,
+        } => commands::ttk_dist(&monster, &player, spec, json),
+        Command::Simulate {
+            boss,
+            monster,
+            monster_version,
+            player,
+            fight,
+            fights,
+            target_ci,
+            seed,
+            threads,
+            trace,
+        } => {
+            let monster = match (boss, monster) {
+                (Boss::SingleWay, Some(name)) => Some(MonsterArgs {
+                    monster: name,
+                    monster_version,
+                    ..MonsterArgs::default()
+                }),
+                (Boss::SingleWay, None) => {
+                    return Err(CliError::InvalidArgs(
+                        "Single-way simulations need a --monster.".to_string(),
+                    ));
+                }
+                _ => None,
+            };
+            let options = commands::SimulateOptions {
+                fights,
+                seed,
+                threads,
+                trace,
+                target_ci,
+            };
+            commands::simulate(boss, monster.as_ref(), &player, fight, &options, json)
+        }
+        Command::Compare { monster, loadouts } => commands::compare(&monster, &loadouts, json),
+        Command::LookupMonster {
+            name,
+            monster_version,
+        } => commands::lookup_monster(&name, monster_version.as_deref(), json),
+    }
+}
@@ -0,0 +1,101 @@
+use std::fmt;
+
+// Plain-text table with left-aligned columns sized to their widest cell
+#[derive(Debug, Default)]
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(headers: &[&str]) -> Self {
+        Self {
+            headers: headers.iter().map(ToString::to_string).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn row<I, S>(&mut self, cells: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.rows
+            .push(cells.into_iter().map(|cell| cell.to_string()).collect());
+    }
+
+    fn widths(&self) -> Vec<usize> {
+        let columns = self
+            .rows
+            .iter()
+            .map(Vec::len)
+            .chain(std::iter::once(self.headers.len()))
+            .max()
+            .unwrap_or(0);
+        (0..columns)
+            .map(|i| {
+                self.rows
+                    .iter()
+                    .chain(std::iter::once(&self.headers))
+                    .filter_map(|row| row.get(i))
+                    .map(|cell| cell.chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect()
+    }
+}
+
+fn write_row(f: &mut fmt::Formatter<'_>, cells: &[String], widths: &[usize]) -> fmt::Result {
+    let line = cells
+        .iter()
+        .zip(widths)
+        .map(|(cell, &width)| format!("{cell:<width$}"))
+        .collect::<Vec<_>>()
+        .join("  ");
+    writeln!(f, "{}", line.trim_end())
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let widths = self.widths();
+        if !self.headers.is_empty() {
+            write_row(f, &self.headers, &widths)?;
+            let separator: Vec<String> = widths.iter().map(|&w| "-".repeat(w)).collect();
+            write_row(f, &separator, &widths)?;
+        }
+        for row in &self.rows {
+            write_row(f, row, &widths)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_alignment() {
+        let mut table = Table::new(&["Loadout", "DPS"]);
+        table.row(["Scythe", "10.25"]);
+        table.row(["Blade of saeldor", "8.1"]);
+
+        assert_eq!(
+            table.to_string(),
+            "Loadout           DPS\n\
+             ----------------  -----\n\
+             Scythe            10.25\n\
+             Blade of saeldor  8.1\n"
+        );
+    }
+
+    #[test]
+    fn test_table_without_headers() {
+        let mut table = Table::new(&[]);
+        table.row(["Max hit", "52"]);
+
+        assert_eq!(table.to_string(), "Max hit  52\n");
+    }
+}
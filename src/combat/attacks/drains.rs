@@ -0,0 +1,132 @@
+// Stat drain rules for special attacks, shared by the simulated specs and the dps calc.
+// Drains that scale with damage take the damage roll after the 0 -> 1 transform but before
+// any other transforms, as in game
+use crate::constants::IMMUNE_TO_STAT_DRAIN;
+use crate::types::monster::{CombatStat, Monster, StatDrain};
+use crate::types::player::Player;
+
+fn immune_to_drains(monster: &Monster) -> bool {
+    IMMUNE_TO_STAT_DRAIN.contains(&monster.id_with_default())
+}
+
+fn is_tekton(monster: &Monster) -> bool {
+    monster.info.name.contains("Tekton")
+}
+
+// Defence, strength, attack, magic, then ranged, with the remainder of each drain carrying over
+fn standard_drain_order() -> Vec<StatDrain> {
+    vec![
+        StatDrain::new(CombatStat::Defence, None),
+        StatDrain::new(CombatStat::Strength, None),
+        StatDrain::new(CombatStat::Attack, None),
+        StatDrain::new(CombatStat::Magic, None),
+        StatDrain::new(CombatStat::Ranged, None),
+    ]
+}
+
+/// Whether the player's spec is guaranteed to hit on the first attack of a fight.
+///
+/// Dorgeshuun weapons always hit first, and the dragon warhammer and elder maul always hit Tekton.
+pub fn spec_always_hits_first_attack(player: &Player, monster: &Monster) -> bool {
+    match player.gear.weapon.name.as_str() {
+        "Bone crossbow" | "Bone dagger" => true,
+        "Dragon warhammer" | "Elder maul" => is_tekton(monster),
+        _ => false,
+    }
+}
+
+/// Drain a percentage of the monster's current defence (dragon warhammer and elder maul).
+///
+/// Tekton isn't protected by drain immunity and still loses 5% of its defence on a miss.
+pub fn drain_defence_percent(monster: &mut Monster, percent: u32, accurate: bool) {
+    let tekton = is_tekton(monster);
+    if accurate && (tekton || !immune_to_drains(monster)) {
+        let amount = monster.stats.defence.current * percent / 100;
+        monster.drain_stat(&CombatStat::Defence, amount, None);
+    } else if !accurate && tekton {
+        let amount = monster.stats.defence.current / 20;
+        monster.drain_stat(&CombatStat::Defence, amount, None);
+    }
+}
+
+/// Drain stats in order by the damage of an accurate bandos godsword spec.
+pub fn bgs_drain(monster: &mut Monster, damage: u32) {
+    if !immune_to_drains(monster) {
+        monster.drain_stats_in_order(damage, standard_drain_order());
+    }
+}
+
+/// Drain stats in order by a tenth of the damage of an accurate barrelchest anchor spec.
+pub fn barrelchest_anchor_drain(monster: &mut Monster, damage: u32) {
+    monster.drain_stats_in_order(damage / 10, standard_drain_order());
+}
+
+/// Drain defence by the damage of an accurate dorgeshuun weapon spec, if it hasn't been drained yet.
+pub fn dorgeshuun_drain(monster: &mut Monster, damage: u32) {
+    if monster.stats.defence.current == monster.stats.defence.base && !immune_to_drains(monster) {
+        monster.drain_stat(&CombatStat::Defence, damage, None);
+    }
+}
+
+/// Drain attack, strength and defence by 1 + 5% of their base levels after an accurate arclight,
+/// darklight or emberlight spec. Demons are drained twice as much, or three times with emberlight.
+pub fn demonbane_drain(monster: &mut Monster, emberlight: bool) {
+    let demon_mod = match (monster.is_demon(), emberlight) {
+        (true, true) => 3,
+        (true, false) => 2,
+        (false, _) => 1,
+    };
+
+    monster.drain_stat(
+        &CombatStat::Attack,
+        monster.stats.attack.base * demon_mod / 20 + 1,
+        None,
+    );
+    monster.drain_stat(
+        &CombatStat::Strength,
+        monster.stats.strength.base * demon_mod / 20 + 1,
+        None,
+    );
+    monster.drain_stat(
+        &CombatStat::Defence,
+        monster.stats.defence.base * demon_mod / 20 + 1,
+        None,
+    );
+}
+
+/// Drain magic and defence by up to 15% of their base levels after an accurate accursed sceptre
+/// spec (less if they're already drained).
+pub fn accursed_sceptre_drain(monster: &mut Monster) {
+    let def_level_cap = monster.stats.defence.base - monster.stats.defence.base * 15 / 100;
+    let magic_level_cap = monster.stats.magic.base - monster.stats.magic.base * 15 / 100;
+
+    if monster.stats.defence.current > def_level_cap {
+        let def_drain_cap = monster.stats.defence.base - def_level_cap;
+        monster.drain_stat(&CombatStat::Defence, def_drain_cap, Some(def_level_cap));
+    }
+
+    if monster.stats.magic.current > magic_level_cap {
+        let magic_drain_cap = monster.stats.magic.base - magic_level_cap;
+        monster.drain_stat(&CombatStat::Magic, magic_drain_cap, Some(magic_level_cap));
+    }
+}
+
+/// Drain magic by the damage of a seercull spec, which always hits.
+pub fn seercull_drain(monster: &mut Monster, damage: u32) {
+    if !immune_to_drains(monster) {
+        monster.drain_stat(&CombatStat::Magic, damage, None);
+    }
+}
+
+/// Drain defence by a tenth of the monster's base magic level for one accurate hit of a
+/// tonalztics of ralos spec, with each drain capped at half of its base defence.
+pub fn tonalztics_drain(monster: &mut Monster) {
+    let amount = monster.stats.magic.base / 10;
+    let cap = Some(monster.stats.defence.base / 2);
+    monster.drain_stat(&CombatStat::Defence, amount, cap);
+}
+
+/// Drain the monster's magic defence bonus by the damage of an accurate eye of ayak spec.
+pub fn eye_of_ayak_drain(monster: &mut Monster, damage: u32) {
+    monster.drain_magic_defence_bonus(damage);
+}
@@ -75,6 +75,39 @@ impl CombatEffect {
     }
 }
 
+// Venom on the player, which hits every 30 ticks starting at 6 and ramping up by 2 to a max of 20
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlayerVenom {
+    pub damage: u32,
+    pub next_tick: i32,
+}
+
+impl PlayerVenom {
+    const START_DAMAGE: u32 = 6;
+    const MAX_DAMAGE: u32 = 20;
+    const TICKS: i32 = 30;
+
+    // Venom inflicted on the given tick deals its first hit on the following tick
+    pub fn new(tick: i32) -> Self {
+        Self {
+            damage: Self::START_DAMAGE,
+            next_tick: tick + 1,
+        }
+    }
+
+    // Returns the venom damage dealt on this tick, if any
+    pub fn tick(&mut self, tick: i32) -> Option<u32> {
+        if tick < self.next_tick {
+            return None;
+        }
+
+        let damage = self.damage;
+        self.damage = (self.damage + 2).min(Self::MAX_DAMAGE);
+        self.next_tick += Self::TICKS;
+        Some(damage)
+    }
+}
+
 #[derive(Debug)]
 pub struct Poison {
     pub tick_counter: Option<i32>,
@@ -1,3 +1,4 @@
+pub mod drains;
 pub mod effects;
 pub mod specs;
 pub mod standard;
@@ -1,19 +1,20 @@
 use crate::calc::rolls::calc_active_player_rolls;
+use crate::combat::attacks::drains;
 use crate::combat::attacks::effects::CombatEffect;
 use crate::combat::attacks::standard::{
     AttackFn, AttackInfo, Hit, accuracy_roll, base_attack, damage_roll, defence_roll,
 };
 use crate::combat::limiters::Limiter;
-use crate::constants::{IMMUNE_TO_MAGIC_MONSTERS, IMMUNE_TO_STAT_DRAIN, VERZIK_IDS};
+use crate::constants::{IMMUNE_TO_MAGIC_MONSTERS, VERZIK_IDS};
 use crate::types::equipment::CombatType;
-use crate::types::monster::{CombatStat, Monster, StatDrain};
+use crate::types::monster::{CombatStat, Monster};
 use crate::types::player::Player;
 use crate::types::spells::{SpecialSpell, Spell};
 use num::clamp;
 use rand::Rng;
 use rand::rngs::SmallRng;
 use std::cmp::max;
-use std::rc::Rc;
+use std::sync::Arc;
 
 pub type SpecialAttackFn =
     fn(&mut Player, &mut Monster, &mut SmallRng, &Option<Box<dyn Limiter>>) -> Hit;
@@ -124,33 +125,7 @@ fn demonbane_melee_spec(
 
     if hit.success {
         hit.damage = max(1, hit.damage);
-
-        // Drains are twice as effective on demons, or 3x if using emberlight against demons
-        let demon_mod = if monster.is_demon() && emberlight {
-            3
-        } else if monster.is_demon() {
-            2
-        } else {
-            1
-        };
-
-        // Drain stats by 1 + 5%, 10%, or 15% of their base values
-        monster.drain_stat(
-            &CombatStat::Attack,
-            monster.stats.attack.base * demon_mod / 20 + 1,
-            None,
-        );
-        monster.drain_stat(
-            &CombatStat::Strength,
-            monster.stats.strength.base * demon_mod / 20 + 1,
-            None,
-        );
-        monster.drain_stat(
-            &CombatStat::Defence,
-            monster.stats.defence.base * demon_mod / 20 + 1,
-            None,
-        );
-
+        drains::demonbane_drain(monster, emberlight);
         hit.apply_transforms(player, monster, rng, limiter);
     }
 
@@ -302,24 +277,7 @@ pub fn bgs_spec(
     // 0 -> 1 transform happens before drains
     if hit.success {
         hit.damage = max(1, hit.damage);
-
-        if !IMMUNE_TO_STAT_DRAIN.contains(&monster.id_with_default()) {
-            let cap = if monster.info.name.contains("Tekton") && !hit.success {
-                Some(10)
-            } else {
-                None
-            };
-
-            let stat_order = vec![
-                StatDrain::new(CombatStat::Defence, cap),
-                StatDrain::new(CombatStat::Strength, cap),
-                StatDrain::new(CombatStat::Attack, cap),
-                StatDrain::new(CombatStat::Magic, cap),
-                StatDrain::new(CombatStat::Ranged, cap),
-            ];
-
-            monster.drain_stats_in_order(hit.damage, stat_order);
-        }
+        drains::bgs_drain(monster, hit.damage);
 
         // Other transforms happen after drains
         hit.apply_transforms(player, monster, rng, limiter);
@@ -477,20 +435,7 @@ pub fn accursed_sceptre_spec(
     let mut hit = base_attack(&info, rng, player.rolls_accuracy_twice());
     if hit.success {
         hit.apply_transforms(player, monster, rng, limiter);
-
-        // Drain magic and defence by up to 15% of base levels (less if already drained)
-        let def_level_cap = monster.stats.defence.base - monster.stats.defence.base * 15 / 100;
-        let magic_level_cap = monster.stats.magic.base - monster.stats.magic.base * 15 / 100;
-
-        if monster.stats.defence.current > def_level_cap {
-            let def_drain_cap = monster.stats.defence.base - def_level_cap;
-            monster.drain_stat(&CombatStat::Defence, def_drain_cap, Some(def_level_cap));
-        }
-
-        if monster.stats.magic.current > magic_level_cap {
-            let magic_drain_cap = monster.stats.magic.base - magic_level_cap;
-            monster.drain_stat(&CombatStat::Magic, magic_drain_cap, Some(magic_level_cap));
-        }
+        drains::accursed_sceptre_drain(monster);
     }
     hit
 }
@@ -560,14 +505,7 @@ pub fn barrelchest_anchor_spec(
         hit.damage = max(1, hit.damage);
 
         // Stat drains happen before transforms, according to Mod Ash
-        let drain_order = vec![
-            StatDrain::new(CombatStat::Defence, None),
-            StatDrain::new(CombatStat::Strength, None),
-            StatDrain::new(CombatStat::Attack, None),
-            StatDrain::new(CombatStat::Magic, None),
-            StatDrain::new(CombatStat::Ranged, None),
-        ];
-        monster.drain_stats_in_order(hit.damage / 10, drain_order);
+        drains::barrelchest_anchor_drain(monster, hit.damage);
 
         hit.apply_transforms(player, monster, rng, limiter);
     }
@@ -584,22 +522,19 @@ pub fn dorgeshuun_weapon_spec(
     let info = AttackInfo::new(player, monster);
 
     // Always hits accurately if it's the first attack
-    let mut hit = if player.state.first_attack {
-        Hit::accurate(damage_roll(info.min_hit, info.max_hit, rng))
-    } else {
-        base_attack(&info, rng, false)
-    };
+    let mut hit =
+        if player.state.first_attack && drains::spec_always_hits_first_attack(player, monster) {
+            Hit::accurate(damage_roll(info.min_hit, info.max_hit, rng))
+        } else {
+            base_attack(&info, rng, false)
+        };
 
     if hit.success {
         // Apply 0 -> 1 transform before drain
         hit.damage = max(1, hit.damage);
 
         // Drains defence by damage, but only if it hasn't been drained already
-        if monster.stats.defence.current == monster.stats.defence.base
-            && !IMMUNE_TO_STAT_DRAIN.contains(&monster.id_with_default())
-        {
-            monster.drain_stat(&CombatStat::Defence, hit.damage, None);
-        }
+        drains::dorgeshuun_drain(monster, hit.damage);
 
         // Apply other transforms after drain
         hit.apply_transforms(player, monster, rng, limiter);
@@ -641,45 +576,32 @@ pub fn dragon_warhammer_spec(
     // Boost damage by 50%
     info.max_hit = info.max_hit * 3 / 2;
 
-    // Store defence drain amount (30% of current level)
-    let def_drain = monster.stats.defence.current * 3 / 10;
-
-    if monster.info.name.contains("Tekton") {
-        // DWH spec always hits on first attack on Tekton
-        if player.state.first_attack {
-            let mut hit = Hit::accurate(damage_roll(info.min_hit, info.max_hit, rng));
-            monster.drain_stat(&CombatStat::Defence, def_drain, None);
-            hit.apply_transforms(player, monster, rng, limiter);
+    // Drain defence by 30% of its current level
+    defence_percent_spec(player, monster, rng, limiter, &info, 30)
+}
 
-            hit
+// Shared by the dragon warhammer and elder maul, which always hit Tekton on the first attack
+fn defence_percent_spec(
+    player: &mut Player,
+    monster: &mut Monster,
+    rng: &mut SmallRng,
+    limiter: &Option<Box<dyn Limiter>>,
+    info: &AttackInfo,
+    percent: u32,
+) -> Hit {
+    let mut hit =
+        if player.state.first_attack && drains::spec_always_hits_first_attack(player, monster) {
+            Hit::accurate(damage_roll(info.min_hit, info.max_hit, rng))
         } else {
-            let mut hit = base_attack(&info, rng, false);
-            if hit.success {
-                hit.apply_transforms(player, monster, rng, limiter);
-                monster.drain_stat(&CombatStat::Defence, def_drain, None);
-            } else {
-                // DWH spec still drains 5% of Tekton's defence on a miss
-                monster.drain_stat(
-                    &CombatStat::Defence,
-                    monster.stats.defence.current / 20,
-                    None,
-                );
-            }
-
-            hit
-        }
-    } else {
-        let mut hit = base_attack(&info, rng, false);
-        if hit.success {
-            hit.apply_transforms(player, monster, rng, limiter);
-
-            if !IMMUNE_TO_STAT_DRAIN.contains(&monster.id_with_default()) {
-                monster.drain_stat(&CombatStat::Defence, def_drain, None);
-            }
-        }
+            base_attack(info, rng, false)
+        };
 
-        hit
+    drains::drain_defence_percent(monster, percent, hit.success);
+    if hit.success {
+        hit.apply_transforms(player, monster, rng, limiter);
     }
+
+    hit
 }
 
 pub fn seercull_spec(
@@ -698,10 +620,7 @@ pub fn seercull_spec(
 
     // Stat drain is determined from damage roll after 0 -> 1 transform
     hit.damage = max(hit.damage, 1);
-
-    if !IMMUNE_TO_STAT_DRAIN.contains(&monster.id_with_default()) {
-        monster.drain_stat(&CombatStat::Magic, hit.damage, None);
-    }
+    drains::seercull_drain(monster, hit.damage);
 
     hit.apply_transforms(player, monster, rng, limiter);
 
@@ -1487,13 +1406,10 @@ pub fn tonalztics_of_ralos_spec(
     // Rolls up to 3/4 of the "true" max hit for each hit
     info.max_hit = info.max_hit * 3 / 4;
 
-    let drain_cap = Some(monster.stats.defence.base / 2);
-    let drain_amount = monster.stats.magic.base / 10;
-
     let mut hit1 = base_attack(&info, rng, false);
     if hit1.success {
         hit1.damage = max(1, hit1.damage);
-        monster.drain_stat(&CombatStat::Defence, drain_amount, drain_cap);
+        drains::tonalztics_drain(monster);
         hit1.apply_transforms(player, monster, rng, limiter);
     }
     if player.gear.weapon.matches_version("Charged") {
@@ -1501,7 +1417,7 @@ pub fn tonalztics_of_ralos_spec(
         let mut hit2 = base_attack(&info, rng, false);
         if hit2.success {
             hit2.damage = max(1, hit2.damage);
-            monster.drain_stat(&CombatStat::Defence, drain_amount, drain_cap);
+            drains::tonalztics_drain(monster);
             hit2.apply_transforms(player, monster, rng, limiter);
         }
         return hit1.combine(&hit2);
@@ -1553,7 +1469,7 @@ pub fn dual_macuahuitl_spec(
     }
 
     // Next attack is guaranteed to be 3 ticks
-    Rc::make_mut(&mut player.gear).weapon.speed = 3;
+    Arc::make_mut(&mut player.gear).weapon.speed = 3;
 
     hit1.combine(&hit2)
 }
@@ -1630,47 +1546,13 @@ pub fn elder_maul_spec(
     rng: &mut SmallRng,
     limiter: &Option<Box<dyn Limiter>>,
 ) -> Hit {
-    let info = AttackInfo::new(player, monster);
-
-    // Store defence drain amount (35% of current level)
-    let def_drain = monster.stats.defence.current * 35 / 100;
-
-    if monster.info.name.contains("Tekton") {
-        // DWH spec always hits on first attack on Tekton
-        if player.state.first_attack {
-            let mut hit = Hit::accurate(damage_roll(info.min_hit, info.max_hit, rng));
-            monster.drain_stat(&CombatStat::Defence, def_drain, None);
-            hit.apply_transforms(player, monster, rng, limiter);
-
-            hit
-        } else {
-            let mut hit = base_attack(&info, rng, false);
-            if hit.success {
-                hit.apply_transforms(player, monster, rng, limiter);
-                monster.drain_stat(&CombatStat::Defence, def_drain, None);
-            } else {
-                // DWH spec still drains 5% of Tekton's defence on a miss
-                monster.drain_stat(
-                    &CombatStat::Defence,
-                    monster.stats.defence.current / 20,
-                    None,
-                );
-            }
-
-            hit
-        }
-    } else {
-        let mut hit = base_attack(&info, rng, false);
-        if hit.success {
-            hit.apply_transforms(player, monster, rng, limiter);
+    let mut info = AttackInfo::new(player, monster);
 
-            if !IMMUNE_TO_STAT_DRAIN.contains(&monster.id_with_default()) {
-                monster.drain_stat(&CombatStat::Defence, def_drain, None);
-            }
-        }
+    // Boost accuracy by 25%, the same spec factor the dps calc has always used
+    info.max_att_roll = info.max_att_roll * 5 / 4;
 
-        hit
-    }
+    // Drain defence by 35% of its current level
+    defence_percent_spec(player, monster, rng, limiter, &info, 35)
 }
 
 pub fn crimson_bludgeon_spec(
@@ -1727,14 +1609,15 @@ pub fn eye_of_ayak_spec(
     let mut hit = base_attack(&info, rng, player.rolls_accuracy_twice());
 
     if hit.success {
-        // Drain the monster's magic defence bonus by the damage dealt, capped at 0
-        monster.bonuses.defence.magic = max(0, monster.bonuses.defence.magic - hit.damage as i32);
+        // Drain the monster's magic defence bonus by the damage dealt after the 0 -> 1 transform
+        hit.damage = max(1, hit.damage);
+        drains::eye_of_ayak_drain(monster, hit.damage);
 
         hit.apply_transforms(player, monster, rng, limiter);
     }
 
     // Spec has a 5-tick attack speed
-    Rc::make_mut(&mut player.gear).weapon.speed = 5;
+    Arc::make_mut(&mut player.gear).weapon.speed = 5;
 
     hit
 }
@@ -1,3 +1,4 @@
+use crate::calc::dps_calc::get_wardens_p2_min_max;
 use crate::calc::rolls::{self, calc_active_player_rolls};
 use crate::combat::attacks::effects::CombatEffect;
 use crate::combat::limiters::Limiter;
@@ -6,11 +7,10 @@ use crate::types::equipment::{CombatStyle, CombatType};
 use crate::types::monster::{CombatStat, Monster};
 use crate::types::player::Player;
 use crate::types::spells::{AncientSpell, Spell};
-use crate::utils::math;
 use rand::Rng;
 use rand::rngs::SmallRng;
 use std::cmp::max;
-use std::rc::Rc;
+use std::sync::Arc;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct AttackInfo {
@@ -909,7 +909,7 @@ pub fn dual_macuahuitl_attack(
     info2.max_hit = max_hit - max_hit / 2;
 
     // Reset attack speed to 4 ticks
-    Rc::make_mut(&mut player.gear).weapon.speed = 4;
+    Arc::make_mut(&mut player.gear).weapon.speed = 4;
 
     // Roll two separate hits
     let mut hit1 = base_attack(&info1, rng, false);
@@ -932,7 +932,7 @@ pub fn dual_macuahuitl_attack(
         && ((hit1.success && rng.random_range(0..3) == 0)
             || (hit2.success && rng.random_range(0..3) == 0))
     {
-        Rc::make_mut(&mut player.gear).weapon.speed = 3;
+        Arc::make_mut(&mut player.gear).weapon.speed = 3;
     }
 
     hit1.combine(&hit2)
@@ -1017,20 +1017,8 @@ pub fn wardens_p2_attack(
     rng: &mut SmallRng,
     _: &Option<Box<dyn Limiter>>,
 ) -> Hit {
-    let att_roll = max(
-        0,
-        player
-            .att_rolls
-            .get(player.combat_type())
-            .expect("Error getting player attack rolls.")
-            - monster.def_rolls.get(player.combat_type()) / 3,
-    );
-
-    let modifier = max(15, math::lerp(att_roll, 0, 42000, 15, 40));
-    let base_max_hit = player.max_hits.get(player.combat_type());
-    let min_hit = base_max_hit * modifier as u32 / 100;
-    let max_hit = base_max_hit * (modifier + 20) as u32 / 100;
-
+    let (min_hit, max_hit) =
+        get_wardens_p2_min_max(player, monster).expect("Error getting player attack rolls.");
     let damage = damage_roll(min_hit, max_hit, rng);
 
     Hit::accurate(damage)
@@ -3,7 +3,7 @@ use rand::{Rng, rngs::SmallRng};
 use std::cmp::{max, min};
 
 // Trait for any post-roll damage transforms applied by the opponent
-pub trait Limiter: DynClone {
+pub trait Limiter: DynClone + Send {
     fn apply(&self, damage: u32, rng: &mut SmallRng) -> u32;
 }
 
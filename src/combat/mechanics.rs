@@ -1,19 +1,33 @@
 use crate::calc::monster_scaling::scale_monster_hp_only;
-use crate::combat::attacks::effects::CombatEffect;
+use crate::calc::rolls::calc_active_player_rolls;
+use crate::combat::attacks::effects::{CombatEffect, PlayerVenom};
 use crate::combat::limiters::Limiter;
+use crate::combat::prayer::{PrayerDrainConfig, PrayerOutBehaviour};
 use crate::combat::simulation::FightResult;
 use crate::combat::simulation::FightVars;
+use crate::combat::spec::{SpecCondition, SpecConfig, SpecState};
 use crate::combat::thralls::Thrall;
 use crate::constants::{self, THRALL_ATTACK_SPEED};
 use crate::error::SimulationError;
-use crate::types::monster::{AttackType, Monster};
-use crate::types::player::Player;
+use crate::types::food::{Food, FoodType, Foods};
+use crate::types::inventory::Inventory;
+use crate::types::monster::{AttackType, CombatStat, Monster};
+use crate::types::monster_specials::{SpecialEffect, SpecialHit};
+use crate::types::player::{GearSwitch, Player};
+use crate::types::potions::{Potion, RedoseConfig};
 use crate::utils::logging::FightLogger;
 use rand::Rng;
 use rand::rngs::SmallRng;
 
 use super::attacks::standard::Hit;
 
+// Chance of taking one hit from an arena hazard (e.g., a venom cloud or lightning), and that hit's max
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Hazard {
+    pub chance: f64,
+    pub max_hit: u32,
+}
+
 pub trait Mechanics {
     fn player_attack(
         &self,
@@ -58,6 +72,151 @@ pub trait Mechanics {
         fight_vars.attack_tick += player.gear.weapon.speed;
     }
 
+    // Perform the first special attack strategy whose conditions are met, switching to its gear
+    // and back again afterwards. Returns whether a special attack was used this tick
+    #[allow(clippy::too_many_arguments)]
+    fn player_special_attack<C: SpecCondition>(
+        &self,
+        player: &mut Player,
+        monster: &mut Monster,
+        spec_config: &mut SpecConfig<C>,
+        spec_state: &mut SpecState,
+        boss_state: &C::BossState,
+        rng: &mut SmallRng,
+        limiter: &Option<Box<dyn Limiter>>,
+        fight_vars: &mut FightVars,
+        logger: &mut FightLogger,
+    ) -> Result<bool, SimulationError> {
+        match spec_config.lowest_cost() {
+            Some(lowest) if player.stats.spec.value() >= lowest => {}
+            _ => return Ok(false),
+        }
+
+        for strategy in &mut spec_config.strategies {
+            if !strategy.can_execute(player, monster, boss_state) {
+                continue;
+            }
+
+            // Make sure the current set of gear is added to the player's gear switches to allow switching back
+            if player.current_switch.is_none() {
+                let current_gear = GearSwitch::from(&*player);
+                player.current_switch = Some(current_gear.switch_type.clone());
+                player.switches.push(current_gear);
+            }
+
+            // Store the previous gear set's label for switching back after the spec
+            let previous_switch = player.current_switch.clone().unwrap();
+
+            // Switch to the spec gear and perform the attack
+            player.switch(&strategy.switch_type)?;
+
+            if logger.enabled {
+                logger.log_gear_switch(fight_vars.tick_counter, &strategy.switch_type);
+                let _ = logger.log_current_player_rolls(player);
+                logger.log_current_player_stats(player);
+                logger.log_current_gear(player);
+            }
+
+            let hit = (player.spec)(player, monster, rng, limiter);
+
+            if logger.enabled {
+                logger.log_player_spec(
+                    fight_vars.tick_counter,
+                    hit.damage,
+                    hit.success,
+                    &strategy.switch_type,
+                );
+            }
+
+            player.state.first_attack = false;
+            monster.take_damage(hit.damage);
+
+            if logger.enabled {
+                logger.log_monster_damage(
+                    fight_vars.tick_counter,
+                    hit.damage,
+                    monster.stats.hitpoints.current,
+                    monster.name(),
+                );
+                logger.log_current_monster_stats(monster);
+                logger.log_current_monster_rolls(monster);
+            }
+
+            strategy.state.attempt_count += 1;
+            if hit.success {
+                strategy.state.success_count += 1;
+            }
+
+            handle_blood_fury(player, &hit, fight_vars, logger, rng);
+            scale_monster_hp_only(monster, true);
+            fight_vars.hit_attempts += 1;
+            fight_vars.hit_count += u32::from(hit.success);
+            fight_vars.hit_amounts.push(hit.damage);
+            fight_vars.attack_tick += player.gear.weapon.speed;
+
+            player.stats.spec.drain(strategy.spec_cost);
+            if !spec_state.spec_regen_timer.is_active() {
+                spec_state.spec_regen_timer.activate();
+            }
+
+            // Switch back to the previous set of gear
+            player.switch(&previous_switch)?;
+
+            if logger.enabled {
+                logger.log_gear_switch(fight_vars.tick_counter, &previous_switch);
+                let _ = logger.log_current_player_rolls(player);
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    // Tick special attack regen, death charge/surge potion cooldowns, and surge potion usage
+    fn process_spec_timers<C: SpecCondition>(
+        &self,
+        player: &mut Player,
+        spec_config: Option<&SpecConfig<C>>,
+        spec_state: &mut SpecState,
+        fight_vars: &FightVars,
+        logger: &mut FightLogger,
+    ) {
+        spec_state.increment_spec(player, fight_vars.tick_counter, logger);
+        spec_state.increment_timers();
+        if let Some(spec_config) = spec_config {
+            spec_state.process_surge_potion(player, spec_config);
+        }
+    }
+
+    // Carry the player into the next kill of a trip: apply any on-kill spec restores, then regen
+    // HP, stats and spec energy over the downtime before the monster respawns
+    fn prepare_next_kill<C: SpecCondition>(
+        &self,
+        player: &mut Player,
+        spec_config: Option<&mut SpecConfig<C>>,
+        spec_state: &mut SpecState,
+        downtime_ticks: u32,
+        logger: &mut FightLogger,
+    ) {
+        if let Some(spec_config) = spec_config
+            && spec_state.on_kill(player, spec_config)
+        {
+            player.stats.spec.regen_full();
+        }
+
+        for tick in 1..=downtime_ticks as i32 {
+            if tick % constants::PLAYER_REGEN_TICKS == 0 {
+                player.regen_all_stats();
+            }
+            spec_state.increment_spec(player, tick, logger);
+            spec_state.increment_timers();
+        }
+
+        player.state.first_attack = true;
+        player.state.last_attack_hit = true;
+    }
+
     fn monster_attack(
         &self,
         monster: &mut Monster,
@@ -94,6 +253,103 @@ pub trait Mechanics {
         Ok(())
     }
 
+    // Apply a monster special attack's damage and the effects that landed
+    fn apply_monster_special(
+        &self,
+        monster: &mut Monster,
+        player: &mut Player,
+        special: &SpecialHit,
+        venom: &mut Option<PlayerVenom>,
+        fight_vars: &mut FightVars,
+        logger: &mut FightLogger,
+    ) {
+        let hit = special.total();
+        if logger.enabled {
+            logger.log_monster_special(fight_vars.tick_counter, &monster.info.name, &special.name);
+            logger.log_monster_attack(
+                monster,
+                fight_vars.tick_counter,
+                hit.damage,
+                hit.success,
+                Some(AttackType::Special),
+            );
+        }
+        player.take_damage(hit.damage);
+        fight_vars.damage_taken += hit.damage;
+        if hit.success {
+            handle_recoil(player, monster, &hit, fight_vars, logger);
+        }
+
+        let tick = fight_vars.tick_counter;
+        let mut rolls_changed = false;
+        for effect in &special.effects {
+            match *effect {
+                SpecialEffect::DrainStat { stat, amount } => {
+                    match stat {
+                        CombatStat::Attack => player.stats.attack.drain(amount),
+                        CombatStat::Strength => player.stats.strength.drain(amount),
+                        CombatStat::Defence => player.stats.defence.drain(amount),
+                        CombatStat::Ranged => player.stats.ranged.drain(amount),
+                        CombatStat::Magic => player.stats.magic.drain(amount),
+                    }
+                    rolls_changed = true;
+                }
+                SpecialEffect::DrainPrayer(amount) => player.stats.prayer.drain(amount),
+                SpecialEffect::Venom => {
+                    venom.get_or_insert_with(|| PlayerVenom::new(tick));
+                }
+                SpecialEffect::Stun(ticks) => {
+                    // A stun stops the player attacking, eating and moving
+                    fight_vars.attack_tick = fight_vars.attack_tick.max(tick + ticks);
+                    fight_vars.eat_delay = fight_vars.eat_delay.max(ticks.max(0) as u32);
+                    fight_vars.player_frozen_until =
+                        fight_vars.player_frozen_until.max(tick + ticks);
+                }
+                SpecialEffect::Freeze(ticks) => {
+                    fight_vars.player_frozen_until =
+                        fight_vars.player_frozen_until.max(tick + ticks);
+                }
+                SpecialEffect::DisablePrayers(ticks) => {
+                    if fight_vars.disabled_prayers.is_none() {
+                        fight_vars.disabled_prayers = Some(std::mem::take(&mut player.prayers));
+                        rolls_changed = true;
+                    }
+                    fight_vars.prayers_disabled_until =
+                        fight_vars.prayers_disabled_until.max(tick + ticks);
+                }
+            }
+
+            if logger.enabled {
+                logger.log_custom(tick, format!("Player was hit by {effect:?}.").as_str());
+            }
+        }
+
+        if rolls_changed {
+            calc_active_player_rolls(player, monster);
+        }
+    }
+
+    // Turn the player's prayers back on once a prayer disable wears off
+    fn process_prayer_disable(
+        &self,
+        player: &mut Player,
+        monster: &Monster,
+        fight_vars: &mut FightVars,
+        logger: &mut FightLogger,
+    ) {
+        if fight_vars.tick_counter < fight_vars.prayers_disabled_until {
+            return;
+        }
+        if let Some(prayers) = fight_vars.disabled_prayers.take() {
+            player.prayers = prayers;
+            calc_active_player_rolls(player, monster);
+
+            if logger.enabled {
+                logger.log_custom(fight_vars.tick_counter, "Player's prayers were restored.");
+            }
+        }
+    }
+
     fn thrall_attack(
         &self,
         monster: &mut Monster,
@@ -102,15 +358,18 @@ pub trait Mechanics {
         rng: &mut SmallRng,
         logger: &mut FightLogger,
     ) {
-        if logger.enabled && monster.is_immune_to_thrall(thrall) {
-            logger.log_custom(
-                fight_vars.tick_counter,
-                format!(
-                    "Thrall hit for 0 damage because {} is immune to it.",
-                    monster.info.name
-                )
-                .as_str(),
-            );
+        if monster.is_immune_to_thrall(thrall) {
+            if logger.enabled {
+                logger.log_custom(
+                    fight_vars.tick_counter,
+                    format!(
+                        "Thrall hit for 0 damage because {} is immune to it.",
+                        monster.info.name
+                    )
+                    .as_str(),
+                );
+            }
+            fight_vars.thrall_attack_tick += THRALL_ATTACK_SPEED;
             return;
         }
 
@@ -244,6 +503,8 @@ pub trait Mechanics {
             damage_taken: fight_vars.damage_taken,
             leftover_burn,
             thrall_damage: fight_vars.thrall_damage,
+            prayer_used: fight_vars.prayer_used,
+            trace: logger.take_trace(),
         })
     }
 
@@ -257,18 +518,27 @@ pub trait Mechanics {
             logger.log_player_death(fight_vars.tick_counter);
         }
 
-        let leftover_burn = calc_leftover_burn(monster);
+        Err(SimulationError::PlayerDeathError(unfinished_fight_result(
+            fight_vars, monster, logger,
+        )))
+    }
 
-        Err(SimulationError::PlayerDeathError(FightResult {
-            ttk_ticks: fight_vars.tick_counter,
-            hit_attempts: fight_vars.hit_attempts,
-            hit_count: fight_vars.hit_count,
-            hit_amounts: fight_vars.hit_amounts.clone(),
-            food_eaten: fight_vars.food_eaten,
-            damage_taken: fight_vars.damage_taken,
-            leftover_burn,
-            thrall_damage: fight_vars.thrall_damage,
-        }))
+    fn process_supply_out(
+        &self,
+        fight_vars: &FightVars,
+        monster: &Monster,
+        logger: &mut FightLogger,
+    ) -> Result<FightResult, SimulationError> {
+        if logger.enabled {
+            logger.log_custom(
+                fight_vars.tick_counter,
+                "Player needed to eat but ran out of supplies.",
+            );
+        }
+
+        Err(SimulationError::SupplyOutError(unfinished_fight_result(
+            fight_vars, monster, logger,
+        )))
     }
 
     fn monster_regen_hp(
@@ -315,11 +585,298 @@ pub trait Mechanics {
     }
 
     fn decrement_eat_delay(&self, fight_vars: &mut FightVars) {
-        if fight_vars.eat_delay > 0 {
-            fight_vars.eat_delay -= 1;
+        fight_vars.eat_delay = fight_vars.eat_delay.saturating_sub(1);
+        fight_vars.combo_eat_delay = fight_vars.combo_eat_delay.saturating_sub(1);
+        fight_vars.potion_delay = fight_vars.potion_delay.saturating_sub(1);
+    }
+
+    // Eat one bite of the given food from the inventory if its delay allows it this tick.
+    // Normal foods, potion-type foods (brews) and combo foods (karambwans) have separate delays
+    // and can all be eaten on the same tick in that order. Returns the attack delay in ticks,
+    // or None if the food couldn't be eaten
+    fn eat_from_inventory(
+        &self,
+        player: &mut Player,
+        inventory: &mut Inventory,
+        food: Foods,
+        fight_vars: &mut FightVars,
+        logger: &mut FightLogger,
+    ) -> Option<i32> {
+        let available = match Food::from_db(food)?.food_type {
+            FoodType::Potion => fight_vars.potion_delay == 0,
+            FoodType::Combo => fight_vars.combo_eat_delay == 0,
+            _ => fight_vars.eat_delay == 0,
+        };
+        if !available {
+            return None;
+        }
+
+        let food = inventory.take_bite(food)?;
+        let heal_amount = food.bite_heal(player);
+        let overheal = food.food_properties.overheal.then_some(heal_amount);
+        player.heal(heal_amount, overheal);
+        if let Some(stat_effect) = food.food_properties.stat_effect {
+            stat_effect(player);
+        }
+        if let Some(delayed_heal) = &food.food_properties.delayed_heal {
+            fight_vars.player_effects.push(delayed_heal.clone());
+        }
+
+        if logger.enabled {
+            logger.log_food_eaten(
+                fight_vars.tick_counter,
+                heal_amount,
+                player.stats.hitpoints.current,
+            );
+        }
+
+        // Each kind of food also blocks the kinds that must be eaten before it on the same tick
+        let delay = food.bite_eat_delay();
+        match food.food_type {
+            FoodType::Combo => {
+                fight_vars.combo_eat_delay = delay;
+                fight_vars.potion_delay = fight_vars.potion_delay.max(delay);
+                fight_vars.eat_delay = fight_vars.eat_delay.max(delay);
+            }
+            FoodType::Potion => {
+                fight_vars.potion_delay = delay;
+                fight_vars.eat_delay = fight_vars.eat_delay.max(delay);
+            }
+            _ => fight_vars.eat_delay = delay,
+        }
+        fight_vars.food_eaten += 1;
+
+        Some(food.attack_delay as i32)
+    }
+
+    // Eat the first regular food in the inventory, then combo eat if `combo_eat` is set and the
+    // player is still missing HP. Falls back to combo food alone once regular food runs out.
+    // Returns the total attack delay in ticks, or None if nothing was eaten
+    fn eat_next_food(
+        &self,
+        player: &mut Player,
+        inventory: &mut Inventory,
+        combo_eat: bool,
+        fight_vars: &mut FightVars,
+        logger: &mut FightLogger,
+    ) -> Option<i32> {
+        let mut attack_delay = None;
+
+        if let Some(food) =
+            inventory.next_food(&[FoodType::Normal, FoodType::MultiBite, FoodType::DelayedHeal])
+        {
+            attack_delay = self.eat_from_inventory(player, inventory, food, fight_vars, logger);
+        }
+
+        let missing_hp = player.stats.hitpoints.current < player.stats.hitpoints.base;
+        if (attack_delay.is_none() || (combo_eat && missing_hp))
+            && let Some(combo) = inventory.next_food(&[FoodType::Combo])
+            && let Some(delay) =
+                self.eat_from_inventory(player, inventory, combo, fight_vars, logger)
+        {
+            attack_delay = Some(attack_delay.unwrap_or(0) + delay);
+        }
+
+        attack_delay
+    }
+
+    // Drink one dose of a boosting potion from the inventory to restore its boost
+    fn drink_potion(
+        &self,
+        player: &mut Player,
+        inventory: &mut Inventory,
+        potion: Potion,
+        fight_vars: &mut FightVars,
+        logger: &mut FightLogger,
+    ) -> bool {
+        if fight_vars.potion_delay > 0 || !inventory.take_dose(potion) {
+            return false;
+        }
+
+        player.drink_potion(potion);
+        fight_vars.potion_delay = constants::EAT_DELAY;
+
+        if logger.enabled {
+            logger.log_custom(
+                fight_vars.tick_counter,
+                format!("Player drank a dose of {potion}").as_str(),
+            );
+        }
+
+        true
+    }
+
+    // Drain prayer points for the tick, sip a restore if needed, and handle running out of prayer.
+    // `attacking` is whether the player attacks this tick, which matters for lazy flicking
+    #[allow(clippy::too_many_arguments)]
+    fn process_prayer(
+        &self,
+        player: &mut Player,
+        monster: &Monster,
+        prayer_config: &PrayerDrainConfig,
+        inventory: &mut Inventory,
+        attacking: bool,
+        fight_vars: &mut FightVars,
+        logger: &mut FightLogger,
+    ) -> Result<(), SimulationError> {
+        let drain_effect = player.prayers.drain_effect();
+        if drain_effect == 0 {
+            return Ok(());
+        }
+
+        if prayer_config.is_active(attacking) {
+            let drained = fight_vars
+                .prayer_drain
+                .tick(drain_effect, player.prayer_drain_resistance());
+            player.stats.prayer.drain(drained);
+            fight_vars.prayer_used += drained;
+        }
+
+        if player.stats.prayer.current <= prayer_config.restore_at {
+            self.drink_potion(
+                player,
+                inventory,
+                prayer_config.restore_potion,
+                fight_vars,
+                logger,
+            );
+        }
+
+        if player.stats.prayer.current == 0 {
+            match prayer_config.on_empty {
+                PrayerOutBehaviour::LosePrayers => {
+                    player.deactivate_prayers(monster);
+                    if logger.enabled {
+                        logger.log_custom(
+                            fight_vars.tick_counter,
+                            "Player ran out of prayer points and lost their prayers.",
+                        );
+                    }
+                }
+                PrayerOutBehaviour::Fail => {
+                    self.process_supply_out(fight_vars, monster, logger)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Drink another dose of a boosting potion once its boost has worn down to the threshold
+    // (e.g. from brew drains), recalculating the player's rolls for the restored levels
+    fn process_redose(
+        &self,
+        player: &mut Player,
+        monster: &Monster,
+        redose: &RedoseConfig,
+        inventory: &mut Inventory,
+        fight_vars: &mut FightVars,
+        logger: &mut FightLogger,
+    ) {
+        if player.needs_redose(redose.potion, redose.redose_at)
+            && self.drink_potion(player, inventory, redose.potion, fight_vars, logger)
+        {
+            calc_active_player_rolls(player, monster);
+        }
+    }
+
+    // Apply any delayed heals from foods eaten earlier in the fight
+    fn process_player_effects(
+        &self,
+        player: &mut Player,
+        fight_vars: &mut FightVars,
+        logger: &mut FightLogger,
+    ) {
+        let mut heal = 0;
+        for effect in &mut fight_vars.player_effects {
+            heal += effect.apply();
+        }
+        fight_vars.player_effects.retain(|effect| {
+            !matches!(
+                effect,
+                CombatEffect::DelayedHeal {
+                    tick_counter: None,
+                    ..
+                }
+            )
+        });
+
+        if heal > 0 {
+            player.heal(heal, None);
+
+            if logger.enabled {
+                logger.log_custom(
+                    fight_vars.tick_counter,
+                    format!(
+                        "Player healed {heal} HP from food ({} hp remaining)",
+                        player.stats.hitpoints.current
+                    )
+                    .as_str(),
+                );
+            }
         }
     }
 
+    // Deal venom damage to the player on the ticks it hits
+    fn process_player_venom(
+        &self,
+        player: &mut Player,
+        venom: &mut Option<PlayerVenom>,
+        fight_vars: &mut FightVars,
+        logger: &mut FightLogger,
+    ) {
+        if let Some(damage) = venom
+            .as_mut()
+            .and_then(|venom| venom.tick(fight_vars.tick_counter))
+        {
+            player.take_damage(damage);
+            fight_vars.damage_taken += damage;
+
+            if logger.enabled {
+                logger.log_monster_effect_damage(
+                    fight_vars.tick_counter,
+                    damage,
+                    "Player",
+                    player.stats.hitpoints.current,
+                );
+            }
+        }
+    }
+
+    // Roll for a hit from a hazard, returning whether the player was hit. A frozen or stunned
+    // player can't move out of the way, so every hazard lands
+    fn process_hazard(
+        &self,
+        player: &mut Player,
+        hazard: &Hazard,
+        source: &str,
+        rng: &mut SmallRng,
+        fight_vars: &mut FightVars,
+        logger: &mut FightLogger,
+    ) -> bool {
+        if !fight_vars.player_frozen() && !rng.random_bool(hazard.chance) {
+            return false;
+        }
+
+        let damage = rng.random_range(1..hazard.max_hit.max(1) + 1);
+        player.take_damage(damage);
+        fight_vars.damage_taken += damage;
+
+        if logger.enabled {
+            logger.log_custom(
+                fight_vars.tick_counter,
+                format!("Player was hit by {source}.").as_str(),
+            );
+            logger.log_player_damage(
+                fight_vars.tick_counter,
+                damage,
+                player.stats.hitpoints.current,
+            );
+        }
+
+        true
+    }
+
     fn eat_food(
         &self,
         player: &mut Player,
@@ -362,6 +919,26 @@ pub trait Mechanics {
     }
 }
 
+fn unfinished_fight_result(
+    fight_vars: &FightVars,
+    monster: &Monster,
+    logger: &mut FightLogger,
+) -> FightResult {
+    // Result of a fight that ended before the monster died
+    FightResult {
+        ttk_ticks: fight_vars.tick_counter,
+        hit_attempts: fight_vars.hit_attempts,
+        hit_count: fight_vars.hit_count,
+        hit_amounts: fight_vars.hit_amounts.clone(),
+        food_eaten: fight_vars.food_eaten,
+        damage_taken: fight_vars.damage_taken,
+        leftover_burn: calc_leftover_burn(monster),
+        thrall_damage: fight_vars.thrall_damage,
+        prayer_used: fight_vars.prayer_used,
+        trace: logger.take_trace(),
+    }
+}
+
 fn calc_leftover_burn(monster: &Monster) -> u32 {
     if let Some(CombatEffect::Burn {
         tick_counter: _,
@@ -432,3 +1009,282 @@ pub fn handle_blood_fury(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::prayers::Prayer;
+    use crate::types::stats::Stat;
+
+    #[derive(Clone)]
+    struct TestMechanics;
+
+    impl Mechanics for TestMechanics {}
+
+    fn hurt_player() -> Player {
+        let mut player = Player::new();
+        player.stats.hitpoints = Stat::new(99, None);
+        player.stats.hitpoints.current = 40;
+        player
+    }
+
+    #[test]
+    fn test_prayer_restore_and_running_out() {
+        let mut player = hurt_player();
+        player.stats.prayer = Stat::new(99, None);
+        player.stats.prayer.current = 10;
+        player.add_prayer(Prayer::Piety);
+        let monster = Monster::new("Ammonite Crab", None).unwrap();
+        let mut inventory = Inventory::builder()
+            .potion(Potion::PrayerPotion, 1)
+            .build()
+            .unwrap();
+        let mut vars = FightVars::new();
+        let mut logger = FightLogger::new(false);
+        let mut config = PrayerDrainConfig::default();
+
+        // Sipping a prayer potion restores 7 + 25% of base prayer
+        TestMechanics
+            .process_prayer(
+                &mut player,
+                &monster,
+                &config,
+                &mut inventory,
+                true,
+                &mut vars,
+                &mut logger,
+            )
+            .unwrap();
+        assert_eq!(player.stats.prayer.current, 41);
+        assert_eq!(inventory.potion_doses(Potion::PrayerPotion), 3);
+
+        // With no restores left, running out either fails the fight or turns prayers off
+        player.stats.prayer.current = 0;
+        let mut empty = Inventory::new();
+        config.on_empty = PrayerOutBehaviour::Fail;
+        assert!(matches!(
+            TestMechanics.process_prayer(
+                &mut player,
+                &monster,
+                &config,
+                &mut empty,
+                true,
+                &mut vars,
+                &mut logger,
+            ),
+            Err(SimulationError::SupplyOutError(_))
+        ));
+
+        config.on_empty = PrayerOutBehaviour::LosePrayers;
+        TestMechanics
+            .process_prayer(
+                &mut player,
+                &monster,
+                &config,
+                &mut empty,
+                true,
+                &mut vars,
+                &mut logger,
+            )
+            .unwrap();
+        assert!(!player.prayers.contains_prayer(Prayer::Piety));
+    }
+
+    #[test]
+    fn test_redose_after_brew_drain() {
+        let mut player = hurt_player();
+        player.stats.strength = Stat::new(99, None);
+        player.add_potion(Potion::SuperCombat);
+        let monster = Monster::new("Ammonite Crab", None).unwrap();
+        let mut inventory = Inventory::builder()
+            .potion(Potion::SuperCombat, 1)
+            .food(Foods::SaradominBrew, 1)
+            .build()
+            .unwrap();
+        let mut vars = FightVars::new();
+        let mut logger = FightLogger::new(false);
+        let redose = RedoseConfig {
+            potion: Potion::SuperCombat,
+            redose_at: 10,
+        };
+
+        // Fully boosted, so no dose is drunk
+        TestMechanics.process_redose(
+            &mut player,
+            &monster,
+            &redose,
+            &mut inventory,
+            &mut vars,
+            &mut logger,
+        );
+        assert_eq!(player.stats.strength.current, 118);
+        assert_eq!(inventory.potion_doses(Potion::SuperCombat), 4);
+
+        // A brew sip drains strength to 118 - (11 + 2) = 105, which is below the threshold
+        TestMechanics.eat_from_inventory(
+            &mut player,
+            &mut inventory,
+            Foods::SaradominBrew,
+            &mut vars,
+            &mut logger,
+        );
+        assert_eq!(player.stats.strength.current, 105);
+        vars.potion_delay = 0;
+        TestMechanics.process_redose(
+            &mut player,
+            &monster,
+            &redose,
+            &mut inventory,
+            &mut vars,
+            &mut logger,
+        );
+        assert_eq!(player.stats.strength.current, 118);
+        assert_eq!(inventory.potion_doses(Potion::SuperCombat), 3);
+    }
+
+    #[test]
+    fn test_combo_eat_same_tick() {
+        let mut player = hurt_player();
+        let mut inventory = Inventory::builder()
+            .food(Foods::Shark, 1)
+            .food(Foods::Karambwan, 1)
+            .build()
+            .unwrap();
+        let mut vars = FightVars::new();
+        let mut logger = FightLogger::new(false);
+
+        let attack_delay =
+            TestMechanics.eat_next_food(&mut player, &mut inventory, true, &mut vars, &mut logger);
+
+        assert_eq!(attack_delay, Some(5));
+        assert_eq!(player.stats.hitpoints.current, 78);
+        assert_eq!(vars.food_eaten, 2);
+        assert!(inventory.is_empty());
+    }
+
+    #[test]
+    fn test_eat_respects_delays() {
+        let mut player = hurt_player();
+        let mut inventory = Inventory::builder()
+            .food(Foods::Shark, 2)
+            .food(Foods::Karambwan, 1)
+            .build()
+            .unwrap();
+        let mut vars = FightVars::new();
+        let mut logger = FightLogger::new(false);
+
+        TestMechanics.eat_next_food(&mut player, &mut inventory, false, &mut vars, &mut logger);
+        assert_eq!(
+            TestMechanics.eat_next_food(&mut player, &mut inventory, true, &mut vars, &mut logger),
+            Some(2)
+        );
+
+        // Neither a shark nor a karambwan can be eaten until the delays run out
+        assert_eq!(
+            TestMechanics.eat_next_food(&mut player, &mut inventory, true, &mut vars, &mut logger),
+            None
+        );
+        for _ in 0..3 {
+            TestMechanics.decrement_eat_delay(&mut vars);
+        }
+        assert!(
+            TestMechanics
+                .eat_next_food(&mut player, &mut inventory, false, &mut vars, &mut logger)
+                .is_some()
+        );
+        assert!(!inventory.has_food());
+    }
+
+    #[test]
+    fn test_monster_special_effects() {
+        use crate::types::monster_specials::{MonsterSpecial, SpecialDamage};
+        use rand::SeedableRng;
+
+        let mut player = hurt_player();
+        player.add_prayer(Prayer::Piety);
+        let mut monster = Monster::new("K'ril Tsutsaroth", None).unwrap();
+        monster.specials = vec![
+            MonsterSpecial::typeless("Curse", SpecialDamage::Fixed(10))
+                .with_effect(SpecialEffect::DrainStat {
+                    stat: CombatStat::Defence,
+                    amount: 5,
+                })
+                .with_effect(SpecialEffect::Venom)
+                .with_effect(SpecialEffect::Stun(4))
+                .with_effect(SpecialEffect::DisablePrayers(10)),
+        ];
+        let mut rng = SmallRng::seed_from_u64(1);
+        let mut venom = None;
+        let mut vars = FightVars::new();
+        let mut logger = FightLogger::new(false);
+
+        let special = monster
+            .special_attack("Curse", &mut player, &mut rng, true)
+            .unwrap();
+        TestMechanics.apply_monster_special(
+            &mut monster,
+            &mut player,
+            &special,
+            &mut venom,
+            &mut vars,
+            &mut logger,
+        );
+        assert_eq!(player.stats.hitpoints.current, 30);
+        assert_eq!(player.stats.defence.current, player.stats.defence.base - 5);
+        assert!(venom.is_some());
+        assert_eq!(vars.attack_tick, 4);
+        assert!(vars.player_frozen());
+        assert!(!player.prayers.contains_prayer(Prayer::Piety));
+
+        // A stunned player can't step out of the way of hazards
+        let hazard = Hazard {
+            chance: 0.0,
+            max_hit: 5,
+        };
+        assert!(TestMechanics.process_hazard(
+            &mut player,
+            &hazard,
+            "a hazard",
+            &mut rng,
+            &mut vars,
+            &mut logger,
+        ));
+        vars.tick_counter = 4;
+        assert!(!TestMechanics.process_hazard(
+            &mut player,
+            &hazard,
+            "a hazard",
+            &mut rng,
+            &mut vars,
+            &mut logger,
+        ));
+
+        // Prayers come back once the disable wears off
+        vars.tick_counter = 9;
+        TestMechanics.process_prayer_disable(&mut player, &monster, &mut vars, &mut logger);
+        assert!(!player.prayers.contains_prayer(Prayer::Piety));
+        vars.tick_counter = 10;
+        TestMechanics.process_prayer_disable(&mut player, &monster, &mut vars, &mut logger);
+        assert!(player.prayers.contains_prayer(Prayer::Piety));
+    }
+
+    #[test]
+    fn test_thrall_attack_advances_on_immune_monster() {
+        use rand::SeedableRng;
+
+        let mut monster = Monster::new("Kraken", Some("Kraken")).unwrap();
+        let mut rng = SmallRng::seed_from_u64(1);
+        let mut vars = FightVars::new();
+        let mut logger = FightLogger::new(false);
+
+        TestMechanics.thrall_attack(
+            &mut monster,
+            Thrall::GreaterMelee,
+            &mut vars,
+            &mut rng,
+            &mut logger,
+        );
+        assert_eq!(vars.thrall_attack_tick, 1 + THRALL_ATTACK_SPEED);
+        assert_eq!(vars.thrall_damage, 0);
+    }
+}
@@ -1,6 +1,9 @@
 pub mod attacks;
 pub mod limiters;
 pub mod mechanics;
+pub mod prayer;
 pub mod simulation;
 pub mod spec;
 pub mod thralls;
+pub mod trip;
+pub mod world;
@@ -0,0 +1,95 @@
+use crate::types::potions::Potion;
+use serde::{Deserialize, Serialize};
+
+// How offensive prayers are kept up between attacks
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FlickPolicy {
+    #[default]
+    AlwaysOn, // Prayers stay on every tick
+    LazyFlick, // Prayers are only on during the tick of each attack
+    Flick,     // Prayers are flicked on and off within each attack tick and never drain
+}
+
+// What happens when the player runs out of prayer points
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PrayerOutBehaviour {
+    #[default]
+    LosePrayers, // Keep fighting with all prayers off
+    Fail, // End the fight as a supply-out
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PrayerDrainConfig {
+    pub flick_policy: FlickPolicy,
+    pub restore_potion: Potion, // Potion sipped from the inventory to restore prayer points
+    pub restore_at: u32,        // Sip a restore once prayer points drop to this or below
+    pub on_empty: PrayerOutBehaviour,
+}
+
+impl Default for PrayerDrainConfig {
+    fn default() -> Self {
+        Self {
+            flick_policy: FlickPolicy::AlwaysOn,
+            restore_potion: Potion::PrayerPotion,
+            restore_at: 20,
+            on_empty: PrayerOutBehaviour::LosePrayers,
+        }
+    }
+}
+
+impl PrayerDrainConfig {
+    pub fn is_active(&self, attacking: bool) -> bool {
+        // Whether prayers are on (and draining) this tick
+        match self.flick_policy {
+            FlickPolicy::AlwaysOn => true,
+            FlickPolicy::LazyFlick => attacking,
+            FlickPolicy::Flick => false,
+        }
+    }
+}
+
+// Prayer drain counter: each tick adds the total drain effect of active prayers, and a prayer
+// point is drained every time the counter exceeds the player's drain resistance
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PrayerDrain {
+    counter: u32,
+}
+
+impl PrayerDrain {
+    pub fn tick(&mut self, drain_effect: u32, resistance: u32) -> u32 {
+        // Returns the number of prayer points drained this tick
+        self.counter += drain_effect;
+        let mut drained = 0;
+        while self.counter > resistance {
+            self.counter -= resistance;
+            drained += 1;
+        }
+        drained
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_piety_drain_rate() {
+        // Piety with no prayer bonus drains a point every 2.5 ticks
+        let mut drain = PrayerDrain::default();
+        let drained: u32 = (0..100).map(|_| drain.tick(24, 60)).sum();
+        assert_eq!(drained, 39);
+    }
+
+    #[test]
+    fn test_flick_policies() {
+        let mut config = PrayerDrainConfig::default();
+        assert!(config.is_active(false));
+
+        config.flick_policy = FlickPolicy::LazyFlick;
+        assert!(!config.is_active(false));
+        assert!(config.is_active(true));
+
+        config.flick_policy = FlickPolicy::Flick;
+        assert!(!config.is_active(true));
+    }
+}
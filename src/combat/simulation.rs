@@ -1,10 +1,22 @@
+use crate::calc::analysis::Estimate;
+use crate::combat::attacks::effects::CombatEffect;
 use crate::combat::limiters;
-use crate::constants::HUEYCOATL_TAIL_ID;
+use crate::combat::prayer::PrayerDrain;
+use crate::constants::{HUEYCOATL_TAIL_ID, SECONDS_PER_TICK};
 use crate::error::SimulationError;
 use crate::types::equipment::CombatType;
+use crate::types::inventory::Inventory;
 use crate::types::monster::Monster;
 use crate::types::player::Player;
+use crate::types::prayers::PrayerBoosts;
 use crate::types::spells::{Spell, StandardSpell};
+use crate::utils::logging::FightTrace;
+use dyn_clone::DynClone;
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+use std::ops::Range;
+use std::sync::Arc;
+use std::thread;
 
 #[derive(Debug, PartialEq, Clone, Default)]
 pub struct FightResult {
@@ -16,6 +28,8 @@ pub struct FightResult {
     pub damage_taken: u32,
     pub leftover_burn: u32,
     pub thrall_damage: u32,
+    pub prayer_used: u32,          // Prayer points drained over the fight
+    pub trace: Option<FightTrace>, // Event trace, if the fight's logger was enabled
 }
 
 impl FightResult {
@@ -31,10 +45,12 @@ pub struct CumulativeResults {
     pub hit_counts: Vec<u32>,
     pub hit_amounts: Vec<u32>,
     pub player_deaths: usize,
+    pub supply_outs: usize,
     pub food_eaten: Vec<u32>,
     pub damage_taken: Vec<u32>,
     pub leftover_burn: Vec<u32>,
     pub thrall_damage: Vec<u32>,
+    pub prayer_used: Vec<u32>,
 }
 
 impl CumulativeResults {
@@ -45,10 +61,12 @@ impl CumulativeResults {
             hit_counts: Vec::<u32>::with_capacity(n),
             hit_amounts: Vec::<u32>::with_capacity(n),
             player_deaths: 0,
+            supply_outs: 0,
             food_eaten: Vec::<u32>::with_capacity(n),
             damage_taken: Vec::<u32>::with_capacity(n),
             leftover_burn: Vec::<u32>::with_capacity(n),
             thrall_damage: Vec::<u32>::with_capacity(n),
+            prayer_used: Vec::<u32>::with_capacity(n),
         }
     }
 
@@ -61,6 +79,22 @@ impl CumulativeResults {
         self.damage_taken.push(result.damage_taken);
         self.leftover_burn.push(result.leftover_burn);
         self.thrall_damage.push(result.thrall_damage);
+        self.prayer_used.push(result.prayer_used);
+    }
+
+    // Append another set of results, e.g. from a worker thread of a parallel run
+    pub fn merge(&mut self, other: CumulativeResults) {
+        self.ttks_ticks.extend(other.ttks_ticks);
+        self.hit_attempt_counts.extend(other.hit_attempt_counts);
+        self.hit_counts.extend(other.hit_counts);
+        self.hit_amounts.extend(other.hit_amounts);
+        self.player_deaths += other.player_deaths;
+        self.supply_outs += other.supply_outs;
+        self.food_eaten.extend(other.food_eaten);
+        self.damage_taken.extend(other.damage_taken);
+        self.leftover_burn.extend(other.leftover_burn);
+        self.thrall_damage.extend(other.thrall_damage);
+        self.prayer_used.extend(other.prayer_used);
     }
 }
 
@@ -80,6 +114,14 @@ pub struct FightVars {
     pub thrall_damage: u32,
     pub spec_regen_timer: Option<i32>,
     pub redemption_procs: u32,
+    pub combo_eat_delay: u32,
+    pub potion_delay: u32,
+    pub player_effects: Vec<CombatEffect>,
+    pub prayer_drain: PrayerDrain,
+    pub prayer_used: u32,
+    pub player_frozen_until: i32,    // Tick the player can move again
+    pub prayers_disabled_until: i32, // Tick the player's prayers come back on
+    pub disabled_prayers: Option<Arc<PrayerBoosts>>, // Prayers turned off by a monster special
 }
 
 impl FightVars {
@@ -89,15 +131,58 @@ impl FightVars {
             ..Default::default()
         }
     }
+
+    pub fn player_frozen(&self) -> bool {
+        self.tick_counter < self.player_frozen_until
+    }
 }
 
-pub trait Simulation {
+pub trait Simulation: DynClone + Send {
     fn simulate(&mut self) -> Result<FightResult, SimulationError>;
     fn is_immune(&self) -> bool;
     fn player(&self) -> &Player;
     fn monster(&self) -> &Monster;
     fn set_attack_function(&mut self);
     fn reset(&mut self);
+    fn set_seed(&mut self, seed: u64);
+
+    // Prepare the next kill of a trip, keeping the player's HP, prayer, spec energy and supplies.
+    // Simulations that don't track carried-over state fall back to a full reset
+    fn next_kill(&mut self, downtime_ticks: u32) {
+        let _ = downtime_ticks;
+        self.reset();
+    }
+
+    // Supplies left for the rest of the trip, if the simulation tracks them
+    fn inventory(&self) -> Option<&Inventory> {
+        None
+    }
+}
+
+dyn_clone::clone_trait_object!(Simulation);
+
+// Build a simulation's RNG, seeded if a seed is provided and from OS entropy otherwise
+pub fn seeded_rng(seed: Option<u64>) -> SmallRng {
+    match seed {
+        Some(seed) => SmallRng::seed_from_u64(seed),
+        None => SmallRng::from_os_rng(),
+    }
+}
+
+/// Derive the seed for a single fight from the seed of a whole run.
+///
+/// Uses the SplitMix64 finalizer so neighbouring fight indices get unrelated seeds. Fight `i` of a
+/// seeded `simulate_n_fights` run can be replayed on its own by seeding the simulation with
+/// `derive_fight_seed(seed, i)` and calling `simulate()` once.
+pub fn derive_fight_seed(seed: u64, fight_index: u64) -> u64 {
+    let mut z = seed.wrapping_add(
+        fight_index
+            .wrapping_add(1)
+            .wrapping_mul(0x9E37_79B9_7F4A_7C15),
+    );
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
 }
 
 pub fn assign_limiter(player: &Player, monster: &Monster) -> Option<Box<dyn limiters::Limiter>> {
@@ -130,8 +215,8 @@ pub fn assign_limiter(player: &Player, monster: &Monster) -> Option<Box<dyn limi
         || ((monster.matches_version("Left claw")
             || (monster.info.name.contains("Great Olm") && monster.matches_version("Head")))
             && player.is_using_magic()))
-        || (monster.matches_version("Right claw")
-            || monster.matches_version("Left claw") && player.is_using_ranged())
+        || ((monster.matches_version("Right claw") || monster.matches_version("Left claw"))
+            && player.is_using_ranged())
         || (monster.info.name.contains("Ice demon")
             && !player.is_using_fire_spell()
             && !player.is_using_demonbane()
@@ -160,8 +245,6 @@ pub fn assign_limiter(player: &Player, monster: &Monster) -> Option<Box<dyn limi
         return Some(Box::new(limiters::HalfDamage {}));
     }
 
-    if player.is_wearing("Efaritay's aid", None) && monster.vampyre_tier() == Some(2) {}
-
     if monster.vampyre_tier() == Some(2) {
         if !player.is_using_vampyrebane(2) && player.is_wearing("Efaritay's aid", None) {
             return Some(Box::new(limiters::HalfDamage {}));
@@ -185,6 +268,7 @@ pub fn simulate_n_fights(
     mut simulation: Box<dyn Simulation>,
     n: u32,
     only_success_stats: bool,
+    seed: Option<u64>,
 ) -> Result<CumulativeResults, SimulationError> {
     // Check if the monster is immune before running simulations
     if simulation.is_immune() {
@@ -199,36 +283,246 @@ pub fn simulate_n_fights(
     // Retrieve attack function and limiter
     simulation.set_attack_function();
 
-    for _ in 0..n {
+    run_fights(
+        simulation.as_mut(),
+        0..n,
+        only_success_stats,
+        seed,
+        &mut results,
+    )?;
+
+    Ok(results)
+}
+
+/// Run `n` fights split across worker threads and merge the results.
+///
+/// Each worker gets its own clone of the simulation. Seeded runs use the same per-fight seeds as
+/// `simulate_n_fights`, so they produce identical results regardless of the thread count.
+/// Unseeded workers are reseeded from OS entropy so they don't replay each other's fights.
+/// `threads` defaults to the available parallelism of the machine.
+pub fn simulate_n_fights_parallel(
+    mut simulation: Box<dyn Simulation>,
+    n: u32,
+    only_success_stats: bool,
+    seed: Option<u64>,
+    threads: Option<usize>,
+) -> Result<CumulativeResults, SimulationError> {
+    // Check if the monster is immune before running simulations
+    if simulation.is_immune() {
+        return Err(SimulationError::MonsterImmune(
+            simulation.monster().info.name.clone(),
+        ));
+    }
+
+    // Retrieve attack function and limiter once so every worker clone inherits them
+    simulation.set_attack_function();
+
+    run_fights_parallel(simulation.as_ref(), 0..n, only_success_stats, seed, threads)
+}
+
+/// Stopping rules for `simulate_until_converged`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConvergenceConfig {
+    pub target_half_width: f64, // Stop once the 95% CI of the mean TTK is this narrow (seconds)
+    pub batch_size: u32,
+    pub min_fights: u32,
+    pub max_fights: u32,
+    pub threads: Option<usize>,
+}
+
+impl Default for ConvergenceConfig {
+    fn default() -> Self {
+        Self {
+            target_half_width: 0.1,
+            batch_size: 1000,
+            min_fights: 1000,
+            max_fights: 1_000_000,
+            threads: None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ConvergedResults {
+    pub results: CumulativeResults,
+    pub fights: u32,
+    pub converged: bool, // False if `max_fights` was reached first
+    pub ttk_half_width: f64,
+}
+
+/// Run batches of fights until the 95% confidence interval of the mean TTK is narrower than
+/// `config.target_half_width`, or until `config.max_fights` have been run.
+///
+/// Fight indices carry on across batches, so a seeded run gives the same fights as
+/// `simulate_n_fights` with the same seed.
+pub fn simulate_until_converged(
+    mut simulation: Box<dyn Simulation>,
+    config: &ConvergenceConfig,
+    only_success_stats: bool,
+    seed: Option<u64>,
+) -> Result<ConvergedResults, SimulationError> {
+    if config.target_half_width <= 0.0 || config.batch_size == 0 {
+        return Err(SimulationError::ConfigError(
+            "Convergence needs a positive target half-width and batch size.".to_string(),
+        ));
+    }
+    if simulation.is_immune() {
+        return Err(SimulationError::MonsterImmune(
+            simulation.monster().info.name.clone(),
+        ));
+    }
+    simulation.set_attack_function();
+
+    let mut converged = ConvergedResults {
+        ttk_half_width: f64::INFINITY,
+        ..ConvergedResults::default()
+    };
+    while converged.fights < config.max_fights {
+        let end = converged
+            .fights
+            .saturating_add(config.batch_size)
+            .min(config.max_fights);
+        let batch = run_fights_parallel(
+            simulation.as_ref(),
+            converged.fights..end,
+            only_success_stats,
+            seed,
+            config.threads,
+        )?;
+        converged.results.merge(batch);
+        converged.fights = end;
+
+        let ttks = converged
+            .results
+            .ttks_ticks
+            .iter()
+            .map(|&ticks| f64::from(ticks) * SECONDS_PER_TICK);
+        converged.ttk_half_width = Estimate::from_samples(ttks).half_width();
+        if converged.fights >= config.min_fights
+            && converged.ttk_half_width <= config.target_half_width
+        {
+            converged.converged = true;
+            break;
+        }
+    }
+
+    Ok(converged)
+}
+
+// Split the fights with the given indices across worker threads and merge the results in order
+fn run_fights_parallel(
+    simulation: &dyn Simulation,
+    fight_indices: Range<u32>,
+    only_success_stats: bool,
+    seed: Option<u64>,
+    threads: Option<usize>,
+) -> Result<CumulativeResults, SimulationError> {
+    let n = fight_indices.len() as u32;
+    let threads = threads
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, |p| p.get()))
+        .clamp(1, n.max(1) as usize) as u32;
+
+    // Split the fights into contiguous chunks, spreading the remainder over the first workers
+    let chunk_size = n / threads;
+    let remainder = n % threads;
+    let mut ranges = Vec::with_capacity(threads as usize);
+    let mut start = fight_indices.start;
+    for t in 0..threads {
+        let len = chunk_size + u32::from(t < remainder);
+        ranges.push(start..start + len);
+        start += len;
+    }
+
+    let worker_results = thread::scope(|scope| {
+        let handles: Vec<_> = ranges
+            .into_iter()
+            .map(|range| {
+                let mut worker = dyn_clone::clone_box(simulation);
+                if seed.is_none() {
+                    worker.set_seed(rand::random());
+                }
+                scope.spawn(move || {
+                    let mut results = CumulativeResults::new(range.len());
+                    run_fights(
+                        worker.as_mut(),
+                        range,
+                        only_success_stats,
+                        seed,
+                        &mut results,
+                    )
+                    .map(|_| results)
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|e| std::panic::resume_unwind(e))
+            })
+            .collect::<Vec<_>>()
+    });
+
+    // Merge in chunk order so the output matches a single-threaded run
+    let mut results = CumulativeResults::new(n as usize);
+    for worker_result in worker_results {
+        results.merge(worker_result?);
+    }
+
+    Ok(results)
+}
+
+// Run the fights with the given indices on one simulation, pushing each result
+fn run_fights(
+    simulation: &mut dyn Simulation,
+    fight_indices: Range<u32>,
+    only_success_stats: bool,
+    seed: Option<u64>,
+    results: &mut CumulativeResults,
+) -> Result<(), SimulationError> {
+    for i in fight_indices {
+        // Reseed per fight so any single fight of a seeded run can be replayed on its own
+        if let Some(seed) = seed {
+            simulation.set_seed(derive_fight_seed(seed, u64::from(i)));
+        }
+
         // Run a single fight simulation and update the result variables
         let result = simulation.simulate();
         match result {
             Ok(result) => {
                 results.push(&result);
             }
-            Err(e) => match e {
-                SimulationError::PlayerDeathError(result) => {
-                    if !only_success_stats {
-                        results.hit_amounts.extend(&result.hit_amounts);
-                        results.hit_attempt_counts.push(result.hit_attempts);
-                        results.hit_counts.push(result.hit_count);
-                        results.food_eaten.push(result.food_eaten);
-                        results.damage_taken.push(result.damage_taken);
-                        results.leftover_burn.push(result.leftover_burn);
-                        results.thrall_damage.push(result.thrall_damage);
+            Err(e) => {
+                let supply_out = matches!(e, SimulationError::SupplyOutError(_));
+                match e {
+                    SimulationError::PlayerDeathError(result)
+                    | SimulationError::SupplyOutError(result) => {
+                        if !only_success_stats {
+                            results.hit_amounts.extend(&result.hit_amounts);
+                            results.hit_attempt_counts.push(result.hit_attempts);
+                            results.hit_counts.push(result.hit_count);
+                            results.food_eaten.push(result.food_eaten);
+                            results.damage_taken.push(result.damage_taken);
+                            results.leftover_burn.push(result.leftover_burn);
+                            results.thrall_damage.push(result.thrall_damage);
+                            results.prayer_used.push(result.prayer_used);
+                        }
+                        if supply_out {
+                            results.supply_outs += 1;
+                        } else {
+                            results.player_deaths += 1;
+                        }
                     }
-                    results.player_deaths += 1;
+                    _ => return Err(e),
                 }
-                _ => return Err(e),
-            },
+            }
         }
         simulation.reset();
     }
 
-    Ok(results)
-
-    // Return a struct with average ttk, average accuracy, and hit distribution
-    // SimulationStats::new(&results)
+    Ok(())
 }
 
 #[cfg(test)]
@@ -270,11 +564,111 @@ mod tests {
         let simulation =
             SingleWayFight::new(player, monster, SingleWayConfig::default(), None, false)
                 .expect("Error setting up single way fight.");
-        let results =
-            simulate_n_fights(Box::new(simulation), 100000, true).expect("Simulation failed.");
+        let results = simulate_n_fights(Box::new(simulation), 100000, true, None)
+            .expect("Simulation failed.");
         let stats = SimulationStats::new(&results);
 
         assert!(num::abs(stats.ttk - 10.2) < 0.1);
         assert!(num::abs(stats.accuracy - 99.04) < 0.1);
     }
+
+    fn vorkath_rapier_fight() -> SingleWayFight {
+        let mut player = Player::new();
+        player.stats = PlayerStats::default();
+        player.add_prayer(Prayer::Piety);
+        player.add_potion(Potion::SuperCombat);
+        player.equip("Ghrazi rapier", None).unwrap();
+        player.equip("Avernic defender", None).unwrap();
+        player.update_bonuses();
+        player.set_active_style(CombatStyle::Lunge);
+        let monster = Monster::new("Vorkath", Some("Post-quest")).expect("Error creating monster.");
+        calc_active_player_rolls(&mut player, &monster);
+
+        SingleWayFight::new(player, monster, SingleWayConfig::default(), None, false)
+            .expect("Error setting up single way fight.")
+    }
+
+    #[test]
+    fn test_seeded_fights_are_reproducible() {
+        let new_fight = vorkath_rapier_fight;
+
+        let seed = 0x5EED;
+        let first = simulate_n_fights(Box::new(new_fight()), 1000, true, Some(seed))
+            .expect("Simulation failed.");
+        let second = simulate_n_fights(Box::new(new_fight()), 1000, true, Some(seed))
+            .expect("Simulation failed.");
+        assert_eq!(first, second);
+
+        // Any single fight can be replayed from its derived seed
+        let mut replay = new_fight();
+        replay.set_attack_function();
+        replay.set_seed(derive_fight_seed(seed, 42));
+        let result = replay.simulate().expect("Simulation failed.");
+        assert_eq!(result.ttk_ticks, first.ttks_ticks[42]);
+        assert_eq!(result.hit_attempts, first.hit_attempt_counts[42]);
+    }
+
+    #[test]
+    fn test_parallel_matches_sequential_when_seeded() {
+        let seed = 0x5EED;
+        let sequential =
+            simulate_n_fights(Box::new(vorkath_rapier_fight()), 1001, true, Some(seed))
+                .expect("Simulation failed.");
+        let parallel = simulate_n_fights_parallel(
+            Box::new(vorkath_rapier_fight()),
+            1001,
+            true,
+            Some(seed),
+            Some(4),
+        )
+        .expect("Simulation failed.");
+        assert_eq!(sequential, parallel);
+
+        // Unseeded workers must not replay each other's fights
+        let unseeded =
+            simulate_n_fights_parallel(Box::new(vorkath_rapier_fight()), 1000, true, None, Some(2))
+                .expect("Simulation failed.");
+        assert_eq!(unseeded.ttks_ticks.len(), 1000);
+        assert_ne!(unseeded.ttks_ticks[..500], unseeded.ttks_ticks[500..]);
+    }
+
+    #[test]
+    fn test_simulate_until_converged() {
+        let seed = 0x5EED;
+        let config = ConvergenceConfig {
+            target_half_width: 0.5,
+            batch_size: 500,
+            min_fights: 1000,
+            threads: Some(2),
+            ..ConvergenceConfig::default()
+        };
+        let converged =
+            simulate_until_converged(Box::new(vorkath_rapier_fight()), &config, true, Some(seed))
+                .expect("Simulation failed.");
+        assert!(converged.converged);
+        assert!(converged.ttk_half_width <= 0.5);
+        assert!(converged.fights >= 1000 && converged.fights.is_multiple_of(500));
+
+        // Same fights as a fixed-size run with the same seed
+        let fixed = simulate_n_fights(
+            Box::new(vorkath_rapier_fight()),
+            converged.fights,
+            true,
+            Some(seed),
+        )
+        .expect("Simulation failed.");
+        assert_eq!(converged.results, fixed);
+
+        // An unreachable target stops at the fight cap
+        let capped = ConvergenceConfig {
+            target_half_width: 1e-9,
+            max_fights: 1200,
+            ..config
+        };
+        let result =
+            simulate_until_converged(Box::new(vorkath_rapier_fight()), &capped, true, Some(seed))
+                .expect("Simulation failed.");
+        assert!(!result.converged);
+        assert_eq!(result.fights, 1200);
+    }
 }
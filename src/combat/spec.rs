@@ -288,7 +288,7 @@ impl<C: SpecCondition> SpecStrategyBuilder<C> {
         }
     }
 
-    fn with_condition(mut self, condition: C) -> Self {
+    pub fn with_condition(mut self, condition: C) -> Self {
         self.strategy.add_condition(condition);
         self
     }
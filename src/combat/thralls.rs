@@ -1,6 +1,7 @@
 use crate::types::monster::AttackType;
+use strum_macros::EnumIter;
 
-#[derive(Debug, Clone, PartialEq, Copy)]
+#[derive(Debug, Clone, PartialEq, Copy, EnumIter)]
 pub enum Thrall {
     LesserMelee,
     LesserRanged,
@@ -0,0 +1,221 @@
+use crate::combat::simulation::{FightResult, Simulation, derive_fight_seed};
+use crate::error::SimulationError;
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TripConfig {
+    pub respawn_ticks: u32, // Downtime between kills while the monster respawns
+    pub bank_ticks: u32,    // Time spent banking and getting back to the monster each trip
+    pub max_kills: Option<u32>, // Bank after this many kills even if supplies are left
+    pub bank_at_food: u32,  // Bank once this many food items or fewer are left
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum TripEnd {
+    OutOfSupplies,
+    PlayerDeath,
+    MaxKills,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TripResult {
+    pub kills: u32,
+    pub trip_ticks: u32, // Time spent at the monster, including respawns but not banking
+    pub kill_ticks: Vec<i32>,
+    pub food_eaten: u32,
+    pub end: TripEnd,
+}
+
+impl TripResult {
+    fn new() -> Self {
+        Self {
+            kills: 0,
+            trip_ticks: 0,
+            kill_ticks: Vec::new(),
+            food_eaten: 0,
+            end: TripEnd::OutOfSupplies,
+        }
+    }
+
+    fn add_fight(&mut self, fight: &FightResult) {
+        self.trip_ticks += fight.ttk_ticks.max(0) as u32;
+        self.food_eaten += fight.food_eaten;
+    }
+}
+
+/// Simulate a single trip of back-to-back kills.
+///
+/// The player's HP, prayer, spec energy and supplies carry over between kills. The trip ends
+/// when the player dies, runs out of supplies (either mid-kill or once `bank_at_food` is reached),
+/// or reaches `max_kills`. Kills that end in death or running out of supplies are not counted.
+pub fn simulate_trip(
+    simulation: &mut dyn Simulation,
+    config: &TripConfig,
+) -> Result<TripResult, SimulationError> {
+    // Without supplies or a kill cap, nothing would ever end the trip
+    if config.max_kills.is_none() && simulation.inventory().is_none() {
+        return Err(SimulationError::ConfigError(
+            "Trips require either a kill limit or a simulation that tracks supplies".to_string(),
+        ));
+    }
+
+    let mut trip = TripResult::new();
+
+    loop {
+        match simulation.simulate() {
+            Ok(fight) => {
+                trip.add_fight(&fight);
+                trip.kills += 1;
+                trip.kill_ticks.push(fight.ttk_ticks);
+            }
+            Err(SimulationError::PlayerDeathError(fight)) => {
+                trip.add_fight(&fight);
+                trip.end = TripEnd::PlayerDeath;
+                break;
+            }
+            Err(SimulationError::SupplyOutError(fight)) => {
+                trip.add_fight(&fight);
+                trip.end = TripEnd::OutOfSupplies;
+                break;
+            }
+            Err(e) => return Err(e),
+        }
+
+        if config.max_kills.is_some_and(|max| trip.kills >= max) {
+            trip.end = TripEnd::MaxKills;
+            break;
+        }
+
+        if simulation
+            .inventory()
+            .is_some_and(|inventory| inventory.food_count() <= config.bank_at_food)
+        {
+            trip.end = TripEnd::OutOfSupplies;
+            break;
+        }
+
+        simulation.next_kill(config.respawn_ticks);
+        trip.trip_ticks += config.respawn_ticks;
+    }
+
+    Ok(trip)
+}
+
+/// Simulate `n` trips, restocking supplies and fully resetting the player between trips.
+///
+/// Seeded runs reseed the simulation at the start of each trip, so any single trip can be
+/// replayed on its own.
+pub fn simulate_n_trips(
+    mut simulation: Box<dyn Simulation>,
+    n: u32,
+    config: &TripConfig,
+) -> Result<Vec<TripResult>, SimulationError> {
+    // Check if the monster is immune before running simulations
+    if simulation.is_immune() {
+        return Err(SimulationError::MonsterImmune(
+            simulation.monster().info.name.clone(),
+        ));
+    }
+
+    simulation.set_attack_function();
+
+    let mut results = Vec::with_capacity(n as usize);
+    for i in 0..n {
+        if let Some(seed) = config.seed {
+            simulation.set_seed(derive_fight_seed(seed, u64::from(i)));
+        }
+
+        results.push(simulate_trip(simulation.as_mut(), config)?);
+        simulation.reset();
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::analysis::TripStats;
+    use crate::calc::rolls::calc_active_player_rolls;
+    use crate::sims::single_way::{SingleWayConfig, SingleWayFight};
+    use crate::sims::vardorvis::{VardorvisConfig, VardorvisFight};
+    use crate::types::food::Foods;
+    use crate::types::inventory::Inventory;
+    use crate::types::monster::Monster;
+    use crate::utils::loadouts;
+
+    #[test]
+    fn test_trip_requires_an_end_condition() {
+        let player = loadouts::max_melee_player();
+        let monster = Monster::new("Ammonite Crab", None).expect("Error creating monster.");
+        let fight = SingleWayFight::new(player, monster, SingleWayConfig::default(), None, false)
+            .expect("Error setting up single way fight.");
+
+        let result = simulate_n_trips(Box::new(fight), 1, &TripConfig::default());
+        assert!(matches!(result, Err(SimulationError::ConfigError(_))));
+    }
+
+    #[test]
+    fn test_max_kills_trip() {
+        let mut player = loadouts::max_melee_player();
+        let monster = Monster::new("Ammonite Crab", None).expect("Error creating monster.");
+        calc_active_player_rolls(&mut player, &monster);
+        let fight = SingleWayFight::new(player, monster, SingleWayConfig::default(), None, false)
+            .expect("Error setting up single way fight.");
+        let config = TripConfig {
+            respawn_ticks: 10,
+            bank_ticks: 100,
+            max_kills: Some(5),
+            seed: Some(3),
+            ..TripConfig::default()
+        };
+
+        let results = simulate_n_trips(Box::new(fight), 20, &config).unwrap();
+        for trip in &results {
+            assert_eq!(trip.kills, 5);
+            assert_eq!(trip.end, TripEnd::MaxKills);
+            assert_eq!(
+                trip.trip_ticks,
+                trip.kill_ticks.iter().sum::<i32>() as u32 + 4 * config.respawn_ticks
+            );
+        }
+
+        let stats = TripStats::new(&results, &config);
+        assert_eq!(stats.kills_per_trip, 5.0);
+        assert!(stats.kills_per_hour > 0.0);
+        assert!((stats.trip_length_dist.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_supplies_carry_between_kills() {
+        let mut player = loadouts::max_melee_player();
+        let vard = Monster::new("Vardorvis", Some("Post-quest")).expect("Error creating monster.");
+        calc_active_player_rolls(&mut player, &vard);
+        let config = VardorvisConfig {
+            inventory: Inventory::builder()
+                .food(Foods::MantaRay, 10)
+                .build()
+                .expect("Error building inventory."),
+            ..VardorvisConfig::default()
+        };
+        let fight =
+            VardorvisFight::new(player, config, None).expect("Error setting up Vardorvis fight.");
+        let trip_config = TripConfig {
+            respawn_ticks: 15,
+            seed: Some(11),
+            ..TripConfig::default()
+        };
+
+        let results = simulate_n_trips(Box::new(fight), 10, &trip_config).unwrap();
+        for trip in &results {
+            assert!(trip.food_eaten <= 10);
+            if trip.end == TripEnd::OutOfSupplies {
+                assert_eq!(trip.food_eaten, 10);
+            }
+        }
+
+        // Ten mantas should last for more than a single kill on average
+        let stats = TripStats::new(&results, &trip_config);
+        assert!(stats.kills_per_trip > 1.0);
+    }
+}
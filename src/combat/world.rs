@@ -0,0 +1,544 @@
+use crate::error::WorldError;
+use crate::types::monster::Monster;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// A single tile in the world, with x increasing to the east and y to the north.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Tile {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Tile {
+    pub fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    pub fn offset(&self, dx: i32, dy: i32) -> Self {
+        Self::new(self.x + dx, self.y + dy)
+    }
+
+    pub fn chebyshev(&self, other: &Tile) -> i32 {
+        // Diagonal moves count the same as straight ones
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+}
+
+/// The square of tiles an entity occupies, anchored at its south-west tile.
+///
+/// Players are always 1x1, while NPC sizes come from the monster database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Footprint {
+    pub tile: Tile,
+    pub size: i32,
+}
+
+impl Footprint {
+    pub fn new(tile: Tile, size: i32) -> Self {
+        Self {
+            tile,
+            size: size.max(1),
+        }
+    }
+
+    pub fn player(tile: Tile) -> Self {
+        Self::new(tile, 1)
+    }
+
+    pub fn from_monster(tile: Tile, monster: &Monster) -> Self {
+        Self::new(tile, monster.info.size as i32)
+    }
+
+    fn max_x(&self) -> i32 {
+        self.tile.x + self.size - 1
+    }
+
+    fn max_y(&self) -> i32 {
+        self.tile.y + self.size - 1
+    }
+
+    pub fn tiles(&self) -> impl Iterator<Item = Tile> + '_ {
+        (0..self.size).flat_map(move |dx| (0..self.size).map(move |dy| self.tile.offset(dx, dy)))
+    }
+
+    pub fn contains(&self, tile: &Tile) -> bool {
+        (self.tile.x..=self.max_x()).contains(&tile.x)
+            && (self.tile.y..=self.max_y()).contains(&tile.y)
+    }
+
+    pub fn overlaps(&self, other: &Footprint) -> bool {
+        self.gap(other) == (0, 0)
+    }
+
+    // Distance between the two squares along each axis (1 when touching, 0 when overlapping)
+    fn gap(&self, other: &Footprint) -> (i32, i32) {
+        let dx = (self.tile.x - other.max_x()).max(other.tile.x - self.max_x());
+        let dy = (self.tile.y - other.max_y()).max(other.tile.y - self.max_y());
+        (dx.max(0), dy.max(0))
+    }
+
+    /// Chebyshev distance between the closest tiles of the two footprints (0 if they overlap).
+    pub fn distance(&self, other: &Footprint) -> i32 {
+        let (dx, dy) = self.gap(other);
+        dx.max(dy)
+    }
+
+    /// Whether an attack with the given range reaches the target, ignoring line of sight.
+    ///
+    /// Range 1 (most melee) can't attack diagonally, and nothing can attack from underneath its target.
+    pub fn in_range(&self, target: &Footprint, range: i32) -> bool {
+        let (dx, dy) = self.gap(target);
+        match (dx, dy) {
+            (0, 0) => false,
+            _ if range <= 1 => dx.min(dy) == 0 && dx.max(dy) == 1,
+            _ => dx.max(dy) <= range,
+        }
+    }
+
+    // Direction (-1, 0 or 1) to step along each axis to move towards the target
+    fn direction_to(&self, target: &Footprint) -> (i32, i32) {
+        let axis = |min: i32, max: i32, target_min: i32, target_max: i32| {
+            if target_min > max {
+                1
+            } else if target_max < min {
+                -1
+            } else {
+                0
+            }
+        };
+        (
+            axis(self.tile.x, self.max_x(), target.tile.x, target.max_x()),
+            axis(self.tile.y, self.max_y(), target.tile.y, target.max_y()),
+        )
+    }
+}
+
+/// What an NPC did with its turn from [`World::npc_turn`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NpcAction {
+    Attack,  // In range with line of sight (possibly after stepping this tick)
+    Walk,    // Stepped towards the target but still can't attack
+    Blocked, // Couldn't step towards the target, e.g., when safespotted
+}
+
+/// A rectangular area of tiles, some of which are blocked (walls, pillars, etc.).
+///
+/// Blocked tiles can't be walked on and also block line of sight.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct World {
+    width: i32,
+    height: i32,
+    blocked: HashSet<Tile>,
+}
+
+impl World {
+    pub fn builder() -> WorldBuilder {
+        WorldBuilder::new()
+    }
+
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    pub fn in_bounds(&self, tile: &Tile) -> bool {
+        (0..self.width).contains(&tile.x) && (0..self.height).contains(&tile.y)
+    }
+
+    pub fn is_walkable(&self, tile: &Tile) -> bool {
+        self.in_bounds(tile) && !self.blocked.contains(tile)
+    }
+
+    pub fn can_occupy(&self, footprint: &Footprint) -> bool {
+        footprint.tiles().all(|tile| self.is_walkable(&tile))
+    }
+
+    /// Whether a line between the two tiles passes only through unblocked tiles.
+    pub fn has_line_of_sight(&self, from: &Tile, to: &Tile) -> bool {
+        // Step along the line with Bresenham's algorithm, skipping the end points
+        let (dx, dy) = ((to.x - from.x).abs(), -(to.y - from.y).abs());
+        let (sx, sy) = ((to.x - from.x).signum(), (to.y - from.y).signum());
+        let mut error = dx + dy;
+        let mut current = *from;
+
+        while current != *to {
+            let doubled = 2 * error;
+            if doubled >= dy {
+                error += dy;
+                current.x += sx;
+            }
+            if doubled <= dx {
+                error += dx;
+                current.y += sy;
+            }
+            if current != *to && self.blocked.contains(&current) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Whether an attacker can hit the target: in range, with line of sight from any of its tiles.
+    pub fn can_attack(&self, attacker: &Footprint, target: &Footprint, range: i32) -> bool {
+        attacker.in_range(target, range)
+            && attacker
+                .tiles()
+                .any(|from| target.tiles().any(|to| self.has_line_of_sight(&from, &to)))
+    }
+
+    // Whether a footprint can take a single step in the given direction, without cutting corners
+    fn can_step(&self, footprint: &Footprint, dx: i32, dy: i32, avoid: Option<&Footprint>) -> bool {
+        let moved = Footprint::new(footprint.tile.offset(dx, dy), footprint.size);
+        if !self.can_occupy(&moved) || avoid.is_some_and(|other| moved.overlaps(other)) {
+            return false;
+        }
+        // Diagonal steps also need both of the straight steps to be clear
+        dx == 0
+            || dy == 0
+            || (self.can_step(footprint, dx, 0, avoid) && self.can_step(footprint, 0, dy, avoid))
+    }
+
+    /// Take one step of OSRS "dumb" NPC pathing towards a target.
+    ///
+    /// The NPC tries a diagonal step first, then the horizontal and vertical steps, and never
+    /// steps onto its target. Returns whether it moved.
+    pub fn npc_step(&self, npc: &mut Footprint, target: &Footprint) -> bool {
+        let (dx, dy) = npc.direction_to(target);
+        let steps = [(dx, dy), (dx, 0), (0, dy)];
+        for (step_x, step_y) in steps {
+            if (step_x, step_y) != (0, 0) && self.can_step(npc, step_x, step_y, Some(target)) {
+                npc.tile = npc.tile.offset(step_x, step_y);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Decide an NPC's turn: attack if it can, otherwise step towards the target and check again.
+    pub fn npc_turn(&self, npc: &mut Footprint, target: &Footprint, range: i32) -> NpcAction {
+        if self.can_attack(npc, target, range) {
+            return NpcAction::Attack;
+        }
+        let moved = self.npc_step(npc, target);
+        if self.can_attack(npc, target, range) {
+            NpcAction::Attack
+        } else if moved {
+            NpcAction::Walk
+        } else {
+            NpcAction::Blocked
+        }
+    }
+
+    /// Shortest walkable path between two tiles (excluding the start), as the player would click it.
+    ///
+    /// Returns `None` if the destination can't be reached.
+    pub fn find_path(&self, from: &Tile, to: &Tile) -> Option<Vec<Tile>> {
+        if !self.is_walkable(to) {
+            return None;
+        }
+        if from == to {
+            return Some(Vec::new());
+        }
+
+        let mut previous: HashMap<Tile, Tile> = HashMap::new();
+        let mut queue = VecDeque::from([*from]);
+        previous.insert(*from, *from);
+
+        while let Some(tile) = queue.pop_front() {
+            if tile == *to {
+                let mut path = vec![tile];
+                while let Some(&step) = previous.get(path.last()?).filter(|&step| step != from) {
+                    path.push(step);
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            // Check straight steps before diagonals, like the game's pathfinder
+            for (dx, dy) in [
+                (-1, 0),
+                (1, 0),
+                (0, -1),
+                (0, 1),
+                (-1, -1),
+                (1, -1),
+                (-1, 1),
+                (1, 1),
+            ] {
+                let next = tile.offset(dx, dy);
+                if !previous.contains_key(&next)
+                    && self.can_step(&Footprint::player(tile), dx, dy, None)
+                {
+                    previous.insert(next, tile);
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+}
+
+/// A player's queued movement along a path, walking one tile per tick or running two.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlayerMovement {
+    path: VecDeque<Tile>,
+    pub running: bool,
+}
+
+impl PlayerMovement {
+    /// Path from one tile to another, or `None` if the destination can't be reached.
+    pub fn new(world: &World, from: &Tile, to: &Tile, running: bool) -> Option<Self> {
+        world.find_path(from, to).map(|path| Self {
+            path: path.into(),
+            running,
+        })
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.path.is_empty()
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.path.len()
+    }
+
+    /// Ticks needed to finish the path.
+    pub fn ticks_remaining(&self) -> usize {
+        if self.running {
+            self.path.len().div_ceil(2)
+        } else {
+            self.path.len()
+        }
+    }
+
+    /// Move the player along the path for one tick, returning whether they moved.
+    pub fn tick(&mut self, position: &mut Tile) -> bool {
+        let steps = if self.running { 2 } else { 1 };
+        let mut moved = false;
+        for _ in 0..steps {
+            if let Some(tile) = self.path.pop_front() {
+                *position = tile;
+                moved = true;
+            }
+        }
+        moved
+    }
+}
+
+/// Builder for constructing a `World` from its size and blocked tiles.
+///
+/// # Example
+/// ```
+/// use osrs::combat::world::{Footprint, Tile, World};
+///
+/// // A pillar between the player and an NPC
+/// let world = World::builder()
+///     .size(10, 10)
+///     .blocked(Tile::new(5, 5))
+///     .build()?;
+///
+/// let player = Footprint::player(Tile::new(5, 3));
+/// let npc = Footprint::new(Tile::new(5, 7), 1);
+/// assert!(!world.can_attack(&npc, &player, 7));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WorldBuilder {
+    width: i32,
+    height: i32,
+    blocked: Vec<Tile>,
+}
+
+impl WorldBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn size(mut self, width: i32, height: i32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    pub fn blocked(mut self, tile: Tile) -> Self {
+        self.blocked.push(tile);
+        self
+    }
+
+    pub fn blocked_area(mut self, footprint: Footprint) -> Self {
+        self.blocked.extend(footprint.tiles());
+        self
+    }
+
+    pub fn build(self) -> Result<World, WorldError> {
+        if self.width < 1 || self.height < 1 {
+            return Err(WorldError::InvalidSize {
+                width: self.width,
+                height: self.height,
+            });
+        }
+
+        let world = World {
+            width: self.width,
+            height: self.height,
+            blocked: HashSet::new(),
+        };
+        if let Some(tile) = self.blocked.iter().find(|tile| !world.in_bounds(tile)) {
+            return Err(WorldError::TileOutOfBounds {
+                x: tile.x,
+                y: tile.y,
+            });
+        }
+
+        Ok(World {
+            blocked: self.blocked.into_iter().collect(),
+            ..world
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::loadouts;
+
+    fn open_world() -> World {
+        World::builder().size(20, 20).build().unwrap()
+    }
+
+    // A horizontal wall across the middle of the world, from x = 3 to 7
+    fn walled_world() -> World {
+        let mut builder = World::builder().size(11, 11);
+        for x in 3..=7 {
+            builder = builder.blocked(Tile::new(x, 4));
+        }
+        builder.build().unwrap()
+    }
+
+    #[test]
+    fn test_distance_to_large_npc() {
+        let npc = Footprint::new(Tile::new(5, 5), 3);
+        assert_eq!(npc.distance(&Footprint::player(Tile::new(8, 6))), 1);
+        assert_eq!(npc.distance(&Footprint::player(Tile::new(10, 0))), 5);
+        assert_eq!(npc.distance(&Footprint::player(Tile::new(6, 6))), 0);
+        assert_eq!(Tile::new(0, 0).chebyshev(&Tile::new(3, -4)), 4);
+    }
+
+    #[test]
+    fn test_melee_range() {
+        let npc = Footprint::new(Tile::new(5, 5), 2);
+        let cardinal = Footprint::player(Tile::new(7, 6));
+        let diagonal = Footprint::player(Tile::new(7, 7));
+        let under = Footprint::player(Tile::new(6, 6));
+
+        assert!(npc.in_range(&cardinal, 1));
+        assert!(!npc.in_range(&diagonal, 1));
+        assert!(npc.in_range(&diagonal, 2));
+        assert!(!npc.in_range(&under, 10));
+    }
+
+    #[test]
+    fn test_line_of_sight() {
+        let world = walled_world();
+        assert!(!world.has_line_of_sight(&Tile::new(5, 1), &Tile::new(5, 8)));
+        assert!(world.has_line_of_sight(&Tile::new(9, 1), &Tile::new(9, 8)));
+
+        let npc = Footprint::player(Tile::new(5, 8));
+        assert!(!world.can_attack(&npc, &Footprint::player(Tile::new(5, 1)), 10));
+        assert!(world.can_attack(&npc, &Footprint::player(Tile::new(9, 5)), 10));
+    }
+
+    #[test]
+    fn test_npc_safespotted_behind_wall() {
+        let world = walled_world();
+        let player = Footprint::player(Tile::new(5, 2));
+        let mut npc = Footprint::player(Tile::new(5, 6));
+
+        assert_eq!(world.npc_turn(&mut npc, &player, 1), NpcAction::Walk);
+        assert_eq!(npc.tile, Tile::new(5, 5));
+        assert_eq!(world.npc_turn(&mut npc, &player, 1), NpcAction::Blocked);
+        assert_eq!(npc.tile, Tile::new(5, 5));
+    }
+
+    #[test]
+    fn test_npc_walks_into_melee_range() {
+        let world = open_world();
+        let player = Footprint::player(Tile::new(10, 10));
+        let mut npc = Footprint::new(Tile::new(4, 4), 2);
+
+        let mut ticks = 0;
+        while world.npc_turn(&mut npc, &player, 1) != NpcAction::Attack {
+            ticks += 1;
+            assert!(ticks < 10);
+        }
+        // Diagonal steps first, then a straight step so it isn't attacking diagonally
+        assert_eq!(ticks, 4);
+        assert!(!npc.overlaps(&player));
+        assert!(npc.in_range(&player, 1));
+    }
+
+    #[test]
+    fn test_player_path_around_wall() {
+        let world = walled_world();
+        let from = Tile::new(5, 2);
+        let to = Tile::new(5, 6);
+        let path = world.find_path(&from, &to).unwrap();
+
+        assert_eq!(path.last(), Some(&to));
+        assert!(path.len() > from.chebyshev(&to) as usize);
+        let mut previous = from;
+        for tile in &path {
+            assert!(world.is_walkable(tile));
+            assert_eq!(previous.chebyshev(tile), 1);
+            previous = *tile;
+        }
+
+        let mut movement = PlayerMovement::new(&world, &from, &to, true).unwrap();
+        assert_eq!(movement.ticks_remaining(), path.len().div_ceil(2));
+        let mut position = from;
+        while movement.tick(&mut position) {}
+        assert_eq!(position, to);
+        assert!(movement.is_finished());
+    }
+
+    #[test]
+    fn test_unreachable_destination() {
+        let world = World::builder()
+            .size(5, 5)
+            .blocked_area(Footprint::new(Tile::new(1, 1), 3))
+            .build()
+            .unwrap();
+        assert!(
+            world
+                .find_path(&Tile::new(0, 0), &Tile::new(2, 2))
+                .is_none()
+        );
+        assert_eq!(
+            world.find_path(&Tile::new(0, 0), &Tile::new(0, 0)),
+            Some(Vec::new())
+        );
+    }
+
+    #[test]
+    fn test_builder_errors() {
+        assert!(matches!(
+            World::builder().size(0, 5).build(),
+            Err(WorldError::InvalidSize { .. })
+        ));
+        assert!(matches!(
+            World::builder().size(5, 5).blocked(Tile::new(5, 0)).build(),
+            Err(WorldError::TileOutOfBounds { x: 5, y: 0 })
+        ));
+    }
+
+    #[test]
+    fn test_sizes_and_ranges_from_data() {
+        let muspah = Monster::new("Phantom Muspah", Some("Ranged")).unwrap();
+        assert_eq!(Footprint::from_monster(Tile::default(), &muspah).size, 5);
+        assert_eq!(loadouts::max_mage_sang_staff_player().attack_range(), 7);
+        assert_eq!(loadouts::max_melee_player().attack_range(), 1);
+    }
+}
@@ -199,6 +199,13 @@ pub const IMMUNE_TO_MAGIC_MONSTERS: &[i32] = DUSK_IDS;
 
 pub const IMMUNE_TO_STAT_DRAIN: &[i32] = &[13011, 13012, 13013];
 
+// Bosses whose attacks pierce protection prayers, with the fraction of damage that gets through
+pub const PRAYER_PIERCING_MONSTERS: &[(&str, u32, u32)] = &[
+    ("Vardorvis", 1, 4),
+    ("Corrupted Hunllef", 10, 41),
+    ("Crystalline Hunllef", 10, 41),
+];
+
 pub const IMMUNE_TO_RECOIL_MONSTERS: &[i32] = &[
     6609, // Callisto
     11482, 11483, // Champion of Scabaras
@@ -548,6 +555,7 @@ pub const MAX_LEVEL: u32 = 99;
 pub const MIN_LEVEL: u32 = 0;
 pub const MIN_HITPOINTS: u32 = 10;
 pub const FULL_SPEC: u8 = 100;
+pub const MAX_ATTACK_RANGE: i32 = 10;
 pub const SPEC_REGEN: u8 = 10;
 pub const DEATH_CHARGE: u8 = 15;
 pub const SURGE_POTION: u8 = 25;
@@ -585,6 +593,14 @@ pub const BLACK_MASKS: [(&str, Option<&str>); 4] = [
 pub const BLACK_MASKS_IMBUED: [(&str, Option<&str>); 2] =
     [("Black mask (i)", None), ("Slayer helmet (i)", None)];
 
+pub const DRAGONFIRE_SHIELDS: [&str; 5] = [
+    "Anti-dragon shield",
+    "Anti-dragon shield (nz)",
+    "Dragonfire shield",
+    "Dragonfire ward",
+    "Ancient wyvern shield",
+];
+
 pub const SALVE_UNENCHANTED: [(&str, Option<&str>); 2] =
     [("Salve amulet", None), ("Salve amulet(i)", None)];
 
@@ -699,6 +715,8 @@ pub const DEMONBANE_WEAPONS: [(&str, Option<&str>); 6] = [
 
 pub const OGRE_BOWS: [(&str, Option<&str>); 2] = [("Ogre bow", None), ("Comp ogre bow", None)];
 pub const EAT_DELAY: u32 = 3;
+pub const INVENTORY_SIZE: usize = 28;
+pub const POTION_DOSES: u32 = 4;
 
 pub const SPEC_COSTS: [(&str, u8); 74] = [
     ("Ancient godsword", 50),
@@ -3,8 +3,15 @@ use std::num::ParseIntError;
 use thiserror::Error;
 
 use crate::{
+    calc::optimizer::GearStyle,
     combat::simulation::FightResult,
-    types::{equipment::CombatStyle, player::SwitchType, spells::Spell},
+    types::{
+        equipment::{CombatStyle, GearSlot},
+        food::Foods,
+        player::SwitchType,
+        potions::Potion,
+        spells::Spell,
+    },
 };
 
 #[derive(Error, Debug)]
@@ -35,6 +42,12 @@ pub enum SimulationError {
     SwitchingError(#[from] PlayerError),
     #[error("Error creating monster: {0}")]
     MonsterCreationError(String),
+    #[error("Player ran out of supplies before the monster died.")]
+    SupplyOutError(FightResult),
+    #[error("Inventory error: {0}")]
+    InventoryError(#[from] InventoryError),
+    #[error("Boss script error: {0}")]
+    ScriptError(#[from] ScriptError),
 }
 
 #[derive(Error, Debug)]
@@ -58,6 +71,11 @@ pub enum MonsterError {
     },
     #[error("Special attack type not supported.")]
     SpecialAttackNotSupported,
+    #[error("{monster_name} has no special attack named {special}.")]
+    SpecialAttackNotFound {
+        monster_name: String,
+        special: String,
+    },
     #[error("None attack type not supported.")]
     NoneAttackNotSupported,
 }
@@ -79,6 +97,12 @@ pub enum PlayerError {
         weapon_name: String,
         style: CombatStyle,
     },
+    #[error("Unknown {kind}: {name}")]
+    UnknownName { kind: &'static str, name: String },
+    #[error("Invalid {stat} level: {level}")]
+    InvalidLevel { stat: &'static str, level: u32 },
+    #[error("Gear switch {0} is defined more than once.")]
+    DuplicateSwitch(String),
 }
 
 #[derive(Error, Debug)]
@@ -120,4 +144,91 @@ pub enum GearError {
     JsonReadError(#[from] std::io::Error),
     #[error("{0} has the slot type None.")]
     NoneSlot(String),
+    #[error("{item_name} goes in the {actual} slot, not the {expected} slot.")]
+    WrongSlot {
+        item_name: String,
+        expected: GearSlot,
+        actual: GearSlot,
+    },
+    #[error("{weapon_name} is two-handed and can't be used with {shield_name}.")]
+    TwoHandedWithShield {
+        weapon_name: String,
+        shield_name: String,
+    },
+}
+
+#[derive(Error, Debug)]
+pub enum OptimizerError {
+    #[error("Gear error: {0}")]
+    GearError(#[from] GearError),
+    #[error("Player error: {0}")]
+    PlayerError(#[from] PlayerError),
+    #[error("No usable {0} weapons in the item pool.")]
+    NoUsableWeapons(GearStyle),
+    #[error("Optimizer config error: {0}")]
+    ConfigError(String),
+}
+
+#[derive(Error, Debug)]
+pub enum BankError {
+    #[error("Error parsing bank JSON: {0}")]
+    JsonError(#[from] serde_json::Error),
+    #[error("No item name found in bank line: {0}")]
+    InvalidLine(String),
+}
+
+#[derive(Error, Debug)]
+pub enum LoadoutError {
+    #[error("Gear error: {0}")]
+    GearError(#[from] GearError),
+    #[error("Player error: {0}")]
+    PlayerError(#[from] PlayerError),
+    #[error("Error reading loadout file: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Error parsing loadout TOML: {0}")]
+    TomlParseError(#[from] toml::de::Error),
+    #[error("Error writing loadout TOML: {0}")]
+    TomlWriteError(#[from] toml::ser::Error),
+    #[error("Error parsing loadout JSON: {0}")]
+    JsonError(#[from] serde_json::Error),
+    #[error("Unsupported loadout file type: {0} (expected .toml or .json)")]
+    UnsupportedFormat(String),
+}
+
+#[derive(Error, Debug)]
+pub enum InventoryError {
+    #[error("Not enough inventory space to add {count} x {item}.")]
+    InventoryFull { item: String, count: u32 },
+    #[error("Food {0:?} not found in the food database.")]
+    FoodNotFound(Foods),
+    #[error("Potion {0} does not have doses.")]
+    NotADosedPotion(Potion),
+}
+
+#[derive(Error, Debug)]
+pub enum WorldError {
+    #[error("World must be at least 1x1 tiles, got {width}x{height}.")]
+    InvalidSize { width: i32, height: i32 },
+    #[error("Tile ({x}, {y}) is outside the world.")]
+    TileOutOfBounds { x: i32, y: i32 },
+}
+
+#[derive(Error, Debug)]
+pub enum ScriptError {
+    #[error("Error reading boss script: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Error parsing boss script TOML: {0}")]
+    TomlParseError(#[from] toml::de::Error),
+    #[error("Error parsing boss script JSON: {0}")]
+    JsonError(#[from] serde_json::Error),
+    #[error("Unsupported boss script file type: {0} (expected .toml or .json)")]
+    UnsupportedFormat(String),
+    #[error("No bundled boss script named {0}.")]
+    BundledScriptNotFound(String),
+    #[error("Boss script refers to unknown {kind} {name}.")]
+    UnknownName { kind: &'static str, name: String },
+    #[error("Invalid boss script phases: {0}")]
+    InvalidPhases(String),
+    #[error("Invalid boss script: {0}")]
+    Invalid(String),
 }
@@ -1,10 +1,15 @@
 use crate::combat::limiters::Limiter;
 use crate::combat::mechanics::Mechanics;
-use crate::combat::simulation::{FightResult, FightVars, Simulation};
+use crate::combat::prayer::PrayerDrainConfig;
+use crate::combat::simulation::{FightResult, FightVars, Simulation, seeded_rng};
+use crate::combat::spec::{CoreCondition, SpecCondition, SpecConfig, SpecState};
 use crate::constants;
 use crate::error::SimulationError;
+use crate::types::food::Foods;
+use crate::types::inventory::Inventory;
 use crate::types::monster::{AttackType, Monster};
 use crate::types::player::Player;
+use crate::types::potions::RedoseConfig;
 use crate::utils::logging::FightLogger;
 use rand::SeedableRng;
 use rand::rngs::SmallRng;
@@ -18,6 +23,7 @@ pub enum GraardorMethod {
     DoorAltar,
 }
 
+#[derive(Clone)]
 struct GraardorMechanics;
 
 impl Mechanics for GraardorMechanics {}
@@ -28,8 +34,11 @@ impl GraardorMechanics {}
 pub struct GraardorConfig {
     pub method: GraardorMethod,
     pub eat_hp: u32,
-    pub heal_amount: u32,
+    pub inventory: Inventory,
     pub logger: FightLogger,
+    pub prayer_drain: Option<PrayerDrainConfig>,
+    pub redose: Option<RedoseConfig>,
+    pub seed: Option<u64>,
 }
 
 impl Default for GraardorConfig {
@@ -37,14 +46,51 @@ impl Default for GraardorConfig {
         Self {
             method: GraardorMethod::DoorAltar,
             eat_hp: 30,
-            heal_amount: 20,
-            logger: FightLogger::new(false, "graardor").expect("Error initializing logger."),
+            inventory: Inventory::builder()
+                .food(Foods::Shark, 28)
+                .build()
+                .expect("Error building inventory."),
+            logger: FightLogger::new(false),
+            prayer_drain: None,
+            redose: None,
+            seed: None,
         }
     }
 }
 
+// Spec conditions specific to the Graardor door-altar method
+#[derive(Debug, Clone, PartialEq)]
+pub enum GraardorCondition {
+    Core(CoreCondition),
+    CycleTickBetween(i32, i32), // The current tick of the door-altar cycle is within this range (inclusive)
+    NotEating,                  // The player is not about to skip an attack to eat
+}
+
+impl SpecCondition for GraardorCondition {
+    type BossState = GraardorState;
+
+    fn evaluate(&self, player: &Player, monster: &Monster, boss_state: &Self::BossState) -> bool {
+        match self {
+            Self::Core(core) => core.evaluate(player, monster, &()),
+            Self::CycleTickBetween(start, end) => (*start..=*end).contains(&boss_state.cycle_tick),
+            Self::NotEating => !boss_state.skip_next_attack,
+        }
+    }
+
+    fn as_core(&self) -> Option<&CoreCondition> {
+        match self {
+            Self::Core(core) => Some(core),
+            _ => None,
+        }
+    }
+
+    fn from_core(core: CoreCondition) -> Self {
+        Self::Core(core)
+    }
+}
+
 #[derive(Debug, Clone)]
-struct GraardorState {
+pub struct GraardorState {
     mage_attack_tick: i32,
     melee_attack_tick: i32,
     skip_next_attack: bool,
@@ -62,6 +108,7 @@ impl Default for GraardorState {
     }
 }
 
+#[derive(Clone)]
 pub struct GraardorFight {
     player: Player,
     graardor: Monster,
@@ -72,10 +119,17 @@ pub struct GraardorFight {
     rng: SmallRng,
     config: GraardorConfig,
     mechanics: GraardorMechanics,
+    spec_config: Option<SpecConfig<GraardorCondition>>,
+    spec_state: SpecState,
+    inventory: Inventory,
 }
 
 impl GraardorFight {
-    pub fn new(player: Player, config: GraardorConfig) -> Result<GraardorFight, SimulationError> {
+    pub fn new(
+        player: Player,
+        config: GraardorConfig,
+        spec_config: Option<SpecConfig<GraardorCondition>>,
+    ) -> Result<GraardorFight, SimulationError> {
         let graardor = Monster::new("General Graardor", None)
             .map_err(|_| SimulationError::MonsterCreationError("General Graardor".to_string()))?;
         let melee_minion = Monster::new("Sergeant Strongstack", None).map_err(|_| {
@@ -86,7 +140,8 @@ impl GraardorFight {
         let mage_minion = Monster::new("Sergeant Steelwill", None)
             .map_err(|_| SimulationError::MonsterCreationError("Sergeant Steelwill".to_string()))?;
         let limiter = crate::combat::simulation::assign_limiter(&player, &graardor);
-        let rng = SmallRng::from_os_rng();
+        let rng = seeded_rng(config.seed);
+        let inventory = config.inventory.clone();
 
         Ok(GraardorFight {
             player,
@@ -98,6 +153,9 @@ impl GraardorFight {
             rng,
             config,
             mechanics: GraardorMechanics,
+            spec_config,
+            spec_state: SpecState::default(),
+            inventory,
         })
     }
 
@@ -110,6 +168,12 @@ impl GraardorFight {
             return Err(SimulationError::ConfigError(error_msg));
         }
 
+        if let Some(ref spec_config) = self.spec_config
+            && let Err(e) = spec_config.validate()
+        {
+            return Err(SimulationError::ConfigError(e));
+        }
+
         let mut vars = FightVars::new();
         let mut state = GraardorState::default();
 
@@ -118,20 +182,62 @@ impl GraardorFight {
             .log_initial_setup(&self.player, &self.graardor);
 
         while self.graardor.stats.hitpoints.current > 0 {
+            if let Some(prayer_config) = self.config.prayer_drain {
+                let attacking = vars.tick_counter == vars.attack_tick && !state.skip_next_attack;
+                self.mechanics.process_prayer(
+                    &mut self.player,
+                    &self.graardor,
+                    &prayer_config,
+                    &mut self.inventory,
+                    attacking,
+                    &mut vars,
+                    &mut self.config.logger,
+                )?;
+            }
+
+            if let Some(redose) = self.config.redose {
+                self.mechanics.process_redose(
+                    &mut self.player,
+                    &self.graardor,
+                    &redose,
+                    &mut self.inventory,
+                    &mut vars,
+                    &mut self.config.logger,
+                );
+            }
+
             // Player attack
             if vars.tick_counter == vars.attack_tick {
                 if state.skip_next_attack {
                     state.skip_next_attack = false;
                     vars.attack_tick += 4;
                 } else {
-                    self.mechanics.player_attack(
-                        &mut self.player,
-                        &mut self.graardor,
-                        &mut self.rng,
-                        &self.limiter,
-                        &mut vars,
-                        &mut self.config.logger,
-                    );
+                    let did_spec = if let Some(ref mut spec_config) = self.spec_config {
+                        self.mechanics.player_special_attack(
+                            &mut self.player,
+                            &mut self.graardor,
+                            spec_config,
+                            &mut self.spec_state,
+                            &state,
+                            &mut self.rng,
+                            &self.limiter,
+                            &mut vars,
+                            &mut self.config.logger,
+                        )?
+                    } else {
+                        false
+                    };
+
+                    if !did_spec {
+                        self.mechanics.player_attack(
+                            &mut self.player,
+                            &mut self.graardor,
+                            &mut self.rng,
+                            &self.limiter,
+                            &mut vars,
+                            &mut self.config.logger,
+                        );
+                    }
                 }
             }
 
@@ -191,16 +297,28 @@ impl GraardorFight {
             // Eat if below the provided threshold and force the player to skip the next attack
             if self.player.stats.hitpoints.current < self.config.eat_hp
                 && VALID_EAT_TICKS.contains(&state.cycle_tick)
-                && vars.eat_delay == 0
             {
-                self.mechanics.eat_food(
-                    &mut self.player,
-                    self.config.heal_amount,
-                    None,
-                    &mut vars,
-                    &mut self.config.logger,
-                );
-                state.skip_next_attack = true;
+                if !self.inventory.has_food() {
+                    return self.mechanics.process_supply_out(
+                        &vars,
+                        &self.graardor,
+                        &mut self.config.logger,
+                    );
+                }
+
+                if self
+                    .mechanics
+                    .eat_next_food(
+                        &mut self.player,
+                        &mut self.inventory,
+                        false,
+                        &mut vars,
+                        &mut self.config.logger,
+                    )
+                    .is_some()
+                {
+                    state.skip_next_attack = true;
+                }
             }
 
             // Regen all stats by 1 for Graardor every 10 ticks
@@ -220,6 +338,14 @@ impl GraardorFight {
                     .player_regen(&mut self.player, &vars, &mut self.config.logger);
             }
 
+            self.mechanics.process_spec_timers(
+                &mut self.player,
+                self.spec_config.as_ref(),
+                &mut self.spec_state,
+                &vars,
+                &mut self.config.logger,
+            );
+
             // Increment tick counter
             vars.tick_counter += 1;
 
@@ -262,14 +388,44 @@ impl Simulation for GraardorFight {
 
     fn set_attack_function(&mut self) {
         self.player.attack = crate::combat::attacks::standard::get_attack_functions(&self.player);
+        self.player.spec = crate::combat::attacks::specs::get_spec_attack_function(&self.player);
     }
 
     fn reset(&mut self) {
-        self.player.reset_current_stats(true);
+        self.config.logger.clear();
+        if let Some(ref mut spec_config) = self.spec_config {
+            let restore_spec = self.spec_state.on_kill(&mut self.player, spec_config);
+            self.player.reset_current_stats(restore_spec);
+        } else {
+            self.player.reset_current_stats(true);
+        }
         self.graardor.reset();
         self.melee_minion.reset();
         self.ranged_minion.reset();
         self.mage_minion.reset();
+        self.inventory = self.config.inventory.clone();
+    }
+
+    fn next_kill(&mut self, downtime_ticks: u32) {
+        self.mechanics.prepare_next_kill(
+            &mut self.player,
+            self.spec_config.as_mut(),
+            &mut self.spec_state,
+            downtime_ticks,
+            &mut self.config.logger,
+        );
+        self.graardor.reset();
+        self.melee_minion.reset();
+        self.ranged_minion.reset();
+        self.mage_minion.reset();
+    }
+
+    fn inventory(&self) -> Option<&Inventory> {
+        Some(&self.inventory)
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.rng = SmallRng::seed_from_u64(seed);
     }
 }
 
@@ -311,12 +467,18 @@ mod tests {
         let fight_config = GraardorConfig {
             method: GraardorMethod::DoorAltar,
             eat_hp: 30,
-            heal_amount: 22,
-            logger: FightLogger::new(false, "graardor").expect("Error initializing logger."),
+            inventory: Inventory::builder()
+                .food(Foods::MantaRay, 28)
+                .build()
+                .expect("Error building inventory."),
+            logger: FightLogger::new(false),
+            prayer_drain: None,
+            redose: None,
+            seed: None,
         };
 
-        let mut fight =
-            GraardorFight::new(player, fight_config).expect("Error setting up Graardor fight.");
+        let mut fight = GraardorFight::new(player, fight_config, None)
+            .expect("Error setting up Graardor fight.");
 
         let result = fight.simulate();
 
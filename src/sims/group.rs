@@ -0,0 +1,797 @@
+use crate::calc::monster_scaling::{PartyScaling, scale_monster_party_hp};
+use crate::combat::limiters::Limiter;
+use crate::combat::mechanics::{Mechanics, handle_recoil};
+use crate::combat::simulation::{
+    FightResult, FightVars, Simulation, assign_limiter, derive_fight_seed, seeded_rng,
+};
+use crate::combat::spec::{CoreCondition, SpecConfig, SpecState};
+use crate::combat::thralls::Thrall;
+use crate::constants::SECONDS_PER_TICK;
+use crate::error::SimulationError;
+use crate::types::monster::Monster;
+use crate::types::player::Player;
+use crate::utils::logging::FightLogger;
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+/// One player in a group fight, with their own gear switches, spec strategy and thrall.
+#[derive(Clone)]
+pub struct GroupMember {
+    pub name: String,
+    pub player: Player,
+    pub spec_config: Option<SpecConfig<CoreCondition>>,
+    pub thrall: Option<Thrall>,
+    pub first_attack_tick: i32, // Offsets this member's attack cycle, e.g. to stagger DWH specs
+}
+
+impl GroupMember {
+    pub fn new(name: &str, player: Player) -> Self {
+        Self {
+            name: name.to_string(),
+            player,
+            spec_config: None,
+            thrall: None,
+            first_attack_tick: 0,
+        }
+    }
+}
+
+/// How the monster picks which member to attack.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AggroPolicy {
+    #[default]
+    FirstAttacker, // Stays on whichever member attacked it first
+    MostDamage,   // Switches to whichever member has dealt the most damage so far
+    Random,       // Picks a random living member for each attack
+    Fixed(usize), // Always attacks this member (a designated tank), while they're alive
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupConfig {
+    pub party_scaling: PartyScaling,
+    pub aggro: AggroPolicy,
+    pub monster_attacks: bool, // Whether the monster fights back against its target
+    pub remove_final_attack_delay: bool,
+    pub logger: FightLogger,
+    pub seed: Option<u64>,
+}
+
+impl Default for GroupConfig {
+    fn default() -> Self {
+        Self {
+            party_scaling: PartyScaling::None,
+            aggro: AggroPolicy::FirstAttacker,
+            monster_attacks: false,
+            remove_final_attack_delay: false,
+            logger: FightLogger::new(false),
+            seed: None,
+        }
+    }
+}
+
+/// A member's contribution to a single group fight.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MemberResult {
+    pub name: String,
+    pub damage_dealt: u32,
+    pub hit_attempts: u32,
+    pub hit_count: u32,
+    pub thrall_damage: u32,
+    pub damage_taken: u32,
+    pub died: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GroupFightResult {
+    pub ttk_ticks: i32,
+    pub wiped: bool, // Every member died before the monster did
+    pub members: Vec<MemberResult>,
+}
+
+impl GroupFightResult {
+    /// Fraction of the total player and thrall damage dealt by each member.
+    pub fn damage_shares(&self) -> Vec<f64> {
+        let total: u32 = self
+            .members
+            .iter()
+            .map(|m| m.damage_dealt + m.thrall_damage)
+            .sum();
+        self.members
+            .iter()
+            .map(|m| {
+                if total == 0 {
+                    0.0
+                } else {
+                    f64::from(m.damage_dealt + m.thrall_damage) / f64::from(total)
+                }
+            })
+            .collect()
+    }
+
+    // Combine every member's stats into a single-player result for the `Simulation` trait
+    fn combined(&self) -> FightResult {
+        let mut result = FightResult {
+            ttk_ticks: self.ttk_ticks,
+            ..FightResult::default()
+        };
+        for member in &self.members {
+            result.hit_attempts += member.hit_attempts;
+            result.hit_count += member.hit_count;
+            result.thrall_damage += member.thrall_damage;
+            result.damage_taken += member.damage_taken;
+        }
+        result
+    }
+}
+
+/// Results of many group fights, from `simulate_n_group_fights`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GroupResults {
+    pub fights: Vec<GroupFightResult>,
+}
+
+impl GroupResults {
+    pub fn wipes(&self) -> usize {
+        self.fights.iter().filter(|fight| fight.wiped).count()
+    }
+
+    /// Average time to kill in seconds, over fights that didn't wipe.
+    pub fn avg_ttk(&self) -> f64 {
+        let kills: Vec<_> = self.fights.iter().filter(|fight| !fight.wiped).collect();
+        if kills.is_empty() {
+            return 0.0;
+        }
+        let total: i64 = kills.iter().map(|fight| i64::from(fight.ttk_ticks)).sum();
+        total as f64 * SECONDS_PER_TICK / kills.len() as f64
+    }
+
+    /// Each member's share of the damage, over all fights.
+    pub fn damage_shares(&self) -> Vec<f64> {
+        let members = self.fights.first().map_or(0, |fight| fight.members.len());
+        let mut damage = vec![0u64; members];
+        for fight in &self.fights {
+            for (total, member) in damage.iter_mut().zip(&fight.members) {
+                *total += u64::from(member.damage_dealt + member.thrall_damage);
+            }
+        }
+        let total: u64 = damage.iter().sum();
+        damage
+            .iter()
+            .map(|&d| {
+                if total == 0 {
+                    0.0
+                } else {
+                    d as f64 / total as f64
+                }
+            })
+            .collect()
+    }
+}
+
+// Per-member state that lasts for a single fight
+#[derive(Clone)]
+struct MemberState {
+    vars: FightVars,
+    spec_state: SpecState,
+    limiter: Option<Box<dyn Limiter>>,
+    damage_dealt: u32,
+    alive: bool,
+}
+
+#[derive(Debug, Clone)]
+struct GroupMechanics;
+
+impl Mechanics for GroupMechanics {}
+
+/// N players attacking the same monster, which shares its HP and stat drains between them.
+#[derive(Clone)]
+pub struct GroupFight {
+    members: Vec<GroupMember>,
+    states: Vec<MemberState>,
+    monster: Monster,
+    target: Option<usize>,
+    rng: SmallRng,
+    config: GroupConfig,
+    mechanics: GroupMechanics,
+}
+
+impl GroupFight {
+    pub fn new(
+        members: Vec<GroupMember>,
+        mut monster: Monster,
+        config: GroupConfig,
+    ) -> Result<Self, SimulationError> {
+        if members.is_empty() {
+            return Err(SimulationError::ConfigError(
+                "Group fights need at least one member.".to_string(),
+            ));
+        }
+        if let AggroPolicy::Fixed(index) = config.aggro
+            && index >= members.len()
+        {
+            return Err(SimulationError::ConfigError(format!(
+                "Aggro target {index} is out of range for a party of {}.",
+                members.len()
+            )));
+        }
+        for member in &members {
+            if let Some(spec_config) = &member.spec_config {
+                spec_config
+                    .validate()
+                    .map_err(SimulationError::ConfigError)?;
+            }
+        }
+
+        scale_monster_party_hp(&mut monster, members.len() as u32, config.party_scaling);
+
+        let states = members
+            .iter()
+            .map(|member| MemberState {
+                vars: FightVars::new(),
+                spec_state: SpecState::default(),
+                limiter: assign_limiter(&member.player, &monster),
+                damage_dealt: 0,
+                alive: true,
+            })
+            .collect();
+        let rng = seeded_rng(config.seed);
+
+        Ok(Self {
+            members,
+            states,
+            monster,
+            target: None,
+            rng,
+            config,
+            mechanics: GroupMechanics,
+        })
+    }
+
+    pub fn members(&self) -> &[GroupMember] {
+        &self.members
+    }
+
+    // Pick the member the monster attacks this turn, if anyone is left alive
+    fn choose_target(&mut self) -> Option<usize> {
+        let alive: Vec<usize> = (0..self.members.len())
+            .filter(|&i| self.states[i].alive)
+            .collect();
+        if alive.is_empty() {
+            return None;
+        }
+
+        let target = match self.config.aggro {
+            AggroPolicy::FirstAttacker => self.target.filter(|&i| self.states[i].alive),
+            AggroPolicy::MostDamage => alive
+                .iter()
+                .copied()
+                .max_by_key(|&i| (self.states[i].damage_dealt, std::cmp::Reverse(i))),
+            AggroPolicy::Random => Some(alive[self.rng.random_range(0..alive.len())]),
+            AggroPolicy::Fixed(index) => Some(index).filter(|&i| self.states[i].alive),
+        };
+        let target = target.unwrap_or(alive[0]);
+        self.target = Some(target);
+        Some(target)
+    }
+
+    fn monster_attack(&mut self, tick: i32) -> Result<(), SimulationError> {
+        let Some(target) = self.choose_target() else {
+            return Ok(());
+        };
+
+        // Monsters with several styles pick one of them at random
+        let style = match self.monster.info.attack_styles.as_deref() {
+            Some(styles) if styles.len() > 1 => {
+                Some(styles[self.rng.random_range(0..styles.len())])
+            }
+            _ => None,
+        };
+        let player = &mut self.members[target].player;
+        let state = &mut self.states[target];
+        let hit = self.monster.attack(player, style, &mut self.rng, false)?;
+
+        if self.config.logger.enabled {
+            self.config.logger.log_monster_target(
+                tick,
+                self.monster.name(),
+                &self.members[target].name,
+            );
+            self.config.logger.log_monster_attack(
+                &self.monster,
+                tick,
+                hit.damage,
+                hit.success,
+                style,
+            );
+        }
+
+        let player = &mut self.members[target].player;
+        if hit.success {
+            player.take_damage(hit.damage);
+            state.vars.damage_taken += hit.damage;
+            handle_recoil(
+                player,
+                &mut self.monster,
+                &hit,
+                &mut state.vars,
+                &mut self.config.logger,
+            );
+        }
+
+        if player.stats.hitpoints.current == 0 {
+            state.alive = false;
+            if self.config.logger.enabled {
+                self.config.logger.log_player_death(tick);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn member_attack(&mut self, index: usize) -> Result<(), SimulationError> {
+        let member = &mut self.members[index];
+        let state = &mut self.states[index];
+        let hp_before = self.monster.stats.hitpoints.current;
+
+        if self.config.logger.enabled {
+            self.config
+                .logger
+                .log_member_attack(state.vars.tick_counter, &member.name);
+        }
+
+        let did_spec = if let Some(ref mut spec_config) = member.spec_config {
+            self.mechanics.player_special_attack(
+                &mut member.player,
+                &mut self.monster,
+                spec_config,
+                &mut state.spec_state,
+                &(),
+                &mut self.rng,
+                &state.limiter,
+                &mut state.vars,
+                &mut self.config.logger,
+            )?
+        } else {
+            false
+        };
+
+        if !did_spec {
+            self.mechanics.player_attack(
+                &mut member.player,
+                &mut self.monster,
+                &mut self.rng,
+                &state.limiter,
+                &mut state.vars,
+                &mut self.config.logger,
+            );
+        }
+
+        state.damage_dealt += hp_before - self.monster.stats.hitpoints.current;
+        if self.target.is_none() {
+            self.target = Some(index);
+        }
+
+        Ok(())
+    }
+
+    pub fn simulate_group_fight(&mut self) -> Result<GroupFightResult, SimulationError> {
+        let mut tick = 0;
+        let mut monster_vars = FightVars::new();
+        let monster_attack_speed = self.monster.info.attack_speed.unwrap_or(4) as i32;
+        let mut monster_attack_tick = monster_attack_speed;
+
+        for (member, state) in self.members.iter().zip(&mut self.states) {
+            state.vars.attack_tick = member.first_attack_tick;
+            state.vars.thrall_attack_tick = member.first_attack_tick + 1;
+        }
+
+        if self.config.logger.enabled {
+            for member in &self.members {
+                self.config
+                    .logger
+                    .log_initial_setup(&member.player, &self.monster);
+            }
+        }
+
+        while self.monster.stats.hitpoints.current > 0 {
+            monster_vars.tick_counter = tick;
+            for state in &mut self.states {
+                state.vars.tick_counter = tick;
+            }
+
+            for index in 0..self.members.len() {
+                if !self.states[index].alive || self.monster.stats.hitpoints.current == 0 {
+                    continue;
+                }
+
+                if tick == self.states[index].vars.attack_tick {
+                    self.member_attack(index)?;
+                }
+
+                if let Some(thrall) = self.members[index].thrall
+                    && tick == self.states[index].vars.thrall_attack_tick
+                {
+                    self.mechanics.thrall_attack(
+                        &mut self.monster,
+                        thrall,
+                        &mut self.states[index].vars,
+                        &mut self.rng,
+                        &mut self.config.logger,
+                    );
+                }
+            }
+
+            self.mechanics.process_monster_effects(
+                &mut self.monster,
+                &monster_vars,
+                &mut self.config.logger,
+            );
+            self.mechanics.process_freeze(
+                &mut self.monster,
+                &mut monster_vars,
+                &mut self.config.logger,
+            );
+
+            if self.config.monster_attacks
+                && self.monster.stats.hitpoints.current > 0
+                && tick == monster_attack_tick
+            {
+                self.monster_attack(tick)?;
+                monster_attack_tick += monster_attack_speed;
+            }
+
+            for (member, state) in self.members.iter_mut().zip(&mut self.states) {
+                self.mechanics.process_spec_timers(
+                    &mut member.player,
+                    member.spec_config.as_ref(),
+                    &mut state.spec_state,
+                    &state.vars,
+                    &mut self.config.logger,
+                );
+            }
+
+            tick += 1;
+
+            if self.states.iter().all(|state| !state.alive) {
+                return Ok(self.result(tick, true));
+            }
+        }
+
+        if self.config.logger.enabled {
+            self.config
+                .logger
+                .log_monster_death(tick, self.monster.name());
+        }
+
+        // Without removing the final attack delay, the kill lands once the slowest member's
+        // final attack cooldown is over
+        let ttk_ticks = if self.config.remove_final_attack_delay {
+            tick
+        } else {
+            self.states
+                .iter()
+                .filter(|state| state.alive)
+                .map(|state| state.vars.attack_tick)
+                .max()
+                .unwrap_or(tick)
+                .max(tick)
+        };
+        Ok(self.result(ttk_ticks, false))
+    }
+
+    fn result(&self, ttk_ticks: i32, wiped: bool) -> GroupFightResult {
+        GroupFightResult {
+            ttk_ticks,
+            wiped,
+            members: self
+                .members
+                .iter()
+                .zip(&self.states)
+                .map(|(member, state)| MemberResult {
+                    name: member.name.clone(),
+                    damage_dealt: state.damage_dealt,
+                    hit_attempts: state.vars.hit_attempts,
+                    hit_count: state.vars.hit_count,
+                    thrall_damage: state.vars.thrall_damage,
+                    damage_taken: state.vars.damage_taken,
+                    died: !state.alive,
+                })
+                .collect(),
+        }
+    }
+
+    // Reset per-fight member state, keeping each member's spec state for `on_kill`
+    fn reset_states(&mut self) {
+        for state in &mut self.states {
+            state.vars = FightVars::new();
+            state.damage_dealt = 0;
+            state.alive = true;
+        }
+        self.target = None;
+        self.monster.reset();
+    }
+}
+
+impl Simulation for GroupFight {
+    fn simulate(&mut self) -> Result<FightResult, SimulationError> {
+        let result = self.simulate_group_fight()?;
+        if result.wiped {
+            Err(SimulationError::PlayerDeathError(result.combined()))
+        } else {
+            Ok(result.combined())
+        }
+    }
+
+    fn is_immune(&self) -> bool {
+        // The fight can only be won if at least one member can damage the monster
+        self.members
+            .iter()
+            .all(|member| self.monster.is_immune(&member.player))
+    }
+
+    fn player(&self) -> &Player {
+        &self.members[0].player
+    }
+
+    fn monster(&self) -> &Monster {
+        &self.monster
+    }
+
+    fn set_attack_function(&mut self) {
+        for member in &mut self.members {
+            member.player.attack =
+                crate::combat::attacks::standard::get_attack_functions(&member.player);
+            member.player.spec =
+                crate::combat::attacks::specs::get_spec_attack_function(&member.player);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.config.logger.clear();
+        for (member, state) in self.members.iter_mut().zip(&mut self.states) {
+            if let Some(ref mut spec_config) = member.spec_config {
+                let restore_spec = state.spec_state.on_kill(&mut member.player, spec_config);
+                member.player.reset_current_stats(restore_spec);
+            } else {
+                member.player.reset_current_stats(true);
+            }
+        }
+        self.reset_states();
+    }
+
+    fn next_kill(&mut self, downtime_ticks: u32) {
+        for (member, state) in self.members.iter_mut().zip(&mut self.states) {
+            self.mechanics.prepare_next_kill(
+                &mut member.player,
+                member.spec_config.as_mut(),
+                &mut state.spec_state,
+                downtime_ticks,
+                &mut self.config.logger,
+            );
+        }
+        self.reset_states();
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.rng = SmallRng::seed_from_u64(seed);
+    }
+}
+
+/// Simulate `n` group fights, keeping each member's damage share.
+///
+/// Seeded runs reseed every fight with `derive_fight_seed`, like `simulate_n_fights`.
+pub fn simulate_n_group_fights(
+    fight: &mut GroupFight,
+    n: u32,
+    seed: Option<u64>,
+) -> Result<GroupResults, SimulationError> {
+    if fight.is_immune() {
+        return Err(SimulationError::MonsterImmune(
+            fight.monster.info.name.clone(),
+        ));
+    }
+    fight.set_attack_function();
+
+    let mut results = GroupResults {
+        fights: Vec::with_capacity(n as usize),
+    };
+    for i in 0..n {
+        if let Some(seed) = seed {
+            fight.set_seed(derive_fight_seed(seed, u64::from(i)));
+        }
+        results.fights.push(fight.simulate_group_fight()?);
+        fight.reset();
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::dps_calc::get_distribution;
+    use crate::calc::rolls::calc_active_player_rolls;
+    use crate::combat::simulation::simulate_n_fights;
+    use crate::combat::spec::{SpecRestorePolicy, SpecStrategy};
+    use crate::types::equipment::CombatStyle;
+    use crate::types::player::{GearSwitch, SwitchType};
+    use crate::utils::loadouts;
+
+    fn member(name: &str, monster: &Monster) -> GroupMember {
+        let mut player = loadouts::max_melee_player();
+        calc_active_player_rolls(&mut player, monster);
+        GroupMember::new(name, player)
+    }
+
+    fn group(size: usize, monster: &Monster, config: GroupConfig) -> GroupFight {
+        let members = (0..size)
+            .map(|i| member(&format!("Player {}", i + 1), monster))
+            .collect();
+        GroupFight::new(members, monster.clone(), config).unwrap()
+    }
+
+    #[test]
+    fn test_more_players_kill_faster() {
+        let monster = Monster::new("General Graardor", None).unwrap();
+        let solo = simulate_n_group_fights(
+            &mut group(1, &monster, GroupConfig::default()),
+            300,
+            Some(1),
+        )
+        .unwrap();
+        let trio = simulate_n_group_fights(
+            &mut group(3, &monster, GroupConfig::default()),
+            300,
+            Some(1),
+        )
+        .unwrap();
+
+        assert!(trio.avg_ttk() < solo.avg_ttk() / 2.0);
+        // Identical setups should split the damage roughly evenly
+        for share in trio.damage_shares() {
+            assert!((share - 1.0 / 3.0).abs() < 0.05);
+        }
+    }
+
+    #[test]
+    fn test_party_scaling() {
+        assert_eq!(PartyScaling::Toa.hp_multiplier(1), 1.0);
+        assert!((PartyScaling::Toa.hp_multiplier(4) - 3.4).abs() < 1e-9);
+        assert_eq!(PartyScaling::Tob.hp_multiplier(2), 0.75);
+        assert_eq!(PartyScaling::Tob.hp_multiplier(5), 1.0);
+
+        let monster = Monster::new("General Graardor", None).unwrap();
+        let config = GroupConfig {
+            party_scaling: PartyScaling::Linear(0.5),
+            ..GroupConfig::default()
+        };
+        let fight = group(3, &monster, config);
+        assert_eq!(
+            fight.monster().stats.hitpoints.current,
+            monster.stats.hitpoints.base * 2
+        );
+    }
+
+    #[test]
+    fn test_fixed_aggro_only_damages_tank() {
+        let monster = Monster::new("General Graardor", None).unwrap();
+        let config = GroupConfig {
+            aggro: AggroPolicy::Fixed(1),
+            monster_attacks: true,
+            seed: Some(3),
+            ..GroupConfig::default()
+        };
+        let mut fight = group(3, &monster, config);
+        fight.set_attack_function();
+        let result = fight.simulate_group_fight().unwrap();
+
+        assert!(result.members[1].damage_taken > 0);
+        assert_eq!(result.members[0].damage_taken, 0);
+        assert_eq!(result.members[2].damage_taken, 0);
+    }
+
+    #[test]
+    fn test_spec_drains_are_shared() {
+        let monster = Monster::new("General Graardor", None).unwrap();
+        let mut specer = member("Player 1", &monster);
+        let mut spec_player = specer.player.clone();
+        spec_player.equip("Dragon warhammer", None).unwrap();
+        spec_player.update_bonuses();
+        spec_player.set_active_style(CombatStyle::Pound);
+        calc_active_player_rolls(&mut spec_player, &monster);
+        let switch = GearSwitch::new(
+            SwitchType::Spec("Dragon warhammer".into()),
+            &spec_player,
+            &monster,
+        );
+        specer.player.switches.push(switch.clone());
+        specer.spec_config = Some(SpecConfig::new(
+            vec![SpecStrategy::builder(&switch).build()],
+            SpecRestorePolicy::RestoreEveryKill,
+            None,
+            false,
+        ));
+        let other = member("Player 2", &monster);
+
+        let config = GroupConfig {
+            seed: Some(1),
+            ..GroupConfig::default()
+        };
+        let mut fight = GroupFight::new(vec![specer, other], monster.clone(), config).unwrap();
+        fight.set_attack_function();
+        fight.simulate_group_fight().unwrap();
+
+        // The second member doesn't spec, so any drain came from the first member's DWH
+        assert!(fight.monster.stats.defence.current < monster.stats.defence.base);
+
+        // Same max hit against a lower defence, so a higher expected hit means a higher hit chance
+        let player = &fight.members[1].player;
+        let before = get_distribution(player, &monster, false).unwrap();
+        let after = get_distribution(player, &fight.monster, false).unwrap();
+        assert_eq!(after.get_max(), before.get_max());
+        assert!(after.get_expected_damage() > before.get_expected_damage());
+    }
+
+    #[test]
+    fn test_most_damage_aggro_hits_top_damage_member() {
+        let monster = Monster::new("General Graardor", None).unwrap();
+        let config = GroupConfig {
+            aggro: AggroPolicy::MostDamage,
+            seed: Some(1),
+            ..GroupConfig::default()
+        };
+        let mut fight = group(3, &monster, config);
+        fight.states[0].damage_dealt = 40;
+        fight.states[2].damage_dealt = 90;
+        assert_eq!(fight.choose_target(), Some(2));
+
+        // Damage moves the aggro, and dead members are skipped
+        fight.states[1].damage_dealt = 120;
+        assert_eq!(fight.choose_target(), Some(1));
+        fight.states[1].alive = false;
+        assert_eq!(fight.choose_target(), Some(2));
+    }
+
+    #[test]
+    fn test_random_aggro_spreads_attacks() {
+        let monster = Monster::new("General Graardor", None).unwrap();
+        let config = GroupConfig {
+            aggro: AggroPolicy::Random,
+            monster_attacks: true,
+            seed: Some(1),
+            ..GroupConfig::default()
+        };
+        let mut fight = group(3, &monster, config);
+        fight.set_attack_function();
+        let result = fight.simulate_group_fight().unwrap();
+
+        let hit_members = result
+            .members
+            .iter()
+            .filter(|member| member.damage_taken > 0)
+            .count();
+        assert!(hit_members > 1);
+    }
+
+    #[test]
+    fn test_runs_as_simulation() {
+        let monster = Monster::new("General Graardor", None).unwrap();
+        let fight = group(2, &monster, GroupConfig::default());
+        let results = simulate_n_fights(Box::new(fight), 50, false, Some(1)).unwrap();
+        assert_eq!(results.ttks_ticks.len(), 50);
+    }
+
+    #[test]
+    fn test_invalid_fixed_target() {
+        let monster = Monster::new("General Graardor", None).unwrap();
+        let config = GroupConfig {
+            aggro: AggroPolicy::Fixed(2),
+            ..GroupConfig::default()
+        };
+        let members = vec![member("Player 1", &monster), member("Player 2", &monster)];
+        assert!(matches!(
+            GroupFight::new(members, monster, config),
+            Err(SimulationError::ConfigError(_))
+        ));
+    }
+}
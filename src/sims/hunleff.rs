@@ -1,10 +1,16 @@
 use crate::combat::limiters::Limiter;
 use crate::combat::mechanics::Mechanics;
-use crate::combat::simulation::{FightResult, FightVars, Simulation};
+use crate::combat::prayer::PrayerDrainConfig;
+use crate::combat::simulation::{FightResult, FightVars, Simulation, seeded_rng};
+use crate::combat::spec::{CoreCondition, SpecCondition, SpecConfig, SpecState};
 use crate::constants;
 use crate::error::SimulationError;
+use crate::types::food::{Food, FoodType, Foods};
+use crate::types::inventory::Inventory;
 use crate::types::monster::{AttackType, Monster, MonsterMaxHit};
 use crate::types::player::{Player, SwitchType};
+use crate::types::potions::RedoseConfig;
+use crate::types::prayers::Prayer;
 use crate::utils::logging::FightLogger;
 use rand::Rng;
 use rand::SeedableRng;
@@ -19,8 +25,6 @@ const T0_MAX_HIT: u32 = 16;
 const T1_MAX_HIT: u32 = 13;
 const T2_MAX_HIT: u32 = 10;
 const T3_MAX_HIT: u32 = 8;
-const PADDLEFISH_HEAL: u32 = 20;
-const PADDLEFISH_DELAY: i32 = 3;
 const HUNLLEF_REGEN_TICKS: i32 = 100;
 const HUNLLEF_ATTACK_SPEED: i32 = 5;
 const ALLOWED_GEAR: [&str; 32] = [
@@ -60,20 +64,27 @@ const ALLOWED_GEAR: [&str; 32] = [
 
 #[derive(Debug, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
 pub struct HunllefConfig {
-    pub food_count: u32, // Only normal paddlefish for now
+    pub inventory: Inventory,
     pub eat_strategy: HunllefEatStrategy,
     pub redemption_strategy: Option<HunllefRedemptionStrat>,
     pub attack_strategy: AttackStrategy,
     pub lost_ticks: i32,
+    pub flick_success: f64, // Chance the right protection prayer is up in time for each attack
     pub logger: FightLogger,
-    pub armor_tier: u32,
+    pub armor_tier: Option<u32>, // Corrupted armour tier (0-3), read from the player's gear if None
     pub only_success_stats: bool,
+    pub prayer_drain: Option<PrayerDrainConfig>,
+    pub redose: Option<RedoseConfig>,
+    pub seed: Option<u64>,
 }
 
 impl Default for HunllefConfig {
     fn default() -> Self {
         Self {
-            food_count: 20,
+            inventory: Inventory::builder()
+                .food(Foods::Paddlefish, 20)
+                .build()
+                .expect("Error building inventory."),
             eat_strategy: HunllefEatStrategy::EatAtHp(50),
             redemption_strategy: None,
             attack_strategy: AttackStrategy::TwoT3Weapons {
@@ -81,9 +92,13 @@ impl Default for HunllefConfig {
                 style2: SwitchType::Magic,
             },
             lost_ticks: 0,
-            logger: FightLogger::new(false, "hunllef").expect("Error initializing logger."),
-            armor_tier: 0,
+            flick_success: 1.0,
+            logger: FightLogger::new(false),
+            armor_tier: None,
             only_success_stats: true,
+            prayer_drain: None,
+            redose: None,
+            seed: None,
         }
     }
 }
@@ -115,8 +130,41 @@ pub enum HunllefRedemptionStrat {
     NoFoodLeft(u32),   // Redemption only if out of food
 }
 
+// Spec conditions specific to the Corrupted Hunllef fight
+#[derive(Debug, Clone, PartialEq)]
+pub enum HunllefCondition {
+    Core(CoreCondition),
+    NotInTornadoPhase,        // No tornadoes are currently active
+    HunllefStyle(AttackType), // Hunllef is currently using this attack style
+    FoodAbove(u32),           // More than this many food items remain
+}
+
+impl SpecCondition for HunllefCondition {
+    type BossState = HunllefState;
+
+    fn evaluate(&self, player: &Player, monster: &Monster, boss_state: &Self::BossState) -> bool {
+        match self {
+            Self::Core(core) => core.evaluate(player, monster, &()),
+            Self::NotInTornadoPhase => boss_state.tornado_timer == 0,
+            Self::HunllefStyle(style) => boss_state.current_style() == *style,
+            Self::FoodAbove(count) => boss_state.inventory.food_count() > *count,
+        }
+    }
+
+    fn as_core(&self) -> Option<&CoreCondition> {
+        match self {
+            Self::Core(core) => Some(core),
+            _ => None,
+        }
+    }
+
+    fn from_core(core: CoreCondition) -> Self {
+        Self::Core(core)
+    }
+}
+
 #[derive(Debug, Clone)]
-struct HunllefState {
+pub struct HunllefState {
     hunllef_attack_tick: i32,
     tornado_chance: u32,
     tornado_cd: u32,
@@ -124,7 +172,7 @@ struct HunllefState {
     player_attack_count: u32,
     hunllef_attack_count: u32,
     queued_damage: Option<u32>,
-    food_count: u32,
+    inventory: Inventory,
 }
 
 impl Default for HunllefState {
@@ -137,11 +185,23 @@ impl Default for HunllefState {
             player_attack_count: 0,
             hunllef_attack_count: 0,
             queued_damage: None,
-            food_count: 20,
+            inventory: Inventory::default(),
+        }
+    }
+}
+
+impl HunllefState {
+    // Hunllef alternates attack styles every 4 attacks, starting with ranged
+    fn current_style(&self) -> AttackType {
+        if (self.hunllef_attack_count / 4).is_multiple_of(2) {
+            AttackType::Ranged
+        } else {
+            AttackType::Magic
         }
     }
 }
 
+#[derive(Clone)]
 struct HunllefMechanics;
 
 impl Mechanics for HunllefMechanics {}
@@ -246,20 +306,18 @@ impl HunllefMechanics {
         rng: &mut SmallRng,
     ) -> Result<(), SimulationError> {
         // Choose Hunllef's attack style, alternating every 4 attacks (starting with ranged)
-        let hunllef_style = if (state.hunllef_attack_count / 4).is_multiple_of(2) {
-            AttackType::Ranged
-        } else {
-            AttackType::Magic
-        };
-        let mut hit = hunllef.attack(player, Some(hunllef_style), rng, false)?;
-
-        // Damage is reduced after it is rolled
-        // Armor reduction occurs first, then protection prayers (source: Mod Arcane in Summit Blue)
-        let base_damage = hit.damage;
-        let armor_reduced = base_damage * (6 - config.armor_tier) / 6;
-        hit.damage = armor_reduced * 10 / 41; // Prayer reduction is 10/41 (source: Mod Ash tweet)
-        let hp_capped_damage = min(hit.damage, player.stats.hitpoints.current);
-        hit.damage = hp_capped_damage;
+        let hunllef_style = state.current_style();
+        player.set_protection_prayer(Prayer::protection_against(hunllef_style));
+        // Roll the hit unprotected, since the armour reduction comes before the prayer's
+        let mut hit = hunllef.attack_with_flick(player, Some(hunllef_style), rng, false, 0.0)?;
+        let prayed = hit.success && rng.random_bool(config.flick_success);
+        let damage = reduce_hunllef_damage(
+            hunllef,
+            hit.damage,
+            config.armor_tier.unwrap_or_default(),
+            prayed,
+        );
+        hit.damage = min(damage, player.stats.hitpoints.current);
 
         if config.logger.enabled {
             config.logger.log_monster_attack(
@@ -287,49 +345,54 @@ impl HunllefMechanics {
         eat_strategy: &HunllefEatStrategy,
         hunllef_max: u32,
         logger: &mut FightLogger,
-    ) {
-        // Handle eating based on set strategy
-        match eat_strategy {
+    ) -> bool {
+        // Handle eating based on set strategy, returning false if the player needed to eat
+        // but had no food left
+        let next_heal = state
+            .inventory
+            .next_food(&[
+                FoodType::Normal,
+                FoodType::MultiBite,
+                FoodType::DelayedHeal,
+                FoodType::Combo,
+            ])
+            .and_then(Food::from_db)
+            .map(|food| food.bite_heal(player));
+        let needs_food = match eat_strategy {
             HunllefEatStrategy::EatAtHp(threshold) => {
-                // Eat if at or below the provided threshold and force the player to skip the next attack
-                if (player.stats.hitpoints.current <= *threshold
-                    || player.stats.hitpoints.current <= hunllef_max)
-                    && vars.eat_delay == 0
-                    && state.food_count > 0
-                {
-                    self.eat_food(player, PADDLEFISH_HEAL, None, vars, logger);
-                    state.food_count -= 1;
-                    vars.attack_tick += PADDLEFISH_DELAY;
-                }
+                player.stats.hitpoints.current <= *threshold
+                    || player.stats.hitpoints.current <= hunllef_max
             }
             HunllefEatStrategy::TickEatOnly => {
-                if state.queued_damage.is_some()
-                    && player.stats.hitpoints.current <= hunllef_max
-                    && vars.eat_delay == 0
-                    && state.food_count > 0
-                {
-                    self.eat_food(player, PADDLEFISH_HEAL, None, vars, logger);
-                    state.food_count -= 1;
-                    vars.attack_tick += PADDLEFISH_DELAY;
-                }
+                state.queued_damage.is_some() && player.stats.hitpoints.current <= hunllef_max
             }
             HunllefEatStrategy::EatToFullDuringNadoes => {
-                if ((state.tornado_timer > 0
-                    && player.stats.hitpoints.base - player.stats.hitpoints.current
-                        >= PADDLEFISH_HEAL)
-                    || player.stats.hitpoints.current <= hunllef_max)
-                    && vars.eat_delay == 0
-                    && state.food_count > 0
-                {
-                    self.eat_food(player, PADDLEFISH_HEAL, None, vars, logger);
-                    state.food_count -= 1;
-                    vars.attack_tick += PADDLEFISH_DELAY;
-                }
+                (state.tornado_timer > 0
+                    && next_heal.is_some_and(|heal| {
+                        player.stats.hitpoints.base - player.stats.hitpoints.current >= heal
+                    }))
+                    || player.stats.hitpoints.current <= hunllef_max
             }
+        };
+
+        if !needs_food {
+            return true;
         }
+        if !state.inventory.has_food() {
+            return false;
+        }
+
+        // Eating forces the player to skip the next attack
+        if let Some(attack_delay) =
+            self.eat_next_food(player, &mut state.inventory, false, vars, logger)
+        {
+            vars.attack_tick += attack_delay;
+        }
+        true
     }
 }
 
+#[derive(Clone)]
 pub struct HunllefFight {
     player: Player,
     hunllef: Monster,
@@ -337,13 +400,30 @@ pub struct HunllefFight {
     rng: SmallRng,
     config: HunllefConfig,
     mechanics: HunllefMechanics,
+    spec_config: Option<SpecConfig<HunllefCondition>>,
+    spec_state: SpecState,
+    inventory: Inventory,
 }
 
 impl HunllefFight {
-    pub fn new(player: Player, mut config: HunllefConfig) -> Result<HunllefFight, SimulationError> {
+    pub fn new(
+        player: Player,
+        mut config: HunllefConfig,
+        spec_config: Option<SpecConfig<HunllefCondition>>,
+    ) -> Result<HunllefFight, SimulationError> {
         if !has_valid_gear(&player) {
             return Err(SimulationError::InvalidGauntletGear);
         }
+        if !(0.0..=1.0).contains(&config.flick_success) {
+            return Err(SimulationError::ConfigError(
+                "Flick success must be between 0 and 1.".to_string(),
+            ));
+        }
+        if config.armor_tier.is_some_and(|tier| tier > 3) {
+            return Err(SimulationError::ConfigError(
+                "Armor tier must be between 0 and 3.".to_string(),
+            ));
+        }
         let mut hunllef = Monster::new("Corrupted Hunllef", None)
             .map_err(|_| SimulationError::MonsterCreationError("Corrupted Hunllef".to_string()))?;
         hunllef.max_hits = Some(vec![
@@ -352,8 +432,9 @@ impl HunllefFight {
         ]);
 
         let limiter = crate::combat::simulation::assign_limiter(&player, &hunllef);
-        let rng = SmallRng::from_os_rng();
-        config.armor_tier = armor_tier(&player);
+        let rng = seeded_rng(config.seed);
+        config.armor_tier.get_or_insert_with(|| armor_tier(&player));
+        let inventory = config.inventory.clone();
         Ok(HunllefFight {
             player,
             hunllef,
@@ -361,16 +442,78 @@ impl HunllefFight {
             rng,
             config,
             mechanics: HunllefMechanics,
+            spec_config,
+            spec_state: SpecState::default(),
+            inventory,
         })
     }
 
+    /// Most damage a prayed Hunllef attack can deal through the player's armour.
+    pub fn max_hit(&self) -> u32 {
+        match self.config.armor_tier {
+            Some(1) => T1_MAX_HIT,
+            Some(2) => T2_MAX_HIT,
+            Some(3) => T3_MAX_HIT,
+            _ => T0_MAX_HIT,
+        }
+    }
+
+    // Whether a NoFoodLeft redemption strategy still has procs to use once food runs out
+    fn redemption_without_food(&self, vars: &FightVars) -> bool {
+        matches!(
+            self.config.redemption_strategy,
+            Some(HunllefRedemptionStrat::NoFoodLeft(max_procs)) if vars.redemption_procs < max_procs
+        )
+    }
+
+    // Use a special attack if any strategy's conditions are met, otherwise attack normally
+    fn player_attack_or_spec(
+        &mut self,
+        state: &HunllefState,
+        vars: &mut FightVars,
+    ) -> Result<(), SimulationError> {
+        let did_spec = if let Some(ref mut spec_config) = self.spec_config {
+            self.mechanics.player_special_attack(
+                &mut self.player,
+                &mut self.hunllef,
+                spec_config,
+                &mut self.spec_state,
+                state,
+                &mut self.rng,
+                &self.limiter,
+                vars,
+                &mut self.config.logger,
+            )?
+        } else {
+            false
+        };
+
+        if !did_spec {
+            self.mechanics.player_attack(
+                &mut self.player,
+                &mut self.hunllef,
+                &mut self.rng,
+                &self.limiter,
+                vars,
+                &mut self.config.logger,
+            );
+        }
+
+        Ok(())
+    }
+
     fn simulate_hunllef_fight(&mut self) -> Result<FightResult, SimulationError> {
+        if let Some(ref spec_config) = self.spec_config
+            && let Err(e) = spec_config.validate()
+        {
+            return Err(SimulationError::ConfigError(e));
+        }
+
         let mut vars = FightVars::new();
         let mut state = HunllefState {
-            food_count: self.config.food_count,
+            inventory: self.inventory.clone(),
             ..HunllefState::default()
         };
-        state.food_count = self.config.food_count;
         vars.attack_tick += self.config.lost_ticks;
 
         let logger_enabled = self.config.logger.enabled;
@@ -383,12 +526,7 @@ impl HunllefFight {
 
         let attack_strategy = self.config.attack_strategy.clone();
 
-        let hunllef_max = match self.config.armor_tier {
-            1 => T1_MAX_HIT,
-            2 => T2_MAX_HIT,
-            3 => T3_MAX_HIT,
-            _ => T0_MAX_HIT,
-        };
+        let hunllef_max = self.max_hit();
 
         match &attack_strategy {
             AttackStrategy::TwoT3Weapons { style1, style2 } => {
@@ -441,7 +579,7 @@ impl HunllefFight {
                                 && self.player.stats.hitpoints.current > hunllef_max => {}
                         _ => {
                             // Handle eating based on set strategy
-                            self.mechanics.handle_eating(
+                            let fed = self.mechanics.handle_eating(
                                 &mut state,
                                 &mut vars,
                                 &mut self.player,
@@ -449,6 +587,15 @@ impl HunllefFight {
                                 hunllef_max,
                                 &mut self.config.logger,
                             );
+
+                            // Out of food is only a failure once there are no redemption procs to fall back on
+                            if !fed && !self.redemption_without_food(&vars) {
+                                return self.mechanics.process_supply_out(
+                                    &vars,
+                                    &self.hunllef,
+                                    &mut self.config.logger,
+                                );
+                            }
                         }
                     }
 
@@ -477,7 +624,8 @@ impl HunllefFight {
                                 );
                             }
                             Some(HunllefRedemptionStrat::NoFoodLeft(max_procs))
-                                if vars.redemption_procs < max_procs && state.food_count == 0 =>
+                                if vars.redemption_procs < max_procs
+                                    && !state.inventory.has_food() =>
                             {
                                 vars.redemption_procs += 1;
                                 self.mechanics.process_redemption(
@@ -490,15 +638,32 @@ impl HunllefFight {
                         }
                     }
 
-                    if vars.tick_counter == vars.attack_tick {
-                        self.mechanics.player_attack(
+                    if let Some(prayer_config) = self.config.prayer_drain {
+                        let attacking = vars.tick_counter == vars.attack_tick;
+                        self.mechanics.process_prayer(
                             &mut self.player,
-                            &mut self.hunllef,
-                            &mut self.rng,
-                            &self.limiter,
+                            &self.hunllef,
+                            &prayer_config,
+                            &mut state.inventory,
+                            attacking,
+                            &mut vars,
+                            &mut self.config.logger,
+                        )?;
+                    }
+
+                    if let Some(redose) = self.config.redose {
+                        self.mechanics.process_redose(
+                            &mut self.player,
+                            &self.hunllef,
+                            &redose,
+                            &mut state.inventory,
                             &mut vars,
                             &mut self.config.logger,
                         );
+                    }
+
+                    if vars.tick_counter == vars.attack_tick {
+                        self.player_attack_or_spec(&state, &mut vars)?;
 
                         // Increment attack count and switch styles every six attacks
                         state.player_attack_count += 1;
@@ -538,6 +703,14 @@ impl HunllefFight {
                         }
                     }
 
+                    self.mechanics.process_spec_timers(
+                        &mut self.player,
+                        self.spec_config.as_ref(),
+                        &mut self.spec_state,
+                        &vars,
+                        &mut self.config.logger,
+                    );
+
                     // Increment tick counter
                     vars.tick_counter += 1;
 
@@ -604,7 +777,7 @@ impl HunllefFight {
                                 && self.player.stats.hitpoints.current > hunllef_max => {}
                         _ => {
                             // Handle eating based on set strategy
-                            self.mechanics.handle_eating(
+                            let fed = self.mechanics.handle_eating(
                                 &mut state,
                                 &mut vars,
                                 &mut self.player,
@@ -612,6 +785,15 @@ impl HunllefFight {
                                 hunllef_max,
                                 &mut self.config.logger,
                             );
+
+                            // Out of food is only a failure once there are no redemption procs to fall back on
+                            if !fed && !self.redemption_without_food(&vars) {
+                                return self.mechanics.process_supply_out(
+                                    &vars,
+                                    &self.hunllef,
+                                    &mut self.config.logger,
+                                );
+                            }
                         }
                     }
 
@@ -640,7 +822,8 @@ impl HunllefFight {
                                 );
                             }
                             Some(HunllefRedemptionStrat::NoFoodLeft(max_procs))
-                                if vars.redemption_procs < max_procs && state.food_count == 0 =>
+                                if vars.redemption_procs < max_procs
+                                    && !state.inventory.has_food() =>
                             {
                                 vars.redemption_procs += 1;
                                 self.mechanics.process_redemption(
@@ -653,15 +836,32 @@ impl HunllefFight {
                         }
                     }
 
-                    if vars.tick_counter == vars.attack_tick {
-                        self.mechanics.player_attack(
+                    if let Some(prayer_config) = self.config.prayer_drain {
+                        let attacking = vars.tick_counter == vars.attack_tick;
+                        self.mechanics.process_prayer(
                             &mut self.player,
-                            &mut self.hunllef,
-                            &mut self.rng,
-                            &self.limiter,
+                            &self.hunllef,
+                            &prayer_config,
+                            &mut state.inventory,
+                            attacking,
+                            &mut vars,
+                            &mut self.config.logger,
+                        )?;
+                    }
+
+                    if let Some(redose) = self.config.redose {
+                        self.mechanics.process_redose(
+                            &mut self.player,
+                            &self.hunllef,
+                            &redose,
+                            &mut state.inventory,
                             &mut vars,
                             &mut self.config.logger,
                         );
+                    }
+
+                    if vars.tick_counter == vars.attack_tick {
+                        self.player_attack_or_spec(&state, &mut vars)?;
 
                         // Increment attack count and switch to melee every 5 attacks
                         state.player_attack_count += 1;
@@ -712,6 +912,14 @@ impl HunllefFight {
                         }
                     }
 
+                    self.mechanics.process_spec_timers(
+                        &mut self.player,
+                        self.spec_config.as_ref(),
+                        &mut self.spec_state,
+                        &vars,
+                        &mut self.config.logger,
+                    );
+
                     // Increment tick counter
                     vars.tick_counter += 1;
 
@@ -726,6 +934,9 @@ impl HunllefFight {
             }
         }
 
+        // Keep whatever supplies are left for the next kill of a trip
+        self.inventory = state.inventory;
+
         let remove_final_attack_delay = true;
         self.mechanics.get_fight_result(
             &self.hunllef,
@@ -755,11 +966,49 @@ impl Simulation for HunllefFight {
 
     fn set_attack_function(&mut self) {
         self.player.attack = crate::combat::attacks::standard::get_attack_functions(&self.player);
+        self.player.spec = crate::combat::attacks::specs::get_spec_attack_function(&self.player);
     }
 
     fn reset(&mut self) {
-        self.player.reset_current_stats(true);
+        self.config.logger.clear();
+        if let Some(ref mut spec_config) = self.spec_config {
+            let restore_spec = self.spec_state.on_kill(&mut self.player, spec_config);
+            self.player.reset_current_stats(restore_spec);
+        } else {
+            self.player.reset_current_stats(true);
+        }
         self.hunllef.reset();
+        self.inventory = self.config.inventory.clone();
+    }
+
+    fn next_kill(&mut self, downtime_ticks: u32) {
+        self.mechanics.prepare_next_kill(
+            &mut self.player,
+            self.spec_config.as_mut(),
+            &mut self.spec_state,
+            downtime_ticks,
+            &mut self.config.logger,
+        );
+        self.hunllef.reset();
+    }
+
+    fn inventory(&self) -> Option<&Inventory> {
+        Some(&self.inventory)
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.rng = SmallRng::seed_from_u64(seed);
+    }
+}
+
+// The armour reduces the hit first, then Hunllef pierces protection prayers with 10/41 of what's
+// left (per PRAYER_PIERCING_MONSTERS). Source: Mod Arcane in Summit Blue
+fn reduce_hunllef_damage(hunllef: &Monster, damage: u32, armor_tier: u32, prayed: bool) -> u32 {
+    let armor_reduced = damage * (6 - armor_tier) / 6;
+    if prayed {
+        hunllef.protected_damage(armor_reduced)
+    } else {
+        armor_reduced
     }
 }
 
@@ -822,7 +1071,7 @@ mod tests {
     use crate::types::prayers::Prayer;
     use crate::types::stats::Stat;
     use std::collections::HashMap;
-    use std::rc::Rc;
+    use std::sync::Arc;
 
     #[test]
     fn test_hunllef_sim() {
@@ -855,7 +1104,7 @@ mod tests {
 
         let ranged_switch = GearSwitch::from(&player);
 
-        Rc::make_mut(&mut player.gear).weapon = Weapon::default();
+        Arc::make_mut(&mut player.gear).weapon = Weapon::default();
         player.update_bonuses();
         player.set_active_style(CombatStyle::Kick);
         player.add_prayer(Prayer::Piety);
@@ -876,11 +1125,14 @@ mod tests {
         //         style2: SwitchType::Ranged,
         //     },
         //     lost_ticks: 0,
-        //     logger: FightLogger::new(false, "hunllef"),
+        //     logger: FightLogger::new(false),
         // };
 
         let fight_config = HunllefConfig {
-            food_count: 20,
+            inventory: Inventory::builder()
+                .food(Foods::Paddlefish, 20)
+                .build()
+                .expect("Error building inventory."),
             eat_strategy: HunllefEatStrategy::EatAtHp(15),
             redemption_strategy: None,
             attack_strategy: AttackStrategy::FiveToOne {
@@ -889,13 +1141,17 @@ mod tests {
                 other_style2: SwitchType::Melee,
             },
             lost_ticks: 0,
-            logger: FightLogger::new(false, "hunllef").expect("Error initializing logger."),
-            armor_tier: 0,
+            flick_success: 1.0,
+            logger: FightLogger::new(false),
+            armor_tier: None,
             only_success_stats: true,
+            prayer_drain: None,
+            redose: None,
+            seed: None,
         };
 
         let mut fight =
-            HunllefFight::new(player, fight_config).expect("Error setting up Hunllef fight.");
+            HunllefFight::new(player, fight_config, None).expect("Error setting up Hunllef fight.");
 
         let result = fight.simulate();
 
@@ -937,6 +1193,61 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_protection_prayer_reduces_once() {
+        let max_hit = |flick_success: f64| {
+            let mut player = Player::new();
+            player.stats.hitpoints = Stat::new(99, None);
+            let mut hunllef = Monster::new("Corrupted Hunllef", None).unwrap();
+            hunllef.max_hits = Some(vec![
+                MonsterMaxHit::new(HUNLLEF_MAX_HIT, AttackType::Ranged),
+                MonsterMaxHit::new(HUNLLEF_MAX_HIT, AttackType::Magic),
+            ]);
+            let mut state = HunllefState::default();
+            let mut config = HunllefConfig {
+                flick_success,
+                ..HunllefConfig::default()
+            };
+            let mut vars = FightVars::new();
+            let mut rng = SmallRng::seed_from_u64(1);
+
+            (0..500)
+                .map(|_| {
+                    HunllefMechanics
+                        .hunllef_attack(
+                            &mut hunllef,
+                            &mut player,
+                            &mut state,
+                            &mut config,
+                            &mut vars,
+                            &mut rng,
+                        )
+                        .unwrap();
+                    state.queued_damage.take().unwrap()
+                })
+                .max()
+                .unwrap()
+        };
+
+        // Prayed hits go through the 10/41 reduction once, so they can still reach the T0 max hit
+        assert_eq!(max_hit(1.0), T0_MAX_HIT);
+        assert!(max_hit(0.0) > T0_MAX_HIT);
+    }
+
+    #[test]
+    fn test_armor_reduces_before_prayer() {
+        let hunllef = Monster::new("Corrupted Hunllef", None).unwrap();
+
+        // Reducing for the prayer first would round these to 5 and 5 instead
+        assert_eq!(reduce_hunllef_damage(&hunllef, 30, 1, true), 6);
+        assert_eq!(reduce_hunllef_damage(&hunllef, 25, 1, true), 4);
+        assert_eq!(reduce_hunllef_damage(&hunllef, 30, 1, false), 25);
+        assert_eq!(
+            reduce_hunllef_damage(&hunllef, HUNLLEF_MAX_HIT, 3, true),
+            T3_MAX_HIT
+        );
+    }
+
     #[test]
     fn test_armor_tier() {
         let mut player = Player::new();
@@ -969,4 +1280,28 @@ mod tests {
         player.equip("Crystal legs (basic)", None).unwrap();
         assert_eq!(armor_tier(&player), 2);
     }
+
+    #[test]
+    fn test_hunllef_conditions() {
+        let player = Player::new();
+        let hunllef = Monster::new("Corrupted Hunllef", None).expect("Error creating monster.");
+        let mut state = HunllefState::default();
+
+        assert!(HunllefCondition::NotInTornadoPhase.evaluate(&player, &hunllef, &state));
+        assert!(
+            HunllefCondition::HunllefStyle(AttackType::Ranged).evaluate(&player, &hunllef, &state)
+        );
+
+        state.tornado_timer = TORNADO_MAX_TIMER;
+        state.hunllef_attack_count = 4;
+        assert!(!HunllefCondition::NotInTornadoPhase.evaluate(&player, &hunllef, &state));
+        assert!(
+            HunllefCondition::HunllefStyle(AttackType::Magic).evaluate(&player, &hunllef, &state)
+        );
+        assert!(!HunllefCondition::FoodAbove(0).evaluate(&player, &hunllef, &state));
+
+        state.inventory = HunllefConfig::default().inventory;
+        assert!(!HunllefCondition::FoodAbove(20).evaluate(&player, &hunllef, &state));
+        assert!(HunllefCondition::FoodAbove(19).evaluate(&player, &hunllef, &state));
+    }
 }
@@ -1,4 +1,11 @@
 pub mod graardor;
+pub mod group;
 pub mod hunleff;
+pub mod muspah;
+pub mod olm;
+pub mod scripted;
 pub mod single_way;
+pub mod toa;
 pub mod vardorvis;
+pub mod vorkath;
+pub mod zulrah;
@@ -0,0 +1,1111 @@
+use crate::calc::rolls::monster_def_rolls;
+use crate::combat::limiters::Limiter;
+use crate::combat::mechanics::{Hazard, Mechanics, handle_recoil};
+use crate::combat::prayer::{PrayerDrainConfig, PrayerOutBehaviour};
+use crate::combat::simulation::{FightResult, FightVars, Simulation, assign_limiter, seeded_rng};
+use crate::combat::spec::{CoreCondition, SpecCondition, SpecConfig, SpecState};
+use crate::constants;
+use crate::error::{PlayerError, SimulationError};
+use crate::types::food::Foods;
+use crate::types::inventory::Inventory;
+use crate::types::monster::{AttackType, Monster, MonsterMaxHit};
+use crate::types::player::{GearSwitch, Player, SwitchType};
+use crate::types::potions::{Potion, RedoseConfig};
+use crate::types::prayers::Prayer;
+use crate::utils::logging::FightLogger;
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+const MUSPAH_REGEN_TICKS: i32 = 100;
+
+// Max hits for each form, overriding the shockwave and unlabelled entries in the database
+const RANGED_MAX_HIT: u32 = 61;
+const MAGIC_MAX_HIT: u32 = 72;
+const MELEE_MAX_HIT: u32 = 34;
+const SHIELDED_RANGED_MAX_HIT: u32 = 72;
+const SHIELDED_MAGIC_MAX_HIT: u32 = 79;
+const SHOCKWAVE_MAX_HIT: u32 = 80;
+
+// The pre-shield phase alternates between the ranged and melee forms after a set number of attacks
+const RANGED_FORM_ATTACKS: i32 = 5;
+const MELEE_FORM_ATTACKS: i32 = 5;
+const MAGIC_BLAST_CHANCE: f64 = 0.2; // Chance of a ranged form attack being a magic blast instead
+
+// The Muspah can't be attacked while changing phase
+const PHASE_TRANSITION_TICKS: i32 = 4;
+
+// The shield drains the player's prayer while it's up, and releases a shockwave if it isn't broken in time
+const SHIELD_PRAYER_DRAIN_TICKS: i32 = 2;
+const SHIELD_SHOCKWAVE_TICKS: i32 = 50;
+
+// Smite drains the Muspah's prayer (its shield) by a quarter of each hit
+const SMITE_SHIELD_DIVISOR: u32 = 4;
+
+// Drain effects of the prayers the player keeps up on top of their offensive prayers
+const PROTECTION_DRAIN_EFFECT: u32 = 12;
+const SMITE_DRAIN_EFFECT: u32 = 18;
+
+// Post-shield specials alternate between spikes and darkness, every few attacks
+const SPECIAL_ATTACKS: i32 = 4;
+const SPIKE_TICKS: i32 = 12;
+const DARKNESS_TICKS: i32 = 10;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MuspahPhase {
+    Ranged,     // Ranged attacks and magic blasts from a distance
+    Melee,      // Walks up to the player and stabs
+    Shielded,   // Prayer-drain shield, broken by damage or skipped entirely by smiting earlier
+    PostShield, // Full HP again, with spike and darkness specials
+}
+
+impl MuspahPhase {
+    // Index of the monster whose stats this phase uses
+    fn monster_index(&self) -> usize {
+        match self {
+            Self::Ranged => 0,
+            Self::Melee => 1,
+            Self::Shielded => 2,
+            Self::PostShield => 3,
+        }
+    }
+
+    fn is_pre_shield(&self) -> bool {
+        matches!(self, Self::Ranged | Self::Melee)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum MuspahSpecial {
+    Spikes,   // Spikes grow from the ground, costing attacks while the player moves around them
+    Darkness, // The Muspah teleports away and lightning clouds fill the arena
+}
+
+/// Gear switch to attack with in each phase of the fight.
+#[derive(Debug, PartialEq, Clone)]
+pub struct MuspahSwitches {
+    pub ranged_form: SwitchType,
+    pub melee_form: SwitchType,
+    pub shielded: SwitchType,
+    pub post_shield: SwitchType,
+}
+
+impl Default for MuspahSwitches {
+    fn default() -> Self {
+        // Each form is weakest to the opposite style from the one it attacks with
+        Self {
+            ranged_form: SwitchType::Ranged,
+            melee_form: SwitchType::Magic,
+            shielded: SwitchType::Ranged,
+            post_shield: SwitchType::Ranged,
+        }
+    }
+}
+
+impl MuspahSwitches {
+    pub fn switch_for(&self, phase: MuspahPhase) -> &SwitchType {
+        match phase {
+            MuspahPhase::Ranged => &self.ranged_form,
+            MuspahPhase::Melee => &self.melee_form,
+            MuspahPhase::Shielded => &self.shielded,
+            MuspahPhase::PostShield => &self.post_shield,
+        }
+    }
+
+    fn switch_types(&self) -> [&SwitchType; 4] {
+        [
+            &self.ranged_form,
+            &self.melee_form,
+            &self.shielded,
+            &self.post_shield,
+        ]
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct MuspahConfig {
+    pub inventory: Inventory,
+    pub eat_hp: u32,
+    pub switches: MuspahSwitches,
+    pub smite_skip: bool, // Smite during the pre-shield phase until the shield is fully drained
+    pub prayer: PrayerDrainConfig, // Flick policy applies to offensive prayers; protection prayers are always on
+    pub flick_success: f64,        // Chance the protection prayer is up in time for each attack
+    pub movement_efficiency: f64,  // Chance of getting each attack off while moving around spikes
+    pub spikes: Option<Hazard>,    // Rolled once per spike special
+    pub lightning: Option<Hazard>, // Rolled once per darkness special
+    pub redose: Option<RedoseConfig>,
+    pub logger: FightLogger,
+    pub seed: Option<u64>,
+}
+
+impl Default for MuspahConfig {
+    fn default() -> Self {
+        Self {
+            inventory: Inventory::builder()
+                .food(Foods::Shark, 18)
+                .potion(Potion::PrayerPotion, 6)
+                .build()
+                .expect("Error building inventory."),
+            eat_hp: 50,
+            switches: MuspahSwitches::default(),
+            smite_skip: false,
+            prayer: PrayerDrainConfig::default(),
+            flick_success: 1.0,
+            movement_efficiency: 0.8,
+            spikes: Some(Hazard {
+                chance: 0.15,
+                max_hit: 30,
+            }),
+            lightning: Some(Hazard {
+                chance: 0.3,
+                max_hit: 20,
+            }),
+            redose: None,
+            logger: FightLogger::new(false),
+            seed: None,
+        }
+    }
+}
+
+// Spec conditions specific to the Muspah fight
+#[derive(Debug, Clone, PartialEq)]
+pub enum MuspahCondition {
+    Core(CoreCondition),
+    Phase(MuspahPhase), // The Muspah is currently in this phase
+}
+
+impl SpecCondition for MuspahCondition {
+    type BossState = MuspahState;
+
+    fn evaluate(&self, player: &Player, monster: &Monster, boss_state: &Self::BossState) -> bool {
+        match self {
+            Self::Core(core) => core.evaluate(player, monster, &()),
+            Self::Phase(phase) => boss_state.phase == *phase,
+        }
+    }
+
+    fn as_core(&self) -> Option<&CoreCondition> {
+        match self {
+            Self::Core(core) => Some(core),
+            _ => None,
+        }
+    }
+
+    fn from_core(core: CoreCondition) -> Self {
+        Self::Core(core)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MuspahState {
+    tick_counter: i32,
+    phase: MuspahPhase,
+    targetable_tick: i32,
+    muspah_attack_tick: i32,
+    attack_count: i32, // Attacks since the last form change or special
+    shockwave_tick: i32,
+    special: Option<(MuspahSpecial, i32)>, // Active special and the tick it ends
+    next_special: MuspahSpecial,
+    smiting: bool,
+}
+
+impl Default for MuspahState {
+    fn default() -> Self {
+        Self {
+            tick_counter: 0,
+            phase: MuspahPhase::Ranged,
+            targetable_tick: 0,
+            muspah_attack_tick: 0,
+            attack_count: 0,
+            shockwave_tick: 0,
+            special: None,
+            next_special: MuspahSpecial::Spikes,
+            smiting: false,
+        }
+    }
+}
+
+impl MuspahState {
+    fn is_targetable(&self) -> bool {
+        self.tick_counter >= self.targetable_tick
+            && !matches!(self.special, Some((MuspahSpecial::Darkness, _)))
+    }
+
+    fn is_dodging_spikes(&self) -> bool {
+        matches!(self.special, Some((MuspahSpecial::Spikes, _)))
+    }
+}
+
+#[derive(Clone)]
+struct MuspahMechanics;
+
+impl Mechanics for MuspahMechanics {}
+
+impl MuspahMechanics {
+    fn muspah_attack(
+        &self,
+        muspah: &mut Monster,
+        player: &mut Player,
+        state: &mut MuspahState,
+        config: &mut MuspahConfig,
+        vars: &mut FightVars,
+        rng: &mut SmallRng,
+    ) -> Result<(), SimulationError> {
+        let style = match state.phase {
+            MuspahPhase::Ranged if rng.random_bool(MAGIC_BLAST_CHANCE) => AttackType::Magic,
+            MuspahPhase::Ranged => AttackType::Ranged,
+            MuspahPhase::Melee => AttackType::Stab,
+            MuspahPhase::Shielded | MuspahPhase::PostShield => {
+                if rng.random_bool(0.5) {
+                    AttackType::Magic
+                } else {
+                    AttackType::Ranged
+                }
+            }
+        };
+
+        // The player prays against every attack while they have prayer points left
+        let flick_success = if player.stats.prayer.current > 0 {
+            player.set_protection_prayer(Prayer::protection_against(style));
+            config.flick_success
+        } else {
+            0.0
+        };
+        let hit = muspah.attack_with_flick(player, Some(style), rng, false, flick_success)?;
+
+        if config.logger.enabled {
+            config.logger.log_monster_attack(
+                muspah,
+                vars.tick_counter,
+                hit.damage,
+                hit.success,
+                Some(style),
+            );
+        }
+
+        if hit.success {
+            player.take_damage(hit.damage);
+            vars.damage_taken += hit.damage;
+            handle_recoil(player, muspah, &hit, vars, &mut config.logger);
+        }
+
+        state.attack_count += 1;
+        state.muspah_attack_tick += muspah.info.attack_speed.unwrap_or(5) as i32;
+
+        Ok(())
+    }
+
+    // Start or end a post-shield special, rolling for its hazard when it starts
+    fn process_specials(
+        &self,
+        player: &mut Player,
+        state: &mut MuspahState,
+        config: &mut MuspahConfig,
+        vars: &mut FightVars,
+        rng: &mut SmallRng,
+    ) {
+        if let Some((_, end_tick)) = state.special
+            && vars.tick_counter >= end_tick
+        {
+            state.special = None;
+            state.muspah_attack_tick = vars.tick_counter;
+        }
+
+        if state.phase != MuspahPhase::PostShield
+            || state.special.is_some()
+            || state.attack_count < SPECIAL_ATTACKS
+        {
+            return;
+        }
+
+        let special = state.next_special;
+        let (duration, hazard, source) = match special {
+            MuspahSpecial::Spikes => (SPIKE_TICKS, config.spikes, "a spike"),
+            MuspahSpecial::Darkness => (DARKNESS_TICKS, config.lightning, "a lightning cloud"),
+        };
+        state.special = Some((special, vars.tick_counter + duration));
+        state.next_special = match special {
+            MuspahSpecial::Spikes => MuspahSpecial::Darkness,
+            MuspahSpecial::Darkness => MuspahSpecial::Spikes,
+        };
+        state.attack_count = 0;
+
+        if config.logger.enabled {
+            config.logger.log_monster_special(
+                vars.tick_counter,
+                "The Phantom Muspah",
+                &format!("{special:?}"),
+            );
+        }
+
+        if let Some(hazard) = hazard {
+            self.process_hazard(player, &hazard, source, rng, vars, &mut config.logger);
+        }
+    }
+
+    // Drain prayer for the tick (offensive, protection, smite and the Muspah's shield) and sip
+    // restores as needed. Returns false if the player ran out of prayer and should supply out
+    #[allow(clippy::too_many_arguments)]
+    fn process_muspah_prayer(
+        &self,
+        player: &mut Player,
+        state: &MuspahState,
+        config: &mut MuspahConfig,
+        inventory: &mut Inventory,
+        attacking: bool,
+        vars: &mut FightVars,
+    ) -> bool {
+        let mut drain_effect = PROTECTION_DRAIN_EFFECT;
+        if state.smiting {
+            drain_effect += SMITE_DRAIN_EFFECT;
+        }
+        if config.prayer.is_active(attacking) {
+            // The protection prayer is already counted above
+            let protection = player.prayers.protection().map_or(0, |p| p.drain_effect());
+            drain_effect += player.prayers.drain_effect() - protection;
+        }
+
+        let mut drained = vars
+            .prayer_drain
+            .tick(drain_effect, player.prayer_drain_resistance());
+        if state.phase == MuspahPhase::Shielded
+            && vars.tick_counter % SHIELD_PRAYER_DRAIN_TICKS == 0
+        {
+            drained += 1;
+        }
+
+        let before = player.stats.prayer.current;
+        player.stats.prayer.drain(drained);
+        vars.prayer_used += before - player.stats.prayer.current;
+
+        if player.stats.prayer.current <= config.prayer.restore_at {
+            self.drink_potion(
+                player,
+                inventory,
+                config.prayer.restore_potion,
+                vars,
+                &mut config.logger,
+            );
+        }
+
+        !(player.stats.prayer.current == 0 && config.prayer.on_empty == PrayerOutBehaviour::Fail)
+    }
+
+    fn handle_eating(
+        &self,
+        config: &mut MuspahConfig,
+        inventory: &mut Inventory,
+        vars: &mut FightVars,
+        player: &mut Player,
+    ) -> bool {
+        // Eat below the threshold, returning false if the player needed to eat but had no food left
+        if player.stats.hitpoints.current > config.eat_hp {
+            return true;
+        }
+        if !inventory.has_food() {
+            return false;
+        }
+
+        if let Some(attack_delay) =
+            self.eat_next_food(player, inventory, false, vars, &mut config.logger)
+        {
+            vars.attack_tick += attack_delay;
+        }
+        true
+    }
+}
+
+#[derive(Clone)]
+pub struct MuspahFight {
+    player: Player,
+    forms: [Monster; 4], // Ranged, melee, shielded and post-shield; the first two share HP
+    active_form: usize,
+    form_switches: [Vec<GearSwitch>; 4], // The player's gear switches with rolls against each form
+    prayer_switches: [Vec<GearSwitch>; 4], // Copy of the above with prayers, restored after a prayer-out
+    prayers_lost: bool,
+    limiter: Option<Box<dyn Limiter>>,
+    rng: SmallRng,
+    config: MuspahConfig,
+    mechanics: MuspahMechanics,
+    spec_config: Option<SpecConfig<MuspahCondition>>,
+    spec_state: SpecState,
+    inventory: Inventory,
+}
+
+impl MuspahFight {
+    pub fn new(
+        mut player: Player,
+        config: MuspahConfig,
+        spec_config: Option<SpecConfig<MuspahCondition>>,
+    ) -> Result<Self, SimulationError> {
+        let forms = [
+            ("Ranged", RANGED_MAX_HIT, MAGIC_MAX_HIT),
+            ("Melee", 0, 0),
+            ("Shielded", SHIELDED_RANGED_MAX_HIT, SHIELDED_MAGIC_MAX_HIT),
+            (
+                "Post-shield",
+                SHIELDED_RANGED_MAX_HIT,
+                SHIELDED_MAGIC_MAX_HIT,
+            ),
+        ]
+        .map(|(version, ranged, magic)| {
+            Monster::new("Phantom Muspah", Some(version)).map(|mut muspah| {
+                muspah.max_hits = Some(if version == "Melee" {
+                    vec![MonsterMaxHit::new(MELEE_MAX_HIT, AttackType::Stab)]
+                } else {
+                    vec![
+                        MonsterMaxHit::new(ranged, AttackType::Ranged),
+                        MonsterMaxHit::new(magic, AttackType::Magic),
+                    ]
+                });
+                muspah
+            })
+        });
+        let [Ok(ranged), Ok(melee), Ok(shielded), Ok(post_shield)] = forms else {
+            return Err(SimulationError::MonsterCreationError(
+                "Phantom Muspah".to_string(),
+            ));
+        };
+        let forms = [ranged, melee, shielded, post_shield];
+
+        if !(0.0..=1.0).contains(&config.movement_efficiency) {
+            return Err(SimulationError::ConfigError(
+                "Movement efficiency must be between 0 and 1.".to_string(),
+            ));
+        }
+        if !(0.0..=1.0).contains(&config.flick_success) {
+            return Err(SimulationError::ConfigError(
+                "Flick success must be between 0 and 1.".to_string(),
+            ));
+        }
+
+        if player.switches.is_empty() {
+            player.switches.push(GearSwitch::from(&player));
+        }
+        for switch_type in config.switches.switch_types() {
+            if !player
+                .switches
+                .iter()
+                .any(|s| &s.switch_type == switch_type)
+            {
+                return Err(PlayerError::GearSwitchNotFound(switch_type.clone()).into());
+            }
+        }
+
+        // Precompute every switch's rolls against each form so phase changes are just swaps
+        let mut form_switches: [Vec<GearSwitch>; 4] = Default::default();
+        for (switches, form) in form_switches.iter_mut().zip(&forms) {
+            *switches = player.switches_against(form)?;
+        }
+
+        let limiter = assign_limiter(&player, &forms[0]);
+        let rng = seeded_rng(config.seed);
+        let inventory = config.inventory.clone();
+
+        Ok(Self {
+            player,
+            forms,
+            active_form: 0,
+            prayer_switches: form_switches.clone(),
+            form_switches,
+            prayers_lost: false,
+            limiter,
+            rng,
+            config,
+            mechanics: MuspahMechanics,
+            spec_config,
+            spec_state: SpecState::default(),
+            inventory,
+        })
+    }
+
+    // Move to a new phase, carrying stat drains and effects (and HP within the pre-shield phase) over
+    fn enter_phase(
+        &mut self,
+        state: &mut MuspahState,
+        phase: MuspahPhase,
+    ) -> Result<(), SimulationError> {
+        let index = phase.monster_index();
+        if index != self.active_form {
+            let shares_hp = state.phase.is_pre_shield() && phase.is_pre_shield();
+            let previous = self.forms[self.active_form].stats;
+            let effects = std::mem::take(&mut self.forms[self.active_form].active_effects);
+
+            let muspah = &mut self.forms[index];
+            muspah.stats.defence = previous.defence;
+            if shares_hp {
+                muspah.stats.hitpoints = previous.hitpoints;
+            }
+            muspah.active_effects = effects;
+            muspah.base_def_rolls = monster_def_rolls(muspah);
+            muspah.def_rolls = muspah.base_def_rolls;
+            self.active_form = index;
+        }
+
+        if !state.phase.is_pre_shield() || !phase.is_pre_shield() {
+            // Changing between the main phases takes a few ticks
+            state.targetable_tick = state.tick_counter + PHASE_TRANSITION_TICKS;
+            state.muspah_attack_tick = state.targetable_tick;
+        }
+        if phase == MuspahPhase::Shielded {
+            state.shockwave_tick = state.targetable_tick + SHIELD_SHOCKWAVE_TICKS;
+        }
+        state.phase = phase;
+        state.attack_count = 0;
+
+        let switch = self.config.switches.switch_for(phase).clone();
+        self.player.switches = self.form_switches[index].clone();
+        self.player.current_switch = None;
+        self.player.switch(&switch)?;
+
+        if self.config.logger.enabled {
+            self.config.logger.log_phase_change(
+                state.tick_counter,
+                "The Phantom Muspah",
+                &format!("{phase:?}"),
+            );
+            self.config
+                .logger
+                .log_gear_switch(state.tick_counter, &switch);
+        }
+
+        Ok(())
+    }
+
+    // Move on to the next phase once the current form runs out of HP, or its form rotation is up
+    fn advance_phase(
+        &mut self,
+        state: &mut MuspahState,
+        vars: &mut FightVars,
+    ) -> Result<(), SimulationError> {
+        let hp = self.forms[self.active_form].stats.hitpoints.current;
+        match state.phase {
+            MuspahPhase::Ranged | MuspahPhase::Melee if hp == 0 => {
+                // A shield fully drained by smite is skipped entirely
+                let shield = self.forms[MuspahPhase::Shielded.monster_index()]
+                    .stats
+                    .hitpoints
+                    .current;
+                state.smiting = false;
+                if shield == 0 {
+                    if self.config.logger.enabled {
+                        self.config
+                            .logger
+                            .log_custom(state.tick_counter, "The shield phase was smite skipped.");
+                    }
+                    self.enter_phase(state, MuspahPhase::PostShield)
+                } else {
+                    self.enter_phase(state, MuspahPhase::Shielded)
+                }
+            }
+            MuspahPhase::Ranged if state.attack_count >= RANGED_FORM_ATTACKS => {
+                self.enter_phase(state, MuspahPhase::Melee)
+            }
+            MuspahPhase::Melee if state.attack_count >= MELEE_FORM_ATTACKS => {
+                self.enter_phase(state, MuspahPhase::Ranged)
+            }
+            MuspahPhase::Shielded if hp == 0 => self.enter_phase(state, MuspahPhase::PostShield),
+            MuspahPhase::Shielded if state.tick_counter >= state.shockwave_tick => {
+                // The shield collapses with an unavoidable shockwave if it isn't broken in time
+                let damage = self.rng.random_range(1..SHOCKWAVE_MAX_HIT + 1);
+                self.player.take_damage(damage);
+                vars.damage_taken += damage;
+                if self.config.logger.enabled {
+                    self.config.logger.log_monster_special(
+                        state.tick_counter,
+                        "The Phantom Muspah",
+                        "Shockwave",
+                    );
+                    self.config.logger.log_player_damage(
+                        state.tick_counter,
+                        damage,
+                        self.player.stats.hitpoints.current,
+                    );
+                }
+                self.enter_phase(state, MuspahPhase::PostShield)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // Turn off offensive prayers against every form once the player runs dry
+    fn lose_prayers(&mut self, tick: i32) -> Result<(), SimulationError> {
+        self.prayers_lost = true;
+        self.player
+            .deactivate_prayers(&self.forms[self.active_form]);
+        for (switches, form) in self.form_switches.iter_mut().zip(&self.forms) {
+            *switches = self.player.switches_against(form)?;
+        }
+        if self.config.logger.enabled {
+            self.config.logger.log_custom(
+                tick,
+                "Player ran out of prayer points and lost their prayers.",
+            );
+        }
+        Ok(())
+    }
+
+    fn simulate_muspah_fight(&mut self) -> Result<FightResult, SimulationError> {
+        if let Some(ref spec_config) = self.spec_config
+            && let Err(e) = spec_config.validate()
+        {
+            return Err(SimulationError::ConfigError(e));
+        }
+
+        let mut vars = FightVars::new();
+        let mut state = MuspahState {
+            smiting: self.config.smite_skip,
+            ..MuspahState::default()
+        };
+
+        // Log the setup first, since it starts a fresh trace
+        let logging_enabled = self.config.logger.enabled;
+        if logging_enabled {
+            self.config.logger.log_initial_setup(
+                &self.player,
+                &self.forms[MuspahPhase::Ranged.monster_index()],
+            );
+        }
+
+        self.enter_phase(&mut state, MuspahPhase::Ranged)?;
+
+        while self.forms[self.active_form].stats.hitpoints.current > 0 {
+            state.tick_counter = vars.tick_counter;
+
+            if vars.tick_counter % MUSPAH_REGEN_TICKS == 0 {
+                self.mechanics.monster_regen_stats(
+                    &mut self.forms[self.active_form],
+                    &vars,
+                    &mut self.config.logger,
+                );
+            }
+
+            // Regen 1 HP for player every 100 ticks
+            if vars.tick_counter % constants::PLAYER_REGEN_TICKS == 0 {
+                self.mechanics
+                    .player_regen(&mut self.player, &vars, &mut self.config.logger);
+            }
+
+            self.mechanics.decrement_eat_delay(&mut vars);
+            self.mechanics.process_player_effects(
+                &mut self.player,
+                &mut vars,
+                &mut self.config.logger,
+            );
+            if !self.mechanics.handle_eating(
+                &mut self.config,
+                &mut self.inventory,
+                &mut vars,
+                &mut self.player,
+            ) {
+                return self.mechanics.process_supply_out(
+                    &vars,
+                    &self.forms[self.active_form],
+                    &mut self.config.logger,
+                );
+            }
+
+            let attacking = vars.tick_counter == vars.attack_tick;
+            if !self.mechanics.process_muspah_prayer(
+                &mut self.player,
+                &state,
+                &mut self.config,
+                &mut self.inventory,
+                attacking,
+                &mut vars,
+            ) {
+                return self.mechanics.process_supply_out(
+                    &vars,
+                    &self.forms[self.active_form],
+                    &mut self.config.logger,
+                );
+            }
+            if self.player.stats.prayer.current == 0 && !self.prayers_lost {
+                self.lose_prayers(vars.tick_counter)?;
+            }
+
+            if let Some(redose) = self.config.redose {
+                self.mechanics.process_redose(
+                    &mut self.player,
+                    &self.forms[self.active_form],
+                    &redose,
+                    &mut self.inventory,
+                    &mut vars,
+                    &mut self.config.logger,
+                );
+            }
+
+            self.mechanics.process_specials(
+                &mut self.player,
+                &mut state,
+                &mut self.config,
+                &mut vars,
+                &mut self.rng,
+            );
+
+            if vars.tick_counter == vars.attack_tick {
+                if !state.is_targetable() {
+                    vars.attack_tick += 1;
+                } else if state.is_dodging_spikes()
+                    && !self.rng.random_bool(self.config.movement_efficiency)
+                {
+                    // Lose the attack while stepping around the spikes
+                    vars.attack_tick += 1;
+                } else {
+                    self.player_attack_or_spec(&mut state, &mut vars)?;
+                }
+            }
+
+            self.mechanics.process_monster_effects(
+                &mut self.forms[self.active_form],
+                &vars,
+                &mut self.config.logger,
+            );
+            self.advance_phase(&mut state, &mut vars)?;
+
+            if self.forms[self.active_form].stats.hitpoints.current > 0
+                && vars.tick_counter == state.muspah_attack_tick
+                && state.special.is_none()
+            {
+                self.mechanics.muspah_attack(
+                    &mut self.forms[self.active_form],
+                    &mut self.player,
+                    &mut state,
+                    &mut self.config,
+                    &mut vars,
+                    &mut self.rng,
+                )?;
+                self.advance_phase(&mut state, &mut vars)?;
+            }
+
+            self.mechanics.process_spec_timers(
+                &mut self.player,
+                self.spec_config.as_ref(),
+                &mut self.spec_state,
+                &vars,
+                &mut self.config.logger,
+            );
+
+            // Increment tick counter
+            vars.tick_counter += 1;
+
+            if self.player.stats.hitpoints.current == 0 {
+                return self.mechanics.process_player_death(
+                    &vars,
+                    &self.forms[self.active_form],
+                    &mut self.config.logger,
+                );
+            }
+        }
+        let remove_final_attack_delay = true;
+        self.mechanics.get_fight_result(
+            &self.forms[self.active_form],
+            &vars,
+            &mut self.config.logger,
+            remove_final_attack_delay,
+        )
+    }
+
+    // Use a special attack if any strategy's conditions are met, otherwise attack normally
+    fn player_attack_or_spec(
+        &mut self,
+        state: &mut MuspahState,
+        vars: &mut FightVars,
+    ) -> Result<(), SimulationError> {
+        let muspah = &mut self.forms[self.active_form];
+        let hp_before = muspah.stats.hitpoints.current;
+        let did_spec = if let Some(ref mut spec_config) = self.spec_config {
+            self.mechanics.player_special_attack(
+                &mut self.player,
+                muspah,
+                spec_config,
+                &mut self.spec_state,
+                state,
+                &mut self.rng,
+                &self.limiter,
+                vars,
+                &mut self.config.logger,
+            )?
+        } else {
+            false
+        };
+
+        if !did_spec {
+            self.mechanics.player_attack(
+                &mut self.player,
+                muspah,
+                &mut self.rng,
+                &self.limiter,
+                vars,
+                &mut self.config.logger,
+            );
+        }
+
+        if state.smiting {
+            let damage = hp_before - muspah.stats.hitpoints.current;
+            let shield = &mut self.forms[MuspahPhase::Shielded.monster_index()];
+            shield.take_damage(damage / SMITE_SHIELD_DIVISOR);
+            if shield.stats.hitpoints.current == 0 {
+                // Nothing left to drain, so turn smite off for the rest of the phase
+                state.smiting = false;
+                if self.config.logger.enabled {
+                    self.config
+                        .logger
+                        .log_custom(vars.tick_counter, "The Muspah's shield was fully smited.");
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Simulation for MuspahFight {
+    fn simulate(&mut self) -> Result<FightResult, SimulationError> {
+        self.simulate_muspah_fight()
+    }
+
+    fn is_immune(&self) -> bool {
+        self.forms[self.active_form].is_immune(&self.player)
+    }
+
+    fn player(&self) -> &Player {
+        &self.player
+    }
+
+    fn monster(&self) -> &Monster {
+        &self.forms[self.active_form]
+    }
+
+    fn set_attack_function(&mut self) {
+        self.player.attack = crate::combat::attacks::standard::get_attack_functions(&self.player);
+        self.player.spec = crate::combat::attacks::specs::get_spec_attack_function(&self.player);
+    }
+
+    fn reset(&mut self) {
+        self.config.logger.clear();
+        if let Some(ref mut spec_config) = self.spec_config {
+            let restore_spec = self.spec_state.on_kill(&mut self.player, spec_config);
+            self.player.reset_current_stats(restore_spec);
+        } else {
+            self.player.reset_current_stats(true);
+        }
+        for muspah in &mut self.forms {
+            muspah.reset();
+        }
+        self.active_form = 0;
+        if self.prayers_lost {
+            self.form_switches = self.prayer_switches.clone();
+            self.prayers_lost = false;
+        }
+        self.inventory = self.config.inventory.clone();
+    }
+
+    fn next_kill(&mut self, downtime_ticks: u32) {
+        self.mechanics.prepare_next_kill(
+            &mut self.player,
+            self.spec_config.as_mut(),
+            &mut self.spec_state,
+            downtime_ticks,
+            &mut self.config.logger,
+        );
+        for muspah in &mut self.forms {
+            muspah.reset();
+        }
+        self.active_form = 0;
+    }
+
+    fn inventory(&self) -> Option<&Inventory> {
+        Some(&self.inventory)
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.rng = SmallRng::seed_from_u64(seed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::loadouts;
+    use crate::utils::logging::FightEventKind;
+
+    // Ranger with a mage switch, with each setup's rolls calculated against the ranged form
+    fn muspah_player() -> Player {
+        let muspah =
+            Monster::new("Phantom Muspah", Some("Ranged")).expect("Error creating monster.");
+        let mut player = loadouts::max_ranged_zcb_player();
+        let mage = loadouts::max_mage_sang_staff_player();
+        player.switches = vec![
+            GearSwitch::new(SwitchType::Ranged, &player, &muspah),
+            GearSwitch::new(SwitchType::Magic, &mage, &muspah),
+        ];
+        player
+    }
+
+    // Styles of the Muspah's attacks in a phase, driving its attack directly
+    fn phase_attacks(phase: MuspahPhase, n: usize) -> Vec<AttackType> {
+        let config = MuspahConfig {
+            logger: FightLogger::new(true),
+            seed: Some(1),
+            ..MuspahConfig::default()
+        };
+        let mut fight =
+            MuspahFight::new(muspah_player(), config, None).expect("Error setting up fight.");
+        let mut state = MuspahState {
+            phase,
+            ..MuspahState::default()
+        };
+        let mut vars = FightVars::new();
+        for _ in 0..n {
+            MuspahMechanics
+                .muspah_attack(
+                    &mut fight.forms[phase.monster_index()],
+                    &mut fight.player,
+                    &mut state,
+                    &mut fight.config,
+                    &mut vars,
+                    &mut fight.rng,
+                )
+                .unwrap();
+            fight.player.stats.hitpoints.reset();
+            fight.player.stats.prayer.reset();
+        }
+
+        fight
+            .config
+            .logger
+            .take_trace()
+            .unwrap()
+            .events
+            .into_iter()
+            .filter_map(|event| match event.kind {
+                FightEventKind::MonsterAttack { style, .. } => Some(style),
+                _ => None,
+            })
+            .collect()
+    }
+
+    // Phases entered over a traced fight, and whether the shield was smite skipped
+    fn phase_order(config: MuspahConfig) -> (Vec<String>, bool) {
+        let config = MuspahConfig {
+            logger: FightLogger::new(true),
+            seed: Some(1),
+            ..config
+        };
+        let mut fight =
+            MuspahFight::new(muspah_player(), config, None).expect("Error setting up fight.");
+        fight.set_attack_function();
+        let result = fight.simulate().unwrap();
+
+        let mut skipped = false;
+        let phases = result
+            .trace
+            .unwrap()
+            .events
+            .into_iter()
+            .filter_map(|event| match event.kind {
+                FightEventKind::Custom { message } => {
+                    skipped |= message == "The shield phase was smite skipped.";
+                    None
+                }
+                FightEventKind::PhaseChange { phase, .. } => Some(phase),
+                _ => None,
+            })
+            .collect();
+        (phases, skipped)
+    }
+
+    #[test]
+    fn test_phase_attack_styles() {
+        let n = 200;
+
+        // The ranged form mixes in the odd magic blast, and the melee form only stabs
+        let ranged = phase_attacks(MuspahPhase::Ranged, n);
+        let blasts = ranged
+            .iter()
+            .filter(|&&style| style == AttackType::Magic)
+            .count();
+        assert_eq!(ranged.len(), n);
+        assert!(blasts > 0 && blasts < n / 2);
+        assert!(
+            ranged
+                .iter()
+                .all(|&style| matches!(style, AttackType::Ranged | AttackType::Magic))
+        );
+        assert!(
+            phase_attacks(MuspahPhase::Melee, n)
+                .iter()
+                .all(|&style| style == AttackType::Stab)
+        );
+
+        // The shielded forms swap between ranged and magic evenly
+        for phase in [MuspahPhase::Shielded, MuspahPhase::PostShield] {
+            let styles = phase_attacks(phase, n);
+            let magic = styles
+                .iter()
+                .filter(|&&style| style == AttackType::Magic)
+                .count();
+            let ranged = styles
+                .iter()
+                .filter(|&&style| style == AttackType::Ranged)
+                .count();
+            assert_eq!(magic + ranged, n);
+            assert!(magic > n * 3 / 10 && ranged > n * 3 / 10);
+        }
+    }
+
+    #[test]
+    fn test_phase_order() {
+        // The pre-shield forms alternate until the shield, which always comes before the post-shield phase
+        let (phases, skipped) = phase_order(MuspahConfig::default());
+        assert!(!skipped);
+        let shield = phases.iter().position(|phase| phase == "Shielded").unwrap();
+        assert!(shield > 0);
+        for (index, phase) in phases[..shield].iter().enumerate() {
+            assert_eq!(phase, if index % 2 == 0 { "Ranged" } else { "Melee" });
+        }
+        assert_eq!(&phases[shield..], ["Shielded", "PostShield"]);
+    }
+
+    #[test]
+    fn test_smite_skip() {
+        // Smiting away the shield early skips the shield phase entirely
+        let (phases, skipped) = phase_order(MuspahConfig {
+            smite_skip: true,
+            ..MuspahConfig::default()
+        });
+        assert!(skipped);
+        assert!(!phases.iter().any(|phase| phase == "Shielded"));
+        assert_eq!(phases.first().unwrap(), "Ranged");
+        assert_eq!(phases.last().unwrap(), "PostShield");
+    }
+
+    #[test]
+    fn test_invalid_movement_efficiency() {
+        let config = MuspahConfig {
+            movement_efficiency: 1.5,
+            ..MuspahConfig::default()
+        };
+        assert!(matches!(
+            MuspahFight::new(muspah_player(), config, None),
+            Err(SimulationError::ConfigError(_))
+        ));
+    }
+
+    #[test]
+    fn test_missing_switch() {
+        let config = MuspahConfig {
+            switches: MuspahSwitches {
+                post_shield: SwitchType::Melee,
+                ..MuspahSwitches::default()
+            },
+            ..MuspahConfig::default()
+        };
+        assert!(matches!(
+            MuspahFight::new(muspah_player(), config, None),
+            Err(SimulationError::SwitchingError(
+                PlayerError::GearSwitchNotFound(_)
+            ))
+        ));
+    }
+}
@@ -0,0 +1,1040 @@
+use crate::calc::monster_scaling::{PartyScaling, scale_monster_party_hp};
+use crate::combat::limiters::Limiter;
+use crate::combat::mechanics::{Hazard, Mechanics, handle_recoil};
+use crate::combat::prayer::PrayerDrainConfig;
+use crate::combat::simulation::{FightResult, FightVars, Simulation, assign_limiter, seeded_rng};
+use crate::combat::spec::{CoreCondition, SpecCondition, SpecConfig, SpecState};
+use crate::combat::thralls::Thrall;
+use crate::constants;
+use crate::error::{PlayerError, SimulationError};
+use crate::types::food::Foods;
+use crate::types::inventory::Inventory;
+use crate::types::monster::{AttackType, Monster, MonsterMaxHit};
+use crate::types::player::{GearSwitch, Player, SwitchType};
+use crate::types::potions::{Potion, RedoseConfig};
+use crate::types::prayers::Prayer;
+use crate::utils::logging::FightLogger;
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+const OLM_REGEN_TICKS: i32 = 100;
+
+// Olm acts every four ticks, and every fourth action is a claw special while the mage hand lives
+const OLM_ATTACK_SPEED: i32 = 4;
+const OLM_ACTIONS_PER_SPECIAL: u32 = 4;
+
+// Head auto attack max hits in the first phase, the middle phases and the final phase
+const OLM_MAX_HITS: [u32; 3] = [27, 28, 29];
+const OLM_CM_MAX_HITS: [u32; 3] = [38, 39, 41];
+
+// Challenge Mode raises every NPC's HP by half; the database lists the normal mode HP
+const CM_HP_NUMERATOR: u32 = 3;
+const CM_HP_DENOMINATOR: u32 = 2;
+
+// Olm gets an extra claw phase for every eight players, and more HP for each player
+const SOLO_CLAW_PHASES: u32 = 2;
+const PLAYERS_PER_EXTRA_PHASE: u32 = 8;
+const OLM_PARTY_SCALING: PartyScaling = PartyScaling::Linear(0.5);
+
+// Ticks lost moving out of the way of each claw special
+const CRYSTAL_BURST_LOST_TICKS: i32 = 1;
+const LIGHTNING_LOST_TICKS: i32 = 2;
+const TELEPORT_LOST_TICKS: i32 = 2;
+
+/// The parts of Olm the player can attack.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum OlmTarget {
+    MageHand,  // The right claw, which casts the crystal, lightning and teleport specials
+    MeleeHand, // The left claw
+    Head,      // Only attackable once both claws are down in the final phase
+}
+
+impl OlmTarget {
+    fn index(&self) -> usize {
+        match self {
+            Self::MageHand => 0,
+            Self::MeleeHand => 1,
+            Self::Head => 2,
+        }
+    }
+
+    fn version(&self, challenge_mode: bool) -> &'static str {
+        match (self, challenge_mode) {
+            (Self::MageHand, false) => "Right claw (Normal)",
+            (Self::MageHand, true) => "Right claw (Challenge Mode)",
+            (Self::MeleeHand, false) => "Left claw (Normal)",
+            (Self::MeleeHand, true) => "Left claw (Challenge Mode)",
+            (Self::Head, false) => "Head (Normal)",
+            (Self::Head, true) => "Head (Challenge Mode)",
+        }
+    }
+}
+
+/// Which claw the player kills first in each phase.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum OlmHandStrategy {
+    #[default]
+    MageHand, // Kill the mage hand first, cutting its specials short
+    MeleeHand, // Kill the melee hand first, leaving the mage hand for last
+}
+
+impl OlmHandStrategy {
+    fn order(&self) -> (OlmTarget, OlmTarget) {
+        match self {
+            Self::MageHand => (OlmTarget::MageHand, OlmTarget::MeleeHand),
+            Self::MeleeHand => (OlmTarget::MeleeHand, OlmTarget::MageHand),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum OlmSpecial {
+    CrystalBurst, // Crystals burst from under the player
+    Lightning,    // Lightning sweeps across the arena
+    Teleport,     // Portals swap the player to a tile that has to be moved off
+}
+
+impl OlmSpecial {
+    // The mage hand cycles through its specials in a fixed order
+    fn next(&self) -> Self {
+        match self {
+            Self::CrystalBurst => Self::Lightning,
+            Self::Lightning => Self::Teleport,
+            Self::Teleport => Self::CrystalBurst,
+        }
+    }
+}
+
+/// Gear switch to attack each part of Olm with.
+#[derive(Debug, PartialEq, Clone)]
+pub struct OlmSwitches {
+    pub mage_hand: SwitchType,
+    pub melee_hand: SwitchType,
+    pub head: SwitchType,
+}
+
+impl Default for OlmSwitches {
+    fn default() -> Self {
+        // The claws and head take a third of the damage from the styles they resist
+        Self {
+            mage_hand: SwitchType::Magic,
+            melee_hand: SwitchType::Melee,
+            head: SwitchType::Ranged,
+        }
+    }
+}
+
+impl OlmSwitches {
+    pub fn switch_for(&self, target: OlmTarget) -> &SwitchType {
+        match target {
+            OlmTarget::MageHand => &self.mage_hand,
+            OlmTarget::MeleeHand => &self.melee_hand,
+            OlmTarget::Head => &self.head,
+        }
+    }
+
+    fn switch_types(&self) -> [&SwitchType; 3] {
+        [&self.mage_hand, &self.melee_hand, &self.head]
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct OlmConfig {
+    pub inventory: Inventory,
+    pub eat_hp: u32,
+    pub switches: OlmSwitches,
+    pub hand_strategy: OlmHandStrategy,
+    pub prep_hp: Option<u32>, // Bring the second claw down to this HP before killing the first
+    pub claw_revive_ticks: Option<i32>, // A dead claw revives if the other is still up this long after
+    pub party_size: u32,
+    pub challenge_mode: bool,
+    pub flick_success: f64, // Chance the right protection prayer is up in time for each auto attack
+    pub phase_transition_ticks: i32, // Olm sinking and rising on the other side of the arena
+    pub crystal_burst: Option<Hazard>, // Rolled once per crystal burst
+    pub lightning: Option<Hazard>, // Rolled once per lightning special
+    pub teleport: Option<Hazard>, // Rolled once per teleport special
+    pub prayer_drain: Option<PrayerDrainConfig>,
+    pub redose: Option<RedoseConfig>,
+    pub thralls: Option<Thrall>,
+    pub logger: FightLogger,
+    pub seed: Option<u64>,
+}
+
+impl Default for OlmConfig {
+    fn default() -> Self {
+        Self {
+            inventory: Inventory::builder()
+                .food(Foods::Anglerfish, 14)
+                .potion(Potion::PrayerPotion, 8)
+                .build()
+                .expect("Error building inventory."),
+            eat_hp: 60,
+            switches: OlmSwitches::default(),
+            hand_strategy: OlmHandStrategy::MageHand,
+            prep_hp: Some(150),
+            claw_revive_ticks: Some(50),
+            party_size: 1,
+            challenge_mode: false,
+            flick_success: 1.0,
+            phase_transition_ticks: 25,
+            crystal_burst: Some(Hazard {
+                chance: 0.1,
+                max_hit: 45,
+            }),
+            lightning: Some(Hazard {
+                chance: 0.1,
+                max_hit: 20,
+            }),
+            teleport: Some(Hazard {
+                chance: 0.05,
+                max_hit: 30,
+            }),
+            prayer_drain: None,
+            redose: None,
+            thralls: None,
+            logger: FightLogger::new(false),
+            seed: None,
+        }
+    }
+}
+
+impl OlmConfig {
+    /// Number of claw phases before the final phase, where the head can be attacked.
+    pub fn claw_phases(&self) -> u32 {
+        SOLO_CLAW_PHASES + self.party_size / PLAYERS_PER_EXTRA_PHASE
+    }
+
+    fn hazard(&self, special: OlmSpecial) -> (Option<Hazard>, i32, &'static str) {
+        match special {
+            OlmSpecial::CrystalBurst => (
+                self.crystal_burst,
+                CRYSTAL_BURST_LOST_TICKS,
+                "a crystal burst",
+            ),
+            OlmSpecial::Lightning => (self.lightning, LIGHTNING_LOST_TICKS, "lightning"),
+            OlmSpecial::Teleport => (self.teleport, TELEPORT_LOST_TICKS, "a teleport"),
+        }
+    }
+}
+
+// Spec conditions specific to the Olm fight
+#[derive(Debug, Clone, PartialEq)]
+pub enum OlmCondition {
+    Core(CoreCondition),
+    Target(OlmTarget), // The player is attacking this part of Olm
+    FinalPhase,        // Olm is in its final phase
+}
+
+impl SpecCondition for OlmCondition {
+    type BossState = OlmState;
+
+    fn evaluate(&self, player: &Player, monster: &Monster, boss_state: &Self::BossState) -> bool {
+        match self {
+            Self::Core(core) => core.evaluate(player, monster, &()),
+            Self::Target(target) => boss_state.target == *target,
+            Self::FinalPhase => boss_state.final_phase,
+        }
+    }
+
+    fn as_core(&self) -> Option<&CoreCondition> {
+        match self {
+            Self::Core(core) => Some(core),
+            _ => None,
+        }
+    }
+
+    fn from_core(core: CoreCondition) -> Self {
+        Self::Core(core)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OlmState {
+    tick_counter: i32,
+    phase: u32,
+    final_phase: bool,
+    target: OlmTarget,
+    olm_attack_tick: i32,
+    targetable_tick: i32,
+    attack_count: u32,
+    next_special: OlmSpecial,
+    revive_tick: Option<(OlmTarget, i32)>, // Dead claw and the tick it revives on
+}
+
+impl Default for OlmState {
+    fn default() -> Self {
+        Self {
+            tick_counter: 0,
+            phase: 0,
+            final_phase: false,
+            target: OlmTarget::MageHand,
+            olm_attack_tick: 0,
+            targetable_tick: 0,
+            attack_count: 0,
+            next_special: OlmSpecial::CrystalBurst,
+            revive_tick: None,
+        }
+    }
+}
+
+impl OlmState {
+    fn is_targetable(&self) -> bool {
+        self.tick_counter >= self.targetable_tick
+    }
+}
+
+#[derive(Clone)]
+struct OlmMechanics;
+
+impl Mechanics for OlmMechanics {}
+
+impl OlmMechanics {
+    fn olm_attack(
+        &self,
+        head: &mut Monster,
+        max_hit: u32,
+        player: &mut Player,
+        config: &mut OlmConfig,
+        vars: &mut FightVars,
+        rng: &mut SmallRng,
+    ) -> Result<(), SimulationError> {
+        let style = if rng.random_bool(0.5) {
+            AttackType::Magic
+        } else {
+            AttackType::Ranged
+        };
+        head.max_hits = Some(vec![MonsterMaxHit::new(max_hit, style)]);
+        player.set_protection_prayer(Prayer::protection_against(style));
+        let hit = head.attack_with_flick(player, Some(style), rng, false, config.flick_success)?;
+
+        if config.logger.enabled {
+            config.logger.log_monster_attack(
+                head,
+                vars.tick_counter,
+                hit.damage,
+                hit.success,
+                Some(style),
+            );
+        }
+
+        if hit.success {
+            player.take_damage(hit.damage);
+            vars.damage_taken += hit.damage;
+            handle_recoil(player, head, &hit, vars, &mut config.logger);
+        }
+
+        Ok(())
+    }
+
+    fn handle_eating(
+        &self,
+        config: &mut OlmConfig,
+        inventory: &mut Inventory,
+        vars: &mut FightVars,
+        player: &mut Player,
+    ) -> bool {
+        // Eat below the threshold, returning false if the player needed to eat but had no food left
+        if player.stats.hitpoints.current > config.eat_hp {
+            return true;
+        }
+        if !inventory.has_food() {
+            return false;
+        }
+
+        if let Some(attack_delay) =
+            self.eat_next_food(player, inventory, false, vars, &mut config.logger)
+        {
+            vars.attack_tick += attack_delay;
+        }
+        true
+    }
+}
+
+#[derive(Clone)]
+pub struct OlmFight {
+    player: Player,
+    templates: [Monster; 3], // Mage hand, melee hand and head, scaled for the party
+    parts: [Monster; 3],
+    target_switches: [Vec<GearSwitch>; 3],
+    limiter: Option<Box<dyn Limiter>>,
+    rng: SmallRng,
+    config: OlmConfig,
+    mechanics: OlmMechanics,
+    spec_config: Option<SpecConfig<OlmCondition>>,
+    spec_state: SpecState,
+    inventory: Inventory,
+}
+
+impl OlmFight {
+    pub fn new(
+        mut player: Player,
+        config: OlmConfig,
+        spec_config: Option<SpecConfig<OlmCondition>>,
+    ) -> Result<Self, SimulationError> {
+        if config.party_size == 0 {
+            return Err(SimulationError::ConfigError(
+                "Party size must be at least 1.".to_string(),
+            ));
+        }
+        if !(0.0..=1.0).contains(&config.flick_success) {
+            return Err(SimulationError::ConfigError(
+                "Flick success must be between 0 and 1.".to_string(),
+            ));
+        }
+
+        let templates = [OlmTarget::MageHand, OlmTarget::MeleeHand, OlmTarget::Head].map(
+            |target| -> Result<Monster, SimulationError> {
+                let mut part =
+                    Monster::new("Great Olm", Some(target.version(config.challenge_mode)))
+                        .map_err(|_| {
+                            SimulationError::MonsterCreationError("Great Olm".to_string())
+                        })?;
+                if config.challenge_mode {
+                    part.stats.hitpoints.base =
+                        part.stats.hitpoints.base * CM_HP_NUMERATOR / CM_HP_DENOMINATOR;
+                    part.reset();
+                }
+                scale_monster_party_hp(&mut part, config.party_size, OLM_PARTY_SCALING);
+                Ok(part)
+            },
+        );
+        let [Ok(mage_hand), Ok(melee_hand), Ok(head)] = templates else {
+            return Err(SimulationError::MonsterCreationError(
+                "Great Olm".to_string(),
+            ));
+        };
+        let templates = [mage_hand, melee_hand, head];
+
+        if player.switches.is_empty() {
+            player.switches.push(GearSwitch::from(&player));
+        }
+        for switch_type in config.switches.switch_types() {
+            if !player
+                .switches
+                .iter()
+                .any(|s| &s.switch_type == switch_type)
+            {
+                return Err(PlayerError::GearSwitchNotFound(switch_type.clone()).into());
+            }
+        }
+
+        // Precompute every switch's rolls against each part so changing targets is just a swap
+        let mut target_switches: [Vec<GearSwitch>; 3] = Default::default();
+        for (switches, part) in target_switches.iter_mut().zip(&templates) {
+            *switches = player.switches_against(part)?;
+        }
+
+        let limiter = assign_limiter(&player, &templates[0]);
+        let rng = seeded_rng(config.seed);
+        let inventory = config.inventory.clone();
+
+        Ok(Self {
+            player,
+            parts: templates.clone(),
+            templates,
+            target_switches,
+            limiter,
+            rng,
+            config,
+            mechanics: OlmMechanics,
+            spec_config,
+            spec_state: SpecState::default(),
+            inventory,
+        })
+    }
+
+    fn is_alive(&self, target: OlmTarget) -> bool {
+        self.parts[target.index()].stats.hitpoints.current > 0
+    }
+
+    // Pick the part to attack: finish a claw once the other is prepped, and the head once both are down
+    fn choose_target(&self, state: &OlmState) -> OlmTarget {
+        let (first, second) = self.config.hand_strategy.order();
+        match (self.is_alive(first), self.is_alive(second)) {
+            (true, true) => {
+                let second_hp = self.parts[second.index()].stats.hitpoints.current;
+                if self.config.prep_hp.is_some_and(|hp| second_hp > hp) {
+                    second
+                } else {
+                    first
+                }
+            }
+            (true, false) => first,
+            (false, true) => second,
+            (false, false) if state.final_phase => OlmTarget::Head,
+            (false, false) => state.target,
+        }
+    }
+
+    fn set_target(
+        &mut self,
+        state: &mut OlmState,
+        target: OlmTarget,
+    ) -> Result<(), SimulationError> {
+        if target == state.target && self.player.current_switch.is_some() {
+            return Ok(());
+        }
+        state.target = target;
+
+        let switch = self.config.switches.switch_for(target).clone();
+        self.player.switches = self.target_switches[target.index()].clone();
+        self.player.current_switch = None;
+        self.player.switch(&switch)?;
+        self.limiter = assign_limiter(&self.player, &self.parts[target.index()]);
+
+        if self.config.logger.enabled {
+            self.config
+                .logger
+                .log_gear_switch(state.tick_counter, &switch);
+        }
+
+        Ok(())
+    }
+
+    // Start a phase with both claws back up
+    fn enter_phase(&mut self, state: &mut OlmState, phase: u32) -> Result<(), SimulationError> {
+        state.phase = phase;
+        state.final_phase = phase == self.config.claw_phases();
+        state.revive_tick = None;
+        state.attack_count = 0;
+        state.next_special = OlmSpecial::CrystalBurst;
+        if phase > 0 {
+            state.targetable_tick = state.tick_counter + self.config.phase_transition_ticks;
+        }
+        state.olm_attack_tick = state.targetable_tick;
+
+        for target in [OlmTarget::MageHand, OlmTarget::MeleeHand] {
+            self.parts[target.index()] = self.templates[target.index()].clone();
+        }
+
+        if self.config.logger.enabled {
+            let phase = if state.final_phase {
+                "Final".to_string()
+            } else {
+                (phase + 1).to_string()
+            };
+            self.config
+                .logger
+                .log_phase_change(state.tick_counter, "Olm", &phase);
+        }
+
+        let target = self.choose_target(state);
+        self.set_target(state, target)
+    }
+
+    // Revive a dead claw if the other is still up when its timer runs out, or start the timer
+    fn process_claws(&mut self, state: &mut OlmState) -> Result<(), SimulationError> {
+        let mage_alive = self.is_alive(OlmTarget::MageHand);
+        let melee_alive = self.is_alive(OlmTarget::MeleeHand);
+        if mage_alive == melee_alive {
+            state.revive_tick = None;
+        } else if let Some(revive_ticks) = self.config.claw_revive_ticks {
+            let dead = if mage_alive {
+                OlmTarget::MeleeHand
+            } else {
+                OlmTarget::MageHand
+            };
+            match state.revive_tick {
+                Some((_, tick)) if state.tick_counter >= tick => {
+                    self.parts[dead.index()] = self.templates[dead.index()].clone();
+                    state.revive_tick = None;
+                    if self.config.logger.enabled {
+                        self.config
+                            .logger
+                            .log_monster_revive(state.tick_counter, &format!("Olm's {dead:?}"));
+                    }
+                }
+                Some(_) => {}
+                None => state.revive_tick = Some((dead, state.tick_counter + revive_ticks)),
+            }
+        }
+
+        let target = self.choose_target(state);
+        self.set_target(state, target)
+    }
+
+    fn olm_action(
+        &mut self,
+        state: &mut OlmState,
+        vars: &mut FightVars,
+    ) -> Result<(), SimulationError> {
+        state.attack_count += 1;
+
+        if state.attack_count.is_multiple_of(OLM_ACTIONS_PER_SPECIAL)
+            && self.is_alive(OlmTarget::MageHand)
+        {
+            let special = state.next_special;
+            state.next_special = special.next();
+            let (hazard, lost_ticks, source) = self.config.hazard(special);
+            if self.config.logger.enabled {
+                self.config.logger.log_monster_special(
+                    vars.tick_counter,
+                    "Olm",
+                    &format!("{special:?}"),
+                );
+            }
+            if let Some(hazard) = hazard {
+                self.mechanics.process_hazard(
+                    &mut self.player,
+                    &hazard,
+                    source,
+                    &mut self.rng,
+                    vars,
+                    &mut self.config.logger,
+                );
+            }
+            vars.attack_tick += lost_ticks;
+            return Ok(());
+        }
+
+        let max_hits = if self.config.challenge_mode {
+            OLM_CM_MAX_HITS
+        } else {
+            OLM_MAX_HITS
+        };
+        let max_hit = if state.final_phase {
+            max_hits[2]
+        } else if state.phase == 0 {
+            max_hits[0]
+        } else {
+            max_hits[1]
+        };
+        self.mechanics.olm_attack(
+            &mut self.parts[OlmTarget::Head.index()],
+            max_hit,
+            &mut self.player,
+            &mut self.config,
+            vars,
+            &mut self.rng,
+        )
+    }
+
+    fn player_attack_or_spec(
+        &mut self,
+        state: &OlmState,
+        vars: &mut FightVars,
+    ) -> Result<(), SimulationError> {
+        let target = &mut self.parts[state.target.index()];
+        let did_spec = if let Some(ref mut spec_config) = self.spec_config {
+            self.mechanics.player_special_attack(
+                &mut self.player,
+                target,
+                spec_config,
+                &mut self.spec_state,
+                state,
+                &mut self.rng,
+                &self.limiter,
+                vars,
+                &mut self.config.logger,
+            )?
+        } else {
+            false
+        };
+
+        if !did_spec {
+            self.mechanics.player_attack(
+                &mut self.player,
+                target,
+                &mut self.rng,
+                &self.limiter,
+                vars,
+                &mut self.config.logger,
+            );
+        }
+
+        Ok(())
+    }
+
+    fn simulate_olm_fight(&mut self) -> Result<FightResult, SimulationError> {
+        if let Some(ref spec_config) = self.spec_config
+            && let Err(e) = spec_config.validate()
+        {
+            return Err(SimulationError::ConfigError(e));
+        }
+
+        let mut vars = FightVars::new();
+        let mut state = OlmState::default();
+        self.parts = self.templates.clone();
+        self.player.current_switch = None;
+
+        // Log the setup first, since it starts a fresh trace
+        let logging_enabled = self.config.logger.enabled;
+        if logging_enabled {
+            self.config
+                .logger
+                .log_initial_setup(&self.player, &self.parts[OlmTarget::Head.index()]);
+        }
+
+        self.enter_phase(&mut state, 0)?;
+
+        while self.is_alive(OlmTarget::Head) {
+            state.tick_counter = vars.tick_counter;
+            if !state.final_phase
+                && !self.is_alive(OlmTarget::MageHand)
+                && !self.is_alive(OlmTarget::MeleeHand)
+            {
+                let next_phase = state.phase + 1;
+                self.enter_phase(&mut state, next_phase)?;
+            }
+            self.process_claws(&mut state)?;
+
+            if vars.tick_counter % OLM_REGEN_TICKS == 0 {
+                for part in self.parts.iter_mut() {
+                    self.mechanics
+                        .monster_regen_stats(part, &vars, &mut self.config.logger);
+                }
+            }
+
+            // Regen 1 HP for player every 100 ticks
+            if vars.tick_counter % constants::PLAYER_REGEN_TICKS == 0 {
+                self.mechanics
+                    .player_regen(&mut self.player, &vars, &mut self.config.logger);
+            }
+
+            self.mechanics.decrement_eat_delay(&mut vars);
+            self.mechanics.process_player_effects(
+                &mut self.player,
+                &mut vars,
+                &mut self.config.logger,
+            );
+            if !self.mechanics.handle_eating(
+                &mut self.config,
+                &mut self.inventory,
+                &mut vars,
+                &mut self.player,
+            ) {
+                return self.mechanics.process_supply_out(
+                    &vars,
+                    &self.parts[OlmTarget::Head.index()],
+                    &mut self.config.logger,
+                );
+            }
+
+            if let Some(prayer_config) = self.config.prayer_drain {
+                let attacking = vars.tick_counter == vars.attack_tick;
+                self.mechanics.process_prayer(
+                    &mut self.player,
+                    &self.parts[state.target.index()],
+                    &prayer_config,
+                    &mut self.inventory,
+                    attacking,
+                    &mut vars,
+                    &mut self.config.logger,
+                )?;
+            }
+
+            if let Some(redose) = self.config.redose {
+                self.mechanics.process_redose(
+                    &mut self.player,
+                    &self.parts[state.target.index()],
+                    &redose,
+                    &mut self.inventory,
+                    &mut vars,
+                    &mut self.config.logger,
+                );
+            }
+
+            if vars.tick_counter == vars.attack_tick {
+                if !state.is_targetable() || !self.is_alive(state.target) {
+                    vars.attack_tick += 1;
+                } else {
+                    self.player_attack_or_spec(&state, &mut vars)?;
+                }
+            }
+
+            if let Some(thrall) = self.config.thralls
+                && vars.tick_counter == vars.thrall_attack_tick
+                && state.is_targetable()
+            {
+                self.mechanics.thrall_attack(
+                    &mut self.parts[state.target.index()],
+                    thrall,
+                    &mut vars,
+                    &mut self.rng,
+                    &mut self.config.logger,
+                );
+            }
+
+            for part in self.parts.iter_mut() {
+                self.mechanics
+                    .process_monster_effects(part, &vars, &mut self.config.logger);
+            }
+
+            if state.is_targetable() && vars.tick_counter >= state.olm_attack_tick {
+                self.olm_action(&mut state, &mut vars)?;
+                state.olm_attack_tick = vars.tick_counter + OLM_ATTACK_SPEED;
+            }
+
+            self.mechanics.process_spec_timers(
+                &mut self.player,
+                self.spec_config.as_ref(),
+                &mut self.spec_state,
+                &vars,
+                &mut self.config.logger,
+            );
+
+            // Increment tick counter
+            vars.tick_counter += 1;
+
+            if self.player.stats.hitpoints.current == 0 {
+                return self.mechanics.process_player_death(
+                    &vars,
+                    &self.parts[OlmTarget::Head.index()],
+                    &mut self.config.logger,
+                );
+            }
+        }
+
+        let remove_final_attack_delay = true;
+        self.mechanics.get_fight_result(
+            &self.parts[OlmTarget::Head.index()],
+            &vars,
+            &mut self.config.logger,
+            remove_final_attack_delay,
+        )
+    }
+}
+
+impl Simulation for OlmFight {
+    fn simulate(&mut self) -> Result<FightResult, SimulationError> {
+        self.simulate_olm_fight()
+    }
+
+    fn is_immune(&self) -> bool {
+        // Immune if the switch for any part can't damage it
+        [OlmTarget::MageHand, OlmTarget::MeleeHand, OlmTarget::Head]
+            .iter()
+            .any(|&target| {
+                let mut player = self.player.clone();
+                player.switches = self.target_switches[target.index()].clone();
+                player.current_switch = None;
+                player
+                    .switch(self.config.switches.switch_for(target))
+                    .is_err()
+                    || self.templates[target.index()].is_immune(&player)
+            })
+    }
+
+    fn player(&self) -> &Player {
+        &self.player
+    }
+
+    fn monster(&self) -> &Monster {
+        &self.parts[OlmTarget::Head.index()]
+    }
+
+    fn set_attack_function(&mut self) {
+        // Attack functions come with each gear switch
+    }
+
+    fn reset(&mut self) {
+        self.config.logger.clear();
+        if let Some(ref mut spec_config) = self.spec_config {
+            let restore_spec = self.spec_state.on_kill(&mut self.player, spec_config);
+            self.player.reset_current_stats(restore_spec);
+        } else {
+            self.player.reset_current_stats(true);
+        }
+        self.parts = self.templates.clone();
+        self.inventory = self.config.inventory.clone();
+    }
+
+    fn next_kill(&mut self, downtime_ticks: u32) {
+        self.mechanics.prepare_next_kill(
+            &mut self.player,
+            self.spec_config.as_mut(),
+            &mut self.spec_state,
+            downtime_ticks,
+            &mut self.config.logger,
+        );
+        self.parts = self.templates.clone();
+    }
+
+    fn inventory(&self) -> Option<&Inventory> {
+        Some(&self.inventory)
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.rng = SmallRng::seed_from_u64(seed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::loadouts;
+    use crate::utils::logging::{FightEventKind, FightTrace};
+
+    // Melee main with magic and ranged switches
+    fn olm_player() -> Player {
+        let mut player = loadouts::max_melee_player();
+        for loadout in [
+            loadouts::max_melee_player(),
+            loadouts::max_mage_sang_staff_player(),
+            loadouts::max_ranged_zcb_player(),
+        ] {
+            player.switches.push(GearSwitch::from(&loadout));
+        }
+        player
+    }
+
+    fn fight_trace(config: OlmConfig) -> FightTrace {
+        let config = OlmConfig {
+            logger: FightLogger::new(true),
+            seed: Some(1),
+            ..config
+        };
+        let mut fight =
+            OlmFight::new(olm_player(), config, None).expect("Error setting up Olm fight.");
+        let result = match fight.simulate() {
+            Ok(result)
+            | Err(SimulationError::SupplyOutError(result))
+            | Err(SimulationError::PlayerDeathError(result)) => result,
+            Err(e) => panic!("Unexpected error: {e:?}"),
+        };
+        result.trace.unwrap()
+    }
+
+    // Gear used on each stretch of damage in the first phase, and the part's HP at the end of it
+    fn first_phase_split(config: OlmConfig) -> Vec<(String, u32)> {
+        let mut switch = String::new();
+        let mut split: Vec<(String, u32)> = Vec::new();
+        for event in fight_trace(config).events {
+            match event.kind {
+                FightEventKind::PhaseChange { phase, .. } if phase == "2" => break,
+                FightEventKind::GearSwitch { switch: label } => switch = label,
+                FightEventKind::MonsterDamage { hp, .. } => match split.last_mut() {
+                    Some((last, last_hp)) if *last == switch => *last_hp = hp,
+                    _ => split.push((switch.clone(), hp)),
+                },
+                _ => {}
+            }
+        }
+        split
+    }
+
+    #[test]
+    fn test_hand_strategy_order() {
+        // Without prepping, each strategy takes its first claw all the way down before touching the other
+        for (hand_strategy, first, second) in [
+            (OlmHandStrategy::MageHand, "Magic", "Melee"),
+            (OlmHandStrategy::MeleeHand, "Melee", "Magic"),
+        ] {
+            let split = first_phase_split(OlmConfig {
+                hand_strategy,
+                prep_hp: None,
+                claw_revive_ticks: None,
+                ..OlmConfig::default()
+            });
+            assert_eq!(split, vec![(first.to_string(), 0), (second.to_string(), 0)]);
+        }
+    }
+
+    #[test]
+    fn test_mage_hand_first_cuts_specials() {
+        // Specials stop once the mage hand is down, so killing it first leaves fewer of them
+        let specials = |hand_strategy: OlmHandStrategy| {
+            fight_trace(OlmConfig {
+                hand_strategy,
+                prep_hp: None,
+                claw_revive_ticks: None,
+                ..OlmConfig::default()
+            })
+            .events
+            .into_iter()
+            .take_while(|event| {
+                !matches!(
+                    &event.kind,
+                    FightEventKind::PhaseChange { phase, .. } if phase == "2"
+                )
+            })
+            .filter(|event| {
+                matches!(
+                    &event.kind,
+                    FightEventKind::MonsterSpecial { monster, .. } if monster == "Olm"
+                )
+            })
+            .count()
+        };
+        assert!(specials(OlmHandStrategy::MageHand) < specials(OlmHandStrategy::MeleeHand));
+    }
+
+    #[test]
+    fn test_prep_hp_split() {
+        // Prepping brings the second claw down to the prep HP, then finishes the first claw before it
+        for (hand_strategy, first, second) in [
+            (OlmHandStrategy::MageHand, "Magic", "Melee"),
+            (OlmHandStrategy::MeleeHand, "Melee", "Magic"),
+        ] {
+            let split = first_phase_split(OlmConfig {
+                hand_strategy,
+                prep_hp: Some(150),
+                claw_revive_ticks: None,
+                ..OlmConfig::default()
+            });
+            let labels: Vec<&str> = split.iter().map(|(label, _)| label.as_str()).collect();
+            assert_eq!(labels, [second, first, second]);
+            assert!(split[0].1 > 0 && split[0].1 <= 150);
+            assert_eq!((split[1].1, split[2].1), (0, 0));
+        }
+    }
+
+    #[test]
+    fn test_claw_revives_without_prep() {
+        // With a short revive window, leaving the second claw at full HP means the first comes back
+        let revived = |prep_hp: Option<u32>, claw_revive_ticks: Option<i32>| {
+            fight_trace(OlmConfig {
+                prep_hp,
+                claw_revive_ticks,
+                ..OlmConfig::default()
+            })
+            .events
+            .into_iter()
+            .any(|event| {
+                matches!(
+                    event.kind,
+                    FightEventKind::MonsterRevive { monster } if monster == "Olm's MageHand"
+                )
+            })
+        };
+        assert!(revived(None, Some(10)));
+        assert!(!revived(None, None));
+        assert!(!revived(Some(20), Some(50)));
+    }
+
+    #[test]
+    fn test_party_and_cm_scaling() {
+        let fight = |config: OlmConfig| OlmFight::new(olm_player(), config, None).unwrap();
+        let solo = fight(OlmConfig::default());
+        let party = fight(OlmConfig {
+            party_size: 8,
+            ..OlmConfig::default()
+        });
+        let cm = fight(OlmConfig {
+            challenge_mode: true,
+            ..OlmConfig::default()
+        });
+
+        let head = |fight: &OlmFight| {
+            fight.templates[OlmTarget::Head.index()]
+                .stats
+                .hitpoints
+                .base
+        };
+        assert_eq!(head(&solo), 800);
+        assert_eq!(head(&party), 3600);
+        assert_eq!(head(&cm), 1200);
+        assert_eq!(party.config.claw_phases(), solo.config.claw_phases() + 1);
+    }
+
+    #[test]
+    fn test_missing_switch() {
+        let player = loadouts::max_melee_player();
+        assert!(matches!(
+            OlmFight::new(player, OlmConfig::default(), None),
+            Err(SimulationError::SwitchingError(
+                PlayerError::GearSwitchNotFound(_)
+            ))
+        ));
+    }
+}
@@ -0,0 +1,1334 @@
+use crate::calc::rolls::calc_active_player_rolls;
+use crate::combat::attacks::effects::PlayerVenom;
+use crate::combat::limiters::Limiter;
+use crate::combat::mechanics::{Mechanics, handle_recoil};
+use crate::combat::prayer::PrayerDrainConfig;
+use crate::combat::simulation::{FightResult, FightVars, Simulation, assign_limiter, seeded_rng};
+use crate::combat::spec::{CoreCondition, SpecCondition, SpecConfig, SpecState};
+use crate::combat::thralls::Thrall;
+use crate::constants;
+use crate::error::{ScriptError, SimulationError};
+use crate::types::food::Foods;
+use crate::types::inventory::Inventory;
+use crate::types::monster::{AttackType, Monster, MonsterMaxHit};
+use crate::types::monster_specials::MonsterSpecial;
+use crate::types::player::Player;
+use crate::types::potions::RedoseConfig;
+use crate::types::prayers::Prayer;
+use crate::utils::logging::FightLogger;
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+const SCRIPTED_REGEN_TICKS: i32 = 100;
+
+// Boss scripts shipped with the crate, looked up by file stem
+const BUNDLED_SCRIPTS: [(&str, &str); 4] = [
+    ("sarachnis", include_str!("../../bosses/sarachnis.toml")),
+    (
+        "kalphite_queen",
+        include_str!("../../bosses/kalphite_queen.toml"),
+    ),
+    ("kreearra", include_str!("../../bosses/kreearra.toml")),
+    ("kril", include_str!("../../bosses/kril.toml")),
+];
+
+/// Declarative description of a boss fight, executed by [`ScriptedFight`].
+///
+/// Scripts are written in TOML or JSON. The boss cycles through the attacks named by its current
+/// phase, fires specials on timers, and moves to later phases as its HP drops (or after a form
+/// dies, for bosses with several HP pools). Phases can spawn minions, which either attack the
+/// player on their own cadence or have to be killed before the boss can be attacked again.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BossScript {
+    pub name: String,
+    pub monster: String,
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Ticks between the boss's attacks, overriding the monster database
+    #[serde(default)]
+    pub attack_speed: Option<i32>,
+    pub attacks: Vec<ScriptAttack>,
+    #[serde(default)]
+    pub specials: Vec<ScriptSpecial>,
+    pub phases: Vec<ScriptPhase>,
+    #[serde(default)]
+    pub minions: Vec<ScriptMinion>,
+}
+
+/// A regular boss attack, rolled against the player's defence like any monster attack.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScriptAttack {
+    pub name: String,
+    pub style: AttackType,
+    pub max_hit: u32,
+    /// Whether a correct protection prayer blocks the attack
+    #[serde(default = "default_prayable")]
+    pub prayable: bool,
+    /// Ticks until the boss's next attack after this one, overriding the boss's attack speed
+    #[serde(default)]
+    pub speed: Option<i32>,
+}
+
+/// A special attack the boss uses on a timer, on top of its attack cycle.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScriptSpecial {
+    /// Ticks between uses
+    pub every: i32,
+    /// Ticks after the phase starts until the first use (defaults to `every`)
+    #[serde(default)]
+    pub first: Option<i32>,
+    /// Chance that the player fails to avoid the special
+    #[serde(default = "default_hit_chance")]
+    pub hit_chance: f64,
+    /// Ticks of attacking the player loses reacting to the special
+    #[serde(default)]
+    pub lost_ticks: i32,
+    /// The attack itself, rolled like any other monster special (named by phases)
+    pub attack: MonsterSpecial,
+}
+
+impl ScriptSpecial {
+    pub fn name(&self) -> &str {
+        &self.attack.name
+    }
+}
+
+/// A stage of the fight with its own attack cycle, specials and minion spawns.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScriptPhase {
+    pub name: String,
+    /// Monster version to switch to on entering the phase, e.g., a new form
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Start the phase once the boss drops below this percentage of its HP
+    #[serde(default)]
+    pub hp_below: Option<f64>,
+    /// Start the phase when the previous form dies, with a fresh HP pool
+    #[serde(default)]
+    pub after_death: bool,
+    /// Attack names used in order (or picked at random)
+    pub cycle: Vec<String>,
+    #[serde(default)]
+    pub random: bool,
+    /// Ticks the boss is untargetable and doesn't attack after the phase starts
+    #[serde(default)]
+    pub transition_ticks: i32,
+    /// Minions spawned on entering the phase
+    #[serde(default)]
+    pub spawn: Vec<String>,
+    /// Specials active during the phase
+    #[serde(default)]
+    pub specials: Vec<String>,
+}
+
+/// A minion spawned by a phase, only blocked when its style matches the player's overhead.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScriptMinion {
+    pub name: String,
+    pub monster: String,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default = "default_minion_count")]
+    pub count: u32,
+    pub style: AttackType,
+    pub max_hit: u32,
+    #[serde(default)]
+    pub attack_speed: Option<i32>,
+    /// Whether the player kills this minion before returning to the boss
+    #[serde(default)]
+    pub kill_first: bool,
+}
+
+fn default_prayable() -> bool {
+    true
+}
+
+fn default_hit_chance() -> f64 {
+    1.0
+}
+
+fn default_minion_count() -> u32 {
+    1
+}
+
+// Script names resolved to indices, so the fight never has to look names up
+#[derive(Debug, Clone, PartialEq)]
+struct ResolvedPhase {
+    cycle: Vec<usize>,
+    specials: Vec<usize>,
+    spawn: Vec<usize>,
+}
+
+impl BossScript {
+    pub fn from_toml_str(toml_str: &str) -> Result<Self, ScriptError> {
+        let script: Self = toml::from_str(toml_str)?;
+        script.validate()?;
+        Ok(script)
+    }
+
+    pub fn from_json_str(json_str: &str) -> Result<Self, ScriptError> {
+        let script: Self = serde_json::from_str(json_str)?;
+        script.validate()?;
+        Ok(script)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, ScriptError> {
+        // Pick the format from the file extension
+        let contents = std::fs::read_to_string(path)?;
+        let extension = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        match extension.as_str() {
+            "toml" => Self::from_toml_str(&contents),
+            "json" => Self::from_json_str(&contents),
+            other => Err(ScriptError::UnsupportedFormat(other.to_string())),
+        }
+    }
+
+    /// Load one of the scripts bundled with the crate, e.g., "sarachnis".
+    pub fn bundled(name: &str) -> Result<Self, ScriptError> {
+        let name = name
+            .to_lowercase()
+            .replace('\'', "")
+            .replace([' ', '-'], "_");
+        BUNDLED_SCRIPTS
+            .iter()
+            .find(|(bundled, _)| *bundled == name)
+            .ok_or(ScriptError::BundledScriptNotFound(name.clone()))
+            .and_then(|(_, contents)| Self::from_toml_str(contents))
+    }
+
+    /// Names of the scripts bundled with the crate.
+    pub fn bundled_names() -> Vec<&'static str> {
+        BUNDLED_SCRIPTS.iter().map(|(name, _)| *name).collect()
+    }
+
+    /// Check that every name resolves and the phase triggers make sense.
+    pub fn validate(&self) -> Result<(), ScriptError> {
+        self.resolve().map(|_| ())
+    }
+
+    fn resolve(&self) -> Result<Vec<ResolvedPhase>, ScriptError> {
+        fn find<T>(
+            items: &[T],
+            name: &str,
+            kind: &'static str,
+            item_name: impl Fn(&T) -> &str,
+        ) -> Result<usize, ScriptError> {
+            items
+                .iter()
+                .position(|item| item_name(item) == name)
+                .ok_or_else(|| ScriptError::UnknownName {
+                    kind,
+                    name: name.to_string(),
+                })
+        }
+
+        if self.attacks.iter().any(|attack| {
+            matches!(attack.style, AttackType::Special | AttackType::None)
+                || attack.speed.is_some_and(|speed| speed <= 0)
+        }) {
+            return Err(ScriptError::Invalid(
+                "Attacks need a combat style and a positive speed.".to_string(),
+            ));
+        }
+        if self
+            .specials
+            .iter()
+            .any(|special| special.every <= 0 || !(0.0..=1.0).contains(&special.hit_chance))
+        {
+            return Err(ScriptError::Invalid(
+                "Specials need a positive interval and a hit chance between 0 and 1.".to_string(),
+            ));
+        }
+        if self.minions.iter().any(|minion| {
+            matches!(minion.style, AttackType::Special | AttackType::None)
+                || minion.attack_speed.is_some_and(|speed| speed <= 0)
+        }) {
+            return Err(ScriptError::Invalid(
+                "Minions need a combat style and a positive attack speed.".to_string(),
+            ));
+        }
+
+        let Some(first) = self.phases.first() else {
+            return Err(ScriptError::InvalidPhases(
+                "At least one phase is required.".to_string(),
+            ));
+        };
+        if first.hp_below.is_some() || first.after_death {
+            return Err(ScriptError::InvalidPhases(format!(
+                "The first phase ({}) can't have a trigger.",
+                first.name
+            )));
+        }
+
+        // HP thresholds must decrease within each HP pool, starting over after a death
+        let mut last_threshold = 100.0;
+        let mut resolved = Vec::with_capacity(self.phases.len());
+        for (index, phase) in self.phases.iter().enumerate() {
+            match (index, phase.hp_below, phase.after_death) {
+                (0, _, _) => {}
+                (_, Some(_), true) | (_, None, false) => {
+                    return Err(ScriptError::InvalidPhases(format!(
+                        "Phase {} needs exactly one of hp_below or after_death.",
+                        phase.name
+                    )));
+                }
+                (_, Some(threshold), false) => {
+                    if !(0.0..last_threshold).contains(&threshold) || threshold == 0.0 {
+                        return Err(ScriptError::InvalidPhases(format!(
+                            "Phase {} has threshold {threshold}, which must be below {last_threshold} and above 0.",
+                            phase.name
+                        )));
+                    }
+                    last_threshold = threshold;
+                }
+                (_, None, true) => last_threshold = 100.0,
+            }
+
+            if phase.cycle.is_empty() {
+                return Err(ScriptError::InvalidPhases(format!(
+                    "Phase {} has no attacks.",
+                    phase.name
+                )));
+            }
+            if phase.transition_ticks < 0 {
+                return Err(ScriptError::InvalidPhases(format!(
+                    "Phase {} has negative transition ticks.",
+                    phase.name
+                )));
+            }
+
+            resolved.push(ResolvedPhase {
+                cycle: phase
+                    .cycle
+                    .iter()
+                    .map(|name| find(&self.attacks, name, "attack", |a| &a.name))
+                    .collect::<Result<_, _>>()?,
+                specials: phase
+                    .specials
+                    .iter()
+                    .map(|name| find(&self.specials, name, "special", |s| s.name()))
+                    .collect::<Result<_, _>>()?,
+                spawn: phase
+                    .spawn
+                    .iter()
+                    .map(|name| find(&self.minions, name, "minion", |m| &m.name))
+                    .collect::<Result<_, _>>()?,
+            });
+        }
+
+        Ok(resolved)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ScriptedConfig {
+    pub inventory: Inventory,
+    pub eat_hp: u32,
+    pub flick_success: f64, // Chance the right protection prayer is up in time against prayable attacks
+    pub prayer_drain: Option<PrayerDrainConfig>,
+    pub redose: Option<RedoseConfig>,
+    pub thralls: Option<Thrall>,
+    pub logger: FightLogger,
+    pub seed: Option<u64>,
+}
+
+impl Default for ScriptedConfig {
+    fn default() -> Self {
+        Self {
+            inventory: Inventory::builder()
+                .food(Foods::Shark, 24)
+                .build()
+                .expect("Error building inventory."),
+            eat_hp: 50,
+            flick_success: 1.0,
+            prayer_drain: None,
+            redose: None,
+            thralls: None,
+            logger: FightLogger::new(false),
+            seed: None,
+        }
+    }
+}
+
+// Spec conditions for scripted bosses
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptedCondition {
+    Core(CoreCondition),
+    Phase(String),   // The boss is in the phase with this name
+    TargetingMinion, // The player is attacking a minion rather than the boss
+}
+
+impl SpecCondition for ScriptedCondition {
+    type BossState = ScriptedState;
+
+    fn evaluate(&self, player: &Player, monster: &Monster, boss_state: &Self::BossState) -> bool {
+        match self {
+            Self::Core(core) => core.evaluate(player, monster, &()),
+            Self::Phase(name) => boss_state.phase_name == *name,
+            Self::TargetingMinion => boss_state.target.is_some(),
+        }
+    }
+
+    fn as_core(&self) -> Option<&CoreCondition> {
+        match self {
+            Self::Core(core) => Some(core),
+            _ => None,
+        }
+    }
+
+    fn from_core(core: CoreCondition) -> Self {
+        Self::Core(core)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ScriptedState {
+    tick_counter: i32,
+    phase: usize,
+    phase_name: String,
+    cycle_index: usize,
+    boss_attack_tick: i32,
+    targetable_tick: i32,
+    special_ticks: Vec<(usize, i32)>, // Next use of each active special
+    target: Option<usize>,            // Index of the minion the player is attacking
+    venom: Option<PlayerVenom>,
+}
+
+impl Default for ScriptedState {
+    fn default() -> Self {
+        Self {
+            tick_counter: 0,
+            phase: 0,
+            phase_name: String::new(),
+            cycle_index: 0,
+            boss_attack_tick: 1,
+            targetable_tick: 0,
+            special_ticks: Vec::new(),
+            target: None,
+            venom: None,
+        }
+    }
+}
+
+impl ScriptedState {
+    fn is_targetable(&self) -> bool {
+        self.tick_counter >= self.targetable_tick
+    }
+}
+
+// A minion's monster with its script settings resolved
+#[derive(Debug, Clone)]
+struct MinionTemplate {
+    monster: Monster,
+    style: AttackType,
+    attack_speed: i32,
+    kill_first: bool,
+}
+
+#[derive(Debug, Clone)]
+struct ActiveMinion {
+    template: usize,
+    monster: Monster,
+    attack_tick: i32,
+}
+
+#[derive(Clone)]
+struct ScriptedMechanics;
+
+impl Mechanics for ScriptedMechanics {}
+
+impl ScriptedMechanics {
+    #[allow(clippy::too_many_arguments)]
+    fn boss_attack(
+        &self,
+        boss: &mut Monster,
+        attack: &ScriptAttack,
+        player: &mut Player,
+        config: &mut ScriptedConfig,
+        vars: &mut FightVars,
+        rng: &mut SmallRng,
+    ) -> Result<(), SimulationError> {
+        // Each attack brings its own max hit, so set it right before rolling
+        boss.max_hits = Some(vec![MonsterMaxHit::new(attack.max_hit, attack.style)]);
+
+        // The player switches to the matching protection prayer for prayable attacks (unless a
+        // special has turned their prayers off), and unprayable ones get through whatever
+        // overhead is up
+        let flick_success = if attack.prayable && vars.disabled_prayers.is_none() {
+            player.set_protection_prayer(Prayer::protection_against(attack.style));
+            config.flick_success
+        } else {
+            0.0
+        };
+        let hit = boss.attack_with_flick(player, Some(attack.style), rng, false, flick_success)?;
+
+        if config.logger.enabled {
+            config
+                .logger
+                .log_named_attack(vars.tick_counter, &boss.info.name, &attack.name);
+            config.logger.log_monster_attack(
+                boss,
+                vars.tick_counter,
+                hit.damage,
+                hit.success,
+                Some(attack.style),
+            );
+        }
+
+        if hit.success {
+            player.take_damage(hit.damage);
+            vars.damage_taken += hit.damage;
+            handle_recoil(player, boss, &hit, vars, &mut config.logger);
+        }
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn special_attack(
+        &self,
+        boss: &mut Monster,
+        special: &ScriptSpecial,
+        player: &mut Player,
+        venom: &mut Option<PlayerVenom>,
+        vars: &mut FightVars,
+        rng: &mut SmallRng,
+        logger: &mut FightLogger,
+    ) -> Result<(), SimulationError> {
+        vars.attack_tick += special.lost_ticks;
+
+        if !rng.random_bool(special.hit_chance) {
+            if logger.enabled {
+                logger.log_special_avoided(vars.tick_counter, special.name());
+            }
+            return Ok(());
+        }
+
+        let hit = boss.special_attack(special.name(), player, rng, false)?;
+        self.apply_monster_special(boss, player, &hit, venom, vars, logger);
+        Ok(())
+    }
+
+    fn minion_attack(
+        &self,
+        minion: &mut Monster,
+        style: AttackType,
+        player: &mut Player,
+        vars: &mut FightVars,
+        rng: &mut SmallRng,
+        logger: &mut FightLogger,
+    ) -> Result<(), SimulationError> {
+        let hit = minion.attack(player, Some(style), rng, false)?;
+
+        if logger.enabled {
+            logger.log_monster_attack(
+                minion,
+                vars.tick_counter,
+                hit.damage,
+                hit.success,
+                Some(style),
+            );
+        }
+
+        if hit.success {
+            player.take_damage(hit.damage);
+            vars.damage_taken += hit.damage;
+            handle_recoil(player, minion, &hit, vars, logger);
+        }
+
+        Ok(())
+    }
+
+    fn handle_eating(
+        &self,
+        config: &mut ScriptedConfig,
+        inventory: &mut Inventory,
+        vars: &mut FightVars,
+        player: &mut Player,
+    ) -> bool {
+        // Eat below the threshold, returning false if the player needed to eat but had no food left
+        if player.stats.hitpoints.current > config.eat_hp {
+            return true;
+        }
+        if !inventory.has_food() {
+            return false;
+        }
+
+        if let Some(attack_delay) =
+            self.eat_next_food(player, inventory, false, vars, &mut config.logger)
+        {
+            vars.attack_tick += attack_delay;
+        }
+        true
+    }
+}
+
+/// Generic simulation that runs any [`BossScript`].
+#[derive(Clone)]
+pub struct ScriptedFight {
+    player: Player,
+    script: BossScript,
+    phases: Vec<ResolvedPhase>,
+    forms: Vec<Monster>, // The boss's monster for each phase
+    boss: Monster,
+    boss_attack_speed: i32,
+    minion_templates: Vec<MinionTemplate>,
+    minions: Vec<ActiveMinion>,
+    limiter: Option<Box<dyn Limiter>>,
+    rng: SmallRng,
+    config: ScriptedConfig,
+    mechanics: ScriptedMechanics,
+    spec_config: Option<SpecConfig<ScriptedCondition>>,
+    spec_state: SpecState,
+    inventory: Inventory,
+}
+
+impl ScriptedFight {
+    pub fn new(
+        mut player: Player,
+        script: BossScript,
+        config: ScriptedConfig,
+        spec_config: Option<SpecConfig<ScriptedCondition>>,
+    ) -> Result<Self, SimulationError> {
+        let phases = script.resolve()?;
+
+        if !(0.0..=1.0).contains(&config.flick_success) {
+            return Err(SimulationError::ConfigError(
+                "Flick success must be between 0 and 1.".to_string(),
+            ));
+        }
+
+        let create = |name: &str, version: Option<&str>| {
+            Monster::new(name, version)
+                .map_err(|_| SimulationError::MonsterCreationError(name.to_string()))
+        };
+
+        // Phases without a version of their own keep the previous phase's form
+        let mut forms: Vec<Monster> = Vec::with_capacity(script.phases.len());
+        for phase in &script.phases {
+            let form = match (&phase.version, forms.last()) {
+                (Some(version), _) => create(&script.monster, Some(version))?,
+                (None, Some(previous)) => previous.clone(),
+                (None, None) => create(&script.monster, script.version.as_deref())?,
+            };
+            forms.push(form);
+        }
+
+        let boss_attack_speed = script
+            .attack_speed
+            .or(forms[0].info.attack_speed.map(|speed| speed as i32))
+            .filter(|speed| *speed > 0)
+            .ok_or_else(|| {
+                SimulationError::ConfigError(format!("{} has no attack speed.", script.name))
+            })?;
+
+        let mut minion_templates = Vec::with_capacity(script.minions.len());
+        for minion in &script.minions {
+            let mut monster = create(&minion.monster, minion.version.as_deref())?;
+            monster.max_hits = Some(vec![MonsterMaxHit::new(minion.max_hit, minion.style)]);
+            let attack_speed = minion
+                .attack_speed
+                .or(monster.info.attack_speed.map(|speed| speed as i32))
+                .ok_or_else(|| {
+                    SimulationError::ConfigError(format!("{} has no attack speed.", minion.name))
+                })?;
+            minion_templates.push(MinionTemplate {
+                monster,
+                style: minion.style,
+                attack_speed,
+                kill_first: minion.kill_first,
+            });
+        }
+
+        // Every form can use the script's specials
+        let specials: Vec<MonsterSpecial> = script
+            .specials
+            .iter()
+            .map(|special| special.attack.clone())
+            .collect();
+        for form in &mut forms {
+            form.specials = specials.clone();
+        }
+
+        calc_active_player_rolls(&mut player, &forms[0]);
+        let limiter = assign_limiter(&player, &forms[0]);
+        let rng = seeded_rng(config.seed);
+        let inventory = config.inventory.clone();
+        let boss = forms[0].clone();
+
+        Ok(Self {
+            player,
+            script,
+            phases,
+            forms,
+            boss,
+            boss_attack_speed,
+            minion_templates,
+            minions: Vec::new(),
+            limiter,
+            rng,
+            config,
+            mechanics: ScriptedMechanics,
+            spec_config,
+            spec_state: SpecState::default(),
+            inventory,
+        })
+    }
+
+    pub fn script(&self) -> &BossScript {
+        &self.script
+    }
+
+    // Move to a new phase, swapping the boss's form and spawning the phase's minions
+    fn enter_phase(&mut self, state: &mut ScriptedState, phase: usize) {
+        let script_phase = &self.script.phases[phase];
+        if script_phase.after_death {
+            self.boss = self.forms[phase].clone();
+        } else if script_phase.version.is_some() {
+            // A new form part way through the same HP pool keeps the damage dealt so far
+            let hitpoints = self.boss.stats.hitpoints;
+            self.boss = self.forms[phase].clone();
+            self.boss.stats.hitpoints = hitpoints;
+        }
+
+        state.phase = phase;
+        state.phase_name = script_phase.name.clone();
+        state.cycle_index = 0;
+        state.targetable_tick = state.tick_counter + script_phase.transition_ticks;
+        state.boss_attack_tick = state.boss_attack_tick.max(state.targetable_tick);
+        state.special_ticks = self.phases[phase]
+            .specials
+            .iter()
+            .map(|&index| {
+                let special = &self.script.specials[index];
+                (
+                    index,
+                    state.targetable_tick + special.first.unwrap_or(special.every),
+                )
+            })
+            .collect();
+
+        for &index in &self.phases[phase].spawn {
+            let template = &self.minion_templates[index];
+            for _ in 0..self.script.minions[index].count {
+                self.minions.push(ActiveMinion {
+                    template: index,
+                    monster: template.monster.clone(),
+                    attack_tick: state.tick_counter + template.attack_speed,
+                });
+            }
+        }
+
+        if self.config.logger.enabled {
+            self.config.logger.log_phase_change(
+                state.tick_counter,
+                &self.script.name,
+                &state.phase_name,
+            );
+        }
+
+        self.retarget(state);
+    }
+
+    // Find the next phase to start, if the boss's HP has crossed its trigger
+    fn next_phase(&self, state: &ScriptedState) -> Option<usize> {
+        let next = state.phase + 1;
+        let phase = self.script.phases.get(next)?;
+        let hitpoints = self.boss.stats.hitpoints;
+        if hitpoints.current == 0 {
+            return phase.after_death.then_some(next);
+        }
+        let percent = 100.0 * hitpoints.current as f64 / hitpoints.base.max(1) as f64;
+        phase
+            .hp_below
+            .is_some_and(|threshold| percent < threshold)
+            .then_some(next)
+    }
+
+    // Point the player at the first kill-first minion (or the boss) and recompute their rolls
+    fn retarget(&mut self, state: &mut ScriptedState) {
+        state.target = self
+            .minions
+            .iter()
+            .position(|minion| self.minion_templates[minion.template].kill_first);
+        let target = match state.target {
+            Some(index) => &self.minions[index].monster,
+            None => &self.boss,
+        };
+        calc_active_player_rolls(&mut self.player, target);
+        self.limiter = assign_limiter(&self.player, target);
+    }
+
+    fn player_attack_or_spec(
+        &mut self,
+        state: &mut ScriptedState,
+        vars: &mut FightVars,
+    ) -> Result<(), SimulationError> {
+        let target = match state.target {
+            Some(index) => &mut self.minions[index].monster,
+            None => &mut self.boss,
+        };
+        let did_spec = if let Some(ref mut spec_config) = self.spec_config {
+            self.mechanics.player_special_attack(
+                &mut self.player,
+                target,
+                spec_config,
+                &mut self.spec_state,
+                state,
+                &mut self.rng,
+                &self.limiter,
+                vars,
+                &mut self.config.logger,
+            )?
+        } else {
+            false
+        };
+
+        if !did_spec {
+            self.mechanics.player_attack(
+                &mut self.player,
+                target,
+                &mut self.rng,
+                &self.limiter,
+                vars,
+                &mut self.config.logger,
+            );
+        }
+
+        Ok(())
+    }
+
+    // Clear out dead minions, returning to the boss once the kill-first minions are gone
+    fn remove_dead_minions(&mut self, state: &mut ScriptedState) {
+        let before = self.minions.len();
+        self.minions
+            .retain(|minion| minion.monster.stats.hitpoints.current > 0);
+        if self.minions.len() != before {
+            self.retarget(state);
+        }
+    }
+
+    fn simulate_scripted_fight(&mut self) -> Result<FightResult, SimulationError> {
+        if let Some(ref spec_config) = self.spec_config
+            && let Err(e) = spec_config.validate()
+        {
+            return Err(SimulationError::ConfigError(e));
+        }
+
+        let mut vars = FightVars::new();
+        let mut state = ScriptedState::default();
+        self.minions.clear();
+
+        // Log the setup first, since it starts a fresh trace
+        let logging_enabled = self.config.logger.enabled;
+        if logging_enabled {
+            self.config
+                .logger
+                .log_initial_setup(&self.player, &self.forms[0]);
+        }
+
+        self.enter_phase(&mut state, 0);
+
+        loop {
+            state.tick_counter = vars.tick_counter;
+            while let Some(next) = self.next_phase(&state) {
+                self.enter_phase(&mut state, next);
+            }
+            if self.boss.stats.hitpoints.current == 0 {
+                break;
+            }
+
+            if vars.tick_counter % SCRIPTED_REGEN_TICKS == 0 {
+                self.mechanics
+                    .monster_regen_stats(&mut self.boss, &vars, &mut self.config.logger);
+            }
+
+            // Regen 1 HP for player every 100 ticks
+            if vars.tick_counter % constants::PLAYER_REGEN_TICKS == 0 {
+                self.mechanics
+                    .player_regen(&mut self.player, &vars, &mut self.config.logger);
+            }
+
+            self.mechanics.decrement_eat_delay(&mut vars);
+            self.mechanics.process_player_effects(
+                &mut self.player,
+                &mut vars,
+                &mut self.config.logger,
+            );
+            self.mechanics.process_player_venom(
+                &mut self.player,
+                &mut state.venom,
+                &mut vars,
+                &mut self.config.logger,
+            );
+            self.mechanics.process_prayer_disable(
+                &mut self.player,
+                &self.boss,
+                &mut vars,
+                &mut self.config.logger,
+            );
+            if !self.mechanics.handle_eating(
+                &mut self.config,
+                &mut self.inventory,
+                &mut vars,
+                &mut self.player,
+            ) {
+                return self.mechanics.process_supply_out(
+                    &vars,
+                    &self.boss,
+                    &mut self.config.logger,
+                );
+            }
+
+            if let Some(prayer_config) = self.config.prayer_drain {
+                let attacking = vars.tick_counter == vars.attack_tick;
+                self.mechanics.process_prayer(
+                    &mut self.player,
+                    &self.boss,
+                    &prayer_config,
+                    &mut self.inventory,
+                    attacking,
+                    &mut vars,
+                    &mut self.config.logger,
+                )?;
+            }
+
+            if let Some(redose) = self.config.redose {
+                self.mechanics.process_redose(
+                    &mut self.player,
+                    &self.boss,
+                    &redose,
+                    &mut self.inventory,
+                    &mut vars,
+                    &mut self.config.logger,
+                );
+            }
+
+            if vars.tick_counter == vars.attack_tick {
+                if state.target.is_none() && !state.is_targetable() {
+                    vars.attack_tick += 1;
+                } else {
+                    self.player_attack_or_spec(&mut state, &mut vars)?;
+                }
+            }
+
+            if let Some(thrall) = self.config.thralls
+                && vars.tick_counter == vars.thrall_attack_tick
+            {
+                if state.target.is_none() && !state.is_targetable() {
+                    vars.thrall_attack_tick += 1;
+                } else {
+                    let target = match state.target {
+                        Some(index) => &mut self.minions[index].monster,
+                        None => &mut self.boss,
+                    };
+                    self.mechanics.thrall_attack(
+                        target,
+                        thrall,
+                        &mut vars,
+                        &mut self.rng,
+                        &mut self.config.logger,
+                    );
+                }
+            }
+
+            self.mechanics
+                .process_monster_effects(&mut self.boss, &vars, &mut self.config.logger);
+
+            if self.boss.stats.hitpoints.current > 0 && state.is_targetable() {
+                if vars.tick_counter >= state.boss_attack_tick {
+                    let cycle = &self.phases[state.phase].cycle;
+                    let index = if self.script.phases[state.phase].random {
+                        cycle[self.rng.random_range(0..cycle.len())]
+                    } else {
+                        cycle[state.cycle_index % cycle.len()]
+                    };
+                    let attack = &self.script.attacks[index];
+                    self.mechanics.boss_attack(
+                        &mut self.boss,
+                        attack,
+                        &mut self.player,
+                        &mut self.config,
+                        &mut vars,
+                        &mut self.rng,
+                    )?;
+                    state.cycle_index += 1;
+                    state.boss_attack_tick =
+                        vars.tick_counter + attack.speed.unwrap_or(self.boss_attack_speed);
+                }
+
+                for (index, tick) in state.special_ticks.iter_mut() {
+                    if vars.tick_counter >= *tick {
+                        let special = &self.script.specials[*index];
+                        self.mechanics.special_attack(
+                            &mut self.boss,
+                            special,
+                            &mut self.player,
+                            &mut state.venom,
+                            &mut vars,
+                            &mut self.rng,
+                            &mut self.config.logger,
+                        )?;
+                        *tick = vars.tick_counter + special.every;
+                    }
+                }
+            }
+
+            for minion in self.minions.iter_mut() {
+                if vars.tick_counter >= minion.attack_tick {
+                    let template = &self.minion_templates[minion.template];
+                    self.mechanics.minion_attack(
+                        &mut minion.monster,
+                        template.style,
+                        &mut self.player,
+                        &mut vars,
+                        &mut self.rng,
+                        &mut self.config.logger,
+                    )?;
+                    minion.attack_tick = vars.tick_counter + template.attack_speed;
+                }
+            }
+            self.remove_dead_minions(&mut state);
+
+            self.mechanics.process_spec_timers(
+                &mut self.player,
+                self.spec_config.as_ref(),
+                &mut self.spec_state,
+                &vars,
+                &mut self.config.logger,
+            );
+
+            // Increment tick counter
+            vars.tick_counter += 1;
+
+            if self.player.stats.hitpoints.current == 0 {
+                return self.mechanics.process_player_death(
+                    &vars,
+                    &self.boss,
+                    &mut self.config.logger,
+                );
+            }
+        }
+
+        let remove_final_attack_delay = true;
+        self.mechanics.get_fight_result(
+            &self.boss,
+            &vars,
+            &mut self.config.logger,
+            remove_final_attack_delay,
+        )
+    }
+}
+
+impl Simulation for ScriptedFight {
+    fn simulate(&mut self) -> Result<FightResult, SimulationError> {
+        // A prayer-disabling special can leave the player's prayers off when the fight ends
+        let prayers = self.player.prayers.clone();
+        let result = self.simulate_scripted_fight();
+        self.player.prayers = prayers;
+        result
+    }
+
+    fn is_immune(&self) -> bool {
+        self.forms.iter().any(|form| form.is_immune(&self.player))
+    }
+
+    fn player(&self) -> &Player {
+        &self.player
+    }
+
+    fn monster(&self) -> &Monster {
+        &self.boss
+    }
+
+    fn set_attack_function(&mut self) {
+        self.player.attack = crate::combat::attacks::standard::get_attack_functions(&self.player);
+        self.player.spec = crate::combat::attacks::specs::get_spec_attack_function(&self.player);
+    }
+
+    fn reset(&mut self) {
+        self.config.logger.clear();
+        if let Some(ref mut spec_config) = self.spec_config {
+            let restore_spec = self.spec_state.on_kill(&mut self.player, spec_config);
+            self.player.reset_current_stats(restore_spec);
+        } else {
+            self.player.reset_current_stats(true);
+        }
+        self.boss = self.forms[0].clone();
+        self.minions.clear();
+        self.inventory = self.config.inventory.clone();
+    }
+
+    fn next_kill(&mut self, downtime_ticks: u32) {
+        self.mechanics.prepare_next_kill(
+            &mut self.player,
+            self.spec_config.as_mut(),
+            &mut self.spec_state,
+            downtime_ticks,
+            &mut self.config.logger,
+        );
+        self.boss = self.forms[0].clone();
+        self.minions.clear();
+    }
+
+    fn inventory(&self) -> Option<&Inventory> {
+        Some(&self.inventory)
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.rng = SmallRng::seed_from_u64(seed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combat::simulation::simulate_n_fights;
+    use crate::types::potions::Potion;
+    use crate::utils::loadouts;
+    use crate::utils::logging::FightEventKind;
+
+    const MINIMAL_SCRIPT: &str = r#"
+        name = "Test"
+        monster = "General Graardor"
+
+        [[attacks]]
+        name = "Smash"
+        style = "Crush"
+        max_hit = 25
+
+        [[phases]]
+        name = "Only"
+        cycle = ["Smash"]
+    "#;
+
+    #[test]
+    fn test_bundled_scripts_parse_and_kill() {
+        for name in BossScript::bundled_names() {
+            let script = BossScript::bundled(name).expect("Error loading bundled script.");
+            let config = ScriptedConfig {
+                seed: Some(3),
+                ..ScriptedConfig::default()
+            };
+            let mut fight =
+                ScriptedFight::new(loadouts::max_ranged_zcb_player(), script, config, None)
+                    .expect("Error setting up scripted fight.");
+            fight.set_attack_function();
+
+            let results = simulate_n_fights(Box::new(fight), 20, false, Some(3)).unwrap();
+            assert!(!results.ttks_ticks.is_empty(), "No kills against {name}");
+        }
+    }
+
+    #[test]
+    fn test_json_matches_toml() {
+        let script = BossScript::from_toml_str(MINIMAL_SCRIPT).unwrap();
+        let json = serde_json::to_string(&script).unwrap();
+        assert_eq!(BossScript::from_json_str(&json).unwrap(), script);
+        assert!(script.attacks[0].prayable);
+    }
+
+    #[test]
+    fn test_validation_errors() {
+        let unknown_attack = MINIMAL_SCRIPT.replace("cycle = [\"Smash\"]", "cycle = [\"Stomp\"]");
+        assert!(matches!(
+            BossScript::from_toml_str(&unknown_attack),
+            Err(ScriptError::UnknownName { kind: "attack", .. })
+        ));
+
+        let triggered_first =
+            MINIMAL_SCRIPT.replace("name = \"Only\"", "name = \"Only\"\nhp_below = 50.0");
+        assert!(matches!(
+            BossScript::from_toml_str(&triggered_first),
+            Err(ScriptError::InvalidPhases(_))
+        ));
+
+        let rising = format!(
+            "{MINIMAL_SCRIPT}\n[[phases]]\nname = \"Two\"\nhp_below = 50.0\ncycle = [\"Smash\"]\n\
+             \n[[phases]]\nname = \"Three\"\nhp_below = 60.0\ncycle = [\"Smash\"]\n"
+        );
+        assert!(matches!(
+            BossScript::from_toml_str(&rising),
+            Err(ScriptError::InvalidPhases(_))
+        ));
+
+        assert!(matches!(
+            BossScript::bundled("Zulrah"),
+            Err(ScriptError::BundledScriptNotFound(_))
+        ));
+        assert!(BossScript::bundled("Kalphite Queen").is_ok());
+    }
+
+    #[test]
+    fn test_flick_success() {
+        let script = BossScript::from_toml_str(MINIMAL_SCRIPT).unwrap();
+        let run = |flick_success: f64| {
+            let config = ScriptedConfig {
+                flick_success,
+                seed: Some(9),
+                ..ScriptedConfig::default()
+            };
+            let mut fight =
+                ScriptedFight::new(loadouts::max_melee_player(), script.clone(), config, None)
+                    .unwrap();
+            fight.set_attack_function();
+            fight.simulate().unwrap()
+        };
+
+        // Perfect prayer blocks every prayable attack
+        assert_eq!(run(1.0).damage_taken, 0);
+        assert!(run(0.0).damage_taken > 0);
+    }
+
+    #[test]
+    fn test_special_effects() {
+        let run = |effect: &str| {
+            let script = BossScript::from_toml_str(&format!(
+                "{MINIMAL_SCRIPT}specials = [\"Curse\"]\n\n[[specials]]\nevery = 5\n\n\
+                 [specials.attack]\nname = \"Curse\"\ndamage = {{ Fixed = 0 }}\n\
+                 effects = [{effect}]\n"
+            ))
+            .unwrap();
+            let config = ScriptedConfig {
+                seed: Some(4),
+                ..ScriptedConfig::default()
+            };
+            let mut fight =
+                ScriptedFight::new(loadouts::max_melee_player(), script, config, None).unwrap();
+            fight.set_attack_function();
+            let prayers = fight.player.prayers.clone();
+            let result = fight.simulate().unwrap();
+            assert_eq!(fight.player.prayers, prayers);
+            result
+        };
+
+        // Perfect prayer blocks every smash, so any damage comes from the curse's effects
+        assert_eq!(run("").damage_taken, 0);
+        assert!(run("\"Venom\"").damage_taken > 0);
+        assert!(run("{ DisablePrayers = 3 }").damage_taken > 0);
+    }
+
+    #[test]
+    fn test_redose_after_stat_drain() {
+        let script = BossScript::from_toml_str(&format!(
+            "{MINIMAL_SCRIPT}specials = [\"Weaken\"]\n\n[[specials]]\nevery = 5\n\n\
+             [specials.attack]\nname = \"Weaken\"\ndamage = {{ Fixed = 0 }}\n\
+             effects = [{{ DrainStat = {{ stat = \"Strength\", amount = 10 }} }}]\n"
+        ))
+        .unwrap();
+        let run = |redose: Option<RedoseConfig>| {
+            let config = ScriptedConfig {
+                inventory: Inventory::builder()
+                    .food(Foods::Shark, 20)
+                    .potion(Potion::SuperCombat, 1)
+                    .build()
+                    .unwrap(),
+                redose,
+                seed: Some(6),
+                ..ScriptedConfig::default()
+            };
+            let mut fight =
+                ScriptedFight::new(loadouts::max_melee_player(), script.clone(), config, None)
+                    .unwrap();
+            fight.set_attack_function();
+            fight.simulate().unwrap();
+            fight.inventory.potion_doses(Potion::SuperCombat)
+        };
+
+        // Doses are only used up once the drains wear the boost down to the threshold
+        assert_eq!(run(None), 4);
+        assert!(
+            run(Some(RedoseConfig {
+                potion: Potion::SuperCombat,
+                redose_at: 5,
+            })) < 4
+        );
+    }
+
+    #[test]
+    fn test_trace_keeps_first_phase() {
+        let script = BossScript::from_toml_str(MINIMAL_SCRIPT).unwrap();
+        let config = ScriptedConfig {
+            logger: FightLogger::new(true),
+            seed: Some(2),
+            ..ScriptedConfig::default()
+        };
+        let mut fight =
+            ScriptedFight::new(loadouts::max_melee_player(), script, config, None).unwrap();
+        fight.set_attack_function();
+        let trace = fight.simulate().unwrap().trace.unwrap();
+        assert!(trace.events.iter().any(|event| matches!(
+            &event.kind,
+            FightEventKind::PhaseChange { monster, phase } if monster == "Test" && phase == "Only"
+        )));
+    }
+
+    #[test]
+    fn test_thralls_wait_for_transition() {
+        let script = BossScript::from_toml_str(&format!(
+            "{MINIMAL_SCRIPT}\n[[phases]]\nname = \"Second\"\nhp_below = 50.0\n\
+             transition_ticks = 20\ncycle = [\"Smash\"]\n"
+        ))
+        .unwrap();
+        let config = ScriptedConfig {
+            thralls: Some(Thrall::GreaterMagic),
+            logger: FightLogger::new(true),
+            seed: Some(5),
+            ..ScriptedConfig::default()
+        };
+        let mut fight =
+            ScriptedFight::new(loadouts::max_melee_player(), script, config, None).unwrap();
+        fight.set_attack_function();
+        let trace = fight.simulate().unwrap().trace.unwrap();
+
+        let transition = trace
+            .events
+            .iter()
+            .find(|event| {
+                matches!(
+                    &event.kind,
+                    FightEventKind::PhaseChange { phase, .. } if phase == "Second"
+                )
+            })
+            .map(|event| event.tick)
+            .unwrap();
+        let untargetable = transition..transition + 20;
+        assert!(!trace.events.iter().any(|event| {
+            untargetable.contains(&event.tick)
+                && matches!(event.kind, FightEventKind::ThrallAttack { .. })
+        }));
+        assert!(trace.events.iter().any(|event| {
+            event.tick >= untargetable.end
+                && matches!(event.kind, FightEventKind::ThrallAttack { .. })
+        }));
+    }
+
+    #[test]
+    fn test_after_death_phase_has_fresh_hp() {
+        let mut script = BossScript::bundled("kalphite_queen").unwrap();
+        script.phases[1].transition_ticks = 0;
+        let config = ScriptedConfig {
+            seed: Some(1),
+            ..ScriptedConfig::default()
+        };
+        let mut fight =
+            ScriptedFight::new(loadouts::max_melee_player(), script, config, None).unwrap();
+        fight.set_attack_function();
+        let result = fight.simulate().unwrap();
+
+        // Both forms have to die, so the player deals more than one HP pool's worth of damage
+        let damage_dealt: u32 = result.hit_amounts.iter().sum();
+        assert!(damage_dealt > fight.forms[0].stats.hitpoints.base);
+        assert_eq!(fight.boss.info.version.as_deref(), Some("Airborne"));
+    }
+}
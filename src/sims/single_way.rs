@@ -3,7 +3,6 @@ use crate::calc::monster_scaling::scale_monster_hp_only;
 use crate::combat::attacks::standard::AttackFn;
 use crate::combat::limiters::Limiter;
 use crate::combat::mechanics::Mechanics;
-use crate::combat::mechanics::handle_blood_fury;
 use crate::combat::simulation::{FightResult, FightVars, Simulation};
 use crate::combat::spec::CoreCondition;
 use crate::combat::spec::SpecConfig;
@@ -11,11 +10,12 @@ use crate::combat::spec::SpecState;
 use crate::combat::thralls::Thrall;
 use crate::constants::P2_WARDEN_IDS;
 use crate::error::SimulationError;
-use crate::types::{monster::Monster, player::GearSwitch, player::Player};
+use crate::types::{monster::Monster, player::Player};
 use crate::utils::logging::FightLogger;
 use rand::SeedableRng;
 use rand::rngs::SmallRng;
 
+#[derive(Clone)]
 pub struct SingleWayFight {
     pub player: Player,
     pub monster: Monster,
@@ -37,10 +37,8 @@ impl SingleWayFight {
         use_logger: bool,
     ) -> Result<SingleWayFight, SimulationError> {
         let limiter = crate::combat::simulation::assign_limiter(&player, &monster);
-        let rng = SmallRng::from_os_rng();
-        let monster_name = monster.info.name.clone();
-        let logger = FightLogger::new(use_logger, monster_name.as_str())
-            .map_err(|e| SimulationError::ConfigError(format!("Error initializing logger: {e}")))?;
+        let rng = crate::combat::simulation::seeded_rng(config.seed);
+        let logger = FightLogger::new(use_logger);
 
         if monster.info.name == "Vardorvis" {
             monster.hp_scaling_table = Some(build_vard_scaling_table(&monster));
@@ -102,6 +100,7 @@ impl Simulation for SingleWayFight {
     }
 
     fn reset(&mut self) {
+        self.logger.clear();
         if let Some(ref mut spec_config) = self.spec_config {
             let restore_spec = self.spec_state.on_kill(&mut self.player, spec_config);
             self.player.reset_current_stats(restore_spec);
@@ -116,6 +115,21 @@ impl Simulation for SingleWayFight {
             self.player.boosts.soulreaper_stacks = stacks;
         }
     }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.rng = SmallRng::seed_from_u64(seed);
+    }
+
+    fn next_kill(&mut self, downtime_ticks: u32) {
+        self.mechanics.prepare_next_kill(
+            &mut self.player,
+            self.spec_config.as_mut(),
+            &mut self.spec_state,
+            downtime_ticks,
+            &mut self.logger,
+        );
+        self.monster.reset();
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -123,6 +137,7 @@ pub struct SingleWayConfig {
     pub thralls: Option<Thrall>,
     pub remove_final_attack_delay: bool,
     pub reset_soulreaper_stacks: Option<u32>,
+    pub seed: Option<u64>,
 }
 
 impl Default for SingleWayConfig {
@@ -131,116 +146,14 @@ impl Default for SingleWayConfig {
             thralls: None,
             remove_final_attack_delay: false,
             reset_soulreaper_stacks: Some(0),
+            seed: None,
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct SingleWayMechanics;
 
-impl SingleWayMechanics {
-    pub fn player_special_attack(
-        fight: &mut SingleWayFight,
-        fight_vars: &mut FightVars,
-    ) -> Result<bool, SimulationError> {
-        if let Some(ref mut spec_config) = fight.spec_config {
-            for strategy in &mut spec_config.strategies {
-                if !strategy.can_execute(&fight.player, &fight.monster, &()) {
-                    continue;
-                }
-
-                // Make sure the current set of gear is added to the player's gear switches to allow switching back
-                if fight.player.current_switch.is_none() {
-                    let current_gear = GearSwitch::from(&fight.player);
-                    fight.player.current_switch = Some(current_gear.switch_type.clone());
-                    fight.player.switches.push(current_gear);
-                }
-
-                // Store the previous gear set's label for switching back after the spec
-                let previous_switch = fight.player.current_switch.clone().unwrap();
-
-                // Switch to the spec gear and perform the attack
-                fight.player.switch(&strategy.switch_type)?;
-
-                if fight.logger.enabled {
-                    fight
-                        .logger
-                        .log_gear_switch(fight_vars.tick_counter, &strategy.switch_type);
-                    let _ = fight.logger.log_current_player_rolls(&fight.player);
-                    fight.logger.log_current_player_stats(&fight.player);
-                    fight.logger.log_current_gear(&fight.player);
-                }
-
-                let hit = (fight.player.spec)(
-                    &mut fight.player,
-                    &mut fight.monster,
-                    &mut fight.rng,
-                    &mut fight.limiter,
-                );
-
-                if fight.logger.enabled {
-                    fight.logger.log_player_spec(
-                        fight_vars.tick_counter,
-                        hit.damage,
-                        hit.success,
-                        &strategy.switch_type,
-                    );
-                }
-
-                fight.player.state.first_attack = false;
-                fight.monster.take_damage(hit.damage);
-
-                if fight.logger.enabled {
-                    fight.logger.log_monster_damage(
-                        fight_vars.tick_counter,
-                        hit.damage,
-                        fight.monster.stats.hitpoints.current,
-                        fight.monster.name(),
-                    );
-                    fight.logger.log_current_monster_stats(&fight.monster);
-                    fight.logger.log_current_monster_rolls(&fight.monster);
-                }
-
-                strategy.state.attempt_count += 1;
-                if hit.success {
-                    strategy.state.success_count += 1;
-                }
-
-                handle_blood_fury(
-                    &mut fight.player,
-                    &hit,
-                    fight_vars,
-                    &mut fight.logger,
-                    &mut fight.rng,
-                );
-                scale_monster_hp_only(&mut fight.monster, true);
-                fight_vars.hit_attempts += 1;
-                fight_vars.hit_count += u32::from(hit.success);
-                fight_vars.hit_amounts.push(hit.damage);
-                fight_vars.attack_tick += fight.player.gear.weapon.speed;
-
-                fight.player.stats.spec.drain(strategy.spec_cost);
-                if !fight.spec_state.spec_regen_timer.is_active() {
-                    fight.spec_state.spec_regen_timer.activate();
-                }
-
-                // Switch back to the previous set of gear
-                fight.player.switch(&previous_switch)?;
-
-                if fight.logger.enabled {
-                    fight
-                        .logger
-                        .log_gear_switch(fight_vars.tick_counter, &previous_switch);
-                    let _ = fight.logger.log_current_player_rolls(&fight.player);
-                }
-
-                return Ok(true);
-            }
-        }
-        Ok(false)
-    }
-}
-
 impl Mechanics for SingleWayMechanics {}
 
 fn simulate_fight(fight: &mut SingleWayFight) -> Result<FightResult, SimulationError> {
@@ -258,16 +171,18 @@ fn simulate_fight(fight: &mut SingleWayFight) -> Result<FightResult, SimulationE
 
     while fight.monster.stats.hitpoints.current > 0 {
         if vars.tick_counter == vars.attack_tick {
-            let did_spec = if let Some(ref spec_config) = fight.spec_config {
-                if let Some(lowest) = spec_config.lowest_cost() {
-                    if fight.player.stats.spec.value() >= lowest {
-                        SingleWayMechanics::player_special_attack(fight, &mut vars)?
-                    } else {
-                        false
-                    }
-                } else {
-                    false
-                }
+            let did_spec = if let Some(ref mut spec_config) = fight.spec_config {
+                fight.mechanics.player_special_attack(
+                    &mut fight.player,
+                    &mut fight.monster,
+                    spec_config,
+                    &mut fight.spec_state,
+                    &(),
+                    &mut fight.rng,
+                    &fight.limiter,
+                    &mut vars,
+                    &mut fight.logger,
+                )?
             } else {
                 false
             };
@@ -302,15 +217,13 @@ fn simulate_fight(fight: &mut SingleWayFight) -> Result<FightResult, SimulationE
         fight
             .mechanics
             .process_freeze(&mut fight.monster, &mut vars, &mut fight.logger);
-        fight
-            .spec_state
-            .increment_spec(&mut fight.player, vars.tick_counter, &mut fight.logger);
-        fight.spec_state.increment_timers();
-        if let Some(ref spec_config) = fight.spec_config {
-            fight
-                .spec_state
-                .process_surge_potion(&mut fight.player, spec_config);
-        }
+        fight.mechanics.process_spec_timers(
+            &mut fight.player,
+            fight.spec_config.as_ref(),
+            &mut fight.spec_state,
+            &vars,
+            &mut fight.logger,
+        );
 
         vars.tick_counter += 1;
     }
@@ -334,7 +247,7 @@ mod tests {
     use crate::types::prayers::Prayer;
     use crate::types::stats::PlayerStats;
 
-    use std::rc::Rc;
+    use std::sync::Arc;
 
     #[test]
     fn test_simulate_fight() {
@@ -343,7 +256,7 @@ mod tests {
         player.add_prayer(Prayer::Piety);
         player.add_potion(Potion::SuperCombat);
 
-        player.gear = Rc::new(Gear {
+        player.gear = Arc::new(Gear {
             head: Some(Armor::new("Torva full helm", None).expect("Error creating equipment.")),
             neck: Some(Armor::new("Amulet of torture", None).expect("Error creating equipment.")),
             cape: Some(Armor::new("Infernal cape", None).expect("Error creating equipment.")),
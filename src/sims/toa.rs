@@ -0,0 +1,1450 @@
+use crate::calc::rolls::{calc_active_player_rolls, monster_def_rolls};
+use crate::combat::attacks::standard::{AttackFn, wardens_p2_attack};
+use crate::combat::limiters::Limiter;
+use crate::combat::mechanics::{Mechanics, handle_recoil};
+use crate::combat::prayer::PrayerDrainConfig;
+use crate::combat::simulation::{
+    FightResult, FightVars, Simulation, assign_limiter, derive_fight_seed, seeded_rng,
+};
+use crate::combat::spec::{CoreCondition, SpecCondition, SpecConfig, SpecState};
+use crate::combat::thralls::Thrall;
+use crate::constants::{self, SECONDS_PER_TICK};
+use crate::error::SimulationError;
+use crate::types::food::Foods;
+use crate::types::inventory::Inventory;
+use crate::types::monster::{AttackType, Monster, MonsterMaxHit};
+use crate::types::monster_specials::{MonsterSpecial, SpecialDamage};
+use crate::types::player::Player;
+use crate::types::potions::RedoseConfig;
+use crate::types::prayers::Prayer;
+use crate::utils::logging::FightLogger;
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use strum_macros::Display;
+
+const TOA_REGEN_TICKS: i32 = 100;
+const TOA_MAX_RAID_LEVEL: u32 = 600;
+const TOA_MAX_PATH_LEVEL: u32 = 6;
+
+/// Invocations that change the encounters, on top of the raid level they add up to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ToaInvocations {
+    pub raid_level: u32,
+    pub path_level: u32,     // Level every path starts at
+    pub walk_the_path: bool, // Each path completed raises the level of the paths after it by one
+    pub overclocked: bool,   // The Wardens attack a tick faster
+    pub overclocked_2: bool, // The Wardens attack another tick faster (needs Overclocked)
+}
+
+impl Default for ToaInvocations {
+    fn default() -> Self {
+        Self {
+            raid_level: 300,
+            path_level: 0,
+            walk_the_path: false,
+            overclocked: false,
+            overclocked_2: false,
+        }
+    }
+}
+
+impl ToaInvocations {
+    pub fn validate(&self) -> Result<(), SimulationError> {
+        if self.raid_level > TOA_MAX_RAID_LEVEL {
+            return Err(SimulationError::ConfigError(format!(
+                "Raid level can be at most {TOA_MAX_RAID_LEVEL}."
+            )));
+        }
+        if self.path_level > TOA_MAX_PATH_LEVEL {
+            return Err(SimulationError::ConfigError(format!(
+                "Path level can be at most {TOA_MAX_PATH_LEVEL}."
+            )));
+        }
+        if self.overclocked_2 && !self.overclocked {
+            return Err(SimulationError::ConfigError(
+                "Overclocked 2 requires Overclocked.".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Path level of a path boss, after `paths_completed` other paths.
+    pub fn path_level_for(&self, paths_completed: u32) -> u32 {
+        let level = if self.walk_the_path {
+            self.path_level + paths_completed
+        } else {
+            self.path_level
+        };
+        level.min(TOA_MAX_PATH_LEVEL)
+    }
+
+    fn warden_speed_reduction(&self) -> i32 {
+        i32::from(self.overclocked) + i32::from(self.overclocked_2)
+    }
+}
+
+/// The rooms covered by the raid simulation. Kephri isn't simulated yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum ToaRoom {
+    #[strum(to_string = "Ba-Ba")]
+    BaBa,
+    Zebak,
+    Akkha,
+    Obelisk,
+    #[strum(to_string = "Wardens P2")]
+    WardensP2,
+    #[strum(to_string = "Wardens P3")]
+    WardensP3,
+}
+
+impl ToaRoom {
+    pub const ALL: [ToaRoom; 6] = [
+        Self::BaBa,
+        Self::Zebak,
+        Self::Akkha,
+        Self::Obelisk,
+        Self::WardensP2,
+        Self::WardensP3,
+    ];
+
+    /// Whether the room's boss is at the end of a path and scales with path level.
+    pub fn is_path(&self) -> bool {
+        self.spec().path
+    }
+
+    fn spec(&self) -> &'static RoomSpec {
+        match self {
+            Self::BaBa => &BABA,
+            Self::Zebak => &ZEBAK,
+            Self::Akkha => &AKKHA,
+            Self::Obelisk => &OBELISK,
+            Self::WardensP2 => &WARDENS_P2,
+            Self::WardensP3 => &WARDENS_P3,
+        }
+    }
+}
+
+// Encounter data for a room. Attack patterns and mechanic damage are approximations for now
+struct RoomSpec {
+    monster: &'static str,
+    version: Option<&'static str>,
+    path: bool,                            // Scales with path level
+    warden: bool,                          // Sped up by the Overclocked invocations
+    attacks: &'static [(AttackType, u32)], // Prayable attacks, one picked at random each time
+    enrage: Option<(u32, &'static str)>,   // Version the boss switches to below this HP percent
+    events: &'static [(u32, RoomEvent)],   // Triggered as the boss drops to each HP percent
+    specials: &'static [RoomSpecial],
+}
+
+#[derive(Debug, Clone, Copy)]
+enum RoomEvent {
+    Shadow(&'static str), // A shadow spawns and has to die before the boss can be attacked again
+    Pause(i32),           // The boss can't be attacked and stops attacking for this many ticks
+}
+
+// A room mechanic the player has to dodge, hitting them if they don't
+struct RoomSpecial {
+    name: &'static str,
+    max_hit: u32,
+    every: i32,
+}
+
+const BABA: RoomSpec = RoomSpec {
+    monster: "Ba-Ba",
+    version: None,
+    path: true,
+    warden: false,
+    attacks: &[(AttackType::Crush, 24)],
+    enrage: None,
+    events: &[],
+    specials: &[RoomSpecial {
+        name: "Falling boulders",
+        max_hit: 20,
+        every: 20,
+    }],
+};
+
+const ZEBAK: RoomSpec = RoomSpec {
+    monster: "Zebak",
+    version: Some("Normal"),
+    path: true,
+    warden: false,
+    attacks: &[(AttackType::Magic, 16), (AttackType::Ranged, 16)],
+    enrage: Some((20, "Enraged")),
+    events: &[],
+    specials: &[RoomSpecial {
+        name: "Wave",
+        max_hit: 35,
+        every: 30,
+    }],
+};
+
+const AKKHA: RoomSpec = RoomSpec {
+    monster: "Akkha",
+    version: None,
+    path: true,
+    warden: false,
+    attacks: &[(AttackType::Magic, 55), (AttackType::Ranged, 55)],
+    enrage: None,
+    events: &[
+        (80, RoomEvent::Shadow("Akkha's Shadow")),
+        (60, RoomEvent::Shadow("Akkha's Shadow")),
+        (40, RoomEvent::Shadow("Akkha's Shadow")),
+        (20, RoomEvent::Shadow("Akkha's Shadow")),
+    ],
+    specials: &[RoomSpecial {
+        name: "Unstable orbs",
+        max_hit: 12,
+        every: 24,
+    }],
+};
+
+const OBELISK: RoomSpec = RoomSpec {
+    monster: "Obelisk (Tombs of Amascut)",
+    version: None,
+    path: false,
+    warden: false,
+    attacks: &[],
+    enrage: None,
+    events: &[],
+    specials: &[RoomSpecial {
+        name: "Lightning",
+        max_hit: 20,
+        every: 15,
+    }],
+};
+
+const WARDENS_P2: RoomSpec = RoomSpec {
+    monster: "Elidinis' Warden",
+    version: Some("Active"),
+    path: false,
+    warden: true,
+    attacks: &[(AttackType::Magic, 22), (AttackType::Ranged, 22)],
+    enrage: None,
+    events: &[],
+    specials: &[],
+};
+
+const WARDENS_P3: RoomSpec = RoomSpec {
+    monster: "Tumeken's Warden",
+    version: Some("Damaged"),
+    path: false,
+    warden: true,
+    attacks: &[(AttackType::Magic, 26), (AttackType::Ranged, 26)],
+    enrage: Some((20, "Enraged")),
+    events: &[(66, RoomEvent::Pause(12)), (33, RoomEvent::Pause(12))],
+    specials: &[RoomSpecial {
+        name: "Lightning",
+        max_hit: 25,
+        every: 12,
+    }],
+};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ToaConfig {
+    pub invocations: ToaInvocations,
+    pub rooms: Vec<ToaRoom>,  // Route through the raid, in order
+    pub inventory: Inventory, // Supplies for the whole raid
+    pub eat_hp: u32,
+    pub flick_success: f64, // Chance the right protection prayer is up in time for each attack
+    pub mechanic_failure: f64, // Chance the player fails to dodge each room mechanic
+    pub prayer_drain: Option<PrayerDrainConfig>,
+    pub redose: Option<RedoseConfig>,
+    pub thralls: Option<Thrall>,
+    pub room_downtime_ticks: u32, // Walking, puzzles and looting between rooms
+    pub logger: FightLogger,
+    pub seed: Option<u64>,
+}
+
+impl Default for ToaConfig {
+    fn default() -> Self {
+        Self {
+            invocations: ToaInvocations::default(),
+            rooms: ToaRoom::ALL.to_vec(),
+            // Sharks stand in for the nectars and ambrosia from the supply chests
+            inventory: Inventory::builder()
+                .food(Foods::Shark, 20)
+                .build()
+                .expect("Error building inventory."),
+            eat_hp: 50,
+            flick_success: 1.0,
+            mechanic_failure: 0.1,
+            prayer_drain: None,
+            redose: None,
+            thralls: None,
+            room_downtime_ticks: 100,
+            logger: FightLogger::new(false),
+            seed: None,
+        }
+    }
+}
+
+impl ToaConfig {
+    pub fn validate(&self) -> Result<(), SimulationError> {
+        self.invocations.validate()?;
+        if self.rooms.is_empty() {
+            return Err(SimulationError::ConfigError(
+                "The raid needs at least one room.".to_string(),
+            ));
+        }
+        for (index, room) in self.rooms.iter().enumerate() {
+            if self.rooms[..index].contains(room) {
+                return Err(SimulationError::ConfigError(format!(
+                    "{room} is in the raid more than once."
+                )));
+            }
+        }
+        if !(0.0..=1.0).contains(&self.flick_success) {
+            return Err(SimulationError::ConfigError(
+                "Flick success must be between 0 and 1.".to_string(),
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.mechanic_failure) {
+            return Err(SimulationError::ConfigError(
+                "Mechanic failure chance must be between 0 and 1.".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    // Number of path bosses before this room on the route
+    fn paths_completed_before(&self, room: ToaRoom) -> u32 {
+        self.rooms
+            .iter()
+            .take_while(|&&other| other != room)
+            .filter(|other| other.is_path())
+            .count() as u32
+    }
+}
+
+// Spec conditions for ToA rooms
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToaCondition {
+    Core(CoreCondition),
+    Room(ToaRoom),   // The player is in this room
+    Enraged,         // The room's boss has enraged
+    TargetingShadow, // The player is attacking a shadow rather than the boss
+}
+
+impl SpecCondition for ToaCondition {
+    type BossState = ToaRoomState;
+
+    fn evaluate(&self, player: &Player, monster: &Monster, boss_state: &Self::BossState) -> bool {
+        match self {
+            Self::Core(core) => core.evaluate(player, monster, &()),
+            Self::Room(room) => boss_state.room == *room,
+            Self::Enraged => boss_state.enraged,
+            Self::TargetingShadow => boss_state.shadow_up,
+        }
+    }
+
+    fn as_core(&self) -> Option<&CoreCondition> {
+        match self {
+            Self::Core(core) => Some(core),
+            _ => None,
+        }
+    }
+
+    fn from_core(core: CoreCondition) -> Self {
+        Self::Core(core)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ToaRoomState {
+    room: ToaRoom,
+    tick_counter: i32,
+    boss_attack_tick: i32,
+    targetable_tick: i32,
+    next_event: usize,
+    enraged: bool,
+    shadow_up: bool,
+    special_ticks: Vec<i32>, // Next use of each of the room's mechanics
+}
+
+impl ToaRoomState {
+    fn new(room: ToaRoom) -> Self {
+        Self {
+            room,
+            tick_counter: 0,
+            boss_attack_tick: 1,
+            targetable_tick: 0,
+            next_event: 0,
+            enraged: false,
+            shadow_up: false,
+            special_ticks: room.spec().specials.iter().map(|s| s.every).collect(),
+        }
+    }
+
+    fn is_targetable(&self) -> bool {
+        self.tick_counter >= self.targetable_tick
+    }
+}
+
+#[derive(Clone)]
+struct ToaMechanics;
+
+impl Mechanics for ToaMechanics {}
+
+impl ToaMechanics {
+    fn boss_attack(
+        &self,
+        boss: &mut Monster,
+        style: AttackType,
+        player: &mut Player,
+        config: &mut ToaConfig,
+        vars: &mut FightVars,
+        rng: &mut SmallRng,
+    ) -> Result<(), SimulationError> {
+        player.set_protection_prayer(Prayer::protection_against(style));
+        let hit = boss.attack_with_flick(player, Some(style), rng, false, config.flick_success)?;
+
+        if config.logger.enabled {
+            config.logger.log_monster_attack(
+                boss,
+                vars.tick_counter,
+                hit.damage,
+                hit.success,
+                Some(style),
+            );
+        }
+
+        if hit.success {
+            player.take_damage(hit.damage);
+            vars.damage_taken += hit.damage;
+            handle_recoil(player, boss, &hit, vars, &mut config.logger);
+        }
+
+        Ok(())
+    }
+
+    fn room_special(
+        &self,
+        boss: &mut Monster,
+        name: &str,
+        player: &mut Player,
+        vars: &mut FightVars,
+        rng: &mut SmallRng,
+        logger: &mut FightLogger,
+    ) -> Result<(), SimulationError> {
+        let special = boss.special_attack(name, player, rng, false)?;
+        // None of the room mechanics envenom the player
+        self.apply_monster_special(boss, player, &special, &mut None, vars, logger);
+        Ok(())
+    }
+
+    fn handle_eating(
+        &self,
+        config: &mut ToaConfig,
+        inventory: &mut Inventory,
+        vars: &mut FightVars,
+        player: &mut Player,
+    ) -> bool {
+        // Eat below the threshold, returning false if the player needed to eat but had no food left
+        if player.stats.hitpoints.current > config.eat_hp {
+            return true;
+        }
+        if !inventory.has_food() {
+            return false;
+        }
+
+        if let Some(attack_delay) =
+            self.eat_next_food(player, inventory, false, vars, &mut config.logger)
+        {
+            vars.attack_tick += attack_delay;
+        }
+        true
+    }
+}
+
+/// Simulation of a single ToA room at the configured invocations.
+///
+/// The room's boss is scaled to the raid level, plus its path level if it's a path boss. Path
+/// levels depend on how many paths come before the room in `ToaConfig::rooms`.
+#[derive(Clone)]
+pub struct ToaRoomFight {
+    player: Player,
+    room: ToaRoom,
+    form: Monster,                 // The boss as it starts the room
+    enraged_form: Option<Monster>, // The boss after it enrages
+    shadow_form: Option<Monster>,  // The shadow spawned by the room's events
+    boss: Monster,
+    shadow: Option<Monster>,
+    max_hp: u32, // Boss HP after raid level scaling
+    boss_attack_speed: i32,
+    limiter: Option<Box<dyn Limiter>>,
+    rng: SmallRng,
+    config: ToaConfig,
+    mechanics: ToaMechanics,
+    spec_config: Option<SpecConfig<ToaCondition>>,
+    spec_state: SpecState,
+    inventory: Inventory,
+}
+
+impl ToaRoomFight {
+    pub fn new(
+        mut player: Player,
+        room: ToaRoom,
+        config: ToaConfig,
+        spec_config: Option<SpecConfig<ToaCondition>>,
+    ) -> Result<Self, SimulationError> {
+        config.validate()?;
+
+        let spec = room.spec();
+        let path_level = if spec.path {
+            config
+                .invocations
+                .path_level_for(config.paths_completed_before(room))
+        } else {
+            0
+        };
+        let create = |name: &str, version: Option<&str>| {
+            let mut monster = Monster::new(name, version)
+                .map_err(|_| SimulationError::MonsterCreationError(name.to_string()))?;
+            monster.set_toa_level(config.invocations.raid_level, path_level);
+            Ok::<Monster, SimulationError>(monster)
+        };
+
+        let mut form = create(spec.monster, spec.version)?;
+        form.specials = spec
+            .specials
+            .iter()
+            .map(|special| {
+                MonsterSpecial::typeless(special.name, SpecialDamage::Roll(special.max_hit))
+            })
+            .collect();
+        let enraged_form = spec
+            .enrage
+            .map(|(_, version)| {
+                let mut enraged = create(spec.monster, Some(version))?;
+                enraged.specials = form.specials.clone();
+                Ok::<Monster, SimulationError>(enraged)
+            })
+            .transpose()?;
+        let shadow_form = spec
+            .events
+            .iter()
+            .find_map(|(_, event)| match event {
+                RoomEvent::Shadow(name) => Some(*name),
+                RoomEvent::Pause(_) => None,
+            })
+            .map(|name| create(name, None))
+            .transpose()?;
+
+        let mut boss_attack_speed = form
+            .info
+            .attack_speed
+            .map(|speed| speed as i32)
+            .ok_or_else(|| SimulationError::ConfigError(format!("{room} has no attack speed.")))?;
+        if spec.warden {
+            boss_attack_speed -= config.invocations.warden_speed_reduction();
+        }
+
+        // Specs don't use the P2 damage formula, so they're saved for other rooms
+        let spec_config = if room == ToaRoom::WardensP2 {
+            None
+        } else {
+            spec_config
+        };
+
+        calc_active_player_rolls(&mut player, &form);
+        let limiter = assign_limiter(&player, &form);
+        let rng = seeded_rng(config.seed);
+        let inventory = config.inventory.clone();
+        let max_hp = form.stats.hitpoints.current;
+
+        Ok(Self {
+            player,
+            room,
+            boss: form.clone(),
+            form,
+            enraged_form,
+            shadow_form,
+            shadow: None,
+            max_hp,
+            boss_attack_speed,
+            limiter,
+            rng,
+            config,
+            mechanics: ToaMechanics,
+            spec_config,
+            spec_state: SpecState::default(),
+            inventory,
+        })
+    }
+
+    pub fn room(&self) -> ToaRoom {
+        self.room
+    }
+
+    // Point the player at the shadow (or the boss) and recompute their rolls
+    fn retarget(&mut self, state: &mut ToaRoomState) {
+        state.shadow_up = self.shadow.is_some();
+        let target = self.shadow.as_ref().unwrap_or(&self.boss);
+        calc_active_player_rolls(&mut self.player, target);
+        self.limiter = assign_limiter(&self.player, target);
+    }
+
+    // Enrage the boss and trigger any events for the HP it has dropped to
+    fn process_hp_events(&mut self, state: &mut ToaRoomState) {
+        let spec = self.room.spec();
+        let current = self.boss.stats.hitpoints.current;
+        let max_hp = self.max_hp;
+        let below = |percent: u32| current * 100 <= percent * max_hp;
+
+        if let Some((percent, _)) = spec.enrage
+            && !state.enraged
+            && current > 0
+            && below(percent)
+            && let Some(ref enraged) = self.enraged_form
+        {
+            // Carry HP, stat drains and effects over to the enraged form
+            let stats = self.boss.stats;
+            let effects = std::mem::take(&mut self.boss.active_effects);
+            self.boss = enraged.clone();
+            let boss = &mut self.boss;
+            for (drained, stat) in [
+                (stats.attack, &mut boss.stats.attack),
+                (stats.strength, &mut boss.stats.strength),
+                (stats.defence, &mut boss.stats.defence),
+                (stats.ranged, &mut boss.stats.ranged),
+                (stats.magic, &mut boss.stats.magic),
+            ] {
+                stat.drain(drained.base.saturating_sub(drained.current));
+            }
+            boss.active_effects = effects;
+            boss.base_def_rolls = monster_def_rolls(boss);
+            boss.def_rolls = boss.base_def_rolls;
+            boss.scale_toa();
+            boss.stats.hitpoints = stats.hitpoints;
+            state.enraged = true;
+            if spec.warden {
+                self.boss_attack_speed = self.boss.info.attack_speed.unwrap_or_default() as i32
+                    - self.config.invocations.warden_speed_reduction();
+            } else if let Some(speed) = self.boss.info.attack_speed {
+                self.boss_attack_speed = speed as i32;
+            }
+            if self.config.logger.enabled {
+                self.config.logger.log_phase_change(
+                    state.tick_counter,
+                    &self.boss.info.name,
+                    "Enraged",
+                );
+            }
+            self.retarget(state);
+        }
+
+        while let Some(&(percent, event)) = spec.events.get(state.next_event) {
+            if current == 0 || !below(percent) {
+                break;
+            }
+            state.next_event += 1;
+            match event {
+                RoomEvent::Shadow(_) => {
+                    // Damage past the threshold is lost, so one hit can't skip a shadow
+                    self.boss.stats.hitpoints.current = percent * max_hp / 100;
+                    self.shadow = self.shadow_form.clone();
+                    self.retarget(state);
+                }
+                RoomEvent::Pause(ticks) => {
+                    state.targetable_tick = state.tick_counter + ticks;
+                    state.boss_attack_tick = state.boss_attack_tick.max(state.targetable_tick);
+                }
+            }
+            if self.config.logger.enabled {
+                self.config.logger.log_hp_threshold(
+                    state.tick_counter,
+                    &self.boss.info.name,
+                    percent,
+                );
+            }
+            if matches!(event, RoomEvent::Shadow(_)) {
+                break;
+            }
+        }
+    }
+
+    fn player_attack_or_spec(
+        &mut self,
+        state: &mut ToaRoomState,
+        vars: &mut FightVars,
+    ) -> Result<(), SimulationError> {
+        let target = self.shadow.as_mut().unwrap_or(&mut self.boss);
+        let did_spec = if let Some(ref mut spec_config) = self.spec_config {
+            self.mechanics.player_special_attack(
+                &mut self.player,
+                target,
+                spec_config,
+                &mut self.spec_state,
+                state,
+                &mut self.rng,
+                &self.limiter,
+                vars,
+                &mut self.config.logger,
+            )?
+        } else {
+            false
+        };
+
+        if !did_spec {
+            self.mechanics.player_attack(
+                &mut self.player,
+                target,
+                &mut self.rng,
+                &self.limiter,
+                vars,
+                &mut self.config.logger,
+            );
+        }
+
+        Ok(())
+    }
+
+    fn simulate_room(&mut self) -> Result<FightResult, SimulationError> {
+        if let Some(ref spec_config) = self.spec_config
+            && let Err(e) = spec_config.validate()
+        {
+            return Err(SimulationError::ConfigError(e));
+        }
+
+        let mut vars = FightVars::new();
+        let mut state = ToaRoomState::new(self.room);
+        self.boss = self.form.clone();
+        self.shadow = None;
+        self.retarget(&mut state);
+        let spec = self.room.spec();
+
+        let logging_enabled = self.config.logger.enabled;
+        if logging_enabled {
+            self.config
+                .logger
+                .log_initial_setup(&self.player, &self.boss);
+        }
+
+        loop {
+            state.tick_counter = vars.tick_counter;
+            self.process_hp_events(&mut state);
+            if self.boss.stats.hitpoints.current == 0 {
+                break;
+            }
+
+            if vars.tick_counter % TOA_REGEN_TICKS == 0 {
+                self.mechanics
+                    .monster_regen_stats(&mut self.boss, &vars, &mut self.config.logger);
+            }
+
+            // Regen 1 HP for player every 100 ticks
+            if vars.tick_counter % constants::PLAYER_REGEN_TICKS == 0 {
+                self.mechanics
+                    .player_regen(&mut self.player, &vars, &mut self.config.logger);
+            }
+
+            self.mechanics.decrement_eat_delay(&mut vars);
+            self.mechanics.process_player_effects(
+                &mut self.player,
+                &mut vars,
+                &mut self.config.logger,
+            );
+            if !self.mechanics.handle_eating(
+                &mut self.config,
+                &mut self.inventory,
+                &mut vars,
+                &mut self.player,
+            ) {
+                return self.mechanics.process_supply_out(
+                    &vars,
+                    &self.boss,
+                    &mut self.config.logger,
+                );
+            }
+
+            if let Some(prayer_config) = self.config.prayer_drain {
+                let attacking = vars.tick_counter == vars.attack_tick;
+                self.mechanics.process_prayer(
+                    &mut self.player,
+                    &self.boss,
+                    &prayer_config,
+                    &mut self.inventory,
+                    attacking,
+                    &mut vars,
+                    &mut self.config.logger,
+                )?;
+            }
+
+            if let Some(redose) = self.config.redose {
+                self.mechanics.process_redose(
+                    &mut self.player,
+                    &self.boss,
+                    &redose,
+                    &mut self.inventory,
+                    &mut vars,
+                    &mut self.config.logger,
+                );
+            }
+
+            if vars.tick_counter == vars.attack_tick {
+                if !state.shadow_up && !state.is_targetable() {
+                    vars.attack_tick += 1;
+                } else {
+                    self.player_attack_or_spec(&mut state, &mut vars)?;
+                }
+            }
+
+            if let Some(thrall) = self.config.thralls
+                && vars.tick_counter == vars.thrall_attack_tick
+            {
+                if !state.shadow_up && !state.is_targetable() {
+                    vars.thrall_attack_tick += 1;
+                } else {
+                    let target = self.shadow.as_mut().unwrap_or(&mut self.boss);
+                    self.mechanics.thrall_attack(
+                        target,
+                        thrall,
+                        &mut vars,
+                        &mut self.rng,
+                        &mut self.config.logger,
+                    );
+                }
+            }
+
+            self.mechanics
+                .process_monster_effects(&mut self.boss, &vars, &mut self.config.logger);
+
+            if self.boss.stats.hitpoints.current > 0
+                && state.is_targetable()
+                && !spec.attacks.is_empty()
+                && vars.tick_counter >= state.boss_attack_tick
+            {
+                let (style, max_hit) = spec.attacks[self.rng.random_range(0..spec.attacks.len())];
+                // Each style brings its own max hit, so set it right before rolling
+                self.boss.max_hits = Some(vec![MonsterMaxHit::new(max_hit, style)]);
+                self.mechanics.boss_attack(
+                    &mut self.boss,
+                    style,
+                    &mut self.player,
+                    &mut self.config,
+                    &mut vars,
+                    &mut self.rng,
+                )?;
+                state.boss_attack_tick = vars.tick_counter + self.boss_attack_speed;
+            }
+
+            for (special, tick) in spec.specials.iter().zip(state.special_ticks.iter_mut()) {
+                if vars.tick_counter >= *tick {
+                    if self.rng.random_bool(self.config.mechanic_failure) {
+                        self.mechanics.room_special(
+                            &mut self.boss,
+                            special.name,
+                            &mut self.player,
+                            &mut vars,
+                            &mut self.rng,
+                            &mut self.config.logger,
+                        )?;
+                    }
+                    *tick = vars.tick_counter + special.every;
+                }
+            }
+
+            if self
+                .shadow
+                .as_ref()
+                .is_some_and(|shadow| shadow.stats.hitpoints.current == 0)
+            {
+                self.shadow = None;
+                self.retarget(&mut state);
+            }
+
+            self.mechanics.process_spec_timers(
+                &mut self.player,
+                self.spec_config.as_ref(),
+                &mut self.spec_state,
+                &vars,
+                &mut self.config.logger,
+            );
+
+            // Increment tick counter
+            vars.tick_counter += 1;
+
+            if self.player.stats.hitpoints.current == 0 {
+                return self.mechanics.process_player_death(
+                    &vars,
+                    &self.boss,
+                    &mut self.config.logger,
+                );
+            }
+        }
+
+        let remove_final_attack_delay = true;
+        self.mechanics.get_fight_result(
+            &self.boss,
+            &vars,
+            &mut self.config.logger,
+            remove_final_attack_delay,
+        )
+    }
+
+    // Carry the player and supplies over from the previous room
+    fn enter(&mut self, player: Player, inventory: Inventory, downtime_ticks: u32) {
+        self.player = player;
+        self.inventory = inventory;
+        self.set_attack_function();
+        if downtime_ticks > 0 {
+            self.mechanics.prepare_next_kill(
+                &mut self.player,
+                self.spec_config.as_mut(),
+                &mut self.spec_state,
+                downtime_ticks,
+                &mut self.config.logger,
+            );
+        }
+    }
+}
+
+impl Simulation for ToaRoomFight {
+    fn simulate(&mut self) -> Result<FightResult, SimulationError> {
+        self.simulate_room()
+    }
+
+    fn is_immune(&self) -> bool {
+        self.form.is_immune(&self.player)
+    }
+
+    fn player(&self) -> &Player {
+        &self.player
+    }
+
+    fn monster(&self) -> &Monster {
+        &self.boss
+    }
+
+    fn set_attack_function(&mut self) {
+        self.player.attack = if self.room == ToaRoom::WardensP2 {
+            wardens_p2_attack as AttackFn
+        } else {
+            crate::combat::attacks::standard::get_attack_functions(&self.player)
+        };
+        self.player.spec = crate::combat::attacks::specs::get_spec_attack_function(&self.player);
+    }
+
+    fn reset(&mut self) {
+        self.config.logger.clear();
+        if let Some(ref mut spec_config) = self.spec_config {
+            let restore_spec = self.spec_state.on_kill(&mut self.player, spec_config);
+            self.player.reset_current_stats(restore_spec);
+        } else {
+            self.player.reset_current_stats(true);
+        }
+        self.boss = self.form.clone();
+        self.shadow = None;
+        self.inventory = self.config.inventory.clone();
+    }
+
+    fn next_kill(&mut self, downtime_ticks: u32) {
+        self.mechanics.prepare_next_kill(
+            &mut self.player,
+            self.spec_config.as_mut(),
+            &mut self.spec_state,
+            downtime_ticks,
+            &mut self.config.logger,
+        );
+        self.boss = self.form.clone();
+        self.shadow = None;
+    }
+
+    fn inventory(&self) -> Option<&Inventory> {
+        Some(&self.inventory)
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.rng = SmallRng::seed_from_u64(seed);
+    }
+}
+
+/// How a single raid ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToaRaidOutcome {
+    Completed,
+    Died(ToaRoom),        // A solo death ends the raid
+    SuppliedOut(ToaRoom), // The player needed to eat with no food left
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToaRoomResult {
+    pub room: ToaRoom,
+    pub ttk_ticks: i32,
+    pub food_eaten: u32,
+    pub damage_taken: u32,
+    pub prayer_used: u32,
+}
+
+impl ToaRoomResult {
+    fn new(room: ToaRoom, result: &FightResult) -> Self {
+        Self {
+            room,
+            ttk_ticks: result.ttk_ticks,
+            food_eaten: result.food_eaten,
+            damage_taken: result.damage_taken,
+            prayer_used: result.prayer_used,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToaRaidResult {
+    pub outcome: ToaRaidOutcome,
+    pub rooms: Vec<ToaRoomResult>, // Every room attempted, including the one that failed
+    pub completion_ticks: i32,     // Time in rooms plus downtime between them
+}
+
+impl ToaRaidResult {
+    pub fn food_eaten(&self) -> u32 {
+        self.rooms.iter().map(|room| room.food_eaten).sum()
+    }
+
+    pub fn damage_taken(&self) -> u32 {
+        self.rooms.iter().map(|room| room.damage_taken).sum()
+    }
+
+    pub fn prayer_used(&self) -> u32 {
+        self.rooms.iter().map(|room| room.prayer_used).sum()
+    }
+}
+
+/// Results of many raids, from `simulate_n_raids`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ToaRaidResults {
+    pub raids: Vec<ToaRaidResult>,
+}
+
+impl ToaRaidResults {
+    pub fn completions(&self) -> usize {
+        self.count(|outcome| outcome == ToaRaidOutcome::Completed)
+    }
+
+    pub fn deaths(&self) -> usize {
+        self.count(|outcome| matches!(outcome, ToaRaidOutcome::Died(_)))
+    }
+
+    pub fn supply_outs(&self) -> usize {
+        self.count(|outcome| matches!(outcome, ToaRaidOutcome::SuppliedOut(_)))
+    }
+
+    pub fn death_rate(&self) -> f64 {
+        self.rate(self.deaths())
+    }
+
+    pub fn completion_rate(&self) -> f64 {
+        self.rate(self.completions())
+    }
+
+    /// Average raid completion time in seconds, over completed raids.
+    pub fn avg_completion_time(&self) -> f64 {
+        let completed: Vec<_> = self.completed().collect();
+        if completed.is_empty() {
+            return 0.0;
+        }
+        let total: i64 = completed
+            .iter()
+            .map(|raid| i64::from(raid.completion_ticks))
+            .sum();
+        total as f64 * SECONDS_PER_TICK / completed.len() as f64
+    }
+
+    /// Average food eaten per completed raid.
+    pub fn avg_food_eaten(&self) -> f64 {
+        self.completed_average(|raid| f64::from(raid.food_eaten()))
+    }
+
+    /// Average damage taken per completed raid.
+    pub fn avg_damage_taken(&self) -> f64 {
+        self.completed_average(|raid| f64::from(raid.damage_taken()))
+    }
+
+    /// Average prayer points used per completed raid.
+    pub fn avg_prayer_used(&self) -> f64 {
+        self.completed_average(|raid| f64::from(raid.prayer_used()))
+    }
+
+    /// Average time spent in each room in seconds, over the rooms that were cleared.
+    pub fn avg_room_times(&self) -> Vec<(ToaRoom, f64)> {
+        let mut totals: Vec<(ToaRoom, i64, usize)> = Vec::new();
+        for raid in &self.raids {
+            let cleared = match raid.outcome {
+                ToaRaidOutcome::Completed => raid.rooms.len(),
+                _ => raid.rooms.len().saturating_sub(1),
+            };
+            for room in &raid.rooms[..cleared] {
+                match totals.iter_mut().find(|(other, _, _)| *other == room.room) {
+                    Some((_, ticks, count)) => {
+                        *ticks += i64::from(room.ttk_ticks);
+                        *count += 1;
+                    }
+                    None => totals.push((room.room, i64::from(room.ttk_ticks), 1)),
+                }
+            }
+        }
+        totals
+            .into_iter()
+            .map(|(room, ticks, count)| (room, ticks as f64 * SECONDS_PER_TICK / count as f64))
+            .collect()
+    }
+
+    /// Number of deaths in each room, in the order they first happened.
+    pub fn deaths_by_room(&self) -> Vec<(ToaRoom, usize)> {
+        let mut deaths: Vec<(ToaRoom, usize)> = Vec::new();
+        for raid in &self.raids {
+            if let ToaRaidOutcome::Died(room) = raid.outcome {
+                match deaths.iter_mut().find(|(other, _)| *other == room) {
+                    Some((_, count)) => *count += 1,
+                    None => deaths.push((room, 1)),
+                }
+            }
+        }
+        deaths
+    }
+
+    fn completed(&self) -> impl Iterator<Item = &ToaRaidResult> {
+        self.raids
+            .iter()
+            .filter(|raid| raid.outcome == ToaRaidOutcome::Completed)
+    }
+
+    fn completed_average(&self, value: impl Fn(&ToaRaidResult) -> f64) -> f64 {
+        let completed: Vec<_> = self.completed().collect();
+        if completed.is_empty() {
+            return 0.0;
+        }
+        completed.iter().map(|raid| value(raid)).sum::<f64>() / completed.len() as f64
+    }
+
+    fn count(&self, matches: impl Fn(ToaRaidOutcome) -> bool) -> usize {
+        self.raids
+            .iter()
+            .filter(|raid| matches(raid.outcome))
+            .count()
+    }
+
+    fn rate(&self, count: usize) -> f64 {
+        if self.raids.is_empty() {
+            0.0
+        } else {
+            count as f64 / self.raids.len() as f64
+        }
+    }
+}
+
+/// A solo raid through every room in `ToaConfig::rooms`, sharing the player's HP, prayer, spec
+/// energy and supplies between rooms.
+#[derive(Clone)]
+pub struct ToaRaid {
+    player: Player,
+    rooms: Vec<ToaRoomFight>,
+    config: ToaConfig,
+}
+
+impl ToaRaid {
+    pub fn new(
+        player: Player,
+        config: ToaConfig,
+        spec_config: Option<SpecConfig<ToaCondition>>,
+    ) -> Result<Self, SimulationError> {
+        config.validate()?;
+        let rooms = config
+            .rooms
+            .iter()
+            .map(|&room| {
+                ToaRoomFight::new(player.clone(), room, config.clone(), spec_config.clone())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            player,
+            rooms,
+            config,
+        })
+    }
+
+    pub fn rooms(&self) -> &[ToaRoomFight] {
+        &self.rooms
+    }
+
+    /// Run one raid from a fresh player and full supplies.
+    pub fn simulate_raid(&mut self) -> Result<ToaRaidResult, SimulationError> {
+        let mut player = self.player.clone();
+        player.reset_current_stats(true);
+        let mut inventory = self.config.inventory.clone();
+        let mut result = ToaRaidResult {
+            outcome: ToaRaidOutcome::Completed,
+            rooms: Vec::with_capacity(self.rooms.len()),
+            completion_ticks: 0,
+        };
+
+        for (index, fight) in self.rooms.iter_mut().enumerate() {
+            let downtime_ticks = if index == 0 {
+                0
+            } else {
+                self.config.room_downtime_ticks
+            };
+            result.completion_ticks += downtime_ticks as i32;
+            fight.enter(player, inventory, downtime_ticks);
+
+            match fight.simulate() {
+                Ok(room_result) => {
+                    result.completion_ticks += room_result.ttk_ticks;
+                    result
+                        .rooms
+                        .push(ToaRoomResult::new(fight.room, &room_result));
+                }
+                Err(SimulationError::PlayerDeathError(room_result)) => {
+                    result
+                        .rooms
+                        .push(ToaRoomResult::new(fight.room, &room_result));
+                    result.outcome = ToaRaidOutcome::Died(fight.room);
+                    return Ok(result);
+                }
+                Err(SimulationError::SupplyOutError(room_result)) => {
+                    result
+                        .rooms
+                        .push(ToaRoomResult::new(fight.room, &room_result));
+                    result.outcome = ToaRaidOutcome::SuppliedOut(fight.room);
+                    return Ok(result);
+                }
+                Err(e) => return Err(e),
+            }
+
+            player = fight.player.clone();
+            inventory = fight.inventory.clone();
+        }
+
+        Ok(result)
+    }
+
+    pub fn set_seed(&mut self, seed: u64) {
+        for (index, fight) in self.rooms.iter_mut().enumerate() {
+            fight.set_seed(derive_fight_seed(seed, index as u64));
+        }
+    }
+}
+
+/// Simulate `n` raids and collect their outcomes.
+///
+/// Seeded runs reseed every raid with `derive_fight_seed`, like `simulate_n_fights`.
+pub fn simulate_n_raids(
+    raid: &mut ToaRaid,
+    n: u32,
+    seed: Option<u64>,
+) -> Result<ToaRaidResults, SimulationError> {
+    if let Some(fight) = raid.rooms.iter().find(|fight| fight.is_immune()) {
+        return Err(SimulationError::MonsterImmune(fight.form.info.name.clone()));
+    }
+
+    let mut results = ToaRaidResults {
+        raids: Vec::with_capacity(n as usize),
+    };
+    for i in 0..n {
+        if let Some(seed) = seed {
+            raid.set_seed(derive_fight_seed(seed, u64::from(i)));
+        }
+        results.raids.push(raid.simulate_raid()?);
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combat::simulation::simulate_n_fights;
+    use crate::types::equipment::CombatType;
+    use crate::types::monster::CombatStat;
+    use crate::utils::loadouts;
+
+    #[test]
+    fn test_path_levels() {
+        let mut invocations = ToaInvocations {
+            path_level: 2,
+            ..ToaInvocations::default()
+        };
+        assert_eq!(invocations.path_level_for(3), 2);
+
+        invocations.walk_the_path = true;
+        assert_eq!(invocations.path_level_for(0), 2);
+        assert_eq!(invocations.path_level_for(3), 5);
+        assert_eq!(invocations.path_level_for(10), TOA_MAX_PATH_LEVEL);
+
+        // Later path bosses get the higher levels, and so more HP
+        let config = ToaConfig {
+            invocations,
+            ..ToaConfig::default()
+        };
+        let player = loadouts::bowfa_crystal_player();
+        let baba = ToaRoomFight::new(player.clone(), ToaRoom::BaBa, config.clone(), None).unwrap();
+        let akkha = ToaRoomFight::new(player, ToaRoom::Akkha, config, None).unwrap();
+        assert_eq!(baba.boss.info.toa_path_level, 2);
+        assert_eq!(akkha.boss.info.toa_path_level, 4);
+        assert_eq!(akkha.max_hp, akkha.boss.stats.hitpoints.current);
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let player = loadouts::bowfa_crystal_player();
+        let run = |config: ToaConfig| ToaRaid::new(player.clone(), config, None);
+
+        let invocations = ToaInvocations {
+            overclocked_2: true,
+            ..ToaInvocations::default()
+        };
+        for config in [
+            ToaConfig {
+                invocations,
+                ..ToaConfig::default()
+            },
+            ToaConfig {
+                rooms: vec![ToaRoom::Zebak, ToaRoom::Zebak],
+                ..ToaConfig::default()
+            },
+            ToaConfig {
+                mechanic_failure: 1.5,
+                ..ToaConfig::default()
+            },
+        ] {
+            assert!(matches!(run(config), Err(SimulationError::ConfigError(_))));
+        }
+    }
+
+    #[test]
+    fn test_rooms_kill() {
+        let config = ToaConfig {
+            seed: Some(1),
+            ..ToaConfig::default()
+        };
+        for room in ToaRoom::ALL {
+            let player = loadouts::bowfa_crystal_player();
+            let fight = ToaRoomFight::new(player, room, config.clone(), None).unwrap();
+            let results = simulate_n_fights(Box::new(fight), 10, false, Some(1)).unwrap();
+            assert_eq!(
+                results.ttks_ticks.len(),
+                10,
+                "{room} didn't finish every kill"
+            );
+        }
+    }
+
+    #[test]
+    fn test_raid_level_scaling() {
+        let run = |raid_level: u32| {
+            let config = ToaConfig {
+                invocations: ToaInvocations {
+                    raid_level,
+                    ..ToaInvocations::default()
+                },
+                mechanic_failure: 0.0,
+                ..ToaConfig::default()
+            };
+            let mut raid = ToaRaid::new(loadouts::bowfa_crystal_player(), config, None).unwrap();
+            simulate_n_raids(&mut raid, 20, Some(3)).unwrap()
+        };
+
+        let low = run(150);
+        let high = run(500);
+        assert_eq!(low.completion_rate(), 1.0);
+        assert!(high.avg_completion_time() > low.avg_completion_time());
+
+        let room_times = low.avg_room_times();
+        assert_eq!(room_times.len(), ToaRoom::ALL.len());
+        let total: f64 = room_times.iter().map(|(_, secs)| secs).sum();
+        assert!(total < low.avg_completion_time());
+    }
+
+    #[test]
+    fn test_overclocked_speeds_up_wardens() {
+        let player = loadouts::bowfa_crystal_player();
+        let overclocked = ToaConfig {
+            invocations: ToaInvocations {
+                overclocked: true,
+                overclocked_2: true,
+                ..ToaInvocations::default()
+            },
+            ..ToaConfig::default()
+        };
+        let normal = ToaRoomFight::new(
+            player.clone(),
+            ToaRoom::WardensP3,
+            ToaConfig::default(),
+            None,
+        )
+        .unwrap();
+        let fast = ToaRoomFight::new(player, ToaRoom::WardensP3, overclocked, None).unwrap();
+        assert_eq!(fast.boss_attack_speed, normal.boss_attack_speed - 2);
+    }
+
+    #[test]
+    fn test_supplies_carry_between_rooms() {
+        // One shark and no prayer means the raid fails part way through
+        let config = ToaConfig {
+            inventory: Inventory::builder()
+                .food(Foods::Shark, 1)
+                .build()
+                .expect("Error building inventory."),
+            flick_success: 0.0,
+            mechanic_failure: 1.0,
+            eat_hp: 90,
+            ..ToaConfig::default()
+        };
+        let mut raid = ToaRaid::new(loadouts::bowfa_crystal_player(), config, None).unwrap();
+        let results = simulate_n_raids(&mut raid, 10, Some(7)).unwrap();
+
+        assert_eq!(results.completions(), 0);
+        assert_eq!(results.deaths() + results.supply_outs(), 10);
+        assert_eq!(results.avg_completion_time(), 0.0);
+        for raid in &results.raids {
+            assert!(raid.food_eaten() <= 1);
+        }
+    }
+
+    #[test]
+    fn test_enrage_keeps_drains() {
+        let player = loadouts::bowfa_crystal_player();
+        let mut fight =
+            ToaRoomFight::new(player, ToaRoom::Zebak, ToaConfig::default(), None).unwrap();
+        let mut state = ToaRoomState::new(ToaRoom::Zebak);
+
+        // Drain defence above the enrage threshold, then drop Zebak below it
+        fight.boss.drain_stat(&CombatStat::Defence, 30, None);
+        let drained = fight.boss.stats.defence.current;
+        fight.boss.stats.hitpoints.current = fight.max_hp / 10;
+        fight.process_hp_events(&mut state);
+
+        let enraged = fight.enraged_form.as_ref().unwrap();
+        assert!(state.enraged);
+        assert_eq!(fight.boss.info.version.as_deref(), Some("Enraged"));
+        assert_eq!(fight.boss.stats.defence.current, drained);
+        assert_eq!(fight.boss.stats.hitpoints.current, fight.max_hp / 10);
+        assert!(
+            fight.boss.def_rolls.get(CombatType::Standard)
+                < enraged.def_rolls.get(CombatType::Standard)
+        );
+    }
+
+    #[test]
+    fn test_one_shadow_per_hit() {
+        let player = loadouts::bowfa_crystal_player();
+        let mut fight =
+            ToaRoomFight::new(player, ToaRoom::Akkha, ToaConfig::default(), None).unwrap();
+        let mut state = ToaRoomState::new(ToaRoom::Akkha);
+
+        // A single hit from full HP to half crosses both the 80% and 60% thresholds
+        fight.boss.take_damage(fight.max_hp / 2);
+        fight.process_hp_events(&mut state);
+        assert!(state.shadow_up);
+        assert_eq!(state.next_event, 1);
+        assert_eq!(fight.boss.stats.hitpoints.current, fight.max_hp * 80 / 100);
+
+        // Nothing else fires until the boss takes more damage
+        fight.shadow = None;
+        fight.retarget(&mut state);
+        fight.process_hp_events(&mut state);
+        assert!(!state.shadow_up);
+        assert_eq!(state.next_event, 1);
+    }
+}
@@ -1,12 +1,18 @@
 use crate::calc::monster_scaling::{build_vard_scaling_table, scale_monster_hp_only};
 use crate::combat::limiters::Limiter;
 use crate::combat::mechanics::{Mechanics, handle_recoil};
-use crate::combat::simulation::{FightResult, FightVars, Simulation, assign_limiter};
+use crate::combat::prayer::PrayerDrainConfig;
+use crate::combat::simulation::{FightResult, FightVars, Simulation, assign_limiter, seeded_rng};
+use crate::combat::spec::{CoreCondition, SpecCondition, SpecConfig, SpecState};
 use crate::combat::thralls::Thrall;
 use crate::constants;
 use crate::error::SimulationError;
+use crate::types::food::Foods;
+use crate::types::inventory::Inventory;
 use crate::types::monster::{AttackType, Monster, MonsterMaxHit};
 use crate::types::player::Player;
+use crate::types::potions::RedoseConfig;
+use crate::types::prayers::Prayer;
 use crate::utils::logging::FightLogger;
 use rand::SeedableRng;
 use rand::rngs::SmallRng;
@@ -17,21 +23,30 @@ const VARDORVIS_REGEN_TICKS: i32 = 100;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct VardorvisConfig {
-    pub food_heal_amount: u32,
-    pub food_eat_delay: i32,
+    pub inventory: Inventory,
     pub eat_strategy: VardorvisEatStrategy,
     pub thralls: Option<Thrall>,
     pub logger: FightLogger,
+    pub prayer_drain: Option<PrayerDrainConfig>,
+    pub redose: Option<RedoseConfig>,
+    pub flick_success: f64, // Chance Protect from Melee is up in time for each attack
+    pub seed: Option<u64>,
 }
 
 impl Default for VardorvisConfig {
     fn default() -> Self {
         Self {
-            food_heal_amount: 22,
-            food_eat_delay: 3,
+            inventory: Inventory::builder()
+                .food(Foods::MantaRay, 28)
+                .build()
+                .expect("Error building inventory."),
             eat_strategy: VardorvisEatStrategy::EatAtHp(20),
             thralls: None,
-            logger: FightLogger::new(false, "vardorvis").expect("Error initializing logger."),
+            logger: FightLogger::new(false),
+            prayer_drain: None,
+            redose: None,
+            flick_success: 1.0,
+            seed: None,
         }
     }
 }
@@ -41,19 +56,61 @@ pub enum VardorvisEatStrategy {
     EatAtHp(u32), // Eat as soon as HP goes below threshold
 }
 
+// Spec conditions specific to Vardorvis, whose defence and strength scale with his remaining HP
+#[derive(Debug, Clone, PartialEq)]
+pub enum VardorvisCondition {
+    Core(CoreCondition),
+    DefenceBelow(u32), // Vardorvis' current (HP-scaled) defence level is at most this
+    MaxHitBelow(u32),  // Vardorvis' current (HP-scaled) max hit is at most this
+    TicksUntilAttackAbove(i32), // More than this many ticks remain before Vardorvis' next attack
+}
+
+impl SpecCondition for VardorvisCondition {
+    type BossState = VardorvisState;
+
+    fn evaluate(&self, player: &Player, monster: &Monster, boss_state: &Self::BossState) -> bool {
+        match self {
+            Self::Core(core) => core.evaluate(player, monster, &()),
+            Self::DefenceBelow(level) => monster.stats.defence.current <= *level,
+            Self::MaxHitBelow(max_hit) => monster
+                .max_hits
+                .as_ref()
+                .and_then(|hits| hits.first())
+                .is_some_and(|hit| hit.value <= *max_hit),
+            Self::TicksUntilAttackAbove(ticks) => {
+                boss_state.vardorvis_attack_tick - boss_state.tick_counter > *ticks
+            }
+        }
+    }
+
+    fn as_core(&self) -> Option<&CoreCondition> {
+        match self {
+            Self::Core(core) => Some(core),
+            _ => None,
+        }
+    }
+
+    fn from_core(core: CoreCondition) -> Self {
+        Self::Core(core)
+    }
+}
+
 #[derive(Debug, Clone)]
-struct VardorvisState {
+pub struct VardorvisState {
+    tick_counter: i32,
     vardorvis_attack_tick: i32,
 }
 
 impl Default for VardorvisState {
     fn default() -> Self {
         Self {
+            tick_counter: 0,
             vardorvis_attack_tick: 2,
         }
     }
 }
 
+#[derive(Clone)]
 struct VardorvisMechanics;
 
 impl Mechanics for VardorvisMechanics {}
@@ -66,10 +123,17 @@ impl VardorvisMechanics {
         state: &mut VardorvisState,
         vars: &mut FightVars,
         rng: &mut SmallRng,
-        logger: &mut FightLogger,
+        config: &mut VardorvisConfig,
     ) -> Result<(), SimulationError> {
-        let mut hit = vard.attack(player, Some(VARDORVIS_ATTACK_STYLE), rng, false)?;
-        hit.damage /= 4; // Assumes Protect from Melee is active
+        // Vardorvis pierces Protect from Melee, so even a good flick only cuts the hit down
+        let hit = vard.attack_with_flick(
+            player,
+            Some(VARDORVIS_ATTACK_STYLE),
+            rng,
+            false,
+            config.flick_success,
+        )?;
+        let logger = &mut config.logger;
 
         if logger.enabled {
             logger.log_monster_attack(
@@ -124,28 +188,34 @@ impl VardorvisMechanics {
     fn handle_eating(
         &self,
         config: &mut VardorvisConfig,
+        inventory: &mut Inventory,
         vars: &mut FightVars,
         player: &mut Player,
-    ) {
-        // Handle eating based on set strategy
+    ) -> bool {
+        // Handle eating based on set strategy, returning false if the player needed to eat
+        // but had no food left
         match config.eat_strategy {
             VardorvisEatStrategy::EatAtHp(threshold) => {
-                // Eat if at or below the provided threshold and force the player to skip the next attack
-                if player.stats.hitpoints.current <= threshold && vars.eat_delay == 0 {
-                    self.eat_food(
-                        player,
-                        config.food_heal_amount,
-                        None,
-                        vars,
-                        &mut config.logger,
-                    );
-                    vars.attack_tick += config.food_eat_delay;
+                if player.stats.hitpoints.current > threshold {
+                    return true;
                 }
+                if !inventory.has_food() {
+                    return false;
+                }
+
+                // Delay the player's next attack by the food's attack delay
+                if let Some(attack_delay) =
+                    self.eat_next_food(player, inventory, false, vars, &mut config.logger)
+                {
+                    vars.attack_tick += attack_delay;
+                }
+                true
             }
         }
     }
 }
 
+#[derive(Clone)]
 pub struct VardorvisFight {
     player: Player,
     vard: Monster,
@@ -153,10 +223,24 @@ pub struct VardorvisFight {
     rng: SmallRng,
     config: VardorvisConfig,
     mechanics: VardorvisMechanics,
+    spec_config: Option<SpecConfig<VardorvisCondition>>,
+    spec_state: SpecState,
+    inventory: Inventory,
 }
 
 impl VardorvisFight {
-    pub fn new(player: Player, config: VardorvisConfig) -> Result<Self, SimulationError> {
+    pub fn new(
+        mut player: Player,
+        config: VardorvisConfig,
+        spec_config: Option<SpecConfig<VardorvisCondition>>,
+    ) -> Result<Self, SimulationError> {
+        if !(0.0..=1.0).contains(&config.flick_success) {
+            return Err(SimulationError::ConfigError(
+                "Flick success must be between 0 and 1.".to_string(),
+            ));
+        }
+        player.set_protection_prayer(Some(Prayer::ProtectFromMelee));
+
         let mut vard = Monster::new("Vardorvis", Some("Post-quest"))
             .map_err(|_| SimulationError::MonsterCreationError("Vardorvis".to_string()))?;
         vard.max_hits = Some(vec![MonsterMaxHit::new(0, AttackType::Slash)]);
@@ -178,7 +262,8 @@ impl VardorvisFight {
         vard.reset();
 
         let limiter = assign_limiter(&player, &vard);
-        let rng = SmallRng::from_os_rng();
+        let rng = seeded_rng(config.seed);
+        let inventory = config.inventory.clone();
 
         Ok(Self {
             player,
@@ -187,10 +272,19 @@ impl VardorvisFight {
             rng,
             config,
             mechanics: VardorvisMechanics,
+            spec_config,
+            spec_state: SpecState::default(),
+            inventory,
         })
     }
 
     fn simulate_vardorvis_fight(&mut self) -> Result<FightResult, SimulationError> {
+        if let Some(ref spec_config) = self.spec_config
+            && let Err(e) = spec_config.validate()
+        {
+            return Err(SimulationError::ConfigError(e));
+        }
+
         let mut vars = FightVars::new();
         let mut state = VardorvisState::default();
         let logging_enabled = self.config.logger.enabled;
@@ -214,18 +308,76 @@ impl VardorvisFight {
             }
 
             self.mechanics.decrement_eat_delay(&mut vars);
-            self.mechanics
-                .handle_eating(&mut self.config, &mut vars, &mut self.player);
+            self.mechanics.process_player_effects(
+                &mut self.player,
+                &mut vars,
+                &mut self.config.logger,
+            );
+            if !self.mechanics.handle_eating(
+                &mut self.config,
+                &mut self.inventory,
+                &mut vars,
+                &mut self.player,
+            ) {
+                return self.mechanics.process_supply_out(
+                    &vars,
+                    &self.vard,
+                    &mut self.config.logger,
+                );
+            }
 
-            if vars.tick_counter == vars.attack_tick {
-                self.mechanics.player_attack(
+            if let Some(prayer_config) = self.config.prayer_drain {
+                let attacking = vars.tick_counter == vars.attack_tick;
+                self.mechanics.process_prayer(
                     &mut self.player,
-                    &mut self.vard,
-                    &mut self.rng,
-                    &self.limiter,
+                    &self.vard,
+                    &prayer_config,
+                    &mut self.inventory,
+                    attacking,
+                    &mut vars,
+                    &mut self.config.logger,
+                )?;
+            }
+
+            if let Some(redose) = self.config.redose {
+                self.mechanics.process_redose(
+                    &mut self.player,
+                    &self.vard,
+                    &redose,
+                    &mut self.inventory,
                     &mut vars,
                     &mut self.config.logger,
                 );
+            }
+
+            if vars.tick_counter == vars.attack_tick {
+                state.tick_counter = vars.tick_counter;
+                let did_spec = if let Some(ref mut spec_config) = self.spec_config {
+                    self.mechanics.player_special_attack(
+                        &mut self.player,
+                        &mut self.vard,
+                        spec_config,
+                        &mut self.spec_state,
+                        &state,
+                        &mut self.rng,
+                        &self.limiter,
+                        &mut vars,
+                        &mut self.config.logger,
+                    )?
+                } else {
+                    false
+                };
+
+                if !did_spec {
+                    self.mechanics.player_attack(
+                        &mut self.player,
+                        &mut self.vard,
+                        &mut self.rng,
+                        &self.limiter,
+                        &mut vars,
+                        &mut self.config.logger,
+                    );
+                }
                 if logging_enabled {
                     self.config.logger.log_current_monster_stats(&self.vard);
                     self.config.logger.log_current_monster_rolls(&self.vard);
@@ -254,10 +406,18 @@ impl VardorvisFight {
                     &mut state,
                     &mut vars,
                     &mut self.rng,
-                    &mut self.config.logger,
+                    &mut self.config,
                 )?;
             }
 
+            self.mechanics.process_spec_timers(
+                &mut self.player,
+                self.spec_config.as_ref(),
+                &mut self.spec_state,
+                &vars,
+                &mut self.config.logger,
+            );
+
             // Increment tick counter
             vars.tick_counter += 1;
 
@@ -298,10 +458,108 @@ impl Simulation for VardorvisFight {
 
     fn set_attack_function(&mut self) {
         self.player.attack = crate::combat::attacks::standard::get_attack_functions(&self.player);
+        self.player.spec = crate::combat::attacks::specs::get_spec_attack_function(&self.player);
     }
 
     fn reset(&mut self) {
-        self.player.reset_current_stats(true);
+        self.config.logger.clear();
+        if let Some(ref mut spec_config) = self.spec_config {
+            let restore_spec = self.spec_state.on_kill(&mut self.player, spec_config);
+            self.player.reset_current_stats(restore_spec);
+        } else {
+            self.player.reset_current_stats(true);
+        }
+        self.vard.reset();
+        self.inventory = self.config.inventory.clone();
+    }
+
+    fn next_kill(&mut self, downtime_ticks: u32) {
+        self.mechanics.prepare_next_kill(
+            &mut self.player,
+            self.spec_config.as_mut(),
+            &mut self.spec_state,
+            downtime_ticks,
+            &mut self.config.logger,
+        );
         self.vard.reset();
     }
+
+    fn inventory(&self) -> Option<&Inventory> {
+        Some(&self.inventory)
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.rng = SmallRng::seed_from_u64(seed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::analysis::SimulationStats;
+    use crate::calc::rolls::calc_active_player_rolls;
+    use crate::combat::prayer::{FlickPolicy, PrayerOutBehaviour};
+    use crate::combat::simulation::simulate_n_fights;
+    use crate::utils::loadouts;
+
+    #[test]
+    fn test_supply_out() {
+        let mut player = loadouts::max_melee_player();
+        let vard = Monster::new("Vardorvis", Some("Post-quest")).expect("Error creating monster.");
+        calc_active_player_rolls(&mut player, &vard);
+
+        // Eating below max HP means the single manta ray runs out immediately
+        let config = VardorvisConfig {
+            inventory: Inventory::builder()
+                .food(Foods::MantaRay, 1)
+                .build()
+                .expect("Error building inventory."),
+            eat_strategy: VardorvisEatStrategy::EatAtHp(99),
+            seed: Some(1),
+            ..VardorvisConfig::default()
+        };
+
+        let mut fight =
+            VardorvisFight::new(player, config, None).expect("Error setting up Vardorvis fight.");
+        fight.set_attack_function();
+        assert!(matches!(
+            fight.simulate(),
+            Err(SimulationError::SupplyOutError(_))
+        ));
+
+        let results = simulate_n_fights(Box::new(fight), 10, false, Some(1)).unwrap();
+        assert_eq!(results.supply_outs, 10);
+        assert_eq!(SimulationStats::new(&results).success_rate, 0.0);
+    }
+
+    #[test]
+    fn test_prayer_drain_policies() {
+        let mut player = loadouts::max_melee_player();
+        let vard = Monster::new("Vardorvis", Some("Post-quest")).expect("Error creating monster.");
+        calc_active_player_rolls(&mut player, &vard);
+
+        let run = |flick_policy: FlickPolicy| {
+            let config = VardorvisConfig {
+                prayer_drain: Some(PrayerDrainConfig {
+                    flick_policy,
+                    on_empty: PrayerOutBehaviour::Fail,
+                    ..PrayerDrainConfig::default()
+                }),
+                seed: Some(5),
+                ..VardorvisConfig::default()
+            };
+            let mut fight = VardorvisFight::new(player.clone(), config, None)
+                .expect("Error setting up Vardorvis fight.");
+            fight.set_attack_function();
+            fight.player.stats.prayer.current = 5;
+            fight.simulate()
+        };
+
+        // Five prayer points don't last a kill with Piety on, but perfect flicking never drains
+        assert!(matches!(
+            run(FlickPolicy::AlwaysOn),
+            Err(SimulationError::SupplyOutError(_))
+        ));
+        assert!(run(FlickPolicy::Flick).is_ok());
+    }
 }
@@ -0,0 +1,1213 @@
+use crate::combat::attacks::effects::PlayerVenom;
+use crate::combat::attacks::standard::Hit;
+use crate::combat::limiters::Limiter;
+use crate::combat::mechanics::{Mechanics, handle_recoil};
+use crate::combat::prayer::PrayerDrainConfig;
+use crate::combat::simulation::{FightResult, FightVars, Simulation, assign_limiter, seeded_rng};
+use crate::combat::spec::{CoreCondition, SpecCondition, SpecConfig, SpecState};
+use crate::combat::thralls::Thrall;
+use crate::constants;
+use crate::error::SimulationError;
+use crate::types::food::Foods;
+use crate::types::inventory::Inventory;
+use crate::types::monster::{AttackType, Monster, MonsterMaxHit};
+use crate::types::monster_specials::{SpecialEffect, SpecialHit};
+use crate::types::player::Player;
+use crate::types::potions::RedoseConfig;
+use crate::types::prayers::Prayer;
+use crate::utils::logging::FightLogger;
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+const VORKATH_ATTACK_SPEED: i32 = 5;
+const VORKATH_REGEN_TICKS: i32 = 100;
+const VORKATH_RANGED_MAX_HIT: u32 = 32;
+const VORKATH_MAGIC_MAX_HIT: u32 = 30;
+const ATTACKS_PER_SPECIAL: u32 = 6;
+
+// Dragonfire max hits depending on the player's protection
+const DRAGONFIRE_MAX_HIT: u32 = 80;
+const PARTIAL_PROTECTION_MAX_HIT: u32 = 20;
+const FULL_PROTECTION_MAX_HIT: u32 = 10;
+
+// Acid phase: Vorkath spits acid and fires a rapid stream of fireballs, one per tick
+const ACID_PHASE_TICKS: i32 = 25;
+const ACID_FIREBALL_MAX_HIT: u32 = 20;
+
+// Firebomb: a high-arc fireball that lands on the player's tile if they don't move off it
+const FIREBOMB_MAX_HIT: u32 = 121;
+
+// Pink dragonfire turns the player's prayers off until they turn them back on the next tick
+const PINK_DRAGONFIRE_DISABLE_TICKS: i32 = 1;
+
+// Zombified spawn: the player is frozen and the spawn walks over, exploding if it arrives
+const SPAWN_APPEAR_DELAY: i32 = 2;
+const SPAWN_WALK_TICKS: i32 = 15;
+const SPAWN_EXPLOSION_MAX_HIT: u32 = 60;
+const CRUMBLE_UNDEAD_SPEED: i32 = 5;
+const CRUMBLE_UNDEAD_HIT_DELAY: i32 = 2;
+const CRUMBLE_UNDEAD_LEVEL: u32 = 39;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct VorkathConfig {
+    pub inventory: Inventory,
+    pub eat_hp: u32, // Eat as soon as HP goes below this during the regular attack cycle
+    pub spawn_eat_hp: u32, // Top up below this HP while frozen by the zombified spawn special
+    pub woox_walk_efficiency: f64, // Fraction of attacks landed during the acid phase (0.0 to 1.0)
+    pub acid_fireball_hit_chance: f64, // Chance each acid phase fireball lands on the player (0.0 to 1.0)
+    pub firebomb_dodge_chance: f64, // Chance of moving off a firebomb's tile in time (0.0 to 1.0)
+    pub antifire: Antifire,
+    pub antivenom: bool,
+    pub protected_style: AttackType, // Vorkath's attack style covered by the player's protection prayer
+    pub flick_success: f64, // Chance the protection prayer is up in time for each attack in that style
+    pub spawn_kill_delay: i32, // Ticks after the spawn appears before crumble undead can be cast
+    pub thralls: Option<Thrall>,
+    pub logger: FightLogger,
+    pub prayer_drain: Option<PrayerDrainConfig>,
+    pub redose: Option<RedoseConfig>,
+    pub seed: Option<u64>,
+}
+
+impl Default for VorkathConfig {
+    fn default() -> Self {
+        Self {
+            inventory: Inventory::builder()
+                .food(Foods::Shark, 20)
+                .build()
+                .expect("Error building inventory."),
+            eat_hp: 50,
+            spawn_eat_hp: 80,
+            woox_walk_efficiency: 0.8,
+            acid_fireball_hit_chance: 0.05,
+            firebomb_dodge_chance: 1.0,
+            antifire: Antifire::Super,
+            antivenom: true,
+            protected_style: AttackType::Ranged,
+            flick_success: 1.0,
+            spawn_kill_delay: 1,
+            thralls: None,
+            logger: FightLogger::new(false),
+            prayer_drain: None,
+            redose: None,
+            seed: None,
+        }
+    }
+}
+
+// Antifire potion active for the whole kill (extended variants only differ in duration)
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Antifire {
+    None,
+    Regular,
+    Super,
+}
+
+impl Antifire {
+    // Approximate max dragonfire hit given the antifire and whether a dragonfire shield is worn
+    fn dragonfire_max_hit(&self, shield: bool) -> u32 {
+        match (self, shield) {
+            (Self::Super, _) => 0,
+            (Self::Regular, true) => FULL_PROTECTION_MAX_HIT,
+            (Self::Regular, false) | (Self::None, true) => PARTIAL_PROTECTION_MAX_HIT,
+            (Self::None, false) => DRAGONFIRE_MAX_HIT,
+        }
+    }
+}
+
+// A typeless hit that always lands, which protection prayers and defensive gear don't affect
+fn typeless_hit(name: &str, damage: u32) -> SpecialHit {
+    SpecialHit {
+        name: name.to_string(),
+        hits: vec![Hit::accurate(damage)],
+        effects: Vec::new(),
+        blockable: false,
+    }
+}
+
+// Vorkath's regular attacks, chosen uniformly at random (melee is omitted since the player stays at range)
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum VorkathAttack {
+    Ranged,
+    Magic,
+    Dragonfire,
+    VenomDragonfire, // Venoms the player unless they're protected by antivenom
+    PinkDragonfire,  // Turns off the player's prayers
+    Firebomb,        // High-arc fireball that hits hard unless the player walks away from it
+}
+
+const STANDARD_ATTACKS: [VorkathAttack; 6] = [
+    VorkathAttack::Ranged,
+    VorkathAttack::Magic,
+    VorkathAttack::Dragonfire,
+    VorkathAttack::VenomDragonfire,
+    VorkathAttack::PinkDragonfire,
+    VorkathAttack::Firebomb,
+];
+
+// Special attacks alternate, starting with a random one
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum VorkathSpecial {
+    Acid,
+    Spawn,
+}
+
+impl VorkathSpecial {
+    fn other(&self) -> Self {
+        match self {
+            Self::Acid => Self::Spawn,
+            Self::Spawn => Self::Acid,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum VorkathPhase {
+    Standard,
+    Acid { end_tick: i32 },
+    Spawn { spawn_tick: i32, explode_tick: i32 },
+}
+
+// Spec conditions specific to the Vorkath fight
+#[derive(Debug, Clone, PartialEq)]
+pub enum VorkathCondition {
+    Core(CoreCondition),
+    StandardPhase,                 // Vorkath is not in the middle of a special attack
+    AttacksUntilSpecialAbove(u32), // More than this many regular attacks remain before the next special
+}
+
+impl SpecCondition for VorkathCondition {
+    type BossState = VorkathState;
+
+    fn evaluate(&self, player: &Player, monster: &Monster, boss_state: &Self::BossState) -> bool {
+        match self {
+            Self::Core(core) => core.evaluate(player, monster, &()),
+            Self::StandardPhase => boss_state.phase == VorkathPhase::Standard,
+            Self::AttacksUntilSpecialAbove(count) => {
+                ATTACKS_PER_SPECIAL.saturating_sub(boss_state.attack_count) > *count
+            }
+        }
+    }
+
+    fn as_core(&self) -> Option<&CoreCondition> {
+        match self {
+            Self::Core(core) => Some(core),
+            _ => None,
+        }
+    }
+
+    fn from_core(core: CoreCondition) -> Self {
+        Self::Core(core)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VorkathState {
+    tick_counter: i32,
+    vorkath_attack_tick: i32,
+    attack_count: u32,
+    next_special: VorkathSpecial,
+    phase: VorkathPhase,
+    venom: Option<PlayerVenom>,
+}
+
+impl Default for VorkathState {
+    fn default() -> Self {
+        Self {
+            tick_counter: 0,
+            vorkath_attack_tick: 2,
+            attack_count: 0,
+            next_special: VorkathSpecial::Acid,
+            phase: VorkathPhase::Standard,
+            venom: None,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct VorkathMechanics;
+
+impl Mechanics for VorkathMechanics {}
+
+impl VorkathMechanics {
+    fn vorkath_attack(
+        &self,
+        vorkath: &mut Monster,
+        player: &mut Player,
+        state: &mut VorkathState,
+        config: &mut VorkathConfig,
+        vars: &mut FightVars,
+        rng: &mut SmallRng,
+    ) -> Result<(), SimulationError> {
+        let attack = STANDARD_ATTACKS[rng.random_range(0..STANDARD_ATTACKS.len())];
+        self.standard_attack(attack, vorkath, player, state, config, vars, rng)?;
+
+        state.attack_count += 1;
+        state.vorkath_attack_tick += VORKATH_ATTACK_SPEED;
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn standard_attack(
+        &self,
+        attack: VorkathAttack,
+        vorkath: &mut Monster,
+        player: &mut Player,
+        state: &mut VorkathState,
+        config: &mut VorkathConfig,
+        vars: &mut FightVars,
+        rng: &mut SmallRng,
+    ) -> Result<(), SimulationError> {
+        match attack {
+            VorkathAttack::Ranged | VorkathAttack::Magic => {
+                let style = if attack == VorkathAttack::Ranged {
+                    AttackType::Ranged
+                } else {
+                    AttackType::Magic
+                };
+                let hit = vorkath.attack_with_flick(
+                    player,
+                    Some(style),
+                    rng,
+                    false,
+                    config.flick_success,
+                )?;
+
+                if config.logger.enabled {
+                    config.logger.log_monster_attack(
+                        vorkath,
+                        vars.tick_counter,
+                        hit.damage,
+                        hit.success,
+                        Some(style),
+                    );
+                }
+
+                if hit.success {
+                    player.take_damage(hit.damage);
+                    vars.damage_taken += hit.damage;
+                    handle_recoil(player, vorkath, &hit, vars, &mut config.logger);
+                }
+            }
+            VorkathAttack::Firebomb => {
+                if config.logger.enabled {
+                    config
+                        .logger
+                        .log_monster_special(vars.tick_counter, "Vorkath", "Firebomb");
+                }
+
+                if vars.player_frozen() || !rng.random_bool(config.firebomb_dodge_chance) {
+                    // The player didn't get off the tile in time
+                    let special =
+                        typeless_hit("Firebomb", rng.random_range(0..FIREBOMB_MAX_HIT + 1));
+                    self.apply_monster_special(
+                        vorkath,
+                        player,
+                        &special,
+                        &mut state.venom,
+                        vars,
+                        &mut config.logger,
+                    );
+                } else if !rng.random_bool(config.woox_walk_efficiency) {
+                    // Walking out of the way costs a tick of attacking unless it's woox walked
+                    vars.attack_tick = vars.attack_tick.max(vars.tick_counter + 1) + 1;
+                }
+            }
+            _ => {
+                // Dragonfire is typeless, so it always hits and ignores protection prayers
+                let shield = constants::DRAGONFIRE_SHIELDS
+                    .iter()
+                    .any(|name| player.is_wearing_any_version(name));
+                let max_hit = config.antifire.dragonfire_max_hit(shield);
+                let mut special = typeless_hit("Dragonfire", rng.random_range(0..max_hit + 1));
+
+                match attack {
+                    VorkathAttack::VenomDragonfire if !config.antivenom => {
+                        special.effects.push(SpecialEffect::Venom);
+                    }
+                    VorkathAttack::PinkDragonfire => {
+                        special
+                            .effects
+                            .push(SpecialEffect::DisablePrayers(PINK_DRAGONFIRE_DISABLE_TICKS));
+
+                        // The player spends the next tick turning their prayers back on
+                        if vars.attack_tick == vars.tick_counter + 1 {
+                            vars.attack_tick += 1;
+                        }
+                    }
+                    _ => {}
+                }
+
+                self.apply_monster_special(
+                    vorkath,
+                    player,
+                    &special,
+                    &mut state.venom,
+                    vars,
+                    &mut config.logger,
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    fn vorkath_special(
+        &self,
+        vorkath: &mut Monster,
+        player: &mut Player,
+        state: &mut VorkathState,
+        vars: &mut FightVars,
+        logger: &mut FightLogger,
+    ) {
+        let tick = vars.tick_counter;
+        match state.next_special {
+            VorkathSpecial::Acid => {
+                let end_tick = tick + ACID_PHASE_TICKS;
+                state.phase = VorkathPhase::Acid { end_tick };
+                state.vorkath_attack_tick = end_tick + VORKATH_ATTACK_SPEED;
+
+                if logger.enabled {
+                    logger.log_phase_change(tick, "Vorkath", "Acid");
+                }
+            }
+            VorkathSpecial::Spawn => {
+                // Vorkath doesn't attack again until the spawn is dealt with
+                let spawn_tick = tick + SPAWN_APPEAR_DELAY;
+                let explode_tick = spawn_tick + SPAWN_WALK_TICKS;
+                state.phase = VorkathPhase::Spawn {
+                    spawn_tick,
+                    explode_tick,
+                };
+                state.vorkath_attack_tick = i32::MAX;
+
+                // The player stays frozen until the spawn dies, at the latest when it explodes
+                let mut special = typeless_hit("Zombified spawn", 0);
+                special
+                    .effects
+                    .push(SpecialEffect::Freeze(explode_tick - tick));
+                self.apply_monster_special(
+                    vorkath,
+                    player,
+                    &special,
+                    &mut state.venom,
+                    vars,
+                    logger,
+                );
+            }
+        }
+
+        state.attack_count = 0;
+        state.next_special = state.next_special.other();
+    }
+
+    // One fireball of the acid phase's rapid stream, which only hurts if the player stops on its tile
+    fn acid_fireball(
+        &self,
+        vorkath: &mut Monster,
+        player: &mut Player,
+        state: &mut VorkathState,
+        config: &mut VorkathConfig,
+        vars: &mut FightVars,
+        rng: &mut SmallRng,
+    ) {
+        if !rng.random_bool(config.acid_fireball_hit_chance) {
+            return;
+        }
+
+        let special = typeless_hit(
+            "Acid phase fireball",
+            rng.random_range(0..ACID_FIREBALL_MAX_HIT + 1),
+        );
+        self.apply_monster_special(
+            vorkath,
+            player,
+            &special,
+            &mut state.venom,
+            vars,
+            &mut config.logger,
+        );
+    }
+
+    fn process_phase_end(
+        &self,
+        player: &mut Player,
+        state: &mut VorkathState,
+        vars: &mut FightVars,
+        rng: &mut SmallRng,
+        logger: &mut FightLogger,
+    ) {
+        match state.phase {
+            VorkathPhase::Acid { end_tick } if vars.tick_counter == end_tick => {
+                state.phase = VorkathPhase::Standard;
+                if logger.enabled {
+                    logger.log_phase_end(vars.tick_counter, "Vorkath", "Acid");
+                }
+            }
+            VorkathPhase::Spawn { explode_tick, .. } if vars.tick_counter == explode_tick => {
+                // The spawn reached the player before crumble undead killed it
+                let damage = rng.random_range(0..SPAWN_EXPLOSION_MAX_HIT + 1);
+                player.take_damage(damage);
+                vars.damage_taken += damage;
+                state.phase = VorkathPhase::Standard;
+                state.vorkath_attack_tick = vars.tick_counter + VORKATH_ATTACK_SPEED;
+
+                if logger.enabled {
+                    logger.log_monster_special(vars.tick_counter, "Zombified spawn", "Explosion");
+                    logger.log_phase_end(vars.tick_counter, "Vorkath", "Spawn");
+                    logger.log_player_damage(
+                        vars.tick_counter,
+                        damage,
+                        player.stats.hitpoints.current,
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Cast crumble undead on the spawn once the player is ready, which always kills it
+    fn cast_crumble_undead(
+        &self,
+        state: &mut VorkathState,
+        vars: &mut FightVars,
+        logger: &mut FightLogger,
+    ) {
+        let death_tick = vars.tick_counter + CRUMBLE_UNDEAD_HIT_DELAY;
+        state.phase = VorkathPhase::Standard;
+        state.vorkath_attack_tick = death_tick + VORKATH_ATTACK_SPEED;
+        vars.attack_tick += CRUMBLE_UNDEAD_SPEED;
+        vars.player_frozen_until = death_tick;
+
+        if logger.enabled {
+            logger.log_custom(
+                vars.tick_counter,
+                "Player cast crumble undead on the zombified spawn.",
+            );
+            logger.log_phase_end(vars.tick_counter, "Vorkath", "Spawn");
+        }
+    }
+
+    fn handle_eating(
+        &self,
+        config: &mut VorkathConfig,
+        state: &VorkathState,
+        inventory: &mut Inventory,
+        vars: &mut FightVars,
+        player: &mut Player,
+    ) -> bool {
+        // Eat below the threshold for the current phase, returning false if the player needed
+        // to eat but had no food left
+        let threshold = match state.phase {
+            VorkathPhase::Spawn { .. } => config.spawn_eat_hp.max(config.eat_hp),
+            _ => config.eat_hp,
+        };
+        if player.stats.hitpoints.current > threshold {
+            return true;
+        }
+        if !inventory.has_food() {
+            // Topping up during the spawn is optional, so only fail below the regular threshold
+            return player.stats.hitpoints.current > config.eat_hp;
+        }
+
+        if let Some(attack_delay) =
+            self.eat_next_food(player, inventory, false, vars, &mut config.logger)
+        {
+            vars.attack_tick += attack_delay;
+        }
+        true
+    }
+}
+
+#[derive(Clone)]
+pub struct VorkathFight {
+    player: Player,
+    vorkath: Monster,
+    limiter: Option<Box<dyn Limiter>>,
+    rng: SmallRng,
+    config: VorkathConfig,
+    mechanics: VorkathMechanics,
+    spec_config: Option<SpecConfig<VorkathCondition>>,
+    spec_state: SpecState,
+    inventory: Inventory,
+}
+
+impl VorkathFight {
+    pub fn new(
+        mut player: Player,
+        config: VorkathConfig,
+        spec_config: Option<SpecConfig<VorkathCondition>>,
+    ) -> Result<Self, SimulationError> {
+        for (name, chance) in [
+            ("Woox walk efficiency", config.woox_walk_efficiency),
+            ("Acid fireball hit chance", config.acid_fireball_hit_chance),
+            ("Firebomb dodge chance", config.firebomb_dodge_chance),
+            ("Flick success", config.flick_success),
+        ] {
+            if !(0.0..=1.0).contains(&chance) {
+                return Err(SimulationError::ConfigError(format!(
+                    "{name} must be between 0 and 1, got {chance}"
+                )));
+            }
+        }
+        if player.stats.magic.current < CRUMBLE_UNDEAD_LEVEL {
+            return Err(SimulationError::ConfigError(format!(
+                "Killing the zombified spawn with crumble undead needs {CRUMBLE_UNDEAD_LEVEL} magic"
+            )));
+        }
+
+        let mut vorkath = Monster::new("Vorkath", Some("Post-quest"))
+            .map_err(|_| SimulationError::MonsterCreationError("Vorkath".to_string()))?;
+        vorkath.max_hits = Some(vec![
+            MonsterMaxHit::new(VORKATH_RANGED_MAX_HIT, AttackType::Ranged),
+            MonsterMaxHit::new(VORKATH_MAGIC_MAX_HIT, AttackType::Magic),
+        ]);
+
+        player.set_protection_prayer(Prayer::protection_against(config.protected_style));
+
+        let limiter = assign_limiter(&player, &vorkath);
+        let rng = seeded_rng(config.seed);
+        let inventory = config.inventory.clone();
+
+        Ok(Self {
+            player,
+            vorkath,
+            limiter,
+            rng,
+            config,
+            mechanics: VorkathMechanics,
+            spec_config,
+            spec_state: SpecState::default(),
+            inventory,
+        })
+    }
+
+    fn simulate_vorkath_fight(&mut self) -> Result<FightResult, SimulationError> {
+        if let Some(ref spec_config) = self.spec_config
+            && let Err(e) = spec_config.validate()
+        {
+            return Err(SimulationError::ConfigError(e));
+        }
+
+        let mut vars = FightVars::new();
+        let mut state = VorkathState {
+            next_special: if self.rng.random_bool(0.5) {
+                VorkathSpecial::Acid
+            } else {
+                VorkathSpecial::Spawn
+            },
+            ..VorkathState::default()
+        };
+        let logging_enabled = self.config.logger.enabled;
+        if logging_enabled {
+            self.config
+                .logger
+                .log_initial_setup(&self.player, &self.vorkath);
+        }
+
+        while self.vorkath.stats.hitpoints.current > 0 {
+            if vars.tick_counter % VORKATH_REGEN_TICKS == 0 {
+                self.mechanics.monster_regen_stats(
+                    &mut self.vorkath,
+                    &vars,
+                    &mut self.config.logger,
+                );
+            }
+
+            // Regen 1 HP for player every 100 ticks
+            if vars.tick_counter % constants::PLAYER_REGEN_TICKS == 0 {
+                self.mechanics
+                    .player_regen(&mut self.player, &vars, &mut self.config.logger);
+            }
+
+            self.mechanics.decrement_eat_delay(&mut vars);
+            self.mechanics.process_player_effects(
+                &mut self.player,
+                &mut vars,
+                &mut self.config.logger,
+            );
+            self.mechanics.process_player_venom(
+                &mut self.player,
+                &mut state.venom,
+                &mut vars,
+                &mut self.config.logger,
+            );
+            self.mechanics.process_phase_end(
+                &mut self.player,
+                &mut state,
+                &mut vars,
+                &mut self.rng,
+                &mut self.config.logger,
+            );
+            self.mechanics.process_prayer_disable(
+                &mut self.player,
+                &self.vorkath,
+                &mut vars,
+                &mut self.config.logger,
+            );
+
+            if !self.mechanics.handle_eating(
+                &mut self.config,
+                &state,
+                &mut self.inventory,
+                &mut vars,
+                &mut self.player,
+            ) {
+                return self.mechanics.process_supply_out(
+                    &vars,
+                    &self.vorkath,
+                    &mut self.config.logger,
+                );
+            }
+
+            if let Some(prayer_config) = self.config.prayer_drain {
+                let attacking = vars.tick_counter == vars.attack_tick;
+                self.mechanics.process_prayer(
+                    &mut self.player,
+                    &self.vorkath,
+                    &prayer_config,
+                    &mut self.inventory,
+                    attacking,
+                    &mut vars,
+                    &mut self.config.logger,
+                )?;
+            }
+
+            if let Some(redose) = self.config.redose {
+                self.mechanics.process_redose(
+                    &mut self.player,
+                    &self.vorkath,
+                    &redose,
+                    &mut self.inventory,
+                    &mut vars,
+                    &mut self.config.logger,
+                );
+            }
+
+            if vars.tick_counter == vars.attack_tick {
+                state.tick_counter = vars.tick_counter;
+                match state.phase {
+                    VorkathPhase::Spawn { spawn_tick, .. }
+                        if vars.tick_counter >= spawn_tick + self.config.spawn_kill_delay =>
+                    {
+                        self.mechanics.cast_crumble_undead(
+                            &mut state,
+                            &mut vars,
+                            &mut self.config.logger,
+                        );
+                    }
+                    VorkathPhase::Acid { .. }
+                        if !self.rng.random_bool(self.config.woox_walk_efficiency) =>
+                    {
+                        // Lost the attack while dodging acid and fireballs
+                        vars.attack_tick += self.player.gear.weapon.speed;
+                        if logging_enabled {
+                            self.config.logger.log_custom(
+                                vars.tick_counter,
+                                "Player missed an attack while woox walking.",
+                            );
+                        }
+                    }
+                    _ => self.player_attack_or_spec(&state, &mut vars)?,
+                }
+            }
+
+            if let Some(thrall) = self.config.thralls
+                && vars.tick_counter == vars.thrall_attack_tick
+            {
+                self.mechanics.thrall_attack(
+                    &mut self.vorkath,
+                    thrall,
+                    &mut vars,
+                    &mut self.rng,
+                    &mut self.config.logger,
+                );
+            }
+
+            self.mechanics.process_monster_effects(
+                &mut self.vorkath,
+                &vars,
+                &mut self.config.logger,
+            );
+
+            if self.vorkath.stats.hitpoints.current > 0
+                && vars.tick_counter == state.vorkath_attack_tick
+            {
+                if state.attack_count == ATTACKS_PER_SPECIAL {
+                    self.mechanics.vorkath_special(
+                        &mut self.vorkath,
+                        &mut self.player,
+                        &mut state,
+                        &mut vars,
+                        &mut self.config.logger,
+                    );
+                } else {
+                    self.mechanics.vorkath_attack(
+                        &mut self.vorkath,
+                        &mut self.player,
+                        &mut state,
+                        &mut self.config,
+                        &mut vars,
+                        &mut self.rng,
+                    )?;
+                }
+            }
+
+            // The acid phase fires one fireball per tick, starting on the tick it begins
+            if matches!(state.phase, VorkathPhase::Acid { .. }) {
+                self.mechanics.acid_fireball(
+                    &mut self.vorkath,
+                    &mut self.player,
+                    &mut state,
+                    &mut self.config,
+                    &mut vars,
+                    &mut self.rng,
+                );
+            }
+
+            self.mechanics.process_spec_timers(
+                &mut self.player,
+                self.spec_config.as_ref(),
+                &mut self.spec_state,
+                &vars,
+                &mut self.config.logger,
+            );
+
+            // Increment tick counter
+            vars.tick_counter += 1;
+
+            if self.player.stats.hitpoints.current == 0 {
+                return self.mechanics.process_player_death(
+                    &vars,
+                    &self.vorkath,
+                    &mut self.config.logger,
+                );
+            }
+        }
+        let remove_final_attack_delay = true;
+        self.mechanics.get_fight_result(
+            &self.vorkath,
+            &vars,
+            &mut self.config.logger,
+            remove_final_attack_delay,
+        )
+    }
+
+    // Use a special attack if any strategy's conditions are met, otherwise attack normally
+    fn player_attack_or_spec(
+        &mut self,
+        state: &VorkathState,
+        vars: &mut FightVars,
+    ) -> Result<(), SimulationError> {
+        let did_spec = if let Some(ref mut spec_config) = self.spec_config {
+            self.mechanics.player_special_attack(
+                &mut self.player,
+                &mut self.vorkath,
+                spec_config,
+                &mut self.spec_state,
+                state,
+                &mut self.rng,
+                &self.limiter,
+                vars,
+                &mut self.config.logger,
+            )?
+        } else {
+            false
+        };
+
+        if !did_spec {
+            self.mechanics.player_attack(
+                &mut self.player,
+                &mut self.vorkath,
+                &mut self.rng,
+                &self.limiter,
+                vars,
+                &mut self.config.logger,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Simulation for VorkathFight {
+    fn simulate(&mut self) -> Result<FightResult, SimulationError> {
+        // Pink dragonfire can leave the player's prayers off when the fight ends
+        let prayers = self.player.prayers.clone();
+        let result = self.simulate_vorkath_fight();
+        self.player.prayers = prayers;
+        result
+    }
+
+    fn is_immune(&self) -> bool {
+        self.vorkath.is_immune(&self.player)
+    }
+
+    fn player(&self) -> &Player {
+        &self.player
+    }
+
+    fn monster(&self) -> &Monster {
+        &self.vorkath
+    }
+
+    fn set_attack_function(&mut self) {
+        self.player.attack = crate::combat::attacks::standard::get_attack_functions(&self.player);
+        self.player.spec = crate::combat::attacks::specs::get_spec_attack_function(&self.player);
+    }
+
+    fn reset(&mut self) {
+        self.config.logger.clear();
+        if let Some(ref mut spec_config) = self.spec_config {
+            let restore_spec = self.spec_state.on_kill(&mut self.player, spec_config);
+            self.player.reset_current_stats(restore_spec);
+        } else {
+            self.player.reset_current_stats(true);
+        }
+        self.vorkath.reset();
+        self.inventory = self.config.inventory.clone();
+    }
+
+    fn next_kill(&mut self, downtime_ticks: u32) {
+        self.mechanics.prepare_next_kill(
+            &mut self.player,
+            self.spec_config.as_mut(),
+            &mut self.spec_state,
+            downtime_ticks,
+            &mut self.config.logger,
+        );
+        self.vorkath.reset();
+    }
+
+    fn inventory(&self) -> Option<&Inventory> {
+        Some(&self.inventory)
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.rng = SmallRng::seed_from_u64(seed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::analysis::SimulationStats;
+    use crate::calc::rolls::calc_active_player_rolls;
+    use crate::combat::simulation::simulate_n_fights;
+    use crate::utils::loadouts;
+    use crate::utils::logging::FightEventKind;
+
+    fn vorkath_player() -> Player {
+        let mut player = loadouts::max_ranged_zcb_player();
+        let vorkath = Monster::new("Vorkath", Some("Post-quest")).expect("Error creating monster.");
+        calc_active_player_rolls(&mut player, &vorkath);
+        player
+    }
+
+    fn run(config: VorkathConfig, n: u32) -> SimulationStats {
+        let mut fight =
+            VorkathFight::new(vorkath_player(), config, None).expect("Error setting up fight.");
+        fight.set_attack_function();
+        let results = simulate_n_fights(Box::new(fight), n, false, Some(1)).unwrap();
+        SimulationStats::new(&results)
+    }
+
+    #[test]
+    fn test_specials_alternate() {
+        let (mut vorkath, mut player, mut state, mut vars, _) = attack_setup();
+        let mut logger = FightLogger::new(false);
+        state.next_special = VorkathSpecial::Acid;
+        state.attack_count = ATTACKS_PER_SPECIAL;
+
+        // The acid phase holds off Vorkath's next attack until it ends
+        vars.tick_counter = 10;
+        VorkathMechanics.vorkath_special(
+            &mut vorkath,
+            &mut player,
+            &mut state,
+            &mut vars,
+            &mut logger,
+        );
+        let end_tick = 10 + ACID_PHASE_TICKS;
+        assert_eq!(state.phase, VorkathPhase::Acid { end_tick });
+        assert_eq!(state.vorkath_attack_tick, end_tick + VORKATH_ATTACK_SPEED);
+        assert_eq!(state.attack_count, 0);
+        assert!(!vars.player_frozen());
+
+        // The spawn freezes the player until crumble undead kills it
+        vars.tick_counter = 60;
+        VorkathMechanics.vorkath_special(
+            &mut vorkath,
+            &mut player,
+            &mut state,
+            &mut vars,
+            &mut logger,
+        );
+        assert!(matches!(state.phase, VorkathPhase::Spawn { .. }));
+        assert_eq!(state.next_special, VorkathSpecial::Acid);
+        assert!(vars.player_frozen());
+
+        VorkathMechanics.cast_crumble_undead(&mut state, &mut vars, &mut logger);
+        assert_eq!(state.phase, VorkathPhase::Standard);
+        vars.tick_counter += CRUMBLE_UNDEAD_HIT_DELAY;
+        assert!(!vars.player_frozen());
+        assert_eq!(
+            state.vorkath_attack_tick,
+            vars.tick_counter + VORKATH_ATTACK_SPEED
+        );
+    }
+
+    #[test]
+    fn test_acid_phase_attack_loss() {
+        // Count the acid phases and the player's attacks during them over a few traced kills
+        let acid_attacks = |woox_walk_efficiency: f64| {
+            let config = VorkathConfig {
+                woox_walk_efficiency,
+                logger: FightLogger::new(true),
+                seed: Some(2),
+                ..VorkathConfig::default()
+            };
+            let mut fight = VorkathFight::new(vorkath_player(), config, None).unwrap();
+            fight.set_attack_function();
+
+            let (mut phases, mut attacks) = (0, 0);
+            for _ in 0..5 {
+                let trace = fight.simulate().unwrap().trace.unwrap();
+                fight.reset();
+
+                let mut in_acid = false;
+                for event in &trace.events {
+                    match &event.kind {
+                        FightEventKind::PhaseChange { phase, .. } if phase == "Acid" => {
+                            in_acid = true;
+                            phases += 1;
+                        }
+                        FightEventKind::PhaseEnd { phase, .. } if phase == "Acid" => {
+                            in_acid = false;
+                        }
+                        FightEventKind::PlayerAttack { .. } | FightEventKind::PlayerSpec { .. }
+                            if in_acid =>
+                        {
+                            attacks += 1;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            (phases, attacks, fight.player.gear.weapon.speed)
+        };
+
+        // Every attack during the acid phase is lost when the player never gets one off
+        let (phases, attacks, _) = acid_attacks(0.0);
+        assert!(phases > 0);
+        assert_eq!(attacks, 0);
+
+        // A perfect woox walk keeps attacking on cooldown through the whole phase
+        let (phases, attacks, speed) = acid_attacks(1.0);
+        assert!(phases > 0);
+        assert!(attacks >= phases * (ACID_PHASE_TICKS / speed - 1));
+        assert!(attacks <= phases * (ACID_PHASE_TICKS / speed + 1));
+    }
+
+    #[test]
+    fn test_dragonfire_and_venom_protection() {
+        let protected = run(VorkathConfig::default(), 200);
+        let unprotected = run(
+            VorkathConfig {
+                antifire: Antifire::None,
+                antivenom: false,
+                ..VorkathConfig::default()
+            },
+            200,
+        );
+        assert!(unprotected.avg_damage_taken > protected.avg_damage_taken);
+        assert!(unprotected.avg_food_eaten > protected.avg_food_eaten);
+    }
+
+    // Vorkath, a fresh fight state and a seeded rng for driving single attacks
+    fn attack_setup() -> (Monster, Player, VorkathState, FightVars, SmallRng) {
+        let fight = VorkathFight::new(vorkath_player(), VorkathConfig::default(), None)
+            .expect("Error setting up fight.");
+        (
+            fight.vorkath,
+            fight.player,
+            VorkathState::default(),
+            FightVars::new(),
+            SmallRng::seed_from_u64(1),
+        )
+    }
+
+    #[test]
+    fn test_flick_success() {
+        let damage = |flick_success: f64| {
+            let (mut vorkath, mut player, mut state, mut vars, mut rng) = attack_setup();
+            let mut config = VorkathConfig {
+                flick_success,
+                ..VorkathConfig::default()
+            };
+            for _ in 0..50 {
+                VorkathMechanics
+                    .standard_attack(
+                        VorkathAttack::Ranged,
+                        &mut vorkath,
+                        &mut player,
+                        &mut state,
+                        &mut config,
+                        &mut vars,
+                        &mut rng,
+                    )
+                    .unwrap();
+                player.stats.hitpoints.reset();
+            }
+            vars.damage_taken
+        };
+
+        // Protect from Missiles blocks every ranged attack, but only if it's up in time
+        assert_eq!(damage(1.0), 0);
+        assert!(damage(0.0) > 0);
+    }
+
+    #[test]
+    fn test_acid_phase_fireballs() {
+        let (mut vorkath, mut player, mut state, mut vars, mut rng) = attack_setup();
+        let mut config = VorkathConfig {
+            acid_fireball_hit_chance: 1.0,
+            ..VorkathConfig::default()
+        };
+        for _ in 0..ACID_PHASE_TICKS {
+            VorkathMechanics.acid_fireball(
+                &mut vorkath,
+                &mut player,
+                &mut state,
+                &mut config,
+                &mut vars,
+                &mut rng,
+            );
+        }
+        assert!(vars.damage_taken > 0);
+        assert!(vars.damage_taken <= ACID_FIREBALL_MAX_HIT * ACID_PHASE_TICKS as u32);
+
+        // Walking cleanly through the whole phase avoids every fireball
+        let mut vars = FightVars::new();
+        config.acid_fireball_hit_chance = 0.0;
+        for _ in 0..ACID_PHASE_TICKS {
+            VorkathMechanics.acid_fireball(
+                &mut vorkath,
+                &mut player,
+                &mut state,
+                &mut config,
+                &mut vars,
+                &mut rng,
+            );
+        }
+        assert_eq!(vars.damage_taken, 0);
+    }
+
+    #[test]
+    fn test_firebomb_hits_on_failed_movement() {
+        let (mut vorkath, mut player, mut state, _, mut rng) = attack_setup();
+        let mut config = VorkathConfig {
+            firebomb_dodge_chance: 0.0,
+            ..VorkathConfig::default()
+        };
+        let mut hit_damage = 0;
+        for _ in 0..10 {
+            let mut vars = FightVars::new();
+            VorkathMechanics
+                .standard_attack(
+                    VorkathAttack::Firebomb,
+                    &mut vorkath,
+                    &mut player,
+                    &mut state,
+                    &mut config,
+                    &mut vars,
+                    &mut rng,
+                )
+                .unwrap();
+            hit_damage += vars.damage_taken;
+            player.stats.hitpoints.restore(99, None);
+        }
+        assert!(hit_damage > 0);
+
+        // Moving off the tile avoids the damage, but a frozen player can't move
+        config.firebomb_dodge_chance = 1.0;
+        let mut vars = FightVars::new();
+        VorkathMechanics
+            .standard_attack(
+                VorkathAttack::Firebomb,
+                &mut vorkath,
+                &mut player,
+                &mut state,
+                &mut config,
+                &mut vars,
+                &mut rng,
+            )
+            .unwrap();
+        assert_eq!(vars.damage_taken, 0);
+
+        let mut frozen_damage = 0;
+        for _ in 0..10 {
+            let mut vars = FightVars::new();
+            vars.player_frozen_until = 5;
+            VorkathMechanics
+                .standard_attack(
+                    VorkathAttack::Firebomb,
+                    &mut vorkath,
+                    &mut player,
+                    &mut state,
+                    &mut config,
+                    &mut vars,
+                    &mut rng,
+                )
+                .unwrap();
+            frozen_damage += vars.damage_taken;
+            player.stats.hitpoints.restore(99, None);
+        }
+        assert!(frozen_damage > 0);
+    }
+
+    #[test]
+    fn test_pink_dragonfire_disables_prayers() {
+        let (mut vorkath, mut player, mut state, mut vars, mut rng) = attack_setup();
+        let mut config = VorkathConfig::default();
+        let prayers = player.prayers.clone();
+        assert!(prayers.active_prayers.is_some());
+
+        vars.attack_tick = 1;
+        VorkathMechanics
+            .standard_attack(
+                VorkathAttack::PinkDragonfire,
+                &mut vorkath,
+                &mut player,
+                &mut state,
+                &mut config,
+                &mut vars,
+                &mut rng,
+            )
+            .unwrap();
+        assert_ne!(player.prayers, prayers);
+        assert_eq!(vars.attack_tick, 2);
+
+        // The player turns them back on the next tick
+        vars.tick_counter = 1;
+        VorkathMechanics.process_prayer_disable(
+            &mut player,
+            &vorkath,
+            &mut vars,
+            &mut config.logger,
+        );
+        assert_eq!(player.prayers, prayers);
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let config = VorkathConfig {
+            woox_walk_efficiency: 1.5,
+            ..VorkathConfig::default()
+        };
+        assert!(matches!(
+            VorkathFight::new(vorkath_player(), config, None),
+            Err(SimulationError::ConfigError(_))
+        ));
+    }
+}
@@ -0,0 +1,989 @@
+use crate::calc::rolls::monster_def_rolls;
+use crate::combat::attacks::effects::PlayerVenom;
+use crate::combat::limiters::Limiter;
+use crate::combat::mechanics::{Hazard, Mechanics, handle_recoil};
+use crate::combat::prayer::PrayerDrainConfig;
+use crate::combat::simulation::{FightResult, FightVars, Simulation, assign_limiter, seeded_rng};
+use crate::combat::spec::{CoreCondition, SpecCondition, SpecConfig, SpecState};
+use crate::constants;
+use crate::error::{PlayerError, SimulationError};
+use crate::types::food::Foods;
+use crate::types::inventory::Inventory;
+use crate::types::monster::{AttackType, Monster, MonsterMaxHit};
+use crate::types::player::{GearSwitch, Player, SwitchType};
+use crate::types::potions::RedoseConfig;
+use crate::types::prayers::Prayer;
+use crate::utils::logging::FightLogger;
+use ZulrahForm::{Jad, Magma, Serpentine, Tanzanite};
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+
+const ZULRAH_ATTACK_SPEED: i32 = 3;
+const ZULRAH_MAX_HIT: u32 = 41;
+const ZULRAH_REGEN_TICKS: i32 = 100;
+
+// Zulrah can't be attacked while surfacing or diving between phases
+const SURFACE_TICKS: i32 = 3;
+const DIVE_TICKS: i32 = 3;
+
+const ROTATION_ONE: [ZulrahForm; 10] = [
+    Serpentine, Magma, Tanzanite, Serpentine, Magma, Tanzanite, Serpentine, Tanzanite, Jad, Magma,
+];
+const ROTATION_TWO: [ZulrahForm; 10] = [
+    Serpentine, Magma, Tanzanite, Serpentine, Tanzanite, Magma, Serpentine, Tanzanite, Jad, Magma,
+];
+const ROTATION_THREE: [ZulrahForm; 11] = [
+    Serpentine, Serpentine, Magma, Tanzanite, Serpentine, Tanzanite, Serpentine, Serpentine,
+    Tanzanite, Jad, Serpentine,
+];
+const ROTATION_FOUR: [ZulrahForm; 12] = [
+    Serpentine, Tanzanite, Serpentine, Tanzanite, Magma, Serpentine, Serpentine, Tanzanite,
+    Serpentine, Tanzanite, Jad, Serpentine,
+];
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum ZulrahForm {
+    Serpentine, // Green: ranged attacks
+    Magma,      // Red: melee attacks aimed at a tile, which the player steps away from
+    Tanzanite,  // Blue: a random mix of magic and ranged attacks
+    Jad,        // Alternates ranged and magic attacks, using the serpentine form's stats
+}
+
+impl ZulrahForm {
+    // Number of attacks Zulrah makes before diving (approximate)
+    fn attacks(&self) -> i32 {
+        match self {
+            Self::Serpentine | Self::Tanzanite => 6,
+            Self::Magma => 4,
+            Self::Jad => 10,
+        }
+    }
+
+    // Index of the monster whose stats this form uses
+    fn monster_index(&self) -> usize {
+        match self {
+            Self::Serpentine | Self::Jad => 0,
+            Self::Magma => 1,
+            Self::Tanzanite => 2,
+        }
+    }
+
+    // Venom clouds and snakelings are only spawned during the regular serpentine and tanzanite phases
+    fn spawns_hazards(&self) -> bool {
+        matches!(self, Self::Serpentine | Self::Tanzanite)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum ZulrahRotation {
+    One,
+    Two,
+    Three,
+    Four,
+}
+
+impl ZulrahRotation {
+    const ALL: [Self; 4] = [Self::One, Self::Two, Self::Three, Self::Four];
+
+    pub fn phases(&self) -> &'static [ZulrahForm] {
+        match self {
+            Self::One => &ROTATION_ONE,
+            Self::Two => &ROTATION_TWO,
+            Self::Three => &ROTATION_THREE,
+            Self::Four => &ROTATION_FOUR,
+        }
+    }
+
+    // The Jad phase starts with magic in the first two rotations and ranged in the others
+    fn jad_first_style(&self) -> AttackType {
+        match self {
+            Self::One | Self::Two => AttackType::Magic,
+            Self::Three | Self::Four => AttackType::Ranged,
+        }
+    }
+}
+
+/// Gear switch to attack with in each phase of the fight.
+///
+/// Each form has a default switch, and `overrides` can replace it for a specific phase,
+/// keyed by rotation and 1-based phase number.
+#[derive(Debug, PartialEq, Clone)]
+pub struct AttackChart {
+    pub serpentine: SwitchType,
+    pub magma: SwitchType,
+    pub tanzanite: SwitchType,
+    pub jad: SwitchType,
+    pub overrides: HashMap<(ZulrahRotation, usize), SwitchType>,
+}
+
+impl Default for AttackChart {
+    fn default() -> Self {
+        Self {
+            serpentine: SwitchType::Magic,
+            magma: SwitchType::Magic,
+            tanzanite: SwitchType::Ranged,
+            jad: SwitchType::Magic,
+            overrides: HashMap::new(),
+        }
+    }
+}
+
+impl AttackChart {
+    pub fn switch_for(
+        &self,
+        rotation: ZulrahRotation,
+        phase: usize,
+        form: ZulrahForm,
+    ) -> &SwitchType {
+        if let Some(switch) = self.overrides.get(&(rotation, phase)) {
+            return switch;
+        }
+        match form {
+            Serpentine => &self.serpentine,
+            Magma => &self.magma,
+            Tanzanite => &self.tanzanite,
+            Jad => &self.jad,
+        }
+    }
+
+    fn switch_types(&self) -> impl Iterator<Item = &SwitchType> {
+        [&self.serpentine, &self.magma, &self.tanzanite, &self.jad]
+            .into_iter()
+            .chain(self.overrides.values())
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ZulrahConfig {
+    pub inventory: Inventory,
+    pub eat_hp: u32,
+    pub attack_chart: AttackChart,
+    pub rotation: Option<ZulrahRotation>, // Fixed rotation, or None for a random one each cycle
+    pub tanzanite_prayer: AttackType, // Style prayed against during tanzanite phases (others are always prayed correctly)
+    pub flick_success: f64,           // Chance the protection prayer is up in time for each attack
+    // Hazards are rolled once per serpentine/tanzanite phase
+    pub venom_clouds: Option<Hazard>, // Cloud hits venom the player unless they have antivenom
+    pub snakelings: Option<Hazard>,
+    pub antivenom: bool,
+    pub logger: FightLogger,
+    pub prayer_drain: Option<PrayerDrainConfig>,
+    pub redose: Option<RedoseConfig>,
+    pub seed: Option<u64>,
+}
+
+impl Default for ZulrahConfig {
+    fn default() -> Self {
+        Self {
+            inventory: Inventory::builder()
+                .food(Foods::Shark, 16)
+                .build()
+                .expect("Error building inventory."),
+            eat_hp: 50,
+            attack_chart: AttackChart::default(),
+            rotation: None,
+            tanzanite_prayer: AttackType::Magic,
+            flick_success: 1.0,
+            venom_clouds: Some(Hazard {
+                chance: 0.1,
+                max_hit: 5,
+            }),
+            snakelings: Some(Hazard {
+                chance: 0.25,
+                max_hit: 15,
+            }),
+            antivenom: true,
+            logger: FightLogger::new(false),
+            prayer_drain: None,
+            redose: None,
+            seed: None,
+        }
+    }
+}
+
+// Spec conditions specific to the Zulrah fight
+#[derive(Debug, Clone, PartialEq)]
+pub enum ZulrahCondition {
+    Core(CoreCondition),
+    Form(ZulrahForm),         // Zulrah is currently in this form
+    TicksUntilDiveAbove(i32), // More than this many ticks remain before Zulrah dives
+}
+
+impl SpecCondition for ZulrahCondition {
+    type BossState = ZulrahState;
+
+    fn evaluate(&self, player: &Player, monster: &Monster, boss_state: &Self::BossState) -> bool {
+        match self {
+            Self::Core(core) => core.evaluate(player, monster, &()),
+            Self::Form(form) => boss_state.form == *form,
+            Self::TicksUntilDiveAbove(ticks) => {
+                boss_state.dive_tick - boss_state.tick_counter > *ticks
+            }
+        }
+    }
+
+    fn as_core(&self) -> Option<&CoreCondition> {
+        match self {
+            Self::Core(core) => Some(core),
+            _ => None,
+        }
+    }
+
+    fn from_core(core: CoreCondition) -> Self {
+        Self::Core(core)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ZulrahState {
+    tick_counter: i32,
+    rotation: ZulrahRotation,
+    phase_index: usize,
+    form: ZulrahForm,
+    targetable_tick: i32,
+    dive_tick: i32,
+    next_phase_tick: i32,
+    zulrah_attack_tick: i32,
+    attack_count: i32,
+    venom: Option<PlayerVenom>,
+}
+
+impl Default for ZulrahState {
+    fn default() -> Self {
+        Self {
+            tick_counter: 0,
+            rotation: ZulrahRotation::One,
+            phase_index: 0,
+            form: Serpentine,
+            targetable_tick: 0,
+            dive_tick: 0,
+            next_phase_tick: 0,
+            zulrah_attack_tick: 0,
+            attack_count: 0,
+            venom: None,
+        }
+    }
+}
+
+impl ZulrahState {
+    // Surface in the given form on the current tick and schedule the rest of the phase
+    fn start_phase(&mut self, form: ZulrahForm, tick: i32) {
+        self.form = form;
+        self.targetable_tick = tick + SURFACE_TICKS;
+        self.zulrah_attack_tick = self.targetable_tick;
+        self.dive_tick = self.targetable_tick + form.attacks() * ZULRAH_ATTACK_SPEED;
+        self.next_phase_tick = self.dive_tick + DIVE_TICKS;
+        self.attack_count = 0;
+    }
+
+    fn is_targetable(&self) -> bool {
+        (self.targetable_tick..self.dive_tick).contains(&self.tick_counter)
+    }
+}
+
+#[derive(Clone)]
+struct ZulrahMechanics;
+
+impl Mechanics for ZulrahMechanics {}
+
+impl ZulrahMechanics {
+    fn zulrah_attack(
+        &self,
+        zulrah: &mut Monster,
+        player: &mut Player,
+        state: &mut ZulrahState,
+        config: &mut ZulrahConfig,
+        vars: &mut FightVars,
+        rng: &mut SmallRng,
+    ) -> Result<(), SimulationError> {
+        let (style, prayed) = match state.form {
+            Serpentine => (AttackType::Ranged, AttackType::Ranged),
+            Tanzanite => {
+                let style = if rng.random_bool(0.5) {
+                    AttackType::Magic
+                } else {
+                    AttackType::Ranged
+                };
+                (style, config.tanzanite_prayer)
+            }
+            Jad => {
+                let first = state.rotation.jad_first_style();
+                let style = match (first, state.attack_count % 2 == 0) {
+                    (AttackType::Magic, true) | (AttackType::Ranged, false) => AttackType::Magic,
+                    _ => AttackType::Ranged,
+                };
+                (style, style)
+            }
+            Magma => {
+                // The player always steps off the targeted tile
+                if config.logger.enabled {
+                    config
+                        .logger
+                        .log_custom(vars.tick_counter, "Player dodged Zulrah's melee attack.");
+                }
+                state.attack_count += 1;
+                state.zulrah_attack_tick += ZULRAH_ATTACK_SPEED;
+                return Ok(());
+            }
+        };
+
+        player.set_protection_prayer(Prayer::protection_against(prayed));
+        let hit =
+            zulrah.attack_with_flick(player, Some(style), rng, false, config.flick_success)?;
+
+        if config.logger.enabled {
+            config.logger.log_monster_attack(
+                zulrah,
+                vars.tick_counter,
+                hit.damage,
+                hit.success,
+                Some(style),
+            );
+        }
+
+        if hit.success {
+            player.take_damage(hit.damage);
+            vars.damage_taken += hit.damage;
+            handle_recoil(player, zulrah, &hit, vars, &mut config.logger);
+        }
+
+        state.attack_count += 1;
+        state.zulrah_attack_tick += ZULRAH_ATTACK_SPEED;
+
+        Ok(())
+    }
+
+    // Roll for venom cloud and snakeling hits left over from the phase that just ended
+    fn process_hazards(
+        &self,
+        player: &mut Player,
+        state: &mut ZulrahState,
+        config: &mut ZulrahConfig,
+        vars: &mut FightVars,
+        rng: &mut SmallRng,
+    ) {
+        if !state.form.spawns_hazards() {
+            return;
+        }
+
+        for (hazard, is_cloud) in [(config.venom_clouds, true), (config.snakelings, false)] {
+            let Some(hazard) = hazard else {
+                continue;
+            };
+            let source = if is_cloud {
+                "a venom cloud"
+            } else {
+                "a snakeling"
+            };
+            let hit = self.process_hazard(player, &hazard, source, rng, vars, &mut config.logger);
+
+            if hit && is_cloud && !config.antivenom && state.venom.is_none() {
+                state.venom = Some(PlayerVenom::new(vars.tick_counter));
+            }
+        }
+    }
+
+    fn handle_eating(
+        &self,
+        config: &mut ZulrahConfig,
+        inventory: &mut Inventory,
+        vars: &mut FightVars,
+        player: &mut Player,
+    ) -> bool {
+        // Eat below the threshold, returning false if the player needed to eat but had no food left
+        if player.stats.hitpoints.current > config.eat_hp {
+            return true;
+        }
+        if !inventory.has_food() {
+            return false;
+        }
+
+        if let Some(attack_delay) =
+            self.eat_next_food(player, inventory, false, vars, &mut config.logger)
+        {
+            vars.attack_tick += attack_delay;
+        }
+        true
+    }
+}
+
+#[derive(Clone)]
+pub struct ZulrahFight {
+    player: Player,
+    forms: [Monster; 3], // Serpentine, magma and tanzanite, which share HP and stat drains
+    active_form: usize,
+    form_switches: [Vec<GearSwitch>; 3], // The player's gear switches with rolls against each form
+    limiter: Option<Box<dyn Limiter>>,
+    rng: SmallRng,
+    config: ZulrahConfig,
+    mechanics: ZulrahMechanics,
+    spec_config: Option<SpecConfig<ZulrahCondition>>,
+    spec_state: SpecState,
+    inventory: Inventory,
+}
+
+impl ZulrahFight {
+    pub fn new(
+        mut player: Player,
+        config: ZulrahConfig,
+        spec_config: Option<SpecConfig<ZulrahCondition>>,
+    ) -> Result<Self, SimulationError> {
+        if !(0.0..=1.0).contains(&config.flick_success) {
+            return Err(SimulationError::ConfigError(
+                "Flick success must be between 0 and 1.".to_string(),
+            ));
+        }
+
+        let forms = ["Serpentine", "Magma", "Tanzanite"].map(|version| {
+            Monster::new("Zulrah", Some(version)).map(|mut zulrah| {
+                zulrah.max_hits = Some(vec![
+                    MonsterMaxHit::new(ZULRAH_MAX_HIT, AttackType::Ranged),
+                    MonsterMaxHit::new(ZULRAH_MAX_HIT, AttackType::Magic),
+                ]);
+                zulrah
+            })
+        });
+        let [Ok(serpentine), Ok(magma), Ok(tanzanite)] = forms else {
+            return Err(SimulationError::MonsterCreationError("Zulrah".to_string()));
+        };
+        let forms = [serpentine, magma, tanzanite];
+
+        if player.switches.is_empty() {
+            player.switches.push(GearSwitch::from(&player));
+        }
+        for switch_type in config.attack_chart.switch_types() {
+            if !player
+                .switches
+                .iter()
+                .any(|s| &s.switch_type == switch_type)
+            {
+                return Err(PlayerError::GearSwitchNotFound(switch_type.clone()).into());
+            }
+        }
+
+        // Precompute every switch's rolls against each form so phase changes are just swaps
+        let mut form_switches: [Vec<GearSwitch>; 3] = Default::default();
+        for (switches, form) in form_switches.iter_mut().zip(&forms) {
+            *switches = player.switches_against(form)?;
+        }
+
+        let limiter = assign_limiter(&player, &forms[0]);
+        let rng = seeded_rng(config.seed);
+        let inventory = config.inventory.clone();
+
+        Ok(Self {
+            player,
+            forms,
+            active_form: 0,
+            form_switches,
+            limiter,
+            rng,
+            config,
+            mechanics: ZulrahMechanics,
+            spec_config,
+            spec_state: SpecState::default(),
+            inventory,
+        })
+    }
+
+    fn choose_rotation(&mut self) -> ZulrahRotation {
+        self.config
+            .rotation
+            .unwrap_or_else(|| ZulrahRotation::ALL[self.rng.random_range(0..4)])
+    }
+
+    // Surface in the next phase of the rotation, moving on to a new rotation after the last one
+    fn start_phase(&mut self, state: &mut ZulrahState) -> Result<(), SimulationError> {
+        let form = state.rotation.phases()[state.phase_index];
+        state.start_phase(form, state.tick_counter);
+
+        // Carry HP, stat drains and effects over to the new form
+        let index = form.monster_index();
+        if index != self.active_form {
+            let stats = self.forms[self.active_form].stats;
+            let effects = std::mem::take(&mut self.forms[self.active_form].active_effects);
+            let zulrah = &mut self.forms[index];
+            zulrah.stats = stats;
+            zulrah.active_effects = effects;
+            zulrah.base_def_rolls = monster_def_rolls(zulrah);
+            zulrah.def_rolls = zulrah.base_def_rolls;
+            self.active_form = index;
+        }
+
+        let switch = self
+            .config
+            .attack_chart
+            .switch_for(state.rotation, state.phase_index + 1, form)
+            .clone();
+        self.player.switches = self.form_switches[index].clone();
+        self.player.current_switch = None;
+        self.player.switch(&switch)?;
+
+        if self.config.logger.enabled {
+            self.config.logger.log_phase_change(
+                state.tick_counter,
+                "Zulrah",
+                &format!(
+                    "{} ({form:?} form, rotation {:?})",
+                    state.phase_index + 1,
+                    state.rotation
+                ),
+            );
+            self.config
+                .logger
+                .log_gear_switch(state.tick_counter, &switch);
+        }
+
+        Ok(())
+    }
+
+    fn simulate_zulrah_fight(&mut self) -> Result<FightResult, SimulationError> {
+        if let Some(ref spec_config) = self.spec_config
+            && let Err(e) = spec_config.validate()
+        {
+            return Err(SimulationError::ConfigError(e));
+        }
+
+        let mut vars = FightVars::new();
+        let mut state = ZulrahState {
+            rotation: self.choose_rotation(),
+            ..ZulrahState::default()
+        };
+
+        // Log the setup first, since it starts a fresh trace
+        let logging_enabled = self.config.logger.enabled;
+        if logging_enabled {
+            self.config
+                .logger
+                .log_initial_setup(&self.player, &self.forms[Serpentine.monster_index()]);
+        }
+
+        self.start_phase(&mut state)?;
+
+        while self.forms[self.active_form].stats.hitpoints.current > 0 {
+            state.tick_counter = vars.tick_counter;
+
+            if vars.tick_counter == state.dive_tick {
+                self.mechanics.process_hazards(
+                    &mut self.player,
+                    &mut state,
+                    &mut self.config,
+                    &mut vars,
+                    &mut self.rng,
+                );
+            }
+            if vars.tick_counter == state.next_phase_tick {
+                state.phase_index += 1;
+                if state.phase_index == state.rotation.phases().len() {
+                    state.phase_index = 0;
+                    state.rotation = self.choose_rotation();
+                }
+                self.start_phase(&mut state)?;
+            }
+
+            if vars.tick_counter % ZULRAH_REGEN_TICKS == 0 {
+                self.mechanics.monster_regen_stats(
+                    &mut self.forms[self.active_form],
+                    &vars,
+                    &mut self.config.logger,
+                );
+            }
+
+            // Regen 1 HP for player every 100 ticks
+            if vars.tick_counter % constants::PLAYER_REGEN_TICKS == 0 {
+                self.mechanics
+                    .player_regen(&mut self.player, &vars, &mut self.config.logger);
+            }
+
+            self.mechanics.decrement_eat_delay(&mut vars);
+            self.mechanics.process_player_effects(
+                &mut self.player,
+                &mut vars,
+                &mut self.config.logger,
+            );
+            self.mechanics.process_player_venom(
+                &mut self.player,
+                &mut state.venom,
+                &mut vars,
+                &mut self.config.logger,
+            );
+            if !self.mechanics.handle_eating(
+                &mut self.config,
+                &mut self.inventory,
+                &mut vars,
+                &mut self.player,
+            ) {
+                return self.mechanics.process_supply_out(
+                    &vars,
+                    &self.forms[self.active_form],
+                    &mut self.config.logger,
+                );
+            }
+
+            if let Some(prayer_config) = self.config.prayer_drain {
+                let attacking = vars.tick_counter == vars.attack_tick;
+                self.mechanics.process_prayer(
+                    &mut self.player,
+                    &self.forms[self.active_form],
+                    &prayer_config,
+                    &mut self.inventory,
+                    attacking,
+                    &mut vars,
+                    &mut self.config.logger,
+                )?;
+            }
+
+            if let Some(redose) = self.config.redose {
+                self.mechanics.process_redose(
+                    &mut self.player,
+                    &self.forms[self.active_form],
+                    &redose,
+                    &mut self.inventory,
+                    &mut vars,
+                    &mut self.config.logger,
+                );
+            }
+
+            if vars.tick_counter == vars.attack_tick {
+                if state.is_targetable() {
+                    self.player_attack_or_spec(&state, &mut vars)?;
+                } else if vars.tick_counter < state.targetable_tick {
+                    vars.attack_tick = state.targetable_tick;
+                } else {
+                    vars.attack_tick = state.next_phase_tick + SURFACE_TICKS;
+                }
+            }
+
+            self.mechanics.process_monster_effects(
+                &mut self.forms[self.active_form],
+                &vars,
+                &mut self.config.logger,
+            );
+
+            if self.forms[self.active_form].stats.hitpoints.current > 0
+                && vars.tick_counter == state.zulrah_attack_tick
+                && vars.tick_counter < state.dive_tick
+            {
+                self.mechanics.zulrah_attack(
+                    &mut self.forms[self.active_form],
+                    &mut self.player,
+                    &mut state,
+                    &mut self.config,
+                    &mut vars,
+                    &mut self.rng,
+                )?;
+            }
+
+            self.mechanics.process_spec_timers(
+                &mut self.player,
+                self.spec_config.as_ref(),
+                &mut self.spec_state,
+                &vars,
+                &mut self.config.logger,
+            );
+
+            // Increment tick counter
+            vars.tick_counter += 1;
+
+            if self.player.stats.hitpoints.current == 0 {
+                return self.mechanics.process_player_death(
+                    &vars,
+                    &self.forms[self.active_form],
+                    &mut self.config.logger,
+                );
+            }
+        }
+        let remove_final_attack_delay = true;
+        self.mechanics.get_fight_result(
+            &self.forms[self.active_form],
+            &vars,
+            &mut self.config.logger,
+            remove_final_attack_delay,
+        )
+    }
+
+    // Use a special attack if any strategy's conditions are met, otherwise attack normally
+    fn player_attack_or_spec(
+        &mut self,
+        state: &ZulrahState,
+        vars: &mut FightVars,
+    ) -> Result<(), SimulationError> {
+        let zulrah = &mut self.forms[self.active_form];
+        let did_spec = if let Some(ref mut spec_config) = self.spec_config {
+            self.mechanics.player_special_attack(
+                &mut self.player,
+                zulrah,
+                spec_config,
+                &mut self.spec_state,
+                state,
+                &mut self.rng,
+                &self.limiter,
+                vars,
+                &mut self.config.logger,
+            )?
+        } else {
+            false
+        };
+
+        if !did_spec {
+            self.mechanics.player_attack(
+                &mut self.player,
+                zulrah,
+                &mut self.rng,
+                &self.limiter,
+                vars,
+                &mut self.config.logger,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Simulation for ZulrahFight {
+    fn simulate(&mut self) -> Result<FightResult, SimulationError> {
+        self.simulate_zulrah_fight()
+    }
+
+    fn is_immune(&self) -> bool {
+        self.forms[self.active_form].is_immune(&self.player)
+    }
+
+    fn player(&self) -> &Player {
+        &self.player
+    }
+
+    fn monster(&self) -> &Monster {
+        &self.forms[self.active_form]
+    }
+
+    fn set_attack_function(&mut self) {
+        self.player.attack = crate::combat::attacks::standard::get_attack_functions(&self.player);
+        self.player.spec = crate::combat::attacks::specs::get_spec_attack_function(&self.player);
+    }
+
+    fn reset(&mut self) {
+        self.config.logger.clear();
+        if let Some(ref mut spec_config) = self.spec_config {
+            let restore_spec = self.spec_state.on_kill(&mut self.player, spec_config);
+            self.player.reset_current_stats(restore_spec);
+        } else {
+            self.player.reset_current_stats(true);
+        }
+        for zulrah in &mut self.forms {
+            zulrah.reset();
+        }
+        self.inventory = self.config.inventory.clone();
+    }
+
+    fn next_kill(&mut self, downtime_ticks: u32) {
+        self.mechanics.prepare_next_kill(
+            &mut self.player,
+            self.spec_config.as_mut(),
+            &mut self.spec_state,
+            downtime_ticks,
+            &mut self.config.logger,
+        );
+        for zulrah in &mut self.forms {
+            zulrah.reset();
+        }
+    }
+
+    fn inventory(&self) -> Option<&Inventory> {
+        Some(&self.inventory)
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.rng = SmallRng::seed_from_u64(seed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::loadouts;
+    use crate::utils::logging::FightEventKind;
+
+    // Mage with a ranged switch, with each setup's rolls calculated against serpentine Zulrah
+    fn zulrah_player() -> Player {
+        let zulrah = Monster::new("Zulrah", Some("Serpentine")).expect("Error creating monster.");
+        let mut player = loadouts::max_mage_sang_staff_player();
+        let ranged = loadouts::max_ranged_zcb_player();
+        player.switches = vec![
+            GearSwitch::new(SwitchType::Magic, &player, &zulrah),
+            GearSwitch::new(SwitchType::Ranged, &ranged, &zulrah),
+        ];
+        player
+    }
+
+    // Zulrah's attacks in one form, as the style and damage of each hit that reached the player
+    fn form_attacks(
+        form: ZulrahForm,
+        rotation: ZulrahRotation,
+        config: ZulrahConfig,
+        n: usize,
+    ) -> Vec<(AttackType, u32)> {
+        let mut fight =
+            ZulrahFight::new(zulrah_player(), config, None).expect("Error setting up fight.");
+        fight.config.logger = FightLogger::new(true);
+        let mut state = ZulrahState {
+            rotation,
+            ..ZulrahState::default()
+        };
+        state.start_phase(form, 0);
+        let mut vars = FightVars::new();
+        for _ in 0..n {
+            ZulrahMechanics
+                .zulrah_attack(
+                    &mut fight.forms[form.monster_index()],
+                    &mut fight.player,
+                    &mut state,
+                    &mut fight.config,
+                    &mut vars,
+                    &mut fight.rng,
+                )
+                .unwrap();
+            fight.player.stats.hitpoints.reset();
+        }
+
+        fight
+            .config
+            .logger
+            .take_trace()
+            .unwrap()
+            .events
+            .into_iter()
+            .filter_map(|event| match event.kind {
+                FightEventKind::MonsterAttack { style, damage, .. } => Some((style, damage)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_rotations_start_serpentine() {
+        for rotation in ZulrahRotation::ALL {
+            assert_eq!(rotation.phases()[0], Serpentine);
+            assert_eq!(
+                rotation
+                    .phases()
+                    .iter()
+                    .filter(|&&form| form == Jad)
+                    .count(),
+                1
+            );
+        }
+    }
+
+    #[test]
+    fn test_attack_chart_overrides() {
+        let mut chart = AttackChart::default();
+        chart
+            .overrides
+            .insert((ZulrahRotation::Three, 2), SwitchType::Ranged);
+        assert_eq!(
+            chart.switch_for(ZulrahRotation::Three, 2, Serpentine),
+            &SwitchType::Ranged
+        );
+        assert_eq!(
+            chart.switch_for(ZulrahRotation::Three, 1, Serpentine),
+            &SwitchType::Magic
+        );
+    }
+
+    #[test]
+    fn test_rotation_order() {
+        for rotation in ZulrahRotation::ALL {
+            let config = ZulrahConfig {
+                rotation: Some(rotation),
+                logger: FightLogger::new(true),
+                seed: Some(1),
+                ..ZulrahConfig::default()
+            };
+            let mut fight = ZulrahFight::new(zulrah_player(), config, None).unwrap();
+            fight.set_attack_function();
+            let trace = fight.simulate().unwrap().trace.unwrap();
+
+            // Zulrah surfaces in each of the rotation's forms in turn
+            let surfaced: Vec<String> = trace
+                .events
+                .into_iter()
+                .filter_map(|event| match event.kind {
+                    FightEventKind::PhaseChange { phase, .. } => Some(phase),
+                    _ => None,
+                })
+                .collect();
+            assert!(surfaced.len() > 2);
+            for (index, phase) in surfaced.iter().enumerate() {
+                let phases = rotation.phases();
+                let form = phases[index % phases.len()];
+                assert_eq!(
+                    phase,
+                    &format!(
+                        "{} ({form:?} form, rotation {rotation:?})",
+                        index % phases.len() + 1
+                    )
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_jad_alternates_styles() {
+        for (rotation, first, second) in [
+            (ZulrahRotation::One, AttackType::Magic, AttackType::Ranged),
+            (ZulrahRotation::Four, AttackType::Ranged, AttackType::Magic),
+        ] {
+            let styles: Vec<AttackType> = form_attacks(Jad, rotation, ZulrahConfig::default(), 6)
+                .into_iter()
+                .map(|(style, _)| style)
+                .collect();
+            assert_eq!(styles, [first, second].repeat(3));
+        }
+
+        // The player steps away from every magma attack
+        assert!(form_attacks(Magma, ZulrahRotation::One, ZulrahConfig::default(), 4).is_empty());
+    }
+
+    #[test]
+    fn test_tanzanite_prayer() {
+        let attacks = form_attacks(Tanzanite, ZulrahRotation::One, ZulrahConfig::default(), 200);
+        assert!(attacks.iter().any(|&(style, _)| style == AttackType::Magic));
+
+        // Only the prayed style is blocked, so every hit that lands is ranged
+        assert!(
+            attacks
+                .iter()
+                .all(|&(style, damage)| style == AttackType::Ranged || damage == 0)
+        );
+        assert!(
+            attacks
+                .iter()
+                .any(|&(style, damage)| style == AttackType::Ranged && damage > 0)
+        );
+
+        // Serpentine's ranged attacks are always prayed against
+        assert!(
+            form_attacks(Serpentine, ZulrahRotation::One, ZulrahConfig::default(), 50)
+                .iter()
+                .all(|&(_, damage)| damage == 0)
+        );
+    }
+
+    #[test]
+    fn test_missing_switch() {
+        let config = ZulrahConfig {
+            attack_chart: AttackChart {
+                jad: SwitchType::Melee,
+                ..AttackChart::default()
+            },
+            ..ZulrahConfig::default()
+        };
+        assert!(matches!(
+            ZulrahFight::new(zulrah_player(), config, None),
+            Err(SimulationError::SwitchingError(
+                PlayerError::GearSwitchNotFound(_)
+            ))
+        ));
+    }
+}
@@ -0,0 +1,145 @@
+use crate::error::BankError;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct BankItem {
+    pub name: String,
+    #[serde(default)]
+    pub version: Option<String>, // None matches any version of the item
+}
+
+// Entries in a JSON bank export, either bare item names or item objects
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum BankEntry {
+    Name(String),
+    Item(BankItem),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Bank {
+    pub items: Vec<BankItem>,
+}
+
+impl Bank {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, name: &str, version: Option<&str>) {
+        self.items.push(BankItem {
+            name: name.to_string(),
+            version: version.map(ToString::to_string),
+        });
+    }
+
+    pub fn parse(export: &str) -> Result<Self, BankError> {
+        // Detect the export format from its first character
+        if export.trim_start().starts_with('[') {
+            Self::from_json(export)
+        } else {
+            Self::from_text(export)
+        }
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, BankError> {
+        // One item per line. Tab-separated exports (e.g., "id, name, quantity") use the first
+        // field that isn't a number as the item name.
+        let mut bank = Bank::new();
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let name = line
+                .split('\t')
+                .map(str::trim)
+                .find(|field| !field.is_empty() && field.parse::<f64>().is_err())
+                .ok_or_else(|| BankError::InvalidLine(line.to_string()))?;
+            bank.add(name, None);
+        }
+
+        Ok(bank)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, BankError> {
+        let entries: Vec<BankEntry> = serde_json::from_str(json)?;
+        let items = entries
+            .into_iter()
+            .map(|entry| match entry {
+                BankEntry::Name(name) => BankItem {
+                    name,
+                    version: None,
+                },
+                BankEntry::Item(item) => item,
+            })
+            .collect();
+
+        Ok(Bank { items })
+    }
+
+    pub fn owns(&self, name: &str, version: Option<&str>) -> bool {
+        self.items.iter().any(|item| {
+            item.name == name
+                && item
+                    .version
+                    .as_deref()
+                    .is_none_or(|owned| Some(owned) == version)
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_text_export() {
+        let bank = Bank::parse(
+            "# Melee tab\nAbyssal whip\n\n4151\tDragon defender\t1\nCoins\t995\t250000\n",
+        )
+        .unwrap();
+
+        assert_eq!(bank.items.len(), 3);
+        assert!(bank.owns("Abyssal whip", None));
+        assert!(bank.owns("Dragon defender", None));
+        assert!(bank.owns("Coins", None));
+        assert!(!bank.owns("Ghrazi rapier", None));
+    }
+
+    #[test]
+    fn test_json_export() {
+        let bank = Bank::parse(
+            r#"[
+                "Abyssal whip",
+                {"name": "Dizana's quiver", "version": "Charged", "quantity": 1},
+                {"id": 11832, "name": "Bandos chestplate"}
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(bank.items.len(), 3);
+        assert!(bank.owns("Dizana's quiver", Some("Charged")));
+        assert!(!bank.owns("Dizana's quiver", Some("Uncharged")));
+
+        // Items exported without a version match every version
+        assert!(bank.owns("Bandos chestplate", None));
+        assert!(bank.owns("Abyssal whip", Some("Any")));
+    }
+
+    #[test]
+    fn test_invalid_exports() {
+        assert!(matches!(
+            Bank::parse("[\"Abyssal whip\""),
+            Err(BankError::JsonError(_))
+        ));
+        assert!(matches!(
+            Bank::from_text("4151\t1"),
+            Err(BankError::InvalidLine(_))
+        ));
+    }
+}
@@ -5,10 +5,20 @@ use std::any::Any;
 use std::collections::HashMap;
 use std::fmt;
 use std::string::ToString;
+use std::sync::LazyLock;
 use strum_macros::{Display, EnumIter};
 
 const EQUIPMENT_JSON_STR: &str = include_str!("../databases/equipment.json");
 
+// Parsed once on first use, for callers that need to scan the whole database
+static EQUIPMENT_DB: LazyLock<Vec<EquipmentJson>> = LazyLock::new(|| {
+    serde_json::from_str(EQUIPMENT_JSON_STR).expect("Error parsing equipment JSON.")
+});
+
+pub fn all_equipment() -> &'static [EquipmentJson] {
+    &EQUIPMENT_DB
+}
+
 // Intermediate struct for JSON deserialization
 #[derive(Debug, Deserialize, PartialEq, Clone)]
 pub struct EquipmentJson {
@@ -47,6 +57,15 @@ impl EquipmentJson {
             .is_two_handed
             .ok_or(GearError::MissingTwoHandedField(self.name.clone()))?;
 
+        // Check if the item is a staff that can cast spells
+        let is_staff = combat_styles.contains_key(&CombatStyle::Spell);
+
+        // Set spec cost, if applicable
+        let spec_cost = constants::SPEC_COSTS
+            .iter()
+            .find(|w| w.0 == self.name)
+            .map(|w| w.1);
+
         let weapon = Weapon {
             name: self.name,
             version: self.version,
@@ -56,10 +75,10 @@ impl EquipmentJson {
             base_speed: speed,
             attack_range,
             is_two_handed,
-            spec_cost: None,
+            spec_cost,
             poison_severity: 0,
             combat_styles,
-            is_staff: false,
+            is_staff,
             image: self.image,
         };
 
@@ -82,7 +101,7 @@ impl EquipmentJson {
 }
 
 // Slots in which a player can equip gear
-#[derive(Debug, PartialEq, Eq, Hash, Default, Deserialize, Clone, Display, Copy)]
+#[derive(Debug, PartialEq, Eq, Hash, Default, Serialize, Deserialize, Clone, Display, Copy)]
 pub enum GearSlot {
     #[default]
     None,
@@ -203,14 +222,14 @@ impl Gear {
 /// ```
 /// use osrs::types::equipment::Gear;
 ///
-/// let gear = Gear::builder()
+/// let _gear = Gear::builder()
 ///     .head("Torva full helm", None)
 ///     .body("Torva platebody", None)
 ///     .legs("Torva platelegs", None)
 ///     .weapon("Osmumten's fang", None)
 ///     .build()?;
 ///
-/// Ok(())
+/// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
 #[derive(Debug, Clone, Default)]
 pub struct GearBuilder {
@@ -310,23 +329,23 @@ impl GearBuilder {
         let mut gear = Gear::default();
 
         if let Some((name, version)) = self.head {
-            gear.head = Some(Armor::new(&name, version.as_deref())?);
+            gear.head = Some(armor_for_slot(&name, version.as_deref(), GearSlot::Head)?);
         }
 
         if let Some((name, version)) = self.neck {
-            gear.neck = Some(Armor::new(&name, version.as_deref())?);
+            gear.neck = Some(armor_for_slot(&name, version.as_deref(), GearSlot::Neck)?);
         }
 
         if let Some((name, version)) = self.cape {
-            gear.cape = Some(Armor::new(&name, version.as_deref())?);
+            gear.cape = Some(armor_for_slot(&name, version.as_deref(), GearSlot::Cape)?);
         }
 
         if let Some((name, version)) = self.ammo {
-            gear.ammo = Some(Armor::new(&name, version.as_deref())?);
+            gear.ammo = Some(armor_for_slot(&name, version.as_deref(), GearSlot::Ammo)?);
         }
 
         if let Some((name, version)) = self.second_ammo {
-            gear.second_ammo = Some(Armor::new(&name, version.as_deref())?);
+            gear.second_ammo = Some(armor_for_slot(&name, version.as_deref(), GearSlot::Ammo)?);
         }
 
         if let Some((name, version)) = self.weapon {
@@ -334,35 +353,67 @@ impl GearBuilder {
         }
 
         if let Some((name, version)) = self.shield {
-            gear.shield = Some(Armor::new(&name, version.as_deref())?);
+            let shield = armor_for_slot(&name, version.as_deref(), GearSlot::Shield)?;
+            if gear.weapon.is_two_handed {
+                return Err(GearError::TwoHandedWithShield {
+                    weapon_name: gear.weapon.name.clone(),
+                    shield_name: shield.name,
+                });
+            }
+            gear.shield = Some(shield);
         }
 
         if let Some((name, version)) = self.body {
-            gear.body = Some(Armor::new(&name, version.as_deref())?);
+            gear.body = Some(armor_for_slot(&name, version.as_deref(), GearSlot::Body)?);
         }
 
         if let Some((name, version)) = self.legs {
-            gear.legs = Some(Armor::new(&name, version.as_deref())?);
+            gear.legs = Some(armor_for_slot(&name, version.as_deref(), GearSlot::Legs)?);
         }
 
         if let Some((name, version)) = self.hands {
-            gear.hands = Some(Armor::new(&name, version.as_deref())?);
+            gear.hands = Some(armor_for_slot(&name, version.as_deref(), GearSlot::Hands)?);
         }
 
         if let Some((name, version)) = self.feet {
-            gear.feet = Some(Armor::new(&name, version.as_deref())?);
+            gear.feet = Some(armor_for_slot(&name, version.as_deref(), GearSlot::Feet)?);
         }
 
         if let Some((name, version)) = self.ring {
-            gear.ring = Some(Armor::new(&name, version.as_deref())?);
+            gear.ring = Some(armor_for_slot(&name, version.as_deref(), GearSlot::Ring)?);
         }
 
         Ok(gear)
     }
 }
 
+// Look up an armor piece and check that it belongs in the given slot
+fn armor_for_slot(name: &str, version: Option<&str>, slot: GearSlot) -> Result<Armor, GearError> {
+    let armor = match Armor::new(name, version) {
+        Err(GearError::NotArmor(_)) => {
+            return Err(GearError::WrongSlot {
+                item_name: name.to_string(),
+                expected: slot,
+                actual: GearSlot::Weapon,
+            });
+        }
+        result => result?,
+    };
+    if armor.slot != slot {
+        return Err(GearError::WrongSlot {
+            item_name: name.to_string(),
+            expected: slot,
+            actual: armor.slot,
+        });
+    }
+
+    Ok(armor)
+}
+
 // Combat types, e.g., stab, slash, crush, magic, etc.
-#[derive(Debug, PartialEq, Eq, Hash, Default, Copy, Clone, EnumIter, Deserialize, Display)]
+#[derive(
+    Debug, PartialEq, Eq, Hash, Default, Copy, Clone, EnumIter, Serialize, Deserialize, Display,
+)]
 pub enum CombatType {
     None,
     Stab,
@@ -399,7 +450,9 @@ pub enum CombatStance {
 }
 
 // Name of the combat style as seen in the weapon interface
-#[derive(Debug, PartialEq, Eq, Hash, Default, Serialize, Deserialize, Clone, Copy, Display)]
+#[derive(
+    Debug, PartialEq, Eq, Hash, Default, Serialize, Deserialize, Clone, Copy, Display, EnumIter,
+)]
 pub enum CombatStyle {
     Chop,
     Slash,
@@ -683,24 +736,7 @@ impl Equipment for Weapon {
                 version: self.version.clone(),
             })?;
 
-        let mut weapon = matching_item.into_weapon()?;
-
-        // Check if the item is a staff that can cast spells
-        if weapon.combat_styles.contains_key(&CombatStyle::Spell) {
-            weapon.is_staff = true;
-        }
-
-        // Set base speed and item slot
-        weapon.base_speed = weapon.speed;
-        weapon.slot = GearSlot::Weapon;
-
-        // Set spec cost, if applicable
-        let spec_cost = constants::SPEC_COSTS.iter().find(|w| w.0 == weapon.name);
-        if let Some(cost) = spec_cost {
-            weapon.spec_cost = Some(cost.1);
-        }
-
-        *self = weapon;
+        *self = matching_item.into_weapon()?;
 
         Ok(())
     }
@@ -2,8 +2,11 @@ use crate::combat::attacks::effects::CombatEffect;
 use crate::types::player::Player;
 use std::collections::HashMap;
 use std::sync::LazyLock;
+use strum_macros::EnumIter;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, serde::Serialize, serde::Deserialize,
+)]
 pub enum Foods {
     Anchovies,
     Sardine,
@@ -200,6 +203,33 @@ impl Food {
         self.food_properties = food_properties;
         self
     }
+
+    pub fn from_db(name: Foods) -> Option<Self> {
+        // Look up a food's stats in the food database
+        FOOD_DB.get(&name).cloned()
+    }
+
+    pub fn bites(&self) -> u32 {
+        // Number of bites (or doses) in a single item of this food
+        self.food_properties.total_bites.unwrap_or(1)
+    }
+
+    pub fn bite_heal(&self, player: &Player) -> u32 {
+        // Amount healed by a single bite, which may depend on the player's stats
+        if let Some(heal_function) = self.food_properties.heal_function {
+            heal_function(player)
+        } else if let Some(bite_heal) = self.food_properties.bite_heal_amount {
+            bite_heal
+        } else {
+            self.heal_amount
+        }
+    }
+
+    pub fn bite_eat_delay(&self) -> u32 {
+        self.food_properties
+            .bite_eat_delay
+            .unwrap_or(self.eat_delay)
+    }
 }
 macro_rules! simple_foods {
     ($map:ident; $($name:ident: $heal:expr, $eat:expr, $atk:expr, $type:ident),* $(,)?) => {
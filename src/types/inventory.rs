@@ -0,0 +1,284 @@
+use crate::constants::{INVENTORY_SIZE, POTION_DOSES};
+use crate::error::InventoryError;
+use crate::types::food::{Food, FoodType, Foods};
+use crate::types::potions::Potion;
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum InventoryItem {
+    // Bites left is the remaining bites of multi-bite foods or doses of brews (1 for normal food)
+    Food { food: Foods, bites_left: u32 },
+    Potion { potion: Potion, doses: u32 },
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Inventory {
+    slots: Vec<Option<InventoryItem>>,
+}
+
+impl Default for Inventory {
+    fn default() -> Self {
+        Self {
+            slots: vec![None; INVENTORY_SIZE],
+        }
+    }
+}
+
+impl Inventory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn builder() -> InventoryBuilder {
+        InventoryBuilder::default()
+    }
+
+    pub fn add_food(&mut self, food: Foods, count: u32) -> Result<(), InventoryError> {
+        let bites = Food::from_db(food)
+            .ok_or(InventoryError::FoodNotFound(food))?
+            .bites();
+        self.add_items(
+            InventoryItem::Food {
+                food,
+                bites_left: bites,
+            },
+            count,
+            format!("{food:?}"),
+        )
+    }
+
+    pub fn add_potion(&mut self, potion: Potion, count: u32) -> Result<(), InventoryError> {
+        // Potions are always added as full 4-dose potions
+        if matches!(
+            potion,
+            Potion::None | Potion::DragonBattleaxe | Potion::ImbuedHeart | Potion::SaturatedHeart
+        ) {
+            return Err(InventoryError::NotADosedPotion(potion));
+        }
+        self.add_items(
+            InventoryItem::Potion {
+                potion,
+                doses: POTION_DOSES,
+            },
+            count,
+            potion.to_string(),
+        )
+    }
+
+    fn add_items(
+        &mut self,
+        item: InventoryItem,
+        count: u32,
+        name: String,
+    ) -> Result<(), InventoryError> {
+        if self.free_slots() < count as usize {
+            return Err(InventoryError::InventoryFull { item: name, count });
+        }
+
+        let mut remaining = count;
+        for slot in self.slots.iter_mut().filter(|slot| slot.is_none()) {
+            if remaining == 0 {
+                break;
+            }
+            *slot = Some(item.clone());
+            remaining -= 1;
+        }
+
+        Ok(())
+    }
+
+    pub fn items(&self) -> impl Iterator<Item = &InventoryItem> {
+        self.slots.iter().flatten()
+    }
+
+    pub fn free_slots(&self) -> usize {
+        self.slots.iter().filter(|slot| slot.is_none()).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.free_slots() == INVENTORY_SIZE
+    }
+
+    pub fn food_count(&self) -> u32 {
+        // Number of food items (a partially eaten pie or brew counts as one)
+        self.items()
+            .filter(|item| matches!(item, InventoryItem::Food { .. }))
+            .count() as u32
+    }
+
+    pub fn count_food(&self, food: Foods) -> u32 {
+        self.items()
+            .filter(|item| matches!(item, InventoryItem::Food { food: f, .. } if *f == food))
+            .count() as u32
+    }
+
+    pub fn has_food(&self) -> bool {
+        self.food_count() > 0
+    }
+
+    pub fn potion_doses(&self, potion: Potion) -> u32 {
+        self.items()
+            .map(|item| match item {
+                InventoryItem::Potion { potion: p, doses } if *p == potion => *doses,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    pub fn next_food(&self, food_types: &[FoodType]) -> Option<Foods> {
+        // First food in the inventory matching any of the given food types
+        self.items().find_map(|item| match item {
+            InventoryItem::Food { food, .. }
+                if Food::from_db(*food).is_some_and(|f| food_types.contains(&f.food_type)) =>
+            {
+                Some(*food)
+            }
+            _ => None,
+        })
+    }
+
+    pub fn take_bite(&mut self, food: Foods) -> Option<Food> {
+        // Consume one bite (or dose) of the given food, removing it once it's fully eaten
+        let slot = self
+            .slots
+            .iter_mut()
+            .find(|slot| matches!(slot, Some(InventoryItem::Food { food: f, .. }) if *f == food))?;
+
+        if let Some(InventoryItem::Food { bites_left, .. }) = slot {
+            *bites_left = bites_left.saturating_sub(1);
+            if *bites_left == 0 {
+                *slot = None;
+            }
+        }
+
+        Food::from_db(food)
+    }
+
+    pub fn take_dose(&mut self, potion: Potion) -> bool {
+        // Consume one dose of the given potion, removing the vial once it's empty
+        let Some(slot) = self.slots.iter_mut().find(
+            |slot| matches!(slot, Some(InventoryItem::Potion { potion: p, .. }) if *p == potion),
+        ) else {
+            return false;
+        };
+
+        if let Some(InventoryItem::Potion { doses, .. }) = slot {
+            *doses = doses.saturating_sub(1);
+            if *doses == 0 {
+                *slot = None;
+            }
+        }
+
+        true
+    }
+}
+
+/// Builder for constructing an `Inventory` from foods and potions.
+///
+/// # Example
+/// ```
+/// use osrs::types::food::Foods;
+/// use osrs::types::inventory::Inventory;
+/// use osrs::types::potions::Potion;
+///
+/// let inventory = Inventory::builder()
+///     .potion(Potion::SuperCombat, 2)
+///     .food(Foods::Shark, 20)
+///     .food(Foods::Karambwan, 6)
+///     .build()?;
+///
+/// assert_eq!(inventory.free_slots(), 0);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct InventoryBuilder {
+    foods: Vec<(Foods, u32)>,
+    potions: Vec<(Potion, u32)>,
+}
+
+impl InventoryBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn food(mut self, food: Foods, count: u32) -> Self {
+        self.foods.push((food, count));
+        self
+    }
+
+    pub fn potion(mut self, potion: Potion, count: u32) -> Self {
+        self.potions.push((potion, count));
+        self
+    }
+
+    pub fn build(self) -> Result<Inventory, InventoryError> {
+        // Potions go first, matching the usual inventory layout
+        let mut inventory = Inventory::new();
+        for (potion, count) in self.potions {
+            inventory.add_potion(potion, count)?;
+        }
+        for (food, count) in self.foods {
+            inventory.add_food(food, count)?;
+        }
+        Ok(inventory)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_and_count_items() {
+        let inventory = Inventory::builder()
+            .potion(Potion::SuperCombat, 1)
+            .food(Foods::Shark, 10)
+            .food(Foods::Karambwan, 4)
+            .build()
+            .unwrap();
+
+        assert_eq!(inventory.free_slots(), 13);
+        assert_eq!(inventory.food_count(), 14);
+        assert_eq!(inventory.count_food(Foods::Karambwan), 4);
+        assert_eq!(inventory.potion_doses(Potion::SuperCombat), 4);
+        assert_eq!(
+            inventory.next_food(&[FoodType::Combo]),
+            Some(Foods::Karambwan)
+        );
+        assert_eq!(inventory.next_food(&[FoodType::Normal]), Some(Foods::Shark));
+    }
+
+    #[test]
+    fn test_inventory_full() {
+        let mut inventory = Inventory::new();
+        inventory.add_food(Foods::Shark, 28).unwrap();
+        assert!(matches!(
+            inventory.add_food(Foods::Karambwan, 1),
+            Err(InventoryError::InventoryFull { .. })
+        ));
+    }
+
+    #[test]
+    fn test_bites_and_doses() {
+        let mut inventory = Inventory::builder()
+            .potion(Potion::SuperCombat, 1)
+            .food(Foods::SaradominBrew, 1)
+            .food(Foods::Shark, 1)
+            .build()
+            .unwrap();
+
+        // Brews take four sips before the slot is freed
+        for _ in 0..3 {
+            assert!(inventory.take_bite(Foods::SaradominBrew).is_some());
+        }
+        assert_eq!(inventory.count_food(Foods::SaradominBrew), 1);
+        inventory.take_bite(Foods::SaradominBrew);
+        assert_eq!(inventory.count_food(Foods::SaradominBrew), 0);
+        assert!(inventory.take_bite(Foods::SaradominBrew).is_none());
+
+        assert!(inventory.take_dose(Potion::SuperCombat));
+        assert_eq!(inventory.potion_doses(Potion::SuperCombat), 3);
+
+        inventory.take_bite(Foods::Shark);
+        assert!(!inventory.has_food());
+    }
+}
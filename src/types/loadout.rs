@@ -0,0 +1,709 @@
+use crate::calc::rolls::calc_active_player_rolls;
+use crate::constants;
+use crate::error::{GearError, LoadoutError, PlayerError};
+use crate::types::equipment::{Armor, CombatStyle, Gear, GearSlot, Weapon};
+use crate::types::monster::Monster;
+use crate::types::player::{GearSwitch, Player, StatusBoosts, SwitchType};
+use crate::types::potions::Potion;
+use crate::types::prayers::{Prayer, PrayerBoosts};
+use crate::types::spells::Spell;
+use crate::types::stats::{PlayerStats, Stat};
+use crate::utils::names::parse_display_name;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::path::Path;
+use strum::IntoEnumIterator;
+
+// Look up a prayer, potion, etc. by name with a readable error
+fn parse_name<T>(kind: &'static str, name: &str) -> Result<T, PlayerError>
+where
+    T: IntoEnumIterator + Debug + std::fmt::Display,
+{
+    parse_display_name(name).ok_or_else(|| PlayerError::UnknownName {
+        kind,
+        name: name.to_string(),
+    })
+}
+
+fn parse_spell(name: &str) -> Result<Spell, PlayerError> {
+    Spell::from_name(name).ok_or_else(|| PlayerError::UnknownName {
+        kind: "spell",
+        name: name.to_string(),
+    })
+}
+
+fn parse_style(name: &str) -> Result<CombatStyle, PlayerError> {
+    parse_name("combat style", name)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LoadoutItem {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+impl LoadoutItem {
+    pub fn new(name: &str, version: Option<&str>) -> Self {
+        Self {
+            name: name.to_string(),
+            version: version.map(ToString::to_string),
+        }
+    }
+}
+
+impl From<&Armor> for LoadoutItem {
+    fn from(armor: &Armor) -> Self {
+        Self::new(&armor.name, armor.version.as_deref())
+    }
+}
+
+// Item in each gear slot; empty slots are left out of the file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LoadoutGear {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub head: Option<LoadoutItem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub neck: Option<LoadoutItem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cape: Option<LoadoutItem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ammo: Option<LoadoutItem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub second_ammo: Option<LoadoutItem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weapon: Option<LoadoutItem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shield: Option<LoadoutItem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<LoadoutItem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub legs: Option<LoadoutItem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hands: Option<LoadoutItem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feet: Option<LoadoutItem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ring: Option<LoadoutItem>,
+}
+
+impl LoadoutGear {
+    /// Put an item in whichever slot it belongs to, removing the shield for two-handed weapons.
+    pub fn equip(&mut self, name: &str, version: Option<&str>) -> Result<(), GearError> {
+        let item = LoadoutItem::new(name, version);
+        let slot = match Armor::new(name, version) {
+            Ok(armor) => armor.slot,
+            Err(GearError::NotArmor(_)) => {
+                if Weapon::new(name, version)?.is_two_handed {
+                    self.shield = None;
+                }
+                GearSlot::Weapon
+            }
+            Err(e) => return Err(e),
+        };
+
+        match slot {
+            GearSlot::Head => self.head = Some(item),
+            GearSlot::Neck => self.neck = Some(item),
+            GearSlot::Cape => self.cape = Some(item),
+            GearSlot::Ammo => self.ammo = Some(item),
+            GearSlot::Weapon => self.weapon = Some(item),
+            GearSlot::Shield => self.shield = Some(item),
+            GearSlot::Body => self.body = Some(item),
+            GearSlot::Legs => self.legs = Some(item),
+            GearSlot::Hands => self.hands = Some(item),
+            GearSlot::Feet => self.feet = Some(item),
+            GearSlot::Ring => self.ring = Some(item),
+            GearSlot::None => return Err(GearError::NoneSlot(name.to_string())),
+        }
+
+        Ok(())
+    }
+
+    pub fn to_gear(&self) -> Result<Gear, GearError> {
+        let mut builder = Gear::builder();
+        if let Some(item) = &self.head {
+            builder = builder.head(&item.name, item.version.as_deref());
+        }
+        if let Some(item) = &self.neck {
+            builder = builder.neck(&item.name, item.version.as_deref());
+        }
+        if let Some(item) = &self.cape {
+            builder = builder.cape(&item.name, item.version.as_deref());
+        }
+        if let Some(item) = &self.ammo {
+            builder = builder.ammo(&item.name, item.version.as_deref());
+        }
+        if let Some(item) = &self.second_ammo {
+            builder = builder.second_ammo(&item.name, item.version.as_deref());
+        }
+        if let Some(item) = &self.weapon {
+            builder = builder.weapon(&item.name, item.version.as_deref());
+        }
+        if let Some(item) = &self.shield {
+            builder = builder.shield(&item.name, item.version.as_deref());
+        }
+        if let Some(item) = &self.body {
+            builder = builder.body(&item.name, item.version.as_deref());
+        }
+        if let Some(item) = &self.legs {
+            builder = builder.legs(&item.name, item.version.as_deref());
+        }
+        if let Some(item) = &self.hands {
+            builder = builder.hands(&item.name, item.version.as_deref());
+        }
+        if let Some(item) = &self.feet {
+            builder = builder.feet(&item.name, item.version.as_deref());
+        }
+        if let Some(item) = &self.ring {
+            builder = builder.ring(&item.name, item.version.as_deref());
+        }
+
+        builder.build()
+    }
+}
+
+impl From<&Gear> for LoadoutGear {
+    fn from(gear: &Gear) -> Self {
+        let weapon = &gear.weapon;
+        Self {
+            head: gear.head.as_ref().map(LoadoutItem::from),
+            neck: gear.neck.as_ref().map(LoadoutItem::from),
+            cape: gear.cape.as_ref().map(LoadoutItem::from),
+            ammo: gear.ammo.as_ref().map(LoadoutItem::from),
+            second_ammo: gear.second_ammo.as_ref().map(LoadoutItem::from),
+            weapon: (*weapon != Weapon::default())
+                .then(|| LoadoutItem::new(&weapon.name, weapon.version.as_deref())),
+            shield: gear.shield.as_ref().map(LoadoutItem::from),
+            body: gear.body.as_ref().map(LoadoutItem::from),
+            legs: gear.legs.as_ref().map(LoadoutItem::from),
+            hands: gear.hands.as_ref().map(LoadoutItem::from),
+            feet: gear.feet.as_ref().map(LoadoutItem::from),
+            ring: gear.ring.as_ref().map(LoadoutItem::from),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LoadoutStats {
+    pub attack: u32,
+    pub strength: u32,
+    pub defence: u32,
+    pub ranged: u32,
+    pub magic: u32,
+    pub hitpoints: u32,
+    pub prayer: u32,
+    pub mining: u32,
+    pub herblore: u32,
+}
+
+impl Default for LoadoutStats {
+    fn default() -> Self {
+        Self::from(&PlayerStats::default())
+    }
+}
+
+impl LoadoutStats {
+    pub fn to_player_stats(&self) -> Result<PlayerStats, PlayerError> {
+        let level = |stat: &'static str, level: u32, min: u32| {
+            if (min..=constants::MAX_LEVEL).contains(&level) {
+                Ok(Stat::from(level))
+            } else {
+                Err(PlayerError::InvalidLevel { stat, level })
+            }
+        };
+
+        Ok(PlayerStats {
+            hitpoints: level("hitpoints", self.hitpoints, constants::MIN_HITPOINTS)?,
+            attack: level("attack", self.attack, 1)?,
+            strength: level("strength", self.strength, 1)?,
+            defence: level("defence", self.defence, 1)?,
+            ranged: level("ranged", self.ranged, 1)?,
+            magic: level("magic", self.magic, 1)?,
+            prayer: level("prayer", self.prayer, 1)?,
+            mining: level("mining", self.mining, 1)?,
+            herblore: level("herblore", self.herblore, 1)?,
+            ..PlayerStats::default()
+        })
+    }
+}
+
+impl From<&PlayerStats> for LoadoutStats {
+    fn from(stats: &PlayerStats) -> Self {
+        Self {
+            attack: stats.attack.base,
+            strength: stats.strength.base,
+            defence: stats.defence.base,
+            ranged: stats.ranged.base,
+            magic: stats.magic.base,
+            hitpoints: stats.hitpoints.base,
+            prayer: stats.prayer.base,
+            mining: stats.mining.base,
+            herblore: stats.herblore.base,
+        }
+    }
+}
+
+// The subset of StatusBoosts that describes the player rather than the state of a fight
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LoadoutBoosts {
+    pub on_task: bool,
+    pub in_wilderness: bool,
+    pub kandarin_diary: bool,
+}
+
+impl Default for LoadoutBoosts {
+    fn default() -> Self {
+        let boosts = StatusBoosts::default();
+        Self {
+            on_task: boosts.on_task,
+            in_wilderness: boosts.in_wilderness,
+            kandarin_diary: boosts.kandarin_diary,
+        }
+    }
+}
+
+/// A named gear switch. Its gear replaces the main gear entirely rather than adding to it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LoadoutSwitch {
+    pub name: String, // SwitchType label, e.g., "Magic" or "DWH spec"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spell: Option<String>,
+    pub prayers: Vec<String>,
+    pub gear: LoadoutGear,
+}
+
+impl From<&GearSwitch> for LoadoutSwitch {
+    fn from(switch: &GearSwitch) -> Self {
+        Self {
+            name: switch.switch_type.label(),
+            style: Some(switch.active_style.to_string()),
+            spell: switch.spell.map(|spell| spell.to_string()),
+            prayers: prayer_names(&switch.prayers),
+            gear: LoadoutGear::from(switch.gear.as_ref()),
+        }
+    }
+}
+
+fn prayer_names(prayers: &PrayerBoosts) -> Vec<String> {
+    prayers.prayers().iter().map(ToString::to_string).collect()
+}
+
+/// Declarative description of a player: stats, gear, style, spell, boosts and gear switches.
+///
+/// Loadouts can be read from and written to TOML or JSON. Names of styles, prayers, potions and
+/// spells are matched ignoring case, spaces and punctuation, so "Super combat" and "SuperCombat"
+/// are equivalent.
+///
+/// # Example
+/// ```
+/// use osrs::types::loadout::Loadout;
+///
+/// let loadout = Loadout::from_toml_str(
+///     r#"
+///     style = "Lash"
+///     prayers = ["Piety"]
+///     potions = ["Super combat"]
+///
+///     [stats]
+///     attack = 90
+///
+///     [gear]
+///     weapon = { name = "Abyssal whip" }
+///     cape = { name = "Fire cape" }
+///     "#,
+/// )?;
+/// let player = loadout.to_player(None)?;
+/// assert!(player.is_wearing("Abyssal whip", None));
+/// assert_eq!(player.stats.attack.base, 90);
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Loadout {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<String>, // Defaults to the weapon's first style
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spell: Option<String>,
+    pub potions: Vec<String>,
+    pub prayers: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active_switch: Option<String>, // Name of the switch to start in, if not the main gear
+    pub stats: LoadoutStats,
+    pub boosts: LoadoutBoosts,
+    pub gear: LoadoutGear,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub switches: Vec<LoadoutSwitch>,
+}
+
+impl Loadout {
+    pub fn from_toml_str(toml_str: &str) -> Result<Self, LoadoutError> {
+        Ok(toml::from_str(toml_str)?)
+    }
+
+    pub fn from_json_str(json_str: &str) -> Result<Self, LoadoutError> {
+        Ok(serde_json::from_str(json_str)?)
+    }
+
+    pub fn to_toml_string(&self) -> Result<String, LoadoutError> {
+        Ok(toml::to_string(self)?)
+    }
+
+    pub fn to_json_string(&self) -> Result<String, LoadoutError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, LoadoutError> {
+        // Pick the format from the file extension
+        let contents = std::fs::read_to_string(path)?;
+        match extension(path).as_str() {
+            "toml" => Self::from_toml_str(&contents),
+            "json" => Self::from_json_str(&contents),
+            other => Err(LoadoutError::UnsupportedFormat(other.to_string())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), LoadoutError> {
+        let contents = match extension(path).as_str() {
+            "toml" => self.to_toml_string()?,
+            "json" => self.to_json_string()?,
+            other => return Err(LoadoutError::UnsupportedFormat(other.to_string())),
+        };
+        std::fs::write(path, contents)?;
+
+        Ok(())
+    }
+
+    /// Build a validated `Player` from the loadout.
+    ///
+    /// With a monster, the player's rolls and every gear switch's rolls are calculated against
+    /// it. Without one, switches are stored with uncalculated rolls.
+    pub fn to_player(&self, monster: Option<&Monster>) -> Result<Player, LoadoutError> {
+        let mut player = self.setup_player(
+            &self.gear,
+            self.style.as_deref(),
+            self.spell.as_deref(),
+            &self.prayers,
+        )?;
+        player.attrs.name.clone_from(&self.name);
+
+        let mut names = HashSet::new();
+        for switch in &self.switches {
+            if !names.insert(switch.name.as_str()) {
+                return Err(PlayerError::DuplicateSwitch(switch.name.clone()).into());
+            }
+
+            let switch_player = self.setup_player(
+                &switch.gear,
+                switch.style.as_deref(),
+                switch.spell.as_deref(),
+                &switch.prayers,
+            )?;
+            let switch_type = SwitchType::from_label(&switch.name);
+            player.switches.push(match monster {
+                Some(monster) => GearSwitch::new(switch_type, &switch_player, monster),
+                None => {
+                    // Keep the uncalculated rolls but use the loadout's name for the switch
+                    GearSwitch {
+                        switch_type,
+                        ..GearSwitch::from(&switch_player)
+                    }
+                }
+            });
+        }
+
+        if let Some(active_switch) = &self.active_switch {
+            player.switch(&SwitchType::from_label(active_switch))?;
+        }
+        if let Some(monster) = monster {
+            calc_active_player_rolls(&mut player, monster);
+        }
+
+        Ok(player)
+    }
+
+    // Build a player with this loadout's stats, potions and boosts wearing the given setup
+    fn setup_player(
+        &self,
+        gear: &LoadoutGear,
+        style: Option<&str>,
+        spell: Option<&str>,
+        prayers: &[String],
+    ) -> Result<Player, LoadoutError> {
+        let gear = gear.to_gear()?;
+        let style = match style {
+            Some(style) => parse_style(style)?,
+            None => CombatStyle::iter()
+                .find(|style| gear.weapon.combat_styles.contains_key(style))
+                .unwrap_or_default(),
+        };
+
+        let mut builder = Player::builder()
+            .player_stats(self.stats.to_player_stats()?)
+            .gear(gear)
+            .active_style(style)
+            .on_task(self.boosts.on_task)
+            .in_wilderness(self.boosts.in_wilderness)
+            .kandarin_diary(self.boosts.kandarin_diary);
+        for potion in &self.potions {
+            builder = builder.potion(parse_name::<Potion>("potion", potion)?);
+        }
+        for prayer in prayers {
+            builder = builder.prayer(parse_name::<Prayer>("prayer", prayer)?);
+        }
+        let mut player = builder.build()?;
+
+        if let Some(spell) = spell {
+            player.set_spell(parse_spell(spell)?)?;
+        }
+        player.set_quiver_bonuses();
+        player.update_bonuses();
+
+        Ok(player)
+    }
+}
+
+fn extension(path: &Path) -> String {
+    path.extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+impl From<&Player> for Loadout {
+    fn from(player: &Player) -> Self {
+        // While switched, export the main setup and keep the active switch separate
+        let (gear, prayers, style, spell) = match &player.base_setup {
+            Some(base) if player.current_switch.is_some() => {
+                (&base.gear, &base.prayers, base.active_style, base.spell)
+            }
+            _ => (
+                &player.gear,
+                &player.prayers,
+                player.attrs.active_style,
+                player.attrs.spell,
+            ),
+        };
+
+        Self {
+            name: player.attrs.name.clone(),
+            style: Some(style.to_string()),
+            spell: spell.map(|spell| spell.to_string()),
+            potions: player
+                .potions
+                .potions()
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            prayers: prayer_names(prayers),
+            active_switch: player.current_switch.as_ref().map(SwitchType::label),
+            stats: LoadoutStats::from(&player.stats),
+            boosts: LoadoutBoosts {
+                on_task: player.boosts.on_task,
+                in_wilderness: player.boosts.in_wilderness,
+                kandarin_diary: player.boosts.kandarin_diary,
+            },
+            gear: LoadoutGear::from(gear.as_ref()),
+            switches: player.switches.iter().map(LoadoutSwitch::from).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::spells::{AncientSpell, ArceuusSpell, SpecialSpell, StandardSpell};
+    use crate::utils::loadouts::max_melee_player;
+
+    const SWITCH_LOADOUT: &str = r#"
+        style = "Lash"
+        prayers = ["Piety"]
+        potions = ["Super combat"]
+        active_switch = "Magic"
+
+        [stats]
+        magic = 94
+
+        [gear]
+        weapon = { name = "Abyssal whip" }
+        shield = { name = "Dragon defender" }
+
+        [[switches]]
+        name = "Magic"
+        spell = "Fire Wave"
+        prayers = ["Augury"]
+        gear = { weapon = { name = "Kodai wand" }, shield = { name = "Elidinis' ward (f)" } }
+    "#;
+
+    #[test]
+    fn test_toml_round_trip() {
+        let player = max_melee_player();
+        let loadout = Loadout::from(&player);
+        let toml_str = loadout.to_toml_string().unwrap();
+        let parsed = Loadout::from_toml_str(&toml_str).unwrap();
+        assert_eq!(parsed, loadout);
+
+        let rebuilt = parsed.to_player(None).unwrap();
+        assert_eq!(rebuilt.gear, player.gear);
+        assert_eq!(rebuilt.bonuses, player.bonuses);
+        assert_eq!(rebuilt.attrs.active_style, CombatStyle::Lunge);
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let loadout = Loadout::from_toml_str(SWITCH_LOADOUT).unwrap();
+        let json_str = loadout.to_json_string().unwrap();
+        assert_eq!(Loadout::from_json_str(&json_str).unwrap(), loadout);
+    }
+
+    #[test]
+    fn test_display_names_parse() {
+        // Exported names must be readable back in
+        for prayer in Prayer::iter() {
+            assert_eq!(
+                parse_display_name::<Prayer>(&prayer.to_string()),
+                Some(prayer)
+            );
+        }
+        for potion in Potion::iter() {
+            assert_eq!(
+                parse_display_name::<Potion>(&potion.to_string()),
+                Some(potion)
+            );
+        }
+        for style in CombatStyle::iter() {
+            assert_eq!(parse_style(&style.to_string()).unwrap(), style);
+        }
+        let spells = StandardSpell::iter()
+            .filter(|spell| *spell != StandardSpell::None)
+            .map(Spell::Standard)
+            .chain(AncientSpell::iter().map(Spell::Ancient))
+            .chain(ArceuusSpell::iter().map(Spell::Arceuus))
+            .chain(SpecialSpell::iter().map(Spell::Special));
+        for spell in spells {
+            assert_eq!(parse_spell(&spell.to_string()).unwrap(), spell);
+        }
+    }
+
+    #[test]
+    fn test_switches() {
+        let loadout = Loadout::from_toml_str(SWITCH_LOADOUT).unwrap();
+        let player = loadout.to_player(None).unwrap();
+
+        assert_eq!(player.switches.len(), 1);
+        assert_eq!(player.current_switch, Some(SwitchType::Magic));
+        assert!(player.is_wearing("Kodai wand", None));
+        assert_eq!(player.stats.magic.base, 94);
+        assert!(player.prayers.contains_prayer(Prayer::Augury));
+
+        // The main setup is exported as the base gear, separate from the active switch
+        let exported = Loadout::from(&player);
+        assert_eq!(exported.active_switch.as_deref(), Some("Magic"));
+        assert_eq!(exported.gear, loadout.gear);
+        assert_eq!(exported.style, loadout.style);
+        assert_eq!(exported.prayers, loadout.prayers);
+        assert_eq!(exported.spell, None);
+        assert_eq!(exported.switches[0].gear, loadout.switches[0].gear);
+        assert_eq!(exported.switches[0].spell.as_deref(), Some("Fire Wave"));
+
+        let reloaded = exported.to_player(None).unwrap();
+        assert_eq!(reloaded.gear, player.gear);
+        assert_eq!(reloaded.attrs.spell, player.attrs.spell);
+        assert_eq!(Loadout::from(&reloaded), exported);
+    }
+
+    #[test]
+    fn test_equip() {
+        let mut gear = LoadoutGear::default();
+        gear.equip("Avernic defender", None).unwrap();
+        gear.equip("Torva full helm", None).unwrap();
+        assert_eq!(
+            gear.shield,
+            Some(LoadoutItem::new("Avernic defender", None))
+        );
+        assert_eq!(gear.head, Some(LoadoutItem::new("Torva full helm", None)));
+
+        gear.equip("Scythe of vitur", Some("Charged")).unwrap();
+        assert_eq!(
+            gear.weapon,
+            Some(LoadoutItem::new("Scythe of vitur", Some("Charged")))
+        );
+        assert_eq!(gear.shield, None);
+    }
+
+    #[test]
+    fn test_duplicate_switch() {
+        let mut loadout = Loadout::from_toml_str(SWITCH_LOADOUT).unwrap();
+        loadout.switches.push(loadout.switches[0].clone());
+        assert!(matches!(
+            loadout.to_player(None),
+            Err(LoadoutError::PlayerError(PlayerError::DuplicateSwitch(name))) if name == "Magic"
+        ));
+    }
+
+    #[test]
+    fn test_validation_errors() {
+        let wrong_slot = Loadout::from_toml_str(r#"gear.head = { name = "Torva platebody" }"#)
+            .unwrap()
+            .to_player(None);
+        assert!(matches!(
+            wrong_slot,
+            Err(LoadoutError::GearError(GearError::WrongSlot {
+                expected: GearSlot::Head,
+                actual: GearSlot::Body,
+                ..
+            }))
+        ));
+
+        let unknown_item = Loadout::from_toml_str(r#"gear.weapon = { name = "Abyssal whipp" }"#)
+            .unwrap()
+            .to_player(None);
+        assert!(matches!(unknown_item, Err(LoadoutError::GearError(_))));
+
+        let unknown_prayer = Loadout::from_toml_str(r#"prayers = ["Pietyy"]"#)
+            .unwrap()
+            .to_player(None);
+        assert_eq!(
+            unknown_prayer.unwrap_err().to_string(),
+            "Player error: Unknown prayer: Pietyy"
+        );
+
+        let invalid_level = Loadout::from_toml_str("stats.hitpoints = 5")
+            .unwrap()
+            .to_player(None);
+        assert!(matches!(
+            invalid_level,
+            Err(LoadoutError::PlayerError(PlayerError::InvalidLevel {
+                stat: "hitpoints",
+                level: 5
+            }))
+        ));
+
+        let two_handed = Loadout::from_toml_str(
+            r#"
+            gear.weapon = { name = "Twisted bow" }
+            gear.shield = { name = "Twisted buckler" }
+            "#,
+        )
+        .unwrap()
+        .to_player(None);
+        assert!(matches!(
+            two_handed,
+            Err(LoadoutError::GearError(
+                GearError::TwoHandedWithShield { .. }
+            ))
+        ));
+
+        assert!(Loadout::from_toml_str("unknown_field = 1").is_err());
+    }
+}
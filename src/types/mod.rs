@@ -1,6 +1,10 @@
+pub mod bank;
 pub mod equipment;
 pub mod food;
+pub mod inventory;
+pub mod loadout;
 pub mod monster;
+pub mod monster_specials;
 pub mod player;
 pub mod potions;
 pub mod prayers;
@@ -6,17 +6,18 @@ use crate::combat::thralls::Thrall;
 use crate::constants;
 use crate::error::MonsterError;
 use crate::types::equipment::{CombatStyle, CombatType};
+use crate::types::monster_specials::{MonsterSpecial, SpecialHit};
 use crate::types::player::Player;
 use crate::types::stats::MonsterStats;
 use rand::Rng;
-use serde::{Deserialize, de::Error};
+use serde::{Deserialize, Serialize, de::Error};
 use std::cmp::{max, min};
 use strum_macros::Display;
 
 const MONSTER_JSON_STR: &str = include_str!("../databases/monsters.json");
 
 // Enum for combat stats
-#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy, Serialize, Deserialize)]
 pub enum CombatStat {
     Attack,
     Strength,
@@ -114,7 +115,7 @@ pub struct Immunities {
 }
 
 // Enum for monster attack types
-#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy, Display)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy, Display, Serialize, Deserialize)]
 pub enum AttackType {
     Stab,
     Slash,
@@ -451,6 +452,8 @@ pub struct Monster {
     pub active_effects: Vec<CombatEffect>, // Will move poison/venom here
     #[serde(skip)]
     pub hp_scaling_table: Option<HpScalingTable>,
+    #[serde(default)]
+    pub specials: Vec<MonsterSpecial>,
 }
 
 impl Monster {
@@ -469,22 +472,47 @@ impl Monster {
             .into_iter()
             .find(|m| m.info.name == name && m.info.version == string_version)
             .ok_or(MonsterError::MonsterNotFound(name.to_string()))?;
+        monster.initialize();
+
+        Ok(monster)
+    }
+
+    pub fn new(name: &str, version: Option<&str>) -> Result<Monster, MonsterError> {
+        Self::from_json_str(name, version, MONSTER_JSON_STR)
+    }
+
+    pub fn search(query: &str) -> Result<Vec<Monster>, MonsterError> {
+        // Find all monsters (and versions) whose names contain the query, ignoring case
+        let query = query.to_lowercase();
+        let all_monsters: Vec<Monster> = serde_json::from_str(MONSTER_JSON_STR)?;
+        let mut matches: Vec<Monster> = all_monsters
+            .into_iter()
+            .filter(|m| m.info.name.to_lowercase().contains(&query))
+            .collect();
+        for monster in &mut matches {
+            monster.initialize();
+        }
+
+        Ok(matches)
+    }
+
+    fn initialize(&mut self) {
         // Set defence level floor
-        monster.set_defence_floor();
+        self.set_defence_floor();
 
         // Set base magic def bonus (to allow it to be drained by the eye of ayak)
-        monster.bonuses.defence.magic_base = monster.bonuses.defence.magic;
+        self.bonuses.defence.magic_base = self.bonuses.defence.magic;
 
         // Calculate base defence rolls and copy to live defence rolls
-        monster.base_def_rolls = rolls::monster_def_rolls(&monster);
-        monster.def_rolls.clone_from(&monster.base_def_rolls);
+        self.base_def_rolls = rolls::monster_def_rolls(self);
+        self.def_rolls.clone_from(&self.base_def_rolls);
 
         // Calculate base attack rolls and copy to live attack rolls
-        monster.base_att_rolls = rolls::monster_att_rolls(&monster);
-        monster.att_rolls.clone_from(&monster.base_att_rolls);
+        self.base_att_rolls = rolls::monster_att_rolls(self);
+        self.att_rolls.clone_from(&self.base_att_rolls);
 
         if let (Some(max_hits), Some(attack_styles)) =
-            (&mut monster.max_hits, &monster.info.attack_styles)
+            (&mut self.max_hits, &self.info.attack_styles)
         {
             if max_hits.len() == 1 && attack_styles.len() == 1 {
                 max_hits[0].style = attack_styles[0];
@@ -500,12 +528,6 @@ impl Monster {
                 }
             }
         }
-
-        Ok(monster)
-    }
-
-    pub fn new(name: &str, version: Option<&str>) -> Result<Monster, MonsterError> {
-        Self::from_json_str(name, version, MONSTER_JSON_STR)
     }
 
     pub fn name(&self) -> &str {
@@ -523,8 +545,20 @@ impl Monster {
         rng: &mut rand::rngs::SmallRng,
         cap_hit: bool,
     ) -> Result<Hit, MonsterError> {
-        // Perform an attack on a player
+        // Perform an attack on a player, whose protection prayer (if any) is always up in time
+        self.attack_with_flick(player, attack_type, rng, cap_hit, 1.0)
+    }
 
+    /// Perform an attack on a player whose protection prayer only goes up in time with the
+    /// given probability, modelling missed switches and flicks.
+    pub fn attack_with_flick(
+        &mut self,
+        player: &mut Player,
+        attack_type: Option<AttackType>,
+        rng: &mut rand::rngs::SmallRng,
+        cap_hit: bool,
+        flick_success: f64,
+    ) -> Result<Hit, MonsterError> {
         let attack_type = if let Some(att_type) = attack_type {
             att_type
         } else if let Some(attack_styles) = &self.info.attack_styles
@@ -535,6 +569,16 @@ impl Monster {
             return Err(MonsterError::AttackTypeNotSpecified(self.info.name.clone()));
         };
 
+        // Special attacks go through the monster's first defined special
+        if attack_type == AttackType::Special {
+            let name = self
+                .specials
+                .first()
+                .map(|special| special.name.clone())
+                .ok_or(MonsterError::SpecialAttackNotSupported)?;
+            return Ok(self.special_attack(&name, player, rng, cap_hit)?.total());
+        }
+
         let max_hit = self
             .max_hits
             .as_ref()
@@ -544,6 +588,111 @@ impl Monster {
                 attack_type: attack_type.to_string(),
             })?;
 
+        let success = self.roll_accuracy(player, attack_type, rng)?;
+
+        let mut damage = if success {
+            rng.random_range(0..max_hit.value + 1)
+        } else {
+            0
+        };
+
+        if success {
+            if player.prayers.protects_against(attack_type)
+                && (flick_success >= 1.0 || rng.random_bool(flick_success.max(0.0)))
+            {
+                damage = self.protected_damage(damage);
+            }
+            damage = reduce_damage(player, Some(attack_type), damage, rng)?;
+        }
+
+        if cap_hit {
+            damage = min(damage, player.stats.hitpoints.current);
+        }
+
+        Ok(Hit::new(damage, success))
+    }
+
+    /// Perform one of the monster's special attacks on a player.
+    ///
+    /// Each hit rolls accuracy in the special's style (typeless hits always land) and goes
+    /// through the same protection prayer and defensive gear reductions as regular attacks.
+    /// Effects are only applied if at least one hit lands.
+    pub fn special_attack(
+        &mut self,
+        name: &str,
+        player: &mut Player,
+        rng: &mut rand::rngs::SmallRng,
+        cap_hit: bool,
+    ) -> Result<SpecialHit, MonsterError> {
+        let special = self
+            .specials
+            .iter()
+            .find(|special| special.name == name)
+            .cloned()
+            .ok_or_else(|| MonsterError::SpecialAttackNotFound {
+                monster_name: self.info.name.clone(),
+                special: name.to_string(),
+            })?;
+
+        let mut hits = Vec::with_capacity(special.hits as usize);
+        let mut remaining_hp = player.stats.hitpoints.current;
+        for _ in 0..special.hits {
+            let success = match special.style {
+                Some(style) => self.roll_accuracy(player, style, rng)?,
+                None => true,
+            };
+
+            let mut damage = if success {
+                special.damage.roll(player, rng)
+            } else {
+                0
+            };
+            if success
+                && special.blockable
+                && special
+                    .style
+                    .is_some_and(|style| player.prayers.protects_against(style))
+            {
+                damage = self.protected_damage(damage);
+            }
+            if success && special.reducible {
+                damage = reduce_damage(player, special.style, damage, rng)?;
+            }
+            if cap_hit {
+                damage = min(damage, remaining_hp);
+                remaining_hp -= damage;
+            }
+
+            hits.push(Hit::new(damage, success));
+        }
+
+        let landed = hits.iter().any(|hit| hit.success);
+        Ok(SpecialHit {
+            name: special.name,
+            hits,
+            effects: if landed { special.effects } else { Vec::new() },
+            blockable: special.blockable && special.style.is_some(),
+        })
+    }
+
+    /// Damage left from a hit after the correct protection prayer: fully blocked for most
+    /// monsters, and reduced by a fixed fraction for bosses that pierce prayers.
+    pub fn protected_damage(&self, damage: u32) -> u32 {
+        constants::PRAYER_PIERCING_MONSTERS
+            .iter()
+            .find(|(name, _, _)| *name == self.info.name)
+            .map_or(0, |(_, numerator, denominator)| {
+                damage * numerator / denominator
+            })
+    }
+
+    // Roll the monster's attack roll against the player's defence roll in the given style
+    fn roll_accuracy(
+        &self,
+        player: &Player,
+        attack_type: AttackType,
+        rng: &mut rand::rngs::SmallRng,
+    ) -> Result<bool, MonsterError> {
         let max_att_roll = match attack_type {
             AttackType::Stab => Ok(self.att_rolls.get(CombatType::Stab)),
             AttackType::Slash => Ok(self.att_rolls.get(CombatType::Slash)),
@@ -580,50 +729,7 @@ impl Monster {
 
         let def_roll = rng.random_range(0..max_def_roll + 1);
 
-        let success = att_roll > def_roll;
-
-        let mut damage = if success {
-            rng.random_range(0..max_hit.value + 1)
-        } else {
-            0
-        };
-
-        if success {
-            if player.is_wearing("Elysian spirit shield", None) && rng.random::<f64>() <= 0.7 {
-                let reduction = max(1, damage / 4);
-                damage -= reduction;
-            } else if player.is_wearing("Dinh's bulwark", None)
-                && player.attrs.active_style == CombatStyle::Block
-            {
-                damage = damage * 8 / 10;
-            }
-
-            if player.set_effects.full_justiciar {
-                let defensive_bonus = match attack_type {
-                    AttackType::Stab => Ok(player.bonuses.defence.stab),
-                    AttackType::Slash => Ok(player.bonuses.defence.slash),
-                    AttackType::Crush => Ok(player.bonuses.defence.crush),
-                    AttackType::Ranged => Ok(player.bonuses.defence.ranged),
-                    AttackType::Magic => Ok(player.bonuses.defence.magic),
-                    AttackType::Melee => Ok({
-                        (player.bonuses.defence.stab
-                            + player.bonuses.defence.slash
-                            + player.bonuses.defence.crush)
-                            / 3
-                    }),
-                    AttackType::Special => Err(MonsterError::SpecialAttackNotSupported),
-                    AttackType::None => Err(MonsterError::NoneAttackNotSupported),
-                }?;
-
-                damage -= damage * defensive_bonus as u32 / 3000;
-            }
-        }
-
-        if cap_hit {
-            damage = min(damage, player.stats.hitpoints.current);
-        }
-
-        Ok(Hit::new(damage, success))
+        Ok(att_roll > def_roll)
     }
 
     pub fn scale_toa(&mut self) {
@@ -882,6 +988,18 @@ impl Monster {
         remainder
     }
 
+    pub fn drain_magic_defence_bonus(&mut self, amount: u32) {
+        // Bonuses drain down to 0, and a negative bonus is left alone
+        if self.bonuses.defence.magic > 0 {
+            self.bonuses.defence.magic = max(0, self.bonuses.defence.magic - amount as i32);
+        }
+
+        // The drained bonus only counts once the defence rolls are recalculated
+        self.base_def_rolls = rolls::monster_def_rolls(self);
+        self.def_rolls.clone_from(&self.base_def_rolls);
+        self.scale_toa();
+    }
+
     pub fn drain_stats_in_order(&mut self, total_amount: u32, drain_order: Vec<StatDrain>) {
         let mut amount = total_amount;
         for drain in drain_order {
@@ -1098,6 +1216,47 @@ fn round_toa_hp(hp: u32) -> u32 {
     }
 }
 
+// Apply the player's defensive gear reductions to a successful hit (typeless hits skip Justiciar)
+fn reduce_damage(
+    player: &Player,
+    attack_type: Option<AttackType>,
+    mut damage: u32,
+    rng: &mut rand::rngs::SmallRng,
+) -> Result<u32, MonsterError> {
+    if player.is_wearing("Elysian spirit shield", None) && rng.random::<f64>() <= 0.7 {
+        let reduction = max(1, damage / 4);
+        damage = damage.saturating_sub(reduction);
+    } else if player.is_wearing("Dinh's bulwark", None)
+        && player.attrs.active_style == CombatStyle::Block
+    {
+        damage = damage * 8 / 10;
+    }
+
+    if player.set_effects.full_justiciar
+        && let Some(attack_type) = attack_type
+    {
+        let defensive_bonus = match attack_type {
+            AttackType::Stab => Ok(player.bonuses.defence.stab),
+            AttackType::Slash => Ok(player.bonuses.defence.slash),
+            AttackType::Crush => Ok(player.bonuses.defence.crush),
+            AttackType::Ranged => Ok(player.bonuses.defence.ranged),
+            AttackType::Magic => Ok(player.bonuses.defence.magic),
+            AttackType::Melee => Ok({
+                (player.bonuses.defence.stab
+                    + player.bonuses.defence.slash
+                    + player.bonuses.defence.crush)
+                    / 3
+            }),
+            AttackType::Special => Err(MonsterError::SpecialAttackNotSupported),
+            AttackType::None => Err(MonsterError::NoneAttackNotSupported),
+        }?;
+
+        damage -= damage * defensive_bonus.max(0) as u32 / 3000;
+    }
+
+    Ok(damage)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1109,6 +1268,22 @@ mod tests {
         assert_eq!(vorkath.info.combat_level, 732);
     }
 
+    #[test]
+    fn test_search() {
+        let results = Monster::search("vorkath").expect("Error searching monsters.");
+        assert!(!results.is_empty());
+        assert!(results.iter().all(|m| m.info.name.contains("Vorkath")));
+
+        let vorkath = Monster::new("Vorkath", Some("Post-quest")).expect("Error creating monster.");
+        let found = results
+            .iter()
+            .find(|m| m.info.version == vorkath.info.version)
+            .expect("Post-quest Vorkath not found.");
+        assert_eq!(found.base_def_rolls, vorkath.base_def_rolls);
+
+        assert!(Monster::search("not a monster").unwrap().is_empty());
+    }
+
     #[test]
     fn test_toa_scaling() {
         let mut baba = Monster::new("Ba-Ba", None).expect("Error creating monster.");
@@ -1214,4 +1389,128 @@ mod tests {
         let cerberus = Monster::new("Cerberus", None).expect("Error creating monster.");
         assert_eq!(cerberus.immunities.burn.unwrap(), BurnType::Normal);
     }
+
+    #[test]
+    fn test_special_attacks() {
+        use crate::types::monster_specials::{MonsterSpecial, SpecialDamage, SpecialEffect};
+        use rand::SeedableRng;
+
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(1);
+        let mut player = Player::new();
+        let mut kril = Monster::new("K'ril Tsutsaroth", None).expect("Error creating monster.");
+        kril.specials = vec![
+            MonsterSpecial::typeless("Smash", SpecialDamage::PercentOfCurrentHp(50))
+                .with_effect(SpecialEffect::DrainPrayer(10)),
+            MonsterSpecial::styled("Flurry", AttackType::Slash, SpecialDamage::Fixed(5))
+                .with_hits(3),
+        ];
+
+        // Typeless hits always land, and their damage can scale with the player's HP
+        let smash = kril
+            .special_attack("Smash", &mut player, &mut rng, false)
+            .unwrap();
+        assert_eq!(smash.damage(), player.stats.hitpoints.current / 2);
+        assert_eq!(smash.effects, vec![SpecialEffect::DrainPrayer(10)]);
+        assert!(!smash.blockable);
+
+        // Each hit of a multi-hit special rolls accuracy on its own
+        let flurry = kril
+            .special_attack("Flurry", &mut player, &mut rng, false)
+            .unwrap();
+        assert_eq!(flurry.hits.len(), 3);
+        assert!(flurry.blockable);
+        assert!(
+            flurry
+                .hits
+                .iter()
+                .all(|hit| hit.damage == if hit.success { 5 } else { 0 })
+        );
+
+        // The generic attack uses the first special, and unknown specials are errors
+        let hit = kril
+            .attack(&mut player, Some(AttackType::Special), &mut rng, false)
+            .unwrap();
+        assert!(hit.success);
+        assert!(matches!(
+            kril.special_attack("Stomp", &mut player, &mut rng, false),
+            Err(MonsterError::SpecialAttackNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_specials_deserialize() {
+        use crate::types::monster_specials::{SpecialDamage, SpecialEffect};
+
+        // Add a special to K'ril's database entry
+        let mut entries: Vec<serde_json::Value> = serde_json::from_str(MONSTER_JSON_STR).unwrap();
+        let mut kril = entries
+            .drain(..)
+            .find(|entry| entry["info"]["name"] == "K'ril Tsutsaroth")
+            .unwrap();
+        kril["specials"] = serde_json::json!([{
+            "name": "Prayer smash",
+            "style": "Slash",
+            "damage": { "Roll": 49 },
+            "blockable": false,
+            "effects": [{ "DrainPrayer": 10 }],
+        }]);
+        let json = serde_json::to_string(&vec![kril]).unwrap();
+
+        let monster = Monster::from_json_str("K'ril Tsutsaroth", None, &json).unwrap();
+        let special = &monster.specials[0];
+        assert_eq!(special.style, Some(AttackType::Slash));
+        assert_eq!(special.damage, SpecialDamage::Roll(49));
+        assert_eq!(special.hits, 1);
+        assert!(!special.blockable && special.reducible);
+        assert_eq!(special.effects, vec![SpecialEffect::DrainPrayer(10)]);
+
+        // Entries without specials still load
+        assert!(
+            Monster::new("K'ril Tsutsaroth", None)
+                .unwrap()
+                .specials
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_protection_prayers() {
+        use crate::types::prayers::Prayer;
+        use rand::SeedableRng;
+
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(2);
+        let mut player = Player::new();
+        player.add_prayer(Prayer::ProtectFromMelee);
+        let mut graardor = Monster::new("General Graardor", None).unwrap();
+        graardor.max_hits = Some(vec![MonsterMaxHit::new(40, AttackType::Slash)]);
+        let mut vard = Monster::new("Vardorvis", Some("Post-quest")).unwrap();
+        vard.max_hits = Some(vec![MonsterMaxHit::new(40, AttackType::Slash)]);
+
+        fn hits(
+            monster: &mut Monster,
+            player: &mut Player,
+            flick: f64,
+            rng: &mut rand::rngs::SmallRng,
+        ) -> u32 {
+            (0..200)
+                .map(|_| {
+                    monster
+                        .attack_with_flick(player, Some(AttackType::Slash), rng, false, flick)
+                        .unwrap()
+                        .damage
+                })
+                .max()
+                .unwrap()
+        }
+
+        // Most monsters are fully blocked, while piercing bosses still hit through the prayer
+        assert_eq!(hits(&mut graardor, &mut player, 1.0, &mut rng), 0);
+        assert!(hits(&mut vard, &mut player, 1.0, &mut rng) <= 10);
+        assert!(hits(&mut vard, &mut player, 1.0, &mut rng) > 0);
+
+        // Missed flicks let the full hit through
+        assert!(hits(&mut graardor, &mut player, 0.0, &mut rng) > 0);
+        player.set_protection_prayer(Some(Prayer::ProtectFromMagic));
+        assert!(hits(&mut graardor, &mut player, 1.0, &mut rng) > 0);
+    }
 }
@@ -0,0 +1,142 @@
+use crate::combat::attacks::standard::Hit;
+use crate::types::monster::{AttackType, CombatStat};
+use crate::types::player::Player;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// How much damage each hit of a monster special attack deals before reductions.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SpecialDamage {
+    Roll(u32),               // Uniform roll from 0 up to this max hit
+    Fixed(u32),              // Always exactly this much
+    PercentOfCurrentHp(u32), // Percentage of the player's current HP
+    PercentOfMaxHp(u32),     // Percentage of the player's base HP
+    PercentOfPrayer(u32),    // Percentage of the player's current prayer points
+}
+
+impl SpecialDamage {
+    pub fn roll(&self, player: &Player, rng: &mut impl Rng) -> u32 {
+        match *self {
+            Self::Roll(max_hit) => rng.random_range(0..max_hit + 1),
+            Self::Fixed(damage) => damage,
+            Self::PercentOfCurrentHp(percent) => player.stats.hitpoints.current * percent / 100,
+            Self::PercentOfMaxHp(percent) => player.stats.hitpoints.base * percent / 100,
+            Self::PercentOfPrayer(percent) => player.stats.prayer.current * percent / 100,
+        }
+    }
+}
+
+/// Effect a monster special attack applies to the player when it lands.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SpecialEffect {
+    DrainStat { stat: CombatStat, amount: u32 },
+    DrainPrayer(u32),
+    Venom,
+    Stun(i32),           // Player can't attack, eat or move for this many ticks
+    Freeze(i32),         // Player can't move for this many ticks
+    DisablePrayers(i32), // Player's prayers are turned off for this many ticks
+}
+
+/// A monster special attack, rolled through the same accuracy and damage reduction
+/// pipeline as the monster's regular attacks.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MonsterSpecial {
+    pub name: String,
+    /// Style used for the accuracy roll and Justiciar's reduction; typeless hits have none and
+    /// always land
+    pub style: Option<AttackType>,
+    pub damage: SpecialDamage,
+    #[serde(default = "default_hits")]
+    pub hits: u32,
+    /// Whether protection prayers can block the special (typeless specials never can)
+    #[serde(default = "default_true")]
+    pub blockable: bool,
+    /// Whether defensive gear (Elysian, Justiciar, bulwark) reduces the damage
+    #[serde(default = "default_true")]
+    pub reducible: bool,
+    #[serde(default)]
+    pub effects: Vec<SpecialEffect>,
+}
+
+fn default_hits() -> u32 {
+    1
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl MonsterSpecial {
+    /// An unblockable special with a single typeless hit.
+    pub fn typeless(name: &str, damage: SpecialDamage) -> Self {
+        Self {
+            name: name.to_string(),
+            style: None,
+            damage,
+            hits: 1,
+            blockable: false,
+            reducible: true,
+            effects: Vec::new(),
+        }
+    }
+
+    /// A blockable special rolled against the player's defence in the given style.
+    pub fn styled(name: &str, style: AttackType, damage: SpecialDamage) -> Self {
+        Self {
+            name: name.to_string(),
+            style: Some(style),
+            damage,
+            hits: 1,
+            blockable: true,
+            reducible: true,
+            effects: Vec::new(),
+        }
+    }
+
+    pub fn with_hits(mut self, hits: u32) -> Self {
+        self.hits = hits;
+        self
+    }
+
+    pub fn with_effect(mut self, effect: SpecialEffect) -> Self {
+        self.effects.push(effect);
+        self
+    }
+
+    pub fn unblockable(mut self) -> Self {
+        self.blockable = false;
+        self
+    }
+}
+
+/// Result of a monster special attack: every hit, plus the effects that landed.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SpecialHit {
+    pub name: String,
+    pub hits: Vec<Hit>,
+    pub effects: Vec<SpecialEffect>,
+    pub blockable: bool,
+}
+
+impl SpecialHit {
+    pub fn damage(&self) -> u32 {
+        self.hits.iter().map(|hit| hit.damage).sum()
+    }
+
+    pub fn success(&self) -> bool {
+        self.hits.iter().any(|hit| hit.success)
+    }
+
+    /// Collapse the special into a single hit, e.g., for recoil.
+    pub fn total(&self) -> Hit {
+        Hit::new(self.damage(), self.success())
+    }
+
+    /// Zero out the damage, e.g., when a protection prayer blocks the special.
+    pub fn block(&mut self) {
+        for hit in &mut self.hits {
+            hit.damage = 0;
+        }
+    }
+}
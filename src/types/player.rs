@@ -17,7 +17,7 @@ use reqwest;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::cmp::max;
 use std::collections::HashMap;
-use std::rc::Rc;
+use std::sync::Arc;
 
 // Struct for holding sunfire rune min hit value
 #[derive(Debug, PartialEq, Default, Clone, Copy)]
@@ -98,33 +98,33 @@ pub enum SwitchType {
     Magic,
     Spec(
         #[serde(
-            serialize_with = "serialize_rc_str",
-            deserialize_with = "deserialize_rc_str"
+            serialize_with = "serialize_arc_str",
+            deserialize_with = "deserialize_arc_str"
         )]
-        Rc<str>,
+        Arc<str>,
     ),
     Custom(
         #[serde(
-            serialize_with = "serialize_rc_str",
-            deserialize_with = "deserialize_rc_str"
+            serialize_with = "serialize_arc_str",
+            deserialize_with = "deserialize_arc_str"
         )]
-        Rc<str>,
+        Arc<str>,
     ),
 }
 
-fn serialize_rc_str<S>(rc: &Rc<str>, serializer: S) -> Result<S::Ok, S::Error>
+fn serialize_arc_str<S>(arc: &Arc<str>, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
-    serializer.serialize_str(rc)
+    serializer.serialize_str(arc)
 }
 
-fn deserialize_rc_str<'de, D>(deserializer: D) -> Result<Rc<str>, D::Error>
+fn deserialize_arc_str<'de, D>(deserializer: D) -> Result<Arc<str>, D::Error>
 where
     D: Deserializer<'de>,
 {
     let s = String::deserialize(deserializer)?;
-    Ok(Rc::from(s.as_str()))
+    Ok(Arc::from(s.as_str()))
 }
 
 impl SwitchType {
@@ -137,6 +137,19 @@ impl SwitchType {
             SwitchType::Custom(custom_label) => custom_label.to_string(),
         }
     }
+
+    pub fn from_label(label: &str) -> Self {
+        // Inverse of label(), e.g., "Magic" or "DWH spec"
+        match label {
+            "Melee" => SwitchType::Melee,
+            "Ranged" => SwitchType::Ranged,
+            "Magic" => SwitchType::Magic,
+            _ => match label.strip_suffix(" spec") {
+                Some(spec_label) => SwitchType::Spec(spec_label.into()),
+                None => SwitchType::Custom(label.into()),
+            },
+        }
+    }
 }
 
 impl std::fmt::Display for SwitchType {
@@ -155,8 +168,8 @@ impl std::fmt::Display for SwitchType {
 #[derive(Debug, Clone, PartialEq)]
 pub struct GearSwitch {
     pub switch_type: SwitchType,
-    pub gear: Rc<Gear>,
-    pub prayers: Rc<PrayerBoosts>,
+    pub gear: Arc<Gear>,
+    pub prayers: Arc<PrayerBoosts>,
     pub spell: Option<spells::Spell>,
     pub active_style: CombatStyle,
     pub set_effects: SetEffects,
@@ -208,8 +221,8 @@ impl From<&Player> for GearSwitch {
 
         Self {
             switch_type,
-            gear: Rc::clone(&player.gear),
-            prayers: Rc::clone(&player.prayers),
+            gear: Arc::clone(&player.gear),
+            prayers: Arc::clone(&player.prayers),
             spell: player.attrs.spell,
             active_style: player.attrs.active_style,
             set_effects: player.set_effects,
@@ -379,10 +392,10 @@ impl Default for PlayerState {
 #[derive(Debug, Clone)]
 pub struct Player {
     pub stats: PlayerStats,
-    pub gear: Rc<Gear>,
+    pub gear: Arc<Gear>,
     pub bonuses: EquipmentBonuses,
     pub potions: PotionBoosts,
-    pub prayers: Rc<PrayerBoosts>,
+    pub prayers: Arc<PrayerBoosts>,
     pub boosts: StatusBoosts,
     pub active_effects: Vec<CombatEffect>,
     pub set_effects: SetEffects,
@@ -394,6 +407,7 @@ pub struct Player {
     pub spec: SpecialAttackFn,
     pub switches: Vec<GearSwitch>,
     pub current_switch: Option<SwitchType>,
+    pub base_setup: Option<GearSwitch>, // Setup worn before the first gear switch replaced it
     pub state: PlayerState,
     combat_type: CombatType,
 }
@@ -402,10 +416,10 @@ impl Default for Player {
     fn default() -> Self {
         Self {
             stats: PlayerStats::default(),
-            gear: Rc::new(Gear::default()),
+            gear: Arc::new(Gear::default()),
             bonuses: EquipmentBonuses::default(),
             potions: PotionBoosts::default(),
-            prayers: Rc::new(PrayerBoosts::default()),
+            prayers: Arc::new(PrayerBoosts::default()),
             boosts: StatusBoosts::default(),
             active_effects: Vec::new(),
             set_effects: SetEffects::default(),
@@ -417,6 +431,7 @@ impl Default for Player {
             spec: standard_attack,
             switches: Vec::new(),
             current_switch: None,
+            base_setup: None,
             state: PlayerState::default(),
             combat_type: CombatType::default(),
         }
@@ -479,10 +494,12 @@ impl Player {
     }
 
     pub fn equip(&mut self, item_name: &str, version: Option<&str>) -> Result<(), GearError> {
-        if let Ok(armor) = Armor::new(item_name, version) {
-            self.equip_item(Box::new(armor))?;
-        } else if let Ok(weapon) = Weapon::new(item_name, version) {
-            self.equip_item(Box::new(weapon))?;
+        match Armor::new(item_name, version) {
+            Ok(armor) => self.equip_item(Box::new(armor))?,
+            Err(GearError::NotArmor(_)) => {
+                self.equip_item(Box::new(Weapon::new(item_name, version)?))?;
+            }
+            Err(e) => return Err(e),
         }
 
         self.update_bonuses();
@@ -492,7 +509,7 @@ impl Player {
     }
 
     pub fn unequip_slot(&mut self, slot: &GearSlot) {
-        let gear = Rc::make_mut(&mut self.gear);
+        let gear = Arc::make_mut(&mut self.gear);
         match slot {
             GearSlot::Ammo => gear.ammo = None,
             GearSlot::Body => gear.body = None,
@@ -516,7 +533,7 @@ impl Player {
     }
 
     pub fn equip_item(&mut self, item: Box<dyn Equipment>) -> Result<(), GearError> {
-        let gear = Rc::make_mut(&mut self.gear);
+        let gear = Arc::make_mut(&mut self.gear);
         let slot = item.slot();
         match slot {
             GearSlot::Weapon => {
@@ -642,8 +659,8 @@ impl Player {
         }
     }
 
-    fn set_quiver_bonuses(&mut self) {
-        let gear = Rc::make_mut(&mut self.gear);
+    pub(crate) fn set_quiver_bonuses(&mut self) {
+        let gear = Arc::make_mut(&mut self.gear);
         // Apply extra +10 accuracy and +1 strength to quiver if applicable
         if gear.is_quiver_bonus_valid()
             && let Some(cape) = &mut gear.cape
@@ -846,7 +863,7 @@ impl Player {
         let stance = self.combat_stance();
         let is_using_standard_spell = self.is_using_standard_spell();
 
-        let gear = Rc::make_mut(&mut self.gear);
+        let gear = Arc::make_mut(&mut self.gear);
 
         // Reduce attack speed by 1 on rapid
         if stance == CombatStance::Rapid {
@@ -881,10 +898,14 @@ impl Player {
             return Ok(());
         }
 
+        if self.current_switch.is_none() && self.base_setup.is_none() {
+            self.base_setup = Some(GearSwitch::from(&*self));
+        }
+
         for switch in &self.switches {
             if &switch.switch_type == switch_type {
-                self.gear = Rc::clone(&switch.gear);
-                self.prayers = Rc::clone(&switch.prayers);
+                self.gear = Arc::clone(&switch.gear);
+                self.prayers = Arc::clone(&switch.prayers);
                 self.attrs.spell = switch.spell;
                 self.attrs.active_style = switch.active_style;
                 self.set_effects = switch.set_effects;
@@ -904,6 +925,21 @@ impl Player {
         Err(PlayerError::GearSwitchNotFound(switch_type.clone()))
     }
 
+    pub fn switches_against(&self, monster: &Monster) -> Result<Vec<GearSwitch>, PlayerError> {
+        // Recalculate every gear switch's rolls against another monster (e.g., a boss's next form)
+        let mut switches = Vec::with_capacity(self.switches.len());
+        for switch in &self.switches {
+            let mut switched = self.clone();
+            switched.switch(&switch.switch_type)?;
+            switches.push(GearSwitch::new(
+                switch.switch_type.clone(),
+                &switched,
+                monster,
+            ));
+        }
+        Ok(switches)
+    }
+
     pub fn is_wearing_black_mask(&self) -> bool {
         // Check if the player is wearing any type of black mask or slayer helmet
         self.is_wearing_any(constants::BLACK_MASKS)
@@ -1027,6 +1063,19 @@ impl Player {
             .contains(&self.combat_stance())
     }
 
+    pub fn attack_range(&self) -> i32 {
+        // Spells always have 10 range, and longrange adds 2 to the weapon's range (up to 10)
+        if self.is_using_spell() {
+            return constants::MAX_ATTACK_RANGE;
+        }
+        let range = i32::from(self.gear.weapon.attack_range).max(1);
+        if self.combat_stance() == CombatStance::Longrange {
+            (range + 2).min(constants::MAX_ATTACK_RANGE)
+        } else {
+            range
+        }
+    }
+
     pub fn is_using_standard_spell(&self) -> bool {
         // Check if the player is casting a spell on the standard spellbook
         self.is_using_spell() && spells::is_standard_spell(self.attrs.spell.as_ref().unwrap())
@@ -1206,18 +1255,111 @@ impl Player {
         self.reset_current_stats(false);
     }
 
+    pub fn drink_potion(&mut self, potion: Potion) {
+        if potion.restores_prayer() {
+            self.drink_restore(potion);
+            return;
+        }
+
+        // Reapply the boost of a previously added potion, e.g. when redosing mid-fight
+        let boosts = [
+            (&self.potions.attack, &mut self.stats.attack),
+            (&self.potions.strength, &mut self.stats.strength),
+            (&self.potions.defence, &mut self.stats.defence),
+            (&self.potions.ranged, &mut self.stats.ranged),
+            (&self.potions.magic, &mut self.stats.magic),
+        ];
+        for (potions, stat) in boosts {
+            if let Some(boost) = potions
+                .iter()
+                .flatten()
+                .find(|p| p.potion_type == potion)
+                .map(|p| p.boost)
+            {
+                stat.boost(boost);
+            }
+        }
+    }
+
+    pub fn needs_redose(&self, potion: Potion, redose_at: u32) -> bool {
+        // Whether a stat boosted by the potion has dropped to the threshold and would gain from a dose
+        [
+            (&self.potions.attack, &self.stats.attack),
+            (&self.potions.strength, &self.stats.strength),
+            (&self.potions.defence, &self.stats.defence),
+            (&self.potions.ranged, &self.stats.ranged),
+            (&self.potions.magic, &self.stats.magic),
+        ]
+        .into_iter()
+        .any(|(potions, stat)| {
+            potions
+                .iter()
+                .flatten()
+                .find(|p| p.potion_type == potion)
+                .is_some_and(|p| {
+                    stat.current <= stat.base + redose_at && stat.current < stat.base + p.boost
+                })
+        })
+    }
+
+    fn drink_restore(&mut self, potion: Potion) {
+        // Prayer potions restore 7 + 25% of base prayer, super restores 8 + 25% of every stat
+        let constant = if potion == Potion::SuperRestore { 8 } else { 7 };
+        self.restore_prayer(constant + self.stats.prayer.base / 4, None);
+
+        if potion == Potion::SuperRestore {
+            for stat in [
+                &mut self.stats.attack,
+                &mut self.stats.strength,
+                &mut self.stats.defence,
+                &mut self.stats.ranged,
+                &mut self.stats.magic,
+            ] {
+                // Restores never lower a boosted stat
+                if stat.current < stat.base {
+                    stat.restore(constant + stat.base / 4, None);
+                }
+            }
+        }
+    }
+
+    pub fn prayer_drain_resistance(&self) -> u32 {
+        60 + 2 * self.bonuses.prayer.max(0) as u32
+    }
+
+    pub fn deactivate_prayers(&mut self, monster: &Monster) {
+        // Turn off all prayers, including those stored in gear switches, and recalculate rolls
+        for i in 0..self.switches.len() {
+            let switch_type = self.switches[i].switch_type.clone();
+            let mut switched = self.clone();
+            if switched.switch(&switch_type).is_ok() {
+                switched.prayers = Arc::new(PrayerBoosts::default());
+                self.switches[i] = GearSwitch::new(switch_type, &switched, monster);
+            }
+        }
+
+        self.prayers = Arc::new(PrayerBoosts::default());
+        calc_active_player_rolls(self, monster);
+    }
+
     pub fn remove_potion(&mut self, potion: Potion) {
         self.potions.remove_potion(potion);
         self.calc_potion_boosts();
         self.reset_current_stats(false);
     }
 
+    pub fn set_protection_prayer(&mut self, prayer: Option<Prayer>) {
+        if self.prayers.protection() != prayer {
+            Arc::make_mut(&mut self.prayers).set_protection(prayer);
+        }
+    }
+
     pub fn add_prayer(&mut self, prayer: Prayer) {
-        Rc::make_mut(&mut self.prayers).add(prayer);
+        Arc::make_mut(&mut self.prayers).add(prayer);
     }
 
     pub fn remove_prayer(&mut self, prayer: Prayer) {
-        Rc::make_mut(&mut self.prayers).remove(prayer);
+        Arc::make_mut(&mut self.prayers).remove(prayer);
     }
 
     pub fn bulwark_bonus(&self) -> i32 {
@@ -1282,8 +1424,11 @@ impl Player {
     }
 
     pub fn restore_prayer(&mut self, amount: u32, max_level: Option<u32>) {
+        // Restore up to `max_level` prayer points (base prayer if None). Stat::restore takes
+        // the allowed boost above base rather than an absolute level
         let cap = max_level.unwrap_or(self.stats.prayer.base);
-        self.stats.prayer.restore(amount, Some(cap));
+        let overboost = cap.saturating_sub(self.stats.prayer.base);
+        self.stats.prayer.restore(amount, Some(overboost));
     }
 
     pub fn seercull_spec_max(&self) -> u32 {
@@ -1348,16 +1493,16 @@ impl Player {
 /// use osrs::types::prayers::Prayer;
 /// use osrs::types::equipment::CombatStyle;
 ///
-/// let player = Player::builder()
+/// let _player = Player::builder()
 ///     .attack(90)
 ///     .strength(90)
 ///     .defence(90)
 ///     .potion(Potion::SuperCombat)
 ///     .prayer(Prayer::Piety)
-///     .active_style(CombatStyle::Lunge)
+///     .active_style(CombatStyle::Punch)
 ///     .build()?;
 ///
-/// Ok(())
+/// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
 #[derive(Debug, Clone, Default)]
 pub struct PlayerBuilder {
@@ -1501,10 +1646,10 @@ impl PlayerBuilder {
     pub fn build(self) -> Result<Player, PlayerError> {
         let mut player = Player {
             stats: self.stats.unwrap_or_default(),
-            gear: Rc::new(self.gear.unwrap_or_default()),
+            gear: Arc::new(self.gear.unwrap_or_default()),
             bonuses: EquipmentBonuses::default(),
             potions: PotionBoosts::default(),
-            prayers: Rc::new(PrayerBoosts::default()),
+            prayers: Arc::new(PrayerBoosts::default()),
             boosts: self.boosts.unwrap_or_default(),
             active_effects: Vec::new(),
             set_effects: SetEffects::default(),
@@ -1520,6 +1665,7 @@ impl PlayerBuilder {
             spec: standard_attack,
             switches: Vec::new(),
             current_switch: None,
+            base_setup: None,
             state: PlayerState::default(),
             combat_type: CombatType::default(),
         };
@@ -1619,10 +1765,10 @@ mod test {
         let player = Player::new();
 
         assert_eq!(player.stats, PlayerStats::default());
-        assert_eq!(player.gear, Rc::new(Gear::default()));
+        assert_eq!(player.gear, Arc::new(Gear::default()));
         assert_eq!(player.bonuses, EquipmentBonuses::default());
         assert_eq!(player.potions, PotionBoosts::default());
-        assert_eq!(player.prayers, Rc::new(PrayerBoosts::default()));
+        assert_eq!(player.prayers, Arc::new(PrayerBoosts::default()));
         assert_eq!(player.boosts, StatusBoosts::default());
         assert_eq!(player.active_effects, Vec::new());
         assert_eq!(player.set_effects, SetEffects::default());
@@ -1650,7 +1796,7 @@ mod test {
     #[test]
     fn test_is_wearing() {
         let mut player = Player::new();
-        let gear = Rc::make_mut(&mut player.gear);
+        let gear = Arc::make_mut(&mut player.gear);
         gear.head = Some(Armor::default());
         gear.head.as_mut().unwrap().name = "Torva full helm".to_string();
         assert!(player.is_wearing("Torva full helm", None));
@@ -1708,7 +1854,7 @@ mod test {
             feet: Some(Armor::new("Primordial boots", None).expect("Error creating equipment.")),
             ring: Some(Armor::new("Ultor ring", None).expect("Error creating equipment.")),
         };
-        player.gear = Rc::new(max_melee_gear);
+        player.gear = Arc::new(max_melee_gear);
         player.update_bonuses();
 
         let max_melee_bonuses = EquipmentBonuses {
@@ -1779,7 +1925,7 @@ mod test {
     #[test]
     fn test_prayer_boost() {
         let mut player = Player::new();
-        let prayers = Rc::make_mut(&mut player.prayers);
+        let prayers = Arc::make_mut(&mut player.prayers);
         prayers.add(Prayer::Chivalry);
         assert_eq!(prayers.attack, 15);
         assert_eq!(prayers.strength, 18);
@@ -1979,4 +2125,18 @@ mod test {
                 if weapon_name == "Osmumten's fang" && style == CombatStyle::Rapid
         ));
     }
+
+    #[test]
+    fn test_restore_prayer_cap() {
+        let mut player = Player::new();
+        player.stats.prayer = Stat::new(99, None);
+
+        // Restores stop at base prayer unless a higher max level is given
+        player.stats.prayer.current = 90;
+        player.restore_prayer(31, None);
+        assert_eq!(player.stats.prayer.current, 99);
+
+        player.restore_prayer(50, Some(120));
+        assert_eq!(player.stats.prayer.current, 120);
+    }
 }
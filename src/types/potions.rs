@@ -2,7 +2,9 @@ use crate::types::stats::Stat;
 use strum_macros::{Display, EnumIter};
 
 // All types of potions or combat level boosting items
-#[derive(Debug, Default, PartialEq, Copy, Clone, Display, EnumIter)]
+#[derive(
+    Debug, Default, PartialEq, Copy, Clone, Display, EnumIter, serde::Serialize, serde::Deserialize,
+)]
 pub enum Potion {
     #[default]
     #[strum(to_string = "None")]
@@ -59,6 +61,10 @@ pub enum Potion {
     SapphireGlacialis,
     #[strum(to_string = "Moonlight")]
     Moonlight,
+    #[strum(to_string = "Prayer potion")]
+    PrayerPotion,
+    #[strum(to_string = "Super restore")]
+    SuperRestore,
 }
 
 impl Potion {
@@ -101,6 +107,10 @@ impl Potion {
         self == &Potion::SuperCombat || self == &Potion::Moonlight
     }
 
+    pub fn restores_prayer(&self) -> bool {
+        self == &Potion::PrayerPotion || self == &Potion::SuperRestore
+    }
+
     pub fn boosts_all(&self) -> bool {
         self == &Potion::SmellingSalts
             || self == &Potion::OverloadMinus
@@ -258,6 +268,33 @@ impl PotionBoosts {
             magic.retain(|p| p.potion_type != potion);
         }
     }
+
+    pub fn potions(&self) -> Vec<Potion> {
+        // Each active potion once, in the order of the stats it boosts
+        let mut potions = Vec::new();
+        for boosts in [
+            &self.attack,
+            &self.strength,
+            &self.defence,
+            &self.ranged,
+            &self.magic,
+        ] {
+            for boost in boosts.iter().flatten() {
+                if !potions.contains(&boost.potion_type) {
+                    potions.push(boost.potion_type);
+                }
+            }
+        }
+
+        potions
+    }
+}
+
+// When to drink another dose of a boosting potion from the inventory mid-fight
+#[derive(Debug, PartialEq, Copy, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RedoseConfig {
+    pub potion: Potion,
+    pub redose_at: u32, // Redose once a stat it boosts is this many levels above base or lower
 }
 
 #[cfg(test)]
@@ -1,9 +1,10 @@
+use crate::types::monster::AttackType;
 use serde::{Deserialize, Serialize};
 use std::fmt;
-use strum_macros::Display;
+use strum_macros::{Display, EnumIter};
 
-// Most combat-related prayers (excluding protection prayers)
-#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Display, Serialize, Deserialize)]
+// Most combat-related prayers, including the overhead protection prayers
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Display, EnumIter, Serialize, Deserialize)]
 pub enum Prayer {
     #[default]
     None,
@@ -49,6 +50,61 @@ pub enum Prayer {
     MysticVigour,
     #[strum(to_string = "Augury")]
     Augury,
+    #[strum(to_string = "Protect from Magic")]
+    ProtectFromMagic,
+    #[strum(to_string = "Protect from Missiles")]
+    ProtectFromMissiles,
+    #[strum(to_string = "Protect from Melee")]
+    ProtectFromMelee,
+}
+
+impl Prayer {
+    // Drain effect of the prayer, per the drain rate tables on the wiki
+    pub fn drain_effect(&self) -> u32 {
+        match self {
+            Prayer::None => 0,
+            Prayer::ClarityOfThought
+            | Prayer::BurstOfStrength
+            | Prayer::ThickSkin
+            | Prayer::SharpEye
+            | Prayer::MysticWill => 1,
+            Prayer::ImprovedReflexes
+            | Prayer::SuperhumanStrength
+            | Prayer::RockSkin
+            | Prayer::HawkEye
+            | Prayer::MysticLore => 6,
+            Prayer::IncredibleReflexes
+            | Prayer::UltimateStrength
+            | Prayer::SteelSkin
+            | Prayer::EagleEye
+            | Prayer::Deadeye
+            | Prayer::MysticMight
+            | Prayer::MysticVigour
+            | Prayer::ProtectFromMagic
+            | Prayer::ProtectFromMissiles
+            | Prayer::ProtectFromMelee => 12,
+            Prayer::Chivalry | Prayer::Piety | Prayer::Rigour | Prayer::Augury => 24,
+        }
+    }
+
+    pub fn is_protection(&self) -> bool {
+        matches!(
+            self,
+            Prayer::ProtectFromMagic | Prayer::ProtectFromMissiles | Prayer::ProtectFromMelee
+        )
+    }
+
+    // Protection prayer that blocks the given monster attack style, if any
+    pub fn protection_against(attack_type: AttackType) -> Option<Prayer> {
+        match attack_type {
+            AttackType::Stab | AttackType::Slash | AttackType::Crush | AttackType::Melee => {
+                Some(Prayer::ProtectFromMelee)
+            }
+            AttackType::Ranged => Some(Prayer::ProtectFromMissiles),
+            AttackType::Magic => Some(Prayer::ProtectFromMagic),
+            AttackType::Special | AttackType::None => None,
+        }
+    }
 }
 
 macro_rules! prayer_boost {
@@ -130,7 +186,9 @@ impl PrayerBoost {
             || self.prayer_type == Prayer::MysticVigour
             || p2.prayer_type == Prayer::MysticVigour;
 
-        self.attack > 0 && (p2.attack > 0 || p2.ranged_att > 0 || p2.magic_att > 0)
+        // Only one overhead prayer can be active at a time
+        self.prayer_type.is_protection() && p2.prayer_type.is_protection()
+            || self.attack > 0 && (p2.attack > 0 || p2.ranged_att > 0 || p2.magic_att > 0)
             || self.strength > 0 && (p2.strength > 0 || p2.ranged_str > 0 || p2.magic_str > 0)
             || !stacks_defence && self.defence > 0 && p2.defence > 0
             || self.ranged_att > 0 && (p2.attack > 0 || p2.ranged_att > 0 || p2.magic_att > 0)
@@ -197,11 +255,46 @@ impl PrayerBoosts {
         }
     }
 
+    pub fn prayers(&self) -> Vec<Prayer> {
+        self.active_prayers
+            .iter()
+            .flatten()
+            .map(|p| p.prayer_type)
+            .collect()
+    }
+
     pub fn contains_prayer(&self, prayer: Prayer) -> bool {
         self.active_prayers
             .as_ref()
             .is_some_and(|prayers| prayers.iter().any(|p| p.prayer_type == prayer))
     }
+
+    pub fn protection(&self) -> Option<Prayer> {
+        self.prayers().into_iter().find(Prayer::is_protection)
+    }
+
+    pub fn protects_against(&self, attack_type: AttackType) -> bool {
+        Prayer::protection_against(attack_type).is_some_and(|prayer| self.contains_prayer(prayer))
+    }
+
+    // Swap the active protection prayer for another one (or none)
+    pub fn set_protection(&mut self, prayer: Option<Prayer>) {
+        if let Some(current) = self.protection() {
+            self.remove(current);
+        }
+        if let Some(prayer) = prayer {
+            self.add(prayer);
+        }
+    }
+
+    pub fn drain_effect(&self) -> u32 {
+        // Total drain effect of all active prayers
+        self.active_prayers
+            .iter()
+            .flatten()
+            .map(|p| p.prayer_type.drain_effect())
+            .sum()
+    }
 }
 
 mod test {
@@ -216,4 +309,33 @@ mod test {
         prayers.remove(Prayer::Augury);
         assert!(!prayers.contains_prayer(Prayer::Augury));
     }
+
+    #[test]
+    fn test_drain_effect() {
+        let mut prayers = PrayerBoosts::default();
+        assert_eq!(prayers.drain_effect(), 0);
+        prayers.add(Prayer::Piety);
+        prayers.add(Prayer::SteelSkin);
+        assert_eq!(prayers.drain_effect(), 12);
+        prayers.add(Prayer::Deadeye);
+        prayers.add(Prayer::SteelSkin);
+        assert_eq!(prayers.drain_effect(), 24);
+    }
+
+    #[test]
+    fn test_protection_prayers() {
+        let mut prayers = PrayerBoosts::default();
+        prayers.add(Prayer::Piety);
+        prayers.add(Prayer::ProtectFromMelee);
+        assert!(prayers.protects_against(AttackType::Crush));
+        assert!(!prayers.protects_against(AttackType::Magic));
+        assert_eq!(prayers.drain_effect(), 36);
+
+        // Overheads replace each other without touching offensive prayers
+        prayers.add(Prayer::ProtectFromMagic);
+        assert_eq!(prayers.protection(), Some(Prayer::ProtectFromMagic));
+        assert!(prayers.contains_prayer(Prayer::Piety));
+        prayers.set_protection(None);
+        assert_eq!(prayers.prayers(), vec![Prayer::Piety]);
+    }
 }
@@ -1,6 +1,7 @@
 use crate::types::player::Player;
+use crate::utils::names::parse_variant;
 use std::cmp::min;
-use strum_macros::Display;
+use strum_macros::{Display, EnumIter};
 
 // pub trait Spell: std::fmt::Debug {
 //     fn max_hit(&self, player: &Player) -> u32;
@@ -44,9 +45,19 @@ impl Spell {
             Spell::Special(_) => 1,
         }
     }
+
+    pub fn from_name(name: &str) -> Option<Spell> {
+        // Search every spellbook, e.g., "Ice Barrage" or "ice-barrage"
+        parse_variant::<StandardSpell>(name)
+            .filter(|spell| *spell != StandardSpell::None)
+            .map(Spell::Standard)
+            .or_else(|| parse_variant(name).map(Spell::Ancient))
+            .or_else(|| parse_variant(name).map(Spell::Arceuus))
+            .or_else(|| parse_variant(name).map(Spell::Special))
+    }
 }
 
-#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy, Display)]
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy, Display, EnumIter)]
 pub enum StandardSpell {
     #[default]
     None,
@@ -173,7 +184,7 @@ impl StandardSpell {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Display)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Display, EnumIter)]
 pub enum AncientSpell {
     #[strum(to_string = "Smoke Rush")]
     SmokeRush,
@@ -253,7 +264,7 @@ impl AncientSpell {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Display)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Display, EnumIter)]
 pub enum ArceuusSpell {
     #[strum(to_string = "Ghostly Grasp")]
     GhostlyGrasp,
@@ -293,7 +304,7 @@ impl ArceuusSpell {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Display)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Display, EnumIter)]
 pub enum SpecialSpell {
     Invocate,
     Immolate,
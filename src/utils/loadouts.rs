@@ -1,119 +1,41 @@
-use crate::types::equipment::{CombatStyle, Gear};
+use crate::types::loadout::Loadout;
 use crate::types::player::Player;
-use crate::types::potions::Potion;
-use crate::types::prayers::Prayer;
-use crate::types::stats::PlayerStats;
 
-use std::rc::Rc;
+// Load one of the bundled loadouts in the top-level loadouts directory
+fn bundled_player(name: &str, toml_str: &str) -> Player {
+    Loadout::from_toml_str(toml_str)
+        .and_then(|loadout| loadout.to_player(None))
+        .unwrap_or_else(|e| panic!("Bundled loadout {name} is invalid: {e}"))
+}
 
 pub fn max_melee_player() -> Player {
-    let mut player = Player::new();
-    player.stats = PlayerStats::default();
-    player.add_prayer(Prayer::Piety);
-    player.add_potion(Potion::SuperCombat);
-
-    player.equip("Torva full helm", None).unwrap();
-    player.equip("Torva platebody", None).unwrap();
-    player.equip("Torva platelegs", None).unwrap();
-    player.equip("Ferocious gloves", None).unwrap();
-    player.equip("Primordial boots", None).unwrap();
-    player.equip("Ghrazi rapier", None).unwrap();
-    player.equip("Avernic defender", None).unwrap();
-    player.equip("Rada's blessing 4", None).unwrap();
-    player.equip("Amulet of rancour", None).unwrap();
-    player.equip("Infernal cape", None).unwrap();
-    player.equip("Ultor ring", None).unwrap();
-
-    player.update_bonuses();
-    player.set_active_style(CombatStyle::Lunge);
-
-    player
+    bundled_player("max_melee", include_str!("../../loadouts/max_melee.toml"))
 }
 
 pub fn max_ranged_zcb_player() -> Player {
-    let mut player = Player::new();
-    player.stats = PlayerStats::default();
-    player.add_prayer(Prayer::Rigour);
-    player.add_potion(Potion::Ranging);
-
-    player.equip("Masori mask (f)", None).unwrap();
-    player.equip("Necklace of anguish", None).unwrap();
-    player.equip("Dizana's quiver", Some("Charged")).unwrap();
-    player.equip("Dragon bolts", Some("Unpoisoned")).unwrap();
-    player.equip("Zaryte crossbow", None).unwrap();
-    player.equip("Twisted buckler", None).unwrap();
-    player.equip("Masori body (f)", None).unwrap();
-    player.equip("Masori chaps (f)", None).unwrap();
-    player.equip("Zaryte vambraces", None).unwrap();
-    player.equip("Pegasian boots", None).unwrap();
-    player.equip("Venator ring", None).unwrap();
-
-    player.update_bonuses();
-    player.set_active_style(CombatStyle::Rapid);
-
-    player
+    bundled_player(
+        "max_ranged_zcb",
+        include_str!("../../loadouts/max_ranged_zcb.toml"),
+    )
 }
 
 pub fn max_mage_sang_staff_player() -> Player {
-    let mut player = Player::new();
-    player.stats = PlayerStats::default();
-    player.add_prayer(Prayer::Augury);
-    player.add_potion(Potion::SaturatedHeart);
-
-    player.equip("Ancestral hat", None).unwrap();
-    player.equip("Occult necklace", None).unwrap();
-    player.equip("Imbued guthix cape", None).unwrap();
-    player.equip("Rada's blessing 4", None).unwrap();
-    player.equip("Sanguinesti staff", Some("Charged")).unwrap();
-    player.equip("Elidinis' ward (f)", None).unwrap();
-    player.equip("Ancestral robe top", None).unwrap();
-    player.equip("Ancestral robe bottom", None).unwrap();
-    player.equip("Tormented bracelet", None).unwrap();
-    player.equip("Eternal boots", None).unwrap();
-    player.equip("Magus ring", None).unwrap();
-
-    player.update_bonuses();
-    player.set_active_style(CombatStyle::Accurate);
-
-    player
+    bundled_player(
+        "max_mage_sang_staff",
+        include_str!("../../loadouts/max_mage_sang_staff.toml"),
+    )
 }
 
 pub fn bowfa_crystal_player() -> Player {
-    let mut player = max_ranged_zcb_player();
-
-    player.equip("Bow of faerdhinen (c)", None).unwrap();
-    player.equip("Crystal helm", Some("Active")).unwrap();
-    player.equip("Crystal body", Some("Active")).unwrap();
-    player.equip("Crystal legs", Some("Active")).unwrap();
-    Rc::make_mut(&mut player.gear).ammo = None;
-    player.equip("Rada's blessing 4", None).unwrap();
-
-    player.update_bonuses();
-    player.update_set_effects();
-
-    player
+    bundled_player(
+        "bowfa_crystal",
+        include_str!("../../loadouts/bowfa_crystal.toml"),
+    )
 }
 
 pub fn full_eclipse_player() -> Player {
-    let gear = Gear::builder()
-        .head("Eclipse moon helm", None)
-        .neck("Amulet of fury", None)
-        .body("Eclipse moon chestplate", None)
-        .legs("Eclipse moon tassets", None)
-        .cape("Dizana's quiver", Some("Uncharged"))
-        .feet("Avernic treads (max)", None)
-        .ring("Ultor ring", None)
-        .hands("Barrows gloves", None)
-        .weapon("Eclipse atlatl", None)
-        .ammo("Atlatl dart", None)
-        .build()
-        .unwrap();
-    Player::builder()
-        .gear(gear)
-        .active_style(CombatStyle::Rapid)
-        .prayer(Prayer::Rigour)
-        .potion(Potion::SuperCombat)
-        .potion(Potion::Ranging)
-        .build()
-        .unwrap()
+    bundled_player(
+        "full_eclipse",
+        include_str!("../../loadouts/full_eclipse.toml"),
+    )
 }
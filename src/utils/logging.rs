@@ -1,226 +1,578 @@
 use crate::error::PlayerError;
-use crate::types::equipment::CombatType;
+use crate::types::equipment::{CombatType, GearSlot};
 use crate::types::monster::{AttackType, Monster};
 use crate::types::player::{Player, SwitchType};
-use chrono::Local;
-use log::{LevelFilter, debug};
-use simplelog::{Config, WriteLogger};
-use std::fs::File;
+use crate::types::prayers::Prayer;
+use serde::{Deserialize, Serialize};
+use std::fmt;
 
-#[derive(Debug, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
+// Attack or defence rolls against each combat type, in the order they are displayed
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct CombatRolls(pub Vec<(CombatType, i32)>);
+
+impl fmt::Display for CombatRolls {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rolls: Vec<String> = self
+            .0
+            .iter()
+            .map(|(combat_type, roll)| format!("{roll} ({combat_type})"))
+            .collect();
+        write!(f, "{}", rolls.join(", "))
+    }
+}
+
+/// Something that happened during a fight.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum FightEventKind {
+    PlayerAttack {
+        damage: u32,
+        success: bool,
+        style: CombatType,
+    },
+    PlayerSpec {
+        damage: u32,
+        success: bool,
+        switch: String,
+    },
+    PlayerDamage {
+        damage: u32,
+        hp: u32,
+    },
+    ThrallAttack {
+        damage: u32,
+    },
+    MonsterAttack {
+        monster: String,
+        damage: u32,
+        success: bool,
+        style: AttackType,
+    },
+    MonsterDamage {
+        monster: String,
+        damage: u32,
+        hp: u32,
+    },
+    EffectDamage {
+        monster: String,
+        damage: u32,
+        hp: u32,
+    },
+    GearSwitch {
+        switch: String,
+    },
+    FoodEaten {
+        heal_amount: u32,
+        hp: u32,
+    },
+    HpRegen {
+        name: String,
+        hp: u32,
+    },
+    StatsRegen {
+        name: String,
+    },
+    FreezeEnd {
+        name: String,
+    },
+    RedemptionProc {
+        heal_amount: u32,
+        hp: u32,
+    },
+    MonsterDeath {
+        monster: String,
+    },
+    PlayerDeath,
+    PlayerRolls {
+        combat_type: CombatType,
+        max_att_roll: i32,
+        max_hit: u32,
+        def_rolls: CombatRolls,
+    },
+    PlayerStats {
+        attack: u32,
+        strength: u32,
+        defence: u32,
+        ranged: u32,
+        magic: u32,
+        prayer: u32,
+        hitpoints: u32,
+        spec_energy: u8,
+        prayers: Vec<Prayer>,
+    },
+    PlayerGear {
+        items: Vec<(GearSlot, String)>,
+    },
+    MonsterStats {
+        monster: String,
+        attack: u32,
+        strength: u32,
+        defence: u32,
+        ranged: u32,
+        magic: u32,
+        hitpoints: u32,
+    },
+    MonsterRolls {
+        monster: String,
+        att_rolls: CombatRolls,
+        max_hits: Vec<(AttackType, u32)>,
+        def_rolls: CombatRolls,
+    },
+    MonsterTarget {
+        monster: String,
+        target: String,
+    },
+    MemberAttack {
+        member: String,
+    },
+    NamedAttack {
+        monster: String,
+        attack: String,
+    },
+    MonsterSpecial {
+        monster: String,
+        special: String,
+    },
+    SpecialAvoided {
+        special: String,
+    },
+    PhaseChange {
+        monster: String,
+        phase: String,
+    },
+    PhaseEnd {
+        monster: String,
+        phase: String,
+    },
+    HpThreshold {
+        monster: String,
+        percent: u32,
+    },
+    MonsterRevive {
+        monster: String,
+    },
+    Custom {
+        message: String,
+    },
+}
+
+impl fmt::Display for FightEventKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::PlayerAttack {
+                damage,
+                success,
+                style,
+            } => {
+                if *success {
+                    write!(f, "Player hit with {style} for {damage} damage")
+                } else {
+                    write!(f, "Player missed with {style}")
+                }
+            }
+            Self::PlayerSpec {
+                damage,
+                success,
+                switch,
+            } => {
+                if *success {
+                    write!(
+                        f,
+                        "Player hit with special attack '{switch}' for {damage} damage"
+                    )
+                } else {
+                    write!(f, "Player missed with special attack '{switch}'")
+                }
+            }
+            Self::PlayerDamage { damage, hp } => {
+                write!(f, "Player took {damage} damage ({hp} hp remaining)")
+            }
+            Self::ThrallAttack { damage } => write!(f, "Thrall hit for {damage} damage"),
+            Self::MonsterAttack {
+                monster,
+                damage,
+                success,
+                style,
+            } => {
+                if *success {
+                    write!(f, "{monster} hit with {style} for {damage} damage")
+                } else {
+                    write!(f, "{monster} missed with {style}")
+                }
+            }
+            Self::MonsterDamage {
+                monster,
+                damage,
+                hp,
+            } => write!(f, "{monster} took {damage} damage ({hp} hp remaining)"),
+            Self::EffectDamage {
+                monster,
+                damage,
+                hp,
+            } => write!(
+                f,
+                "{monster} took {damage} effect damage ({hp} hp remaining)"
+            ),
+            Self::GearSwitch { switch } => write!(f, "Player switched to a {switch} setup"),
+            Self::FoodEaten { heal_amount, hp } => write!(
+                f,
+                "Player ate food for {heal_amount} hp ({hp} hp remaining)"
+            ),
+            Self::HpRegen { name, hp } => {
+                write!(f, "{name} regenerated 1 hp ({hp} hp remaining)")
+            }
+            Self::StatsRegen { name } => write!(f, "{name} regenerated stats by 1"),
+            Self::FreezeEnd { name } => write!(f, "{name} is no longer frozen"),
+            Self::RedemptionProc { heal_amount, hp } => write!(
+                f,
+                "Player used Redemption and healed for {heal_amount} ({hp} hp remaining)"
+            ),
+            Self::MonsterDeath { monster } => write!(f, "{monster} has died."),
+            Self::PlayerDeath => write!(f, "Player has died, ending the fight"),
+            Self::PlayerRolls {
+                combat_type,
+                max_att_roll,
+                max_hit,
+                def_rolls,
+            } => write!(
+                f,
+                "Player's combat type: {combat_type}, max attack roll: {max_att_roll}, \
+                 max hit: {max_hit}, defence rolls: {def_rolls}"
+            ),
+            Self::PlayerStats {
+                attack,
+                strength,
+                defence,
+                ranged,
+                magic,
+                prayer,
+                hitpoints,
+                spec_energy,
+                prayers,
+            } => {
+                let prayers = if prayers.is_empty() {
+                    "None".to_string()
+                } else {
+                    prayers
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                };
+                write!(
+                    f,
+                    "Player's stats: {attack} Attack, {strength} Strength, {defence} Defence, \
+                     {ranged} Ranged, {magic} Magic, {prayer} Prayer, {hitpoints} Hitpoints, \
+                     {spec_energy}% spec energy; active prayers: {prayers}"
+                )
+            }
+            Self::PlayerGear { items } => {
+                let items: Vec<String> = items
+                    .iter()
+                    .map(|(slot, name)| format!("{slot}: {name}"))
+                    .collect();
+                write!(f, "Player's equipment: {}", items.join(", "))
+            }
+            Self::MonsterStats {
+                monster,
+                attack,
+                strength,
+                defence,
+                ranged,
+                magic,
+                hitpoints,
+            } => write!(
+                f,
+                "{monster}'s stats: {attack} Attack, {strength} Strength, {defence} Defence, \
+                 {ranged} Ranged, {magic} Magic, {hitpoints} Hitpoints"
+            ),
+            Self::MonsterRolls {
+                monster,
+                att_rolls,
+                max_hits,
+                def_rolls,
+            } => {
+                let max_hits = if max_hits.is_empty() {
+                    "None".to_string()
+                } else {
+                    max_hits
+                        .iter()
+                        .map(|(style, value)| format!("{value} ({style})"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                };
+                write!(
+                    f,
+                    "{monster}'s attack rolls: {att_rolls}; max hits: {max_hits}; \
+                     defence rolls: {def_rolls}"
+                )
+            }
+            Self::MonsterTarget { monster, target } => write!(f, "{monster} attacks {target}"),
+            Self::MemberAttack { member } => write!(f, "{member} attacks"),
+            Self::NamedAttack { monster, attack } => write!(f, "{monster} used {attack}"),
+            Self::MonsterSpecial { monster, special } => {
+                write!(f, "{monster} used its {special} special")
+            }
+            Self::SpecialAvoided { special } => write!(f, "Player avoided {special}"),
+            Self::PhaseChange { monster, phase } => write!(f, "{monster} entered phase {phase}"),
+            Self::PhaseEnd { monster, phase } => write!(f, "{monster}'s {phase} phase ended"),
+            Self::HpThreshold { monster, percent } => {
+                write!(f, "{monster} reached {percent}% hp")
+            }
+            Self::MonsterRevive { monster } => write!(f, "{monster} revived"),
+            Self::Custom { message } => write!(f, "{message}"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct FightEvent {
+    pub tick: i32,
+    #[serde(flatten)]
+    pub kind: FightEventKind,
+}
+
+impl fmt::Display for FightEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[Tick {}] {}", self.tick, self.kind)
+    }
+}
+
+/// Ordered list of events from a single fight.
+///
+/// Serializes to JSON Lines (one event per line) or to readable text via `Display`.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct FightTrace {
+    pub events: Vec<FightEvent>,
+}
+
+impl FightTrace {
+    pub fn to_jsonl(&self) -> Result<String, serde_json::Error> {
+        let mut jsonl = String::new();
+        for event in &self.events {
+            jsonl.push_str(&serde_json::to_string(event)?);
+            jsonl.push('\n');
+        }
+
+        Ok(jsonl)
+    }
+
+    pub fn from_jsonl(jsonl: &str) -> Result<Self, serde_json::Error> {
+        let events = jsonl
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { events })
+    }
+
+    pub fn at_tick(&self, tick: i32) -> impl Iterator<Item = &FightEvent> {
+        self.events.iter().filter(move |event| event.tick == tick)
+    }
+
+    pub fn last_tick(&self) -> Option<i32> {
+        self.events.last().map(|event| event.tick)
+    }
+}
+
+impl fmt::Display for FightTrace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for event in &self.events {
+            writeln!(f, "{event}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Collects a `FightTrace` for the current fight when enabled.
+///
+/// Events are only recorded when `enabled` is set; callers are expected to check it before
+/// building events to avoid the overhead in bulk simulations. The trace is handed off with
+/// the `FightResult` at the end of each fight.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
 pub struct FightLogger {
     pub enabled: bool,
+    pub trace: FightTrace,
+    tick: i32, // Tick of the latest event, used for setup snapshots
 }
 
 impl FightLogger {
-    pub fn new(enabled: bool, name: &str) -> Result<Self, log::SetLoggerError> {
-        if enabled {
-            std::fs::create_dir_all("logs").unwrap_or_else(|e| {
-                eprintln!("Failed to create log directory: {e}");
-            });
-
-            let timestamp = Local::now().format("%Y%m%d_%H%M%S").to_string();
-            let filename = format!("logs/{name}_{timestamp}.log");
-
-            WriteLogger::init(
-                LevelFilter::Debug,
-                Config::default(),
-                File::create(filename).unwrap_or_else(|e| {
-                    eprintln!("Failed to create log file: {e}",);
-                    panic!("Failed to create log file");
-                }),
-            )?;
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            ..Self::default()
+        }
+    }
+
+    fn push(&mut self, tick: i32, kind: FightEventKind) {
+        if self.enabled {
+            self.tick = tick;
+            self.trace.events.push(FightEvent { tick, kind });
         }
-        Ok(Self { enabled })
+    }
+
+    /// Take the trace collected so far, leaving an empty one for the next fight.
+    pub fn take_trace(&mut self) -> Option<FightTrace> {
+        self.tick = 0;
+        self.enabled.then(|| std::mem::take(&mut self.trace))
+    }
+
+    pub fn clear(&mut self) {
+        self.tick = 0;
+        self.trace.events.clear();
     }
 
     pub fn log_current_player_rolls(&mut self, player: &Player) -> Result<(), PlayerError> {
-        debug!("Player's active combat style: {}", player.combat_type());
-        debug!(
-            "Player's max attack roll: {}",
-            player.att_rolls.get(player.combat_type())?
-        );
-        debug!(
-            "Player's max hit: {}",
-            player.max_hits.get(player.combat_type())
-        );
-        debug!(
-            "Player's max defence rolls\n: {} (Stab), {} (Slash), {} (Crush), {} (Ranged), {} (Magic)\n",
-            player.def_rolls.get(CombatType::Stab),
-            player.def_rolls.get(CombatType::Slash),
-            player.def_rolls.get(CombatType::Crush),
-            player.def_rolls.get(CombatType::Ranged),
-            player.def_rolls.get(CombatType::Magic)
+        let combat_type = player.combat_type();
+        let def_rolls = [
+            CombatType::Stab,
+            CombatType::Slash,
+            CombatType::Crush,
+            CombatType::Ranged,
+            CombatType::Magic,
+        ]
+        .into_iter()
+        .map(|combat_type| (combat_type, player.def_rolls.get(combat_type)))
+        .collect();
+        self.push(
+            self.tick,
+            FightEventKind::PlayerRolls {
+                combat_type,
+                max_att_roll: player.att_rolls.get(combat_type)?,
+                max_hit: player.max_hits.get(combat_type),
+                def_rolls: CombatRolls(def_rolls),
+            },
         );
 
         Ok(())
     }
 
-    pub fn log_current_gear(&self, player: &Player) {
-        debug!("Player's equipment:");
-        debug!(
-            "Head: {}",
-            player
-                .gear
-                .head
-                .as_ref()
-                .map_or("None".to_string(), |armor| armor.name.clone())
-        );
-        debug!(
-            "Neck: {}",
-            player
-                .gear
-                .neck
-                .as_ref()
-                .map_or("None".to_string(), |armor| armor.name.clone())
-        );
-        debug!(
-            "Cape: {}",
-            player
-                .gear
-                .cape
-                .as_ref()
-                .map_or("None".to_string(), |armor| armor.name.clone())
-        );
-        debug!(
-            "Ammo: {}",
-            player
-                .gear
-                .ammo
-                .as_ref()
-                .map_or("None".to_string(), |armor| armor.name.clone())
-        );
-        debug!("Weapon: {}", player.gear.weapon.name);
-        debug!(
-            "Shield: {}",
-            player
-                .gear
-                .shield
-                .as_ref()
-                .map_or("None".to_string(), |armor| armor.name.clone())
-        );
-        debug!(
-            "Body: {}",
-            player
-                .gear
-                .body
-                .as_ref()
-                .map_or("None".to_string(), |armor| armor.name.clone())
-        );
-        debug!(
-            "Hands: {}",
-            player
-                .gear
-                .hands
-                .as_ref()
-                .map_or("None".to_string(), |armor| armor.name.clone())
-        );
-        debug!(
-            "Legs: {}",
-            player
-                .gear
-                .legs
-                .as_ref()
-                .map_or("None".to_string(), |armor| armor.name.clone())
-        );
-        debug!(
-            "Feet: {}",
-            player
-                .gear
-                .feet
-                .as_ref()
-                .map_or("None".to_string(), |armor| armor.name.clone())
-        );
-        debug!(
-            "Ring: {}",
-            player
-                .gear
-                .ring
-                .as_ref()
-                .map_or("None".to_string(), |armor| armor.name.clone())
+    pub fn log_current_gear(&mut self, player: &Player) {
+        let gear = &player.gear;
+        let armor = [
+            (GearSlot::Head, &gear.head),
+            (GearSlot::Neck, &gear.neck),
+            (GearSlot::Cape, &gear.cape),
+            (GearSlot::Ammo, &gear.ammo),
+        ];
+        let mut items: Vec<(GearSlot, String)> = armor
+            .into_iter()
+            .filter_map(|(slot, armor)| armor.as_ref().map(|armor| (slot, armor.name.clone())))
+            .collect();
+        items.push((GearSlot::Weapon, gear.weapon.name.clone()));
+        let armor = [
+            (GearSlot::Shield, &gear.shield),
+            (GearSlot::Body, &gear.body),
+            (GearSlot::Hands, &gear.hands),
+            (GearSlot::Legs, &gear.legs),
+            (GearSlot::Feet, &gear.feet),
+            (GearSlot::Ring, &gear.ring),
+        ];
+        items.extend(
+            armor
+                .into_iter()
+                .filter_map(|(slot, armor)| armor.as_ref().map(|armor| (slot, armor.name.clone()))),
         );
+
+        self.push(self.tick, FightEventKind::PlayerGear { items });
     }
 
-    pub fn log_current_player_stats(&self, player: &Player) {
-        debug!("Player's stats (with boosts):");
-        debug!("Attack: {}", player.stats.attack.current);
-        debug!("Strength: {}", player.stats.strength.current);
-        debug!("Ranged: {}", player.stats.ranged.current);
-        debug!("Defence: {}", player.stats.defence.current);
-        debug!("Magic: {}", player.stats.magic.current);
-        debug!("Prayer: {}", player.stats.prayer.current);
-        debug!("Hitpoints: {}", player.stats.hitpoints.current);
-        debug!("Special Attack Energy: {}\n", player.stats.spec.value());
-        debug!("Active prayers:");
-        if let Some(prayers) = &player.prayers.active_prayers {
-            for prayer in prayers {
-                debug!("{prayer}");
-            }
-        } else {
-            debug!("None\n");
-        }
+    pub fn log_current_player_stats(&mut self, player: &Player) {
+        let stats = &player.stats;
+        self.push(
+            self.tick,
+            FightEventKind::PlayerStats {
+                attack: stats.attack.current,
+                strength: stats.strength.current,
+                defence: stats.defence.current,
+                ranged: stats.ranged.current,
+                magic: stats.magic.current,
+                prayer: stats.prayer.current,
+                hitpoints: stats.hitpoints.current,
+                spec_energy: stats.spec.value(),
+                prayers: player.prayers.prayers(),
+            },
+        );
     }
 
-    pub fn log_current_monster_stats(&self, monster: &Monster) {
-        debug!("Monster's stats:");
-        debug!("Attack: {}", monster.stats.attack.current);
-        debug!("Strength: {}", monster.stats.strength.current);
-        debug!("Ranged: {}", monster.stats.ranged.current);
-        debug!("Defence: {}", monster.stats.defence.current);
-        debug!("Magic: {}", monster.stats.magic.current);
-        debug!("Hitpoints: {}\n", monster.stats.hitpoints.current);
-    }
-
-    pub fn log_current_monster_rolls(&self, monster: &Monster) {
-        debug!(
-            "Monster's max attack rolls: {} (Stab), {} (Slash), {} (Crush), {} (Ranged), {} (Magic)\n",
-            monster.att_rolls.get(CombatType::Stab),
-            monster.att_rolls.get(CombatType::Slash),
-            monster.att_rolls.get(CombatType::Crush),
-            monster.att_rolls.get(CombatType::Ranged),
-            monster.att_rolls.get(CombatType::Magic)
+    pub fn log_current_monster_stats(&mut self, monster: &Monster) {
+        let stats = &monster.stats;
+        self.push(
+            self.tick,
+            FightEventKind::MonsterStats {
+                monster: monster.name().to_string(),
+                attack: stats.attack.current,
+                strength: stats.strength.current,
+                defence: stats.defence.current,
+                ranged: stats.ranged.current,
+                magic: stats.magic.current,
+                hitpoints: stats.hitpoints.current,
+            },
         );
-        if let Some(max_hits) = &monster.max_hits {
-            debug!(
-                "Monster's max hit(s): {}\n",
-                max_hits
-                    .iter()
-                    .map(|max_hit| format!("{} ({})", max_hit.value, max_hit.style))
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            );
-        } else {
-            debug!("Monster has no stored max hits.\n");
-        }
-        debug!(
-            "Monster's max defence rolls: {} (Stab), {} (Slash), {} (Crush), {} (Light), {} (Standard), {} (Heavy), {} (Magic)\n",
-            monster.def_rolls.get(CombatType::Stab),
-            monster.def_rolls.get(CombatType::Slash),
-            monster.def_rolls.get(CombatType::Crush),
-            monster.def_rolls.get(CombatType::Light),
-            monster.def_rolls.get(CombatType::Standard),
-            monster.def_rolls.get(CombatType::Heavy),
-            monster.def_rolls.get(CombatType::Magic)
+    }
+
+    pub fn log_current_monster_rolls(&mut self, monster: &Monster) {
+        let att_rolls = [
+            CombatType::Stab,
+            CombatType::Slash,
+            CombatType::Crush,
+            CombatType::Ranged,
+            CombatType::Magic,
+        ]
+        .into_iter()
+        .map(|combat_type| (combat_type, monster.att_rolls.get(combat_type)))
+        .collect();
+        let def_rolls = [
+            CombatType::Stab,
+            CombatType::Slash,
+            CombatType::Crush,
+            CombatType::Light,
+            CombatType::Standard,
+            CombatType::Heavy,
+            CombatType::Magic,
+        ]
+        .into_iter()
+        .map(|combat_type| (combat_type, monster.def_rolls.get(combat_type)))
+        .collect();
+        let max_hits = monster.max_hits.as_ref().map_or_else(Vec::new, |max_hits| {
+            max_hits
+                .iter()
+                .map(|max_hit| (max_hit.style, max_hit.value))
+                .collect()
+        });
+
+        self.push(
+            self.tick,
+            FightEventKind::MonsterRolls {
+                monster: monster.name().to_string(),
+                att_rolls: CombatRolls(att_rolls),
+                max_hits,
+                def_rolls: CombatRolls(def_rolls),
+            },
         );
     }
 
     pub fn log_initial_setup(&mut self, player: &Player, monster: &Monster) {
-        debug!("Initial setup:");
+        // Start a fresh trace at tick 0 for the new fight
+        self.clear();
         let _ = self.log_current_player_rolls(player);
         self.log_current_player_stats(player);
         self.log_current_gear(player);
-
-        debug!("\n");
         self.log_current_monster_stats(monster);
         self.log_current_monster_rolls(monster);
     }
 
     pub fn log_player_attack(&mut self, tick: i32, damage: u32, success: bool, style: CombatType) {
-        if success {
-            debug!("[Tick {tick}] Player hit with {style} for {damage} damage");
-        } else {
-            debug!("[Tick {tick}] Player missed with {style}");
-        }
+        self.push(
+            tick,
+            FightEventKind::PlayerAttack {
+                damage,
+                success,
+                style,
+            },
+        );
     }
 
     pub fn log_player_spec(
@@ -230,20 +582,22 @@ impl FightLogger {
         success: bool,
         switch_type: &SwitchType,
     ) {
-        let label = switch_type.label();
-        if success {
-            debug!("[Tick {tick}] Player hit with special attack '{label}' for {damage} damage");
-        } else {
-            debug!("[Tick {tick}] Player missed with special attack '{label}'");
-        }
+        self.push(
+            tick,
+            FightEventKind::PlayerSpec {
+                damage,
+                success,
+                switch: switch_type.label(),
+            },
+        );
     }
 
     pub fn log_player_damage(&mut self, tick: i32, damage: u32, hp: u32) {
-        debug!("[Tick {tick}] Player took {damage} damage ({hp} hp remaining)");
+        self.push(tick, FightEventKind::PlayerDamage { damage, hp });
     }
 
     pub fn log_thrall_attack(&mut self, tick: i32, damage: u32) {
-        debug!("[Tick {tick}] Thrall hit for {damage} damage");
+        self.push(tick, FightEventKind::ThrallAttack { damage });
     }
 
     pub fn log_monster_attack(
@@ -266,59 +620,329 @@ impl FightLogger {
         } else {
             AttackType::None
         };
-        let name = monster.name();
 
-        if success {
-            debug!("[Tick {tick}] {name} hit with {style} for {damage} damage");
-        } else {
-            debug!("[Tick {tick}] {name} missed with {style}");
-        }
+        self.push(
+            tick,
+            FightEventKind::MonsterAttack {
+                monster: monster.name().to_string(),
+                damage,
+                success,
+                style,
+            },
+        );
     }
 
     pub fn log_monster_damage(&mut self, tick: i32, damage: u32, hp: u32, name: &str) {
-        debug!("[Tick {tick}] {name} took {damage} damage ({hp} hp remaining)");
+        self.push(
+            tick,
+            FightEventKind::MonsterDamage {
+                monster: name.to_string(),
+                damage,
+                hp,
+            },
+        );
     }
 
     pub fn log_gear_switch(&mut self, tick: i32, switch_type: &SwitchType) {
-        let label = switch_type.label();
-        debug!("[Tick {tick}] Player switched to a {label} setup");
+        self.push(
+            tick,
+            FightEventKind::GearSwitch {
+                switch: switch_type.label(),
+            },
+        );
     }
 
     pub fn log_food_eaten(&mut self, tick: i32, heal_amount: u32, hp: u32) {
-        debug!("[Tick {tick}] Player ate food for {heal_amount} hp ({hp} hp remaining)");
+        self.push(tick, FightEventKind::FoodEaten { heal_amount, hp });
     }
 
     pub fn log_hp_regen(&mut self, tick: i32, hp: u32, name: &str) {
-        debug!("[Tick {tick}] {name} regenerated 1 hp ({hp} hp remaining)");
+        self.push(
+            tick,
+            FightEventKind::HpRegen {
+                name: name.to_string(),
+                hp,
+            },
+        );
     }
 
     pub fn log_stats_regen(&mut self, tick: i32, name: &str) {
-        debug!("[Tick {tick}] {name} regenerated stats by 1");
+        self.push(
+            tick,
+            FightEventKind::StatsRegen {
+                name: name.to_string(),
+            },
+        );
     }
 
     pub fn log_monster_death(&mut self, tick: i32, name: &str) {
-        debug!("[Tick {tick}] {name} has died.");
+        self.push(
+            tick,
+            FightEventKind::MonsterDeath {
+                monster: name.to_string(),
+            },
+        );
     }
 
     pub fn log_player_death(&mut self, tick: i32) {
-        debug!("[Tick {tick}] Player has died, ending the fight");
+        self.push(tick, FightEventKind::PlayerDeath);
     }
 
     pub fn log_monster_effect_damage(&mut self, tick: i32, damage: u32, name: &str, hp: u32) {
-        debug!("[Tick {tick}] {name} took {damage} effect damage ({hp} hp remaining)");
+        self.push(
+            tick,
+            FightEventKind::EffectDamage {
+                monster: name.to_string(),
+                damage,
+                hp,
+            },
+        );
+    }
+
+    pub fn log_monster_target(&mut self, tick: i32, monster: &str, target: &str) {
+        self.push(
+            tick,
+            FightEventKind::MonsterTarget {
+                monster: monster.to_string(),
+                target: target.to_string(),
+            },
+        );
+    }
+
+    pub fn log_member_attack(&mut self, tick: i32, member: &str) {
+        self.push(
+            tick,
+            FightEventKind::MemberAttack {
+                member: member.to_string(),
+            },
+        );
+    }
+
+    pub fn log_named_attack(&mut self, tick: i32, monster: &str, attack: &str) {
+        self.push(
+            tick,
+            FightEventKind::NamedAttack {
+                monster: monster.to_string(),
+                attack: attack.to_string(),
+            },
+        );
+    }
+
+    pub fn log_monster_special(&mut self, tick: i32, monster: &str, special: &str) {
+        self.push(
+            tick,
+            FightEventKind::MonsterSpecial {
+                monster: monster.to_string(),
+                special: special.to_string(),
+            },
+        );
+    }
+
+    pub fn log_special_avoided(&mut self, tick: i32, special: &str) {
+        self.push(
+            tick,
+            FightEventKind::SpecialAvoided {
+                special: special.to_string(),
+            },
+        );
+    }
+
+    pub fn log_phase_change(&mut self, tick: i32, monster: &str, phase: &str) {
+        self.push(
+            tick,
+            FightEventKind::PhaseChange {
+                monster: monster.to_string(),
+                phase: phase.to_string(),
+            },
+        );
+    }
+
+    pub fn log_phase_end(&mut self, tick: i32, monster: &str, phase: &str) {
+        self.push(
+            tick,
+            FightEventKind::PhaseEnd {
+                monster: monster.to_string(),
+                phase: phase.to_string(),
+            },
+        );
+    }
+
+    pub fn log_hp_threshold(&mut self, tick: i32, monster: &str, percent: u32) {
+        self.push(
+            tick,
+            FightEventKind::HpThreshold {
+                monster: monster.to_string(),
+                percent,
+            },
+        );
+    }
+
+    pub fn log_monster_revive(&mut self, tick: i32, monster: &str) {
+        self.push(
+            tick,
+            FightEventKind::MonsterRevive {
+                monster: monster.to_string(),
+            },
+        );
     }
 
     pub fn log_custom(&mut self, tick: i32, message: &str) {
-        debug!("[Tick {tick}] {message}");
+        self.push(
+            tick,
+            FightEventKind::Custom {
+                message: message.to_string(),
+            },
+        );
     }
 
     pub fn log_freeze_end(&mut self, tick: i32, name: &str) {
-        debug!("[Tick {tick}] {name} is no longer frozen");
+        self.push(
+            tick,
+            FightEventKind::FreezeEnd {
+                name: name.to_string(),
+            },
+        );
     }
 
     pub fn log_redemption_proc(&mut self, tick: i32, heal_amount: u32, hp: u32) {
-        debug!(
-            "[Tick {tick}] Player used Redemption and healed for {heal_amount} ({hp} hp remaining)"
+        self.push(tick, FightEventKind::RedemptionProc { heal_amount, hp });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::rolls::calc_active_player_rolls;
+    use crate::combat::simulation::Simulation;
+    use crate::sims::single_way::{SingleWayConfig, SingleWayFight};
+    use crate::utils::loadouts::max_melee_player;
+
+    fn sample_trace() -> FightTrace {
+        let mut logger = FightLogger::new(true);
+        logger.log_player_attack(0, 25, true, CombatType::Slash);
+        logger.log_monster_attack(
+            &Monster::new("General Graardor", None).unwrap(),
+            1,
+            0,
+            false,
+            Some(AttackType::Melee),
         );
+        logger.log_food_eaten(3, 22, 80);
+        logger.log_gear_switch(4, &SwitchType::Spec("DWH".into()));
+        logger.log_player_death(5);
+        logger.take_trace().unwrap()
+    }
+
+    #[test]
+    fn test_disabled_logger_records_nothing() {
+        let mut logger = FightLogger::new(false);
+        logger.log_player_attack(0, 25, true, CombatType::Slash);
+        assert!(logger.trace.events.is_empty());
+        assert_eq!(logger.take_trace(), None);
+    }
+
+    #[test]
+    fn test_trace_jsonl_round_trip() {
+        let trace = sample_trace();
+        let jsonl = trace.to_jsonl().unwrap();
+        assert_eq!(jsonl.lines().count(), 5);
+        assert!(jsonl.starts_with(
+            r#"{"tick":0,"event":"player_attack","damage":25,"success":true,"style":"Slash"}"#
+        ));
+        assert_eq!(FightTrace::from_jsonl(&jsonl).unwrap(), trace);
+    }
+
+    #[test]
+    fn test_trace_text() {
+        let trace = sample_trace();
+        let text = trace.to_string();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "[Tick 0] Player hit with Slash for 25 damage");
+        assert_eq!(lines[1], "[Tick 1] General Graardor missed with Melee");
+        assert_eq!(lines[3], "[Tick 4] Player switched to a DWH spec setup");
+        assert_eq!(trace.at_tick(3).count(), 1);
+        assert_eq!(trace.last_tick(), Some(5));
+    }
+
+    #[test]
+    fn test_phase_events() {
+        let mut logger = FightLogger::new(true);
+        logger.log_phase_change(10, "Vorkath", "Acid");
+        logger.log_monster_special(12, "Olm", "Lightning");
+        logger.log_phase_end(35, "Vorkath", "Acid");
+        let trace = logger.take_trace().unwrap();
+
+        let jsonl = trace.to_jsonl().unwrap();
+        assert!(jsonl.starts_with(
+            r#"{"tick":10,"event":"phase_change","monster":"Vorkath","phase":"Acid"}"#
+        ));
+        assert_eq!(FightTrace::from_jsonl(&jsonl).unwrap(), trace);
+
+        let text = trace.to_string();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "[Tick 10] Vorkath entered phase Acid");
+        assert_eq!(lines[1], "[Tick 12] Olm used its Lightning special");
+        assert_eq!(lines[2], "[Tick 35] Vorkath's Acid phase ended");
+    }
+
+    #[test]
+    fn test_fight_result_trace() {
+        let monster = Monster::new("General Graardor", None).unwrap();
+        let mut player = max_melee_player();
+        calc_active_player_rolls(&mut player, &monster);
+        let config = SingleWayConfig {
+            seed: Some(1),
+            ..SingleWayConfig::default()
+        };
+        let mut fight = SingleWayFight::new(player, monster, config, None, true).unwrap();
+        fight.set_attack_function();
+
+        let result = fight.simulate().unwrap();
+        let trace = result.trace.unwrap();
+        assert!(matches!(
+            trace.events[0].kind,
+            FightEventKind::PlayerRolls { .. }
+        ));
+        assert!(matches!(
+            trace.events.last().unwrap().kind,
+            FightEventKind::MonsterDeath { .. }
+        ));
+        let hits = trace
+            .events
+            .iter()
+            .filter(|event| matches!(event.kind, FightEventKind::PlayerAttack { .. }))
+            .count();
+        assert_eq!(hits as u32, result.hit_attempts);
+
+        // The next fight starts a new trace
+        fight.reset();
+        let next = fight.simulate().unwrap().trace.unwrap();
+        let setups = next
+            .events
+            .iter()
+            .filter(|event| matches!(event.kind, FightEventKind::PlayerRolls { .. }))
+            .count();
+        assert_eq!(setups, 1);
+        assert!(matches!(
+            next.events.last().unwrap().kind,
+            FightEventKind::MonsterDeath { .. }
+        ));
+    }
+
+    #[test]
+    fn test_reset_clears_unfinished_trace() {
+        let monster = Monster::new("General Graardor", None).unwrap();
+        let mut player = max_melee_player();
+        calc_active_player_rolls(&mut player, &monster);
+        let mut fight =
+            SingleWayFight::new(player, monster, SingleWayConfig::default(), None, true).unwrap();
+
+        // Events left over from a fight that never returned a trace
+        fight
+            .logger
+            .log_player_attack(0, 25, true, CombatType::Slash);
+        fight.reset();
+        assert!(fight.logger.trace.events.is_empty());
+        assert_eq!(fight.logger.tick, 0);
     }
 }
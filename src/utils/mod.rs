@@ -1,3 +1,4 @@
 pub mod loadouts;
 pub mod logging;
 pub mod math;
+pub mod names;
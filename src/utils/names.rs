@@ -0,0 +1,72 @@
+use std::fmt::{Debug, Display};
+use strum::IntoEnumIterator;
+
+// Lowercase a name and strip everything but letters and digits
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// Look up an enum variant by name, ignoring case, spaces and punctuation.
+///
+/// "Super combat", "super-combat" and "SuperCombat" all match `Potion::SuperCombat`.
+pub fn parse_variant<T>(name: &str) -> Option<T>
+where
+    T: IntoEnumIterator + Debug,
+{
+    let name = normalize(name);
+    T::iter().find(|variant| normalize(&format!("{variant:?}")) == name)
+}
+
+/// Like `parse_variant`, but an exact (case-insensitive) match on the display name wins.
+///
+/// Needed for names that only differ in punctuation, e.g., "Overload (-)" and "Overload".
+pub fn parse_display_name<T>(name: &str) -> Option<T>
+where
+    T: IntoEnumIterator + Debug + Display,
+{
+    let trimmed = name.trim();
+    T::iter()
+        .find(|variant| variant.to_string().eq_ignore_ascii_case(trimmed))
+        .or_else(|| parse_variant(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::food::Foods;
+    use crate::types::potions::Potion;
+    use crate::types::prayers::Prayer;
+
+    #[test]
+    fn test_parse_variant() {
+        assert_eq!(
+            parse_variant::<Potion>("Super combat"),
+            Some(Potion::SuperCombat)
+        );
+        assert_eq!(
+            parse_variant::<Prayer>("clarity-of-thought"),
+            Some(Prayer::ClarityOfThought)
+        );
+        assert_eq!(parse_variant::<Foods>("manta_ray"), Some(Foods::MantaRay));
+        assert_eq!(parse_variant::<Prayer>("Pietyy"), None);
+    }
+
+    #[test]
+    fn test_parse_display_name() {
+        assert_eq!(
+            parse_display_name::<Potion>("Overload (-)"),
+            Some(Potion::OverloadMinus)
+        );
+        assert_eq!(
+            parse_display_name::<Potion>("overload"),
+            Some(Potion::Overload)
+        );
+        assert_eq!(
+            parse_display_name::<Potion>("SuperCombat"),
+            Some(Potion::SuperCombat)
+        );
+    }
+}
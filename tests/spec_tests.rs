@@ -1,17 +1,40 @@
 #[cfg(test)]
 mod spec_tests {
+    use osrs::calc::rolls::calc_active_player_rolls;
+    use osrs::combat::attacks::specs::{elder_maul_spec, eye_of_ayak_spec};
     use osrs::combat::simulation::Simulation;
     use osrs::combat::spec::{
         CoreCondition, SpecCondition, SpecConfig, SpecRestorePolicy, SpecStrategy,
     };
     use osrs::sims::single_way::*;
-    use osrs::types::equipment::CombatStyle;
+    use osrs::sims::vardorvis::{VardorvisCondition, VardorvisConfig, VardorvisFight};
+    use osrs::types::equipment::{CombatStyle, CombatType};
     use osrs::types::monster::{CombatStat, Monster};
     use osrs::types::player::SwitchType;
     use osrs::types::player::{GearSwitch, Player};
     use osrs::types::potions::Potion;
     use osrs::types::prayers::Prayer;
     use osrs::types::stats::PlayerStats;
+    use rand::SeedableRng;
+    use rand::rngs::SmallRng;
+
+    fn spec_player(weapon: &str, style: CombatStyle, monster: &Monster) -> Player {
+        let mut player = Player::new();
+        player.equip(weapon, None).unwrap();
+        player.update_bonuses();
+        player.set_active_style(style);
+        calc_active_player_rolls(&mut player, monster);
+        player
+    }
+
+    fn hit_chance(att_roll: i32, def_roll: i32) -> f64 {
+        let (att, def) = (f64::from(att_roll), f64::from(def_roll));
+        if att > def {
+            1.0 - (def + 2.0) / (2.0 * (att + 1.0))
+        } else {
+            att / (2.0 * (def + 1.0))
+        }
+    }
 
     fn create_test_player() -> Player {
         let mut player = Player::new();
@@ -231,4 +254,151 @@ mod spec_tests {
         assert_eq!(strategy.state.attempt_count, 0);
         assert_eq!(strategy.state.success_count, 0);
     }
+
+    #[test]
+    fn test_boss_condition_wraps_core() {
+        let player = create_test_player();
+        let mut monster =
+            Monster::new("Vardorvis", Some("Post-quest")).expect("Error creating monster.");
+        let switch = GearSwitch::new(SwitchType::Custom("Test spec".into()), &player, &monster);
+
+        // Core builder conditions are lifted into the boss-specific condition type
+        let strategy: SpecStrategy<VardorvisCondition> = SpecStrategy::builder(&switch)
+            .with_monster_hp_below(100)
+            .build();
+        assert_eq!(
+            strategy.conditions[0].as_core(),
+            Some(&CoreCondition::MonsterHpBelow(100))
+        );
+
+        // Boss-specific conditions don't map back to core conditions
+        let strategy: SpecStrategy<VardorvisCondition> = SpecStrategy::builder(&switch)
+            .with_condition(VardorvisCondition::DefenceBelow(200))
+            .build();
+        assert_eq!(strategy.conditions[0].as_core(), None);
+
+        monster.stats.defence.current = 250;
+        assert!(!strategy.conditions[0].evaluate(&player, &monster, &Default::default()));
+        monster.stats.defence.current = 150;
+        assert!(VardorvisCondition::DefenceBelow(200).evaluate(
+            &player,
+            &monster,
+            &Default::default()
+        ));
+    }
+
+    #[test]
+    fn test_vardorvis_specs_above_half_hp() {
+        let mut player = create_test_player();
+        let monster =
+            Monster::new("Vardorvis", Some("Post-quest")).expect("Error creating monster.");
+        calc_active_player_rolls(&mut player, &monster);
+
+        let mut claws_player = player.clone();
+        claws_player.equip("Dragon claws", None).unwrap();
+        claws_player.update_bonuses();
+        claws_player.set_active_style(CombatStyle::Slash);
+        let claws = GearSwitch::new(SwitchType::Spec("Claws".into()), &claws_player, &monster);
+        player.switches.push(claws.clone());
+
+        let spec_config = |condition: VardorvisCondition| {
+            SpecConfig::new(
+                vec![
+                    SpecStrategy::builder(&claws)
+                        .with_condition(condition)
+                        .build(),
+                ],
+                SpecRestorePolicy::RestoreEveryKill,
+                None,
+                false,
+            )
+        };
+        let config = VardorvisConfig {
+            seed: Some(7),
+            ..VardorvisConfig::default()
+        };
+
+        let run = |spec_config: Option<SpecConfig<VardorvisCondition>>| {
+            let mut fight = VardorvisFight::new(player.clone(), config.clone(), spec_config)
+                .expect("Error setting up Vardorvis fight.");
+            fight.set_attack_function();
+            fight.simulate().expect("Simulation failed.")
+        };
+
+        let no_spec = run(None);
+        let never_spec = run(Some(spec_config(VardorvisCondition::DefenceBelow(0))));
+        let spec = run(Some(spec_config(VardorvisCondition::Core(
+            CoreCondition::MonsterHpPercentAbove(50),
+        ))));
+
+        // A strategy whose condition never holds leaves the fight untouched
+        assert_eq!(no_spec, never_spec);
+        // Speccing while Vardorvis is above half HP changes the fight
+        assert_ne!(no_spec, spec);
+    }
+
+    #[test]
+    fn test_elder_maul_spec_accuracy() {
+        let monster = Monster::new("General Graardor", None).expect("Error creating monster.");
+        let mut player = spec_player("Elder maul", CombatStyle::Pound, &monster);
+        let mut rng = SmallRng::seed_from_u64(1);
+
+        let n = 20_000;
+        let hits = (0..n)
+            .filter(|_| elder_maul_spec(&mut player, &mut monster.clone(), &mut rng, &None).success)
+            .count();
+        let rate = hits as f64 / f64::from(n);
+
+        // The spec rolls accuracy at 125% of the maul's attack roll
+        let att_roll = player.att_rolls.get(CombatType::Crush).unwrap();
+        let def_roll = monster.def_rolls.get(CombatType::Crush);
+        let boosted = hit_chance(att_roll * 5 / 4, def_roll);
+        assert!(boosted - hit_chance(att_roll, def_roll) > 0.05);
+        assert!((rate - boosted).abs() < 0.015, "{rate} vs {boosted}");
+    }
+
+    #[test]
+    fn test_eye_of_ayak_spec_drains_at_least_one() {
+        let monster = Monster::new("Vorkath", Some("Post-quest")).expect("Error creating monster.");
+        let mut player = Player::new();
+        player.equip("Eye of ayak", Some("Charged")).unwrap();
+        player.update_bonuses();
+        player.set_active_style(CombatStyle::Accurate);
+        // The rolls calc has no max hit for the eye, so set the magic rolls directly
+        player.att_rolls.set(CombatType::Magic, 30_000).unwrap();
+        player.max_hits.set(CombatType::Magic, 3);
+        let mut rng = SmallRng::seed_from_u64(2);
+
+        // Accurate hits that roll 0 still deal 1 damage and drain 1 magic defence
+        let mut zero_rolls = 0;
+        for _ in 0..5_000 {
+            let mut target = monster.clone();
+            let hit = eye_of_ayak_spec(&mut player, &mut target, &mut rng, &None);
+            let drained = monster.bonuses.defence.magic - target.bonuses.defence.magic;
+            if hit.success {
+                assert!(hit.damage >= 1);
+                assert_eq!(drained, hit.damage as i32);
+                if hit.damage == 1 {
+                    zero_rolls += 1;
+                }
+            } else {
+                assert_eq!(drained, 0);
+            }
+        }
+        assert!(zero_rolls > 0);
+    }
+
+    #[test]
+    fn test_magic_defence_bonus_drain_updates_rolls() {
+        let mut monster =
+            Monster::new("Vorkath", Some("Post-quest")).expect("Error creating monster.");
+        let magic_def_roll = monster.def_rolls.get(CombatType::Magic);
+
+        monster.drain_magic_defence_bonus(30);
+        assert!(monster.def_rolls.get(CombatType::Magic) < magic_def_roll);
+        assert_eq!(
+            monster.base_def_rolls.get(CombatType::Magic),
+            monster.def_rolls.get(CombatType::Magic)
+        );
+    }
 }
@@ -12,6 +12,9 @@ use rstest::rstest;
 mod fixtures;
 use fixtures::*;
 
+// Fixed seed so the simulated side of each comparison is reproducible
+const SEED: u64 = 42;
+
 #[rstest]
 #[case(max_melee_player())]
 #[case(max_ranged_zcb_player())]
@@ -28,8 +31,8 @@ fn test_max_setups_ammonite_crab_ttk(#[case] mut player: Player, ammonite_crab:
         false,
     )
     .expect("Error setting up single way fight.");
-    let results =
-        simulate_n_fights(Box::new(simulation), 100000, true).expect("Simulation failed.");
+    let results = simulate_n_fights(Box::new(simulation), 100000, true, Some(SEED))
+        .expect("Simulation failed.");
     let stats = SimulationStats::new(&results);
 
     let dist = dps_calc::get_distribution(&player, &monster, false)
@@ -56,8 +59,8 @@ fn test_max_mage_brimstone_ring_kril_ttk(
         false,
     )
     .expect("Error setting up single way fight.");
-    let results =
-        simulate_n_fights(Box::new(simulation), 100000, true).expect("Simulation failed.");
+    let results = simulate_n_fights(Box::new(simulation), 100000, true, Some(SEED))
+        .expect("Simulation failed.");
     let stats = SimulationStats::new(&results);
 
     let dist = dps_calc::get_distribution(&player, &monster, false)
@@ -83,8 +86,8 @@ fn test_vardorvis_ttk(#[case] mut player: Player, vardorvis: Monster) {
         false,
     )
     .expect("Error setting up single way fight.");
-    let results =
-        simulate_n_fights(Box::new(simulation), 100000, true).expect("Simulation failed.");
+    let results = simulate_n_fights(Box::new(simulation), 100000, true, Some(SEED))
+        .expect("Simulation failed.");
     let stats = SimulationStats::new(&results);
 
     let dist = dps_calc::get_distribution(&player, &monster, false)
@@ -109,8 +112,8 @@ fn test_fang_ttk(max_melee_fang_player: Player, #[case] monster: Monster) {
         false,
     )
     .expect("Error setting up single way fight.");
-    let results =
-        simulate_n_fights(Box::new(simulation), 100000, true).expect("Simulation failed.");
+    let results = simulate_n_fights(Box::new(simulation), 100000, true, Some(SEED))
+        .expect("Simulation failed.");
     let stats = SimulationStats::new(&results);
 
     let dist = dps_calc::get_distribution(&player, &monster, false)
@@ -141,8 +144,8 @@ fn test_barrows_gear_ttks(#[case] mut player: Player, scurrius: Monster) {
         false,
     )
     .expect("Error setting up single way fight.");
-    let results =
-        simulate_n_fights(Box::new(simulation), 100000, true).expect("Simulation failed.");
+    let results = simulate_n_fights(Box::new(simulation), 100000, true, Some(SEED))
+        .expect("Simulation failed.");
     let stats = SimulationStats::new(&results);
 
     let dist = dps_calc::get_distribution(&player, &monster, false)
@@ -169,8 +172,8 @@ fn test_blue_keris_kq_ttk(
         false,
     )
     .expect("Error setting up single way fight.");
-    let results =
-        simulate_n_fights(Box::new(simulation), 100000, true).expect("Simulation failed.");
+    let results = simulate_n_fights(Box::new(simulation), 100000, true, Some(SEED))
+        .expect("Simulation failed.");
     let stats = SimulationStats::new(&results);
 
     let dist = dps_calc::get_distribution(&player, &monster, false)
@@ -203,8 +206,8 @@ fn test_enchanted_bolt_acb_ttks(#[case] bolt_name: &str) {
         false,
     )
     .expect("Error setting up single way fight.");
-    let results =
-        simulate_n_fights(Box::new(simulation), 100000, true).expect("Simulation failed.");
+    let results = simulate_n_fights(Box::new(simulation), 100000, true, Some(SEED))
+        .expect("Simulation failed.");
     let stats = SimulationStats::new(&results);
 
     let dist = dps_calc::get_distribution(&player, &monster, false)
@@ -236,8 +239,8 @@ fn test_enchanted_bolt_zcb_ttks(#[case] bolt_name: &str) {
         false,
     )
     .expect("Error setting up single way fight.");
-    let results =
-        simulate_n_fights(Box::new(simulation), 100000, true).expect("Simulation failed.");
+    let results = simulate_n_fights(Box::new(simulation), 100000, true, Some(SEED))
+        .expect("Simulation failed.");
     let stats = SimulationStats::new(&results);
 
     let dist = dps_calc::get_distribution(&player, &monster, false)
@@ -267,8 +270,8 @@ fn test_scythe_against_different_sizes_ttk(
         false,
     )
     .expect("Error setting up single way fight.");
-    let results =
-        simulate_n_fights(Box::new(simulation), 100000, true).expect("Simulation failed.");
+    let results = simulate_n_fights(Box::new(simulation), 100000, true, Some(SEED))
+        .expect("Simulation failed.");
     let stats = SimulationStats::new(&results);
 
     let dist = dps_calc::get_distribution(&player, &monster, false)
@@ -293,8 +296,8 @@ fn test_gadderhammer_ttk(max_melee_player: Player, #[case] monster: Monster) {
         false,
     )
     .expect("Error setting up single way fight.");
-    let results =
-        simulate_n_fights(Box::new(simulation), 100000, true).expect("Simulation failed.");
+    let results = simulate_n_fights(Box::new(simulation), 100000, true, Some(SEED))
+        .expect("Simulation failed.");
     let stats = SimulationStats::new(&results);
 
     let dist = dps_calc::get_distribution(&player, &monster, false)
@@ -319,8 +322,8 @@ fn test_tonalztics_ttk(#[case] mut player: Player, scurrius: Monster) {
         false,
     )
     .expect("Error setting up single way fight.");
-    let results =
-        simulate_n_fights(Box::new(simulation), 100000, true).expect("Simulation failed.");
+    let results = simulate_n_fights(Box::new(simulation), 100000, true, Some(SEED))
+        .expect("Simulation failed.");
     let stats = SimulationStats::new(&results);
 
     let dist = dps_calc::get_distribution(&player, &monster, false)
@@ -344,8 +347,8 @@ fn test_macuahuitl_no_set_effect_ttk(max_melee_macuahuitl_player: Player, scurri
         false,
     )
     .expect("Error setting up single way fight.");
-    let results =
-        simulate_n_fights(Box::new(simulation), 100000, true).expect("Simulation failed.");
+    let results = simulate_n_fights(Box::new(simulation), 100000, true, Some(SEED))
+        .expect("Simulation failed.");
     let stats = SimulationStats::new(&results);
 
     let dist = dps_calc::get_distribution(&player, &monster, false)
@@ -370,8 +373,8 @@ fn test_macuahuitl_no_set_effect_baba_ttk(max_melee_macuahuitl_player: Player, b
         false,
     )
     .expect("Error setting up single way fight.");
-    let results =
-        simulate_n_fights(Box::new(simulation), 100000, true).expect("Simulation failed.");
+    let results = simulate_n_fights(Box::new(simulation), 100000, true, Some(SEED))
+        .expect("Simulation failed.");
     let stats = SimulationStats::new(&results);
 
     let dist = dps_calc::get_distribution(&player, &monster, false)
@@ -396,8 +399,8 @@ fn test_max_range_zulrah(#[case] mut player: Player, zulrah_tanzanite: Monster)
         false,
     )
     .expect("Error setting up single way fight.");
-    let results =
-        simulate_n_fights(Box::new(simulation), 100000, true).expect("Simulation failed.");
+    let results = simulate_n_fights(Box::new(simulation), 100000, true, Some(SEED))
+        .expect("Simulation failed.");
     let stats = SimulationStats::new(&results);
 
     let dist = dps_calc::get_distribution(&player, &monster, false)
@@ -421,8 +424,8 @@ fn test_max_mage_shadow_zulrah(max_mage_shadow_player: Player, zulrah_magma: Mon
         false,
     )
     .expect("Error setting up single way fight.");
-    let results =
-        simulate_n_fights(Box::new(simulation), 100000, true).expect("Simulation failed.");
+    let results = simulate_n_fights(Box::new(simulation), 100000, true, Some(SEED))
+        .expect("Simulation failed.");
     let stats = SimulationStats::new(&results);
 
     let dist = dps_calc::get_distribution(&player, &monster, false)
@@ -446,8 +449,8 @@ fn test_max_mage_seren(max_mage_shadow_player: Player, seren: Monster) {
         false,
     )
     .expect("Error setting up single way fight.");
-    let results =
-        simulate_n_fights(Box::new(simulation), 100000, true).expect("Simulation failed.");
+    let results = simulate_n_fights(Box::new(simulation), 100000, true, Some(SEED))
+        .expect("Simulation failed.");
     let stats = SimulationStats::new(&results);
 
     let dist = dps_calc::get_distribution(&player, &monster, false)
@@ -472,7 +475,8 @@ fn test_max_ranged_kraken(max_ranged_tbow_player: Player, kraken: Monster) {
         false,
     )
     .expect("Error setting up single way fight.");
-    let results = simulate_n_fights(Box::new(simulation), 10000, true).expect("Simulation failed.");
+    let results = simulate_n_fights(Box::new(simulation), 10000, true, Some(SEED))
+        .expect("Simulation failed.");
     let stats = SimulationStats::new(&results);
 
     let dist = dps_calc::get_distribution(&player, &monster, false)
@@ -498,7 +502,8 @@ fn test_verzik_p1(#[case] mut player: Player, verzik_p1: Monster) {
         false,
     )
     .expect("Error setting up single way fight.");
-    let results = simulate_n_fights(Box::new(simulation), 10000, true).expect("Simulation failed.");
+    let results = simulate_n_fights(Box::new(simulation), 10000, true, Some(SEED))
+        .expect("Simulation failed.");
     let stats = SimulationStats::new(&results);
 
     let dist = dps_calc::get_distribution(&player, &monster, false)
@@ -522,8 +527,8 @@ fn test_max_mage_tekton(max_mage_shadow_player: Player, tekton: Monster) {
         false,
     )
     .expect("Error setting up single way fight.");
-    let results =
-        simulate_n_fights(Box::new(simulation), 100000, true).expect("Simulation failed.");
+    let results = simulate_n_fights(Box::new(simulation), 100000, true, Some(SEED))
+        .expect("Simulation failed.");
     let stats = SimulationStats::new(&results);
 
     let dist = dps_calc::get_distribution(&player, &monster, false)
@@ -547,8 +552,8 @@ fn max_mage_vasa_crystal(max_mage_shadow_player: Player, vasa_crystal: Monster)
         false,
     )
     .expect("Error setting up single way fight.");
-    let results =
-        simulate_n_fights(Box::new(simulation), 100000, true).expect("Simulation failed.");
+    let results = simulate_n_fights(Box::new(simulation), 100000, true, Some(SEED))
+        .expect("Simulation failed.");
     let stats = SimulationStats::new(&results);
 
     let dist = dps_calc::get_distribution(&player, &monster, false)
@@ -573,8 +578,8 @@ fn test_olm_mage_offstyle(max_mage_shadow_player: Player, #[case] monster: Monst
         false,
     )
     .expect("Error setting up single way fight.");
-    let results =
-        simulate_n_fights(Box::new(simulation), 100000, true).expect("Simulation failed.");
+    let results = simulate_n_fights(Box::new(simulation), 100000, true, Some(SEED))
+        .expect("Simulation failed.");
     let stats = SimulationStats::new(&results);
 
     let dist = dps_calc::get_distribution(&player, &monster, false)
@@ -599,8 +604,8 @@ fn test_olm_ranged_offstyle(max_ranged_tbow_overload_player: Player, #[case] mon
         false,
     )
     .expect("Error setting up single way fight.");
-    let results =
-        simulate_n_fights(Box::new(simulation), 100000, true).expect("Simulation failed.");
+    let results = simulate_n_fights(Box::new(simulation), 100000, true, Some(SEED))
+        .expect("Simulation failed.");
     let stats = SimulationStats::new(&results);
 
     let dist = dps_calc::get_distribution(&player, &monster, false)
@@ -624,8 +629,8 @@ fn test_max_ranged_tbow_ice_demon(max_ranged_tbow_overload_player: Player, ice_d
         false,
     )
     .expect("Error setting up single way fight.");
-    let results =
-        simulate_n_fights(Box::new(simulation), 100000, true).expect("Simulation failed.");
+    let results = simulate_n_fights(Box::new(simulation), 100000, true, Some(SEED))
+        .expect("Simulation failed.");
     let stats = SimulationStats::new(&results);
 
     let dist = dps_calc::get_distribution(&player, &monster, false)
@@ -649,8 +654,8 @@ fn test_max_melee_slagilith(max_melee_player: Player, slagilith: Monster) {
         false,
     )
     .expect("Error setting up single way fight.");
-    let results =
-        simulate_n_fights(Box::new(simulation), 100000, true).expect("Simulation failed.");
+    let results = simulate_n_fights(Box::new(simulation), 100000, true, Some(SEED))
+        .expect("Simulation failed.");
     let stats = SimulationStats::new(&results);
 
     let dist = dps_calc::get_distribution(&player, &monster, false)
@@ -676,8 +681,8 @@ fn test_zogre_ttk(#[case] mut player: Player, zogre: Monster) {
         false,
     )
     .expect("Error setting up single way fight.");
-    let results =
-        simulate_n_fights(Box::new(simulation), 100000, true).expect("Simulation failed.");
+    let results = simulate_n_fights(Box::new(simulation), 100000, true, Some(SEED))
+        .expect("Simulation failed.");
     let stats = SimulationStats::new(&results);
 
     let dist = dps_calc::get_distribution(&player, &monster, false)
@@ -705,8 +710,8 @@ fn test_ruby_bolts_zcb_zebak_500(max_ranged_zcb_ruby_player: Player, zebak: Mons
         false,
     )
     .expect("Error setting up single way fight.");
-    let results =
-        simulate_n_fights(Box::new(simulation), 100000, true).expect("Simulation failed.");
+    let results = simulate_n_fights(Box::new(simulation), 100000, true, Some(SEED))
+        .expect("Simulation failed.");
     let stats = SimulationStats::new(&results);
 
     let dist = dps_calc::get_distribution(&player, &monster, false)
@@ -732,8 +737,8 @@ fn test_corp_limiters(#[case] mut player: Player, corp: Monster) {
         false,
     )
     .expect("Error setting up single way fight.");
-    let results =
-        simulate_n_fights(Box::new(simulation), 100000, true).expect("Simulation failed.");
+    let results = simulate_n_fights(Box::new(simulation), 100000, true, Some(SEED))
+        .expect("Simulation failed.");
     let stats = SimulationStats::new(&results);
 
     let dist = dps_calc::get_distribution(&player, &monster, false)
@@ -764,8 +769,8 @@ fn test_blood_moon_set(full_blood_moon_player: Player, baba_300: Monster) {
         false,
     )
     .expect("Error setting up single way fight.");
-    let results1 =
-        simulate_n_fights(Box::new(simulation1), 100000, true).expect("Simulation failed.");
+    let results1 = simulate_n_fights(Box::new(simulation1), 100000, true, Some(SEED))
+        .expect("Simulation failed.");
     let stats1 = SimulationStats::new(&results1);
 
     let simulation2 = SingleWayFight::new(
@@ -776,8 +781,8 @@ fn test_blood_moon_set(full_blood_moon_player: Player, baba_300: Monster) {
         false,
     )
     .expect("Error setting up single way fight.");
-    let results2 =
-        simulate_n_fights(Box::new(simulation2), 100000, true).expect("Simulation failed.");
+    let results2 = simulate_n_fights(Box::new(simulation2), 100000, true, Some(SEED))
+        .expect("Simulation failed.");
     let stats2 = SimulationStats::new(&results2);
 
     assert!(stats1.ttk < stats2.ttk);